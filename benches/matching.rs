@@ -0,0 +1,103 @@
+//! Matching a very large board: `filter_jobs` against the per-job lowercasing
+//! it replaced, over the same synthetic listing of 100,000 jobs.
+//!
+//! ```text
+//! cargo bench --bench matching
+//! ```
+//!
+//! Both must find the same jobs, so the comparison is of the same work.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use greenhouse_job_search::keywords::KeywordSpec;
+use greenhouse_job_search::search::{filter_jobs, listed_job, Job, JobLocation};
+use greenhouse_job_search::SearchQuery;
+use std::collections::HashSet;
+
+const JOBS: usize = 100_000;
+
+const TITLES: &[&str] = &[
+    "Senior Software Engineer, Payments",
+    "Product Designer",
+    "Staff Product Manager, Growth",
+    "Account Executive - Enterprise",
+    "Data Scientist II",
+    "Engineering Manager, Infrastructure",
+    "Technical Program Manager",
+    "Customer Success Associate",
+    "Director of Product Management",
+    "Recruiting Coordinator",
+    "Product Manager, Platform",
+    "Site Reliability Engineer",
+];
+
+const LOCATIONS: &[&str] = &[
+    "Remote", "New York, NY", "Seattle, WA", "London, United Kingdom", "Berlin, Germany", "Toronto, Canada",
+    "Dublin, Ireland", "Austin, TX", "Tokyo, Japan", "Paris, France", "Singapore", "Bangalore, India",
+];
+
+// Every title against every location in turn, with the id mixed into the
+// title so titles aren't all the same few strings
+fn synthetic_listing() -> Vec<Job> {
+    (0..JOBS)
+        .map(|i| Job {
+            id: i as u64,
+            title: format!("{} ({})", TITLES[i % TITLES.len()], i),
+            updated_at: "2024-06-01T12:00:00Z".to_string(),
+            location: JobLocation { name: LOCATIONS[(i / TITLES.len()) % LOCATIONS.len()].to_string() },
+            absolute_url: format!("https://boards.greenhouse.io/bench/jobs/{}", i),
+            departments: None,
+            extra: serde_json::Map::new(),
+        })
+        .collect()
+}
+
+// `filter_jobs` as it was before `BoardMatcher`: the same listed ids and
+// results, but the title, the job's location and the query's location
+// lowercased again for every job
+fn per_job_lowercasing(jobs: &[Job], keywords: &KeywordSpec, location: &str) -> usize {
+    const LOOSE: &[&str] = &[
+        "remote", "bay area", "san francisco", "california", "ca", "fremont", "silicon valley", "sf", "anywhere",
+        "us", "united states",
+    ];
+    let listed_ids: HashSet<u64> = jobs.iter().map(|job| job.id).collect();
+    black_box(&listed_ids);
+    jobs.iter()
+        .filter(|job| {
+            let title = keywords.fold_title(&job.title);
+            let has = |term: &str| title.contains(term);
+            keywords.terms().iter().all(|term| {
+                let kw = term.text.as_str();
+                has(kw)
+                    || (kw == "principal" && (has("senior") || has("staff") || has("lead")))
+                    || (kw == "manager" && has("management"))
+            })
+        })
+        .filter(|job| {
+            let job_location = job.location.name.to_lowercase();
+            job_location.contains(&location.to_lowercase()) || LOOSE.iter().any(|loose| job_location.contains(loose))
+        })
+        .map(|job| listed_job("bench", job))
+        .collect::<Vec<_>>()
+        .len()
+}
+
+fn matching(c: &mut Criterion) {
+    let jobs = synthetic_listing();
+    let query = SearchQuery::new("product manager", "Seattle").expect("the keywords parse");
+    assert_eq!(
+        filter_jobs("bench", &jobs, &query).matches.len(),
+        per_job_lowercasing(&jobs, &query.keywords, &query.location),
+        "both matchers should find the same jobs"
+    );
+
+    let mut group = c.benchmark_group("matching 100k jobs");
+    group.sample_size(20);
+    group.bench_function("per-job lowercasing", |b| {
+        b.iter(|| per_job_lowercasing(black_box(&jobs), black_box(&query.keywords), black_box(&query.location)))
+    });
+    group.bench_function("filter_jobs", |b| b.iter(|| filter_jobs("bench", black_box(&jobs), black_box(&query))));
+    group.finish();
+}
+
+criterion_group!(benches, matching);
+criterion_main!(benches);
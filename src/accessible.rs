@@ -0,0 +1,343 @@
+use crate::store::PriorApplication;
+use crate::{badges, locale, tags, AppView, JobApplicationSystem, JobResult};
+use std::error::Error;
+use std::io::{self, BufRead, Write};
+
+// Linear, prompt-driven alternative to the full-screen TUI for screen readers.
+// Everything is plain sequential text: no raw mode, cursor movement, color or
+// icons, and it drives the same JobApplicationSystem state as the TUI.
+
+const HELP: &str = "Commands: list, view <number>, next, previous, apply <number>, form <number>, \
+tag <number> <tags> (with no tags clears them), hide <number> (marks it not interested), \
+filter <text> (#tag words match tags), filter (with no text clears it), help, quit";
+
+impl JobApplicationSystem {
+    pub fn run_accessible(&mut self) -> Result<(), Box<dyn Error>> {
+        if self.jobs.is_empty() {
+            println!("No jobs available for application.");
+            return Ok(());
+        }
+
+        println!("Accessible job browser. {} jobs loaded.", self.jobs.len());
+        if let Some(offline) = self.offline_description() {
+            println!("{}. Application forms aren't available.", offline);
+        }
+        println!("{}", HELP);
+        self.print_job_list();
+
+        self.restore_session();
+        self.load_hidden();
+        let result = self.accessible_loop();
+        self.save_state();
+        result
+    }
+
+    fn accessible_loop(&mut self) -> Result<(), Box<dyn Error>> {
+        let stdin = io::stdin();
+        let mut lines = stdin.lock().lines();
+
+        loop {
+            print!("command> ");
+            io::stdout().flush()?;
+
+            let Some(line) = lines.next() else {
+                return Ok(());
+            };
+            let line = line?;
+            let (command, argument) = match line.trim().split_once(char::is_whitespace) {
+                Some((command, argument)) => (command.to_lowercase(), argument.trim().to_string()),
+                None => (line.trim().to_lowercase(), String::new()),
+            };
+
+            match command.as_str() {
+                "" => {}
+                "q" | "quit" | "exit" => return Ok(()),
+                "h" | "help" => println!("{}", HELP),
+                "l" | "list" => self.print_job_list(),
+                "n" | "next" => {
+                    self.step_visible(true);
+                    self.view_selected();
+                }
+                "p" | "prev" | "previous" => {
+                    self.step_visible(false);
+                    self.view_selected();
+                }
+                "v" | "view" => {
+                    if self.select_by_number(&argument) {
+                        self.view_selected();
+                    }
+                }
+                "form" | "questions" if self.offline_since.is_some() => {
+                    println!("Application forms aren't fetched while browsing saved results.");
+                }
+                "form" | "questions" => {
+                    if self.select_by_number(&argument) {
+                        self.print_application_form();
+                    }
+                }
+                "a" | "apply" => {
+                    if self.select_by_number(&argument) {
+                        self.apply_selected(&mut lines)?;
+                    }
+                }
+                "t" | "tag" => {
+                    let (number, tags) = argument.split_once(char::is_whitespace).unwrap_or((&argument, ""));
+                    if self.select_by_number(number) {
+                        let index = self.list_state.selected().unwrap_or_default();
+                        let tags = tags::parse(tags);
+                        match self.save_tags(index, tags) {
+                            Ok(()) if self.jobs[index].tags.is_empty() => println!("Tags cleared."),
+                            Ok(()) => println!("Tags set to {}.", self.jobs[index].tags.join(", ")),
+                            Err(e) => println!("Tags not saved: {}.", e),
+                        }
+                    }
+                }
+                "x" | "hide" => {
+                    if self.select_by_number(&argument) {
+                        let index = self.list_state.selected().unwrap_or_default();
+                        match self.hide_job(index) {
+                            Ok(()) => println!("Hid job {}. It won't appear in future searches.", index + 1),
+                            Err(e) => println!("Hid job {} for now, but {}.", index + 1, e),
+                        }
+                    }
+                }
+                "f" | "filter" => {
+                    self.set_filter(if argument.is_empty() { None } else { Some(argument) });
+                    match &self.filter {
+                        Some(text) => println!("Filter set to \"{}\".", text),
+                        None => println!("Filter cleared."),
+                    }
+                    self.print_job_list();
+                }
+                other => println!("Unknown command \"{}\". {}", other, HELP),
+            }
+            // Ctrl-C ends the process without unwinding, so nothing waits for the end
+            self.save_state();
+        }
+    }
+
+    fn print_job_list(&self) {
+        let visible = self.visible_job_indices();
+        match &self.filter {
+            Some(text) => println!("{} of {} jobs match the filter \"{}\":", visible.len(), self.jobs.len(), text),
+            None if visible.len() < self.jobs.len() => {
+                println!("{} of {} jobs, the rest are hidden:", visible.len(), self.jobs.len());
+            }
+            None => println!("{} jobs:", self.jobs.len()),
+        }
+        let now = chrono::Utc::now();
+        for index in visible {
+            let job = &self.jobs[index];
+            let new_note = if job.first_seen.is_none() { " New since the last run." } else { "" };
+            let applied_note = match self.prior_application(job) {
+                Some(PriorApplication::Applied(_)) => " Already applied.",
+                Some(PriorApplication::PossiblyApplied(_)) => " Possibly applied before.",
+                Some(PriorApplication::FollowUp(_)) => " Opened in the browser, not marked done.",
+                None => "",
+            };
+            let tags_note = if job.tags.is_empty() { String::new() } else { format!(" Tags: {}.", job.tags.join(", ")) };
+            let closed_note = if job.closed_at.is_some() { " Closed." } else { "" };
+            let favorite_note = if job.favorite { " Favorite company." } else { "" };
+            let badges: Vec<&str> = badges::for_job(job, now).iter().map(badges::Badge::words).collect();
+            let badges_note = if badges.is_empty() { String::new() } else { format!(" Badges: {}.", badges.join(", ")) };
+            println!(
+                "Job {}: {}, at {}, located in {}.{}{}{}{}{}{}",
+                index + 1, job.title, job.company, job.location, badges_note, favorite_note, new_note, applied_note, tags_note,
+                closed_note
+            );
+        }
+    }
+
+    // Job numbers always refer to the unfiltered list so they stay stable while filtering
+    fn select_by_number(&mut self, argument: &str) -> bool {
+        let index = match argument.parse::<usize>() {
+            Ok(number) if number >= 1 && number <= self.jobs.len() => number - 1,
+            _ => {
+                println!("Please give a job number between 1 and {}.", self.jobs.len());
+                return false;
+            }
+        };
+        self.list_state.select(Some(index));
+        true
+    }
+
+    fn view_selected(&mut self) {
+        if self.visible_job_indices().is_empty() {
+            println!("No jobs match the current filter.");
+            return;
+        }
+        self.select_current_job();
+        if self.needs_application_form() {
+            println!("Loading application requirements...");
+            self.load_application_form();
+        }
+
+        let Some(index) = self.selected_job_index else {
+            return;
+        };
+        let job = &self.jobs[index];
+        print_job_details(index, self.jobs.len(), job);
+        let similar = self.similar_jobs(index);
+        if !similar.is_empty() {
+            println!("Similar jobs (use view with the number):");
+            for (other, overlap) in similar {
+                let other_job = &self.jobs[other];
+                println!("  Job {}: {}, at {}. {:.0}% title match.", other + 1, other_job.title, other_job.company, overlap * 100.0);
+            }
+        }
+        println!("Application requirements:");
+        for line in self.application_requirements(job) {
+            println!("  {}", line);
+        }
+        if let Some((heading, lines)) = self.archived_posting(job) {
+            println!("{}:", heading);
+            for line in lines {
+                println!("  {}", line);
+            }
+        }
+    }
+
+    fn print_application_form(&mut self) {
+        self.select_current_job();
+        self.profiles = crate::profile::Profiles::load();
+        self.cover_letter_template = crate::cover_letter::load_template();
+        self.answer_overrides = crate::overrides::Overrides::load();
+        if self.needs_application_form() {
+            println!("Loading the application form...");
+            self.load_application_form();
+        }
+
+        let Some(index) = self.selected_job_index else {
+            return;
+        };
+        let job = &self.jobs[index];
+        println!("Application form for {} at {}:", job.title, job.company);
+        for line in self.application_questions(job) {
+            if !line.is_empty() {
+                println!("{}", line);
+            }
+        }
+    }
+
+    fn apply_selected<B: BufRead>(&mut self, lines: &mut io::Lines<B>) -> Result<(), Box<dyn Error>> {
+        self.select_current_job();
+        self.confirm_application();
+
+        let Some(index) = self.selected_job_index else {
+            return Ok(());
+        };
+        let job = &self.jobs[index];
+        let already_applied = match self.prior_application(job) {
+            Some(PriorApplication::Applied(record)) => {
+                println!("Warning: you already applied to this job on {}.", record.applied_date());
+                true
+            }
+            Some(PriorApplication::PossiblyApplied(record)) => {
+                println!(
+                    "Note: you possibly applied before, to \"{}\" at {} on {}.",
+                    record.title, record.company, record.applied_date()
+                );
+                false
+            }
+            Some(PriorApplication::FollowUp(record)) => {
+                println!("Note: you opened this job in the browser on {}; type log once you've sent it.", record.applied_date());
+                false
+            }
+            None => false,
+        };
+        if self.needs_application_form() {
+            println!("Loading the application form...");
+            self.load_application_form();
+        }
+        let answer = loop {
+            let job = &self.jobs[index];
+            for line in self.submission_preview(job) {
+                println!("{}", line.trim_start());
+            }
+            if let Some(reason) = self.api_unavailable(job) {
+                println!("The API can't take this application: {}. Type browser to open it in your browser and log it as a follow-up.", reason);
+            }
+            print!(
+                "Submit the application to {} at {}? Type yes, edit to change the cover letter, log if you applied yourself and only want it logged, or no: ",
+                job.title, job.company
+            );
+            io::stdout().flush()?;
+
+            let answer = read_answer(lines)?;
+            if answer != "edit" {
+                break answer;
+            }
+            self.edit_cover_letter();
+            if let Some(notice) = self.notice.take() {
+                println!("{}", notice.trim_start_matches(|c: char| !c.is_ascii_alphabetic()));
+            }
+        };
+        let mut confirmed = answer.starts_with('y');
+        if confirmed && already_applied {
+            print!("This would be a duplicate application. Type yes again to apply anyway: ");
+            io::stdout().flush()?;
+            confirmed = read_yes(lines)?;
+        }
+        if confirmed {
+            println!("Submitting...");
+            self.apply_to_job();
+        } else if answer == "browser" {
+            self.apply_in_browser();
+        } else if answer == "log" {
+            self.log_application();
+        } else {
+            println!("Application cancelled.");
+        }
+        if matches!(self.current_view, AppView::ApplicationComplete) {
+            let (heading, outcome) = self.application_outcome(&self.jobs[index]);
+            // Without the icon, in sentence case
+            let heading = heading.trim_start_matches(|c: char| !c.is_ascii_alphabetic()).to_lowercase();
+            println!("{}{}.", heading[..1].to_uppercase(), &heading[1..]);
+            for line in outcome.iter().filter(|line| !line.is_empty()) {
+                println!("{}", line.trim_start());
+            }
+        }
+        self.back_to_list();
+        Ok(())
+    }
+}
+
+fn read_yes<B: BufRead>(lines: &mut io::Lines<B>) -> io::Result<bool> {
+    Ok(read_answer(lines)?.starts_with('y'))
+}
+
+fn read_answer<B: BufRead>(lines: &mut io::Lines<B>) -> io::Result<String> {
+    let answer = match lines.next() {
+        Some(line) => line?,
+        None => String::new(),
+    };
+    Ok(answer.trim().to_lowercase())
+}
+
+fn print_job_details(index: usize, total: usize, job: &JobResult) {
+    println!("Job {} of {}.", index + 1, total);
+    println!("Title: {}", job.title);
+    println!("Company: {}", job.company);
+    if job.favorite {
+        println!("Favorite: the board is in your --favorites list");
+    }
+    if let Some(relevance) = job.relevance {
+        println!("Relevance: {:.0}%", relevance * 100.0);
+    }
+    if !job.departments.is_empty() {
+        println!("Departments: {}", job.departments.join(", "));
+    }
+    if !job.tags.is_empty() {
+        println!("Tags: {}", job.tags.join(", "));
+    }
+    if let Some(closed_at) = &job.closed_at {
+        println!("Closed: no longer listed on the board since {}.", locale::current().timestamp_date(closed_at));
+    }
+    println!("Location: {}", job.location);
+    println!("Date posted: {}", locale::current().timestamp_date_time(&job.date_posted));
+    println!("Link: {}", job.url);
+    println!("Job ID: {} on board {}", job.id, job.board_token);
+    for (label, value) in &job.extras {
+        println!("{}: {}", label, value);
+    }
+}
@@ -0,0 +1,235 @@
+use crate::JobResult;
+use serde::{Deserialize, Deserializer, Serialize};
+
+// Application form as returned by `/v1/boards/{token}/jobs/{id}?questions=true`.
+// Boards that don't expose their questions simply omit the array, and some
+// send `null` for the sections they don't use.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ApplicationForm {
+    #[serde(default, deserialize_with = "null_as_empty")]
+    pub questions: Vec<Question>,
+    // Voluntary self-identification sections (EEOC and the like)
+    #[serde(default, deserialize_with = "null_as_empty")]
+    pub compliance: Vec<ComplianceSection>,
+    // "education_optional" or "education_required" when the board asks for
+    // schools and degrees without listing the fields (see `education`)
+    #[serde(default)]
+    pub education: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Question {
+    pub label: String,
+    #[serde(default)]
+    pub required: bool,
+    // Escaped HTML shown under the label, when the board adds any
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default, deserialize_with = "null_as_empty")]
+    pub fields: Vec<QuestionField>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct QuestionField {
+    pub name: String,
+    // input_text, input_file, textarea, multi_value_single_select, multi_value_multi_select, ...
+    #[serde(rename = "type")]
+    pub field_type: String,
+    // Choices for select fields
+    #[serde(default, deserialize_with = "null_as_empty")]
+    pub values: Vec<FieldValue>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FieldValue {
+    pub label: String,
+    // What gets submitted for this choice; a number on most boards, a string on some
+    #[serde(default)]
+    pub value: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ComplianceSection {
+    // "eeoc" on US boards
+    #[serde(rename = "type")]
+    pub kind: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default, deserialize_with = "null_as_empty")]
+    pub questions: Vec<Question>,
+}
+
+fn null_as_empty<'de, D: Deserializer<'de>, T: Deserialize<'de>>(deserializer: D) -> Result<Vec<T>, D::Error> {
+    Ok(Option::<Vec<T>>::deserialize(deserializer)?.unwrap_or_default())
+}
+
+impl Question {
+    // "short text", "a file upload", "one of: Yes, No" and so on, for previews
+    pub fn describe_fields(&self) -> String {
+        let kinds: Vec<String> = self
+            .fields
+            .iter()
+            .map(|field| match field.field_type.as_str() {
+                "input_file" => "a file upload".to_string(),
+                "input_text" => "short text".to_string(),
+                "textarea" => "long text".to_string(),
+                "input_hidden" => "hidden".to_string(),
+                "multi_value_single_select" | "multi_value_multi_select" if !field.values.is_empty() => {
+                    let choices: Vec<&str> = field.values.iter().map(|v| v.label.as_str()).collect();
+                    let kind = if field.field_type.ends_with("multi_select") { "any of" } else { "one of" };
+                    format!("{}: {}", kind, choices.join(", "))
+                }
+                other => other.replace('_', " "),
+            })
+            .collect();
+        kinds.join(" or ")
+    }
+}
+
+impl QuestionField {
+    // The choice that declines to answer: "Decline To Self Identify", "I don't wish to answer" and the like
+    pub fn decline_option(&self) -> Option<&FieldValue> {
+        const DECLINING: &[&str] = &["decline", "don't wish", "do not wish", "prefer not", "not to answer", "not to disclose", "not to say"];
+        self.values.iter().find(|option| {
+            let label = option.label.to_lowercase().replace('\u{2019}', "'");
+            DECLINING.iter().any(|phrase| label.contains(phrase))
+        })
+    }
+
+    pub fn is_select(&self) -> bool {
+        matches!(self.field_type.as_str(), "multi_value_single_select" | "multi_value_multi_select") && !self.values.is_empty()
+    }
+}
+
+impl ApplicationForm {
+    pub fn required_questions(&self) -> impl Iterator<Item = &Question> {
+        self.questions.iter().filter(|q| q.required)
+    }
+
+    // Find a question by field name (e.g. "resume", "cover_letter")
+    pub fn question_with_field(&self, field_name: &str) -> Option<&Question> {
+        self.questions
+            .iter()
+            .find(|q| q.fields.iter().any(|f| f.name == field_name))
+    }
+
+    // Free-text questions beyond the standard contact/resume fields
+    pub fn essay_question_count(&self) -> usize {
+        self.questions
+            .iter()
+            .filter(|q| q.fields.iter().any(|f| f.field_type == "textarea"))
+            .filter(|q| q.fields.iter().all(|f| !f.name.starts_with("resume") && !f.name.starts_with("cover_letter")))
+            .count()
+    }
+}
+
+// Why the form fetch failed when the board has no questions endpoint for the
+// job, which also means the API won't take its applications
+pub const NOT_PUBLISHED: &str = "this board doesn't publish its application form through the API";
+
+pub async fn get_application_form(client: &reqwest::Client, job: &JobResult) -> Result<ApplicationForm, String> {
+    fetch_application_form(client, &job.board_token, job.id).await
+}
+
+pub async fn fetch_application_form(client: &reqwest::Client, board_token: &str, job_id: u64)
+    -> Result<ApplicationForm, String> {
+    let api_url = format!("https://boards-api.greenhouse.io/v1/boards/{}/jobs/{}?questions=true", board_token, job_id);
+
+    let response = client.get(&api_url).send().await
+        .map_err(|e| format!("network error: {}", e))?;
+
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Err(NOT_PUBLISHED.to_string());
+    }
+    if !response.status().is_success() {
+        return Err(format!("board returned status {}", response.status()));
+    }
+
+    let mut form = response.json::<ApplicationForm>().await
+        .map_err(|e| format!("unexpected response: {}", e))?;
+    crate::education::add_questions(&mut form);
+    Ok(form)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Responses in the shape the boards API returns, trimmed to a few questions each
+    fn fixture(name: &str) -> ApplicationForm {
+        let path = format!("{}/tests/fixtures/application_forms/{}.json", env!("CARGO_MANIFEST_DIR"), name);
+        let text = std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {}", path, e));
+        serde_json::from_str(&text).unwrap_or_else(|e| panic!("{}: {}", path, e))
+    }
+
+    #[test]
+    fn standard_fields_parse_with_empty_compliance() {
+        let form = fixture("standard_fields");
+        assert_eq!(form.questions.len(), 6);
+        assert_eq!(form.required_questions().count(), 4);
+        assert!(form.compliance.is_empty());
+
+        let resume = form.question_with_field("resume").expect("resume question");
+        assert_eq!(resume.label, "Resume/CV");
+        assert!(resume.required);
+        assert_eq!(resume.describe_fields(), "a file upload or long text");
+        assert_eq!(form.question_with_field("phone").map(|q| q.required), Some(false));
+        assert_eq!(form.essay_question_count(), 0);
+    }
+
+    #[test]
+    fn custom_questions_keep_select_options_and_values() {
+        let form = fixture("custom_questions_eeoc");
+        assert_eq!(form.questions.len(), 7);
+        assert_eq!(form.essay_question_count(), 1);
+
+        let authorized = form.question_with_field("question_31604873").expect("work authorization question");
+        assert!(authorized.required);
+        assert_eq!(authorized.fields[0].field_type, "multi_value_single_select");
+        let choices: Vec<(&str, &serde_json::Value)> =
+            authorized.fields[0].values.iter().map(|v| (v.label.as_str(), &v.value)).collect();
+        assert_eq!(choices, [("Yes", &serde_json::json!(1)), ("No", &serde_json::json!(0))]);
+        assert_eq!(authorized.describe_fields(), "one of: Yes, No");
+
+        let languages = form.question_with_field("question_31604875[]").expect("multi-select question");
+        assert_eq!(languages.describe_fields(), "any of: Rust, Go, Java");
+
+        let essay = form.question_with_field("question_31604874").expect("essay question");
+        assert_eq!(essay.description.as_deref(), Some("&lt;p&gt;A few sentences is plenty.&lt;/p&gt;"));
+        assert_eq!(essay.describe_fields(), "long text");
+    }
+
+    #[test]
+    fn compliance_sections_parse_with_string_values() {
+        let form = fixture("custom_questions_eeoc");
+        assert_eq!(form.compliance.len(), 1);
+        let eeoc = &form.compliance[0];
+        assert_eq!(eeoc.kind, "eeoc");
+        assert!(eeoc.description.as_deref().is_some_and(|d| d.contains("equal opportunity")));
+        let labels: Vec<&str> = eeoc.questions.iter().map(|q| q.label.as_str()).collect();
+        assert_eq!(labels, ["Gender", "Are you Hispanic/Latino?", "VeteranStatus"]);
+        assert!(eeoc.questions.iter().all(|q| !q.required));
+        assert_eq!(eeoc.questions[0].fields[0].values[2].value, serde_json::json!("3"));
+        // Compliance questions aren't counted among the main form's questions
+        assert_eq!(form.required_questions().count(), 5);
+    }
+
+    #[test]
+    fn boards_without_published_questions_give_an_empty_form() {
+        let form = fixture("no_questions");
+        assert!(form.questions.is_empty());
+        assert!(form.compliance.is_empty());
+    }
+
+    #[test]
+    fn null_sections_and_unknown_field_types_are_tolerated() {
+        let form: ApplicationForm = serde_json::from_value(serde_json::json!({
+            "questions": [{ "label": "Portfolio", "fields": [{ "name": "portfolio", "type": "input_url", "values": null }] }],
+            "compliance": null,
+        }))
+        .expect("parses");
+        assert!(!form.questions[0].required);
+        assert_eq!(form.questions[0].describe_fields(), "input url");
+        assert!(form.compliance.is_empty());
+    }
+}
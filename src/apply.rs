@@ -0,0 +1,192 @@
+use crate::store::{self, PriorApplication};
+use crate::{applying_system, cli, discovery, icons, listed_job, paths, recorded_job, Job, JobResult};
+use std::error::Error;
+use std::io::{self, BufRead, Write};
+
+// `apply --job-url`: one application from a posting's link, for scripts and
+// jobs found elsewhere. The same steps as the confirm screen run in order:
+// the form is fetched and filled from the profile and overrides.toml, what
+// would be sent is printed, and nothing goes out without a yes. A dry run can
+// skip the question with --yes; a real submission can't.
+
+// A job as a link names it; careers pages with only `gh_jid` leave the board out
+#[derive(Debug, PartialEq)]
+pub struct JobLink {
+    pub board: Option<String>,
+    pub job_id: u64,
+}
+
+// boards.greenhouse.io/<token>/jobs/<id>, the same on job-boards.greenhouse.io
+// and the EU hosts, embedded forms (`embed/job_app?for=<token>&token=<id>`),
+// and any page with a `gh_jid=<id>` parameter
+pub fn parse_job_url(url: &str) -> Result<JobLink, String> {
+    let parsed = reqwest::Url::parse(url.trim()).map_err(|e| format!("{} isn't a link: {}", url, e))?;
+    let query = |key: &str| parsed.query_pairs().find(|(name, _)| name == key).map(|(_, value)| value.into_owned());
+    let job_id = |text: &str| text.parse::<u64>().map_err(|_| format!("\"{}\" in {} isn't a job id", text, url));
+
+    let host = parsed.host_str().unwrap_or_default();
+    let greenhouse = host.ends_with(".greenhouse.io") && host.split('.').next().is_some_and(|first| first.ends_with("boards"));
+    if greenhouse {
+        let segments: Vec<&str> = parsed.path_segments().into_iter().flatten().filter(|segment| !segment.is_empty()).collect();
+        match segments.as_slice() {
+            ["embed", "job_app"] => {
+                if let (Some(board), Some(id)) = (query("for"), query("token")) {
+                    return Ok(JobLink { board: Some(board), job_id: job_id(&id)? });
+                }
+            }
+            [board, "jobs", id, ..] => return Ok(JobLink { board: Some(board.to_string()), job_id: job_id(id)? }),
+            _ => {}
+        }
+    }
+    match query("gh_jid") {
+        Some(id) => Ok(JobLink { board: query("for"), job_id: job_id(&id)? }),
+        None => Err(format!(
+            "can't find a job in {}; use the posting's boards.greenhouse.io/<board>/jobs/<id> link or a careers page link with gh_jid",
+            url
+        )),
+    }
+}
+
+pub fn run(cli: &cli::Cli, job_url: &str, board: Option<&str>, profile: Option<&str>, dry_run: bool, yes: bool)
+    -> Result<(), Box<dyn Error>> {
+    let dry_run = dry_run || cli.dry_run;
+    if yes && !dry_run {
+        return Err("--yes only skips the question for a dry run; a real submission always asks".into());
+    }
+    let link = parse_job_url(job_url)?;
+    let store = store::JobStore::open(&paths::database_path())?;
+    let client = reqwest::Client::new();
+    let board = match link.board.as_deref().or(board) {
+        Some(board) => board.to_string(),
+        None => find_board(&client, &store, job_url, link.job_id)?,
+    };
+
+    println!("{}Looking up job {} on {}...", icons::SEARCHING, link.job_id, board);
+    let job = block_on(fetch_posting(&client, &board, link.job_id))?;
+    println!("{}{} at {} ({})\n", icons::JOB, job.title, job.company, job.url);
+
+    let mut app_system = applying_system(cli, vec![job.clone()]);
+    if let Some(profile) = profile {
+        app_system.profile_name = Some(profile.to_string());
+    }
+    app_system.dry_run = dry_run;
+    app_system.attach_store(store);
+    if let Some(PriorApplication::Applied(record)) = app_system.prior_application(&job) {
+        println!("{}You already applied to this job on {}", icons::WARNING, record.applied_date());
+    }
+    if let Some(reason) = app_system.rate_limited().filter(|_| !dry_run) {
+        return Err(reason.into());
+    }
+
+    app_system.selected_job_index = Some(0);
+    app_system.confirm_application();
+    println!("{}Loading the application form...", icons::SEARCHING);
+    app_system.load_application_form();
+    if let Some(unavailable) = app_system.api_unavailable(&job) {
+        return Err(format!("the API can't take this application ({}); apply at {}", unavailable, job.url).into());
+    }
+    app_system.filled_form(&job)?;
+    for line in app_system.submission_preview(&job) {
+        println!("  {}", line);
+    }
+    if app_system.filled_form(&job).is_ok_and(|(_, filled)| !filled.problems.is_empty()) {
+        return Err("some required questions aren't answered; fill them in with the job browser or overrides.toml".into());
+    }
+
+    let question = if dry_run { "Write the dry run?" } else { "Submit this application?" };
+    if !yes && !confirm(question, &mut io::stdin().lock().lines())? {
+        println!("{}Nothing sent", icons::SKIPPED);
+        return Ok(());
+    }
+
+    if dry_run {
+        app_system.dry_run_application();
+        for line in app_system.dry_run_result.iter().flat_map(|report| report.lines()) {
+            println!("  {}", line);
+        }
+        if let Some(status) = &app_system.status_message {
+            println!("{}{}", icons::DRY_RUN, status);
+        }
+        return Ok(());
+    }
+    println!("{}Submitting...", icons::APPLICATIONS);
+    app_system.apply_to_job();
+    match &app_system.submission {
+        Some(Ok(())) => {
+            println!("{}Submitted. {}", icons::DONE, app_system.status_message.as_deref().unwrap_or_default());
+            Ok(())
+        }
+        Some(Err(e)) => {
+            for field in &e.fields {
+                println!("  {}: {}", field.field, field.message);
+            }
+            if let Some(status) = app_system.status_message.as_deref().filter(|_| e.transient) {
+                println!("{}{}", icons::QUEUE, status);
+            }
+            Err(format!("not submitted: {}", e.message).into())
+        }
+        None => Err("nothing was sent".into()),
+    }
+}
+
+// For careers page links: the job store's record of the job, then the
+// boards the page links to, as long as there's only one
+fn find_board(client: &reqwest::Client, store: &store::JobStore, url: &str, job_id: u64) -> Result<String, Box<dyn Error>> {
+    if let Ok(job) = recorded_job(store, job_id, None) {
+        return Ok(job.board_token);
+    }
+    let boards = block_on(discovery::from_url(client, url)).unwrap_or_default();
+    let mut boards: Vec<String> = boards.into_iter().collect();
+    match boards.len() {
+        1 => Ok(boards.remove(0)),
+        0 => Err(format!("can't tell which board job {} is on from {}; pass --board", job_id, url).into()),
+        _ => {
+            boards.sort();
+            Err(format!("{} links to several boards ({}); pick one with --board", url, boards.join(", ")).into())
+        }
+    }
+}
+
+async fn fetch_posting(client: &reqwest::Client, board: &str, job_id: u64) -> Result<JobResult, String> {
+    let url = format!("https://boards-api.greenhouse.io/v1/boards/{}/jobs/{}", board, job_id);
+    let response = client.get(&url).send().await.map_err(|e| format!("network error: {}", e))?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Err(format!("{} has no job {}; it may have been taken down", board, job_id));
+    }
+    if !response.status().is_success() {
+        return Err(format!("board returned status {}", response.status()));
+    }
+    let job: Job = response.json().await.map_err(|e| format!("unexpected response: {}", e))?;
+    Ok(listed_job(board, &job))
+}
+
+// Commands run synchronously inside the runtime, so block on the fetches
+fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    tokio::task::block_in_place(|| tokio::runtime::Handle::current().block_on(future))
+}
+
+// Anything but y, including the end of input, is a no
+pub fn confirm<B: BufRead>(question: &str, lines: &mut io::Lines<B>) -> io::Result<bool> {
+    print!("{} (y/n): ", question);
+    io::stdout().flush()?;
+    Ok(lines.next().transpose()?.is_some_and(|answer| matches!(answer.trim(), "y" | "yes")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn job_links_give_the_board_and_job_id() {
+        let link = |board: Option<&str>, job_id| JobLink { board: board.map(str::to_string), job_id };
+        assert_eq!(parse_job_url("https://boards.greenhouse.io/figma/jobs/5012345").unwrap(), link(Some("figma"), 5012345));
+        assert_eq!(parse_job_url("https://job-boards.greenhouse.io/figma/jobs/5012345?gh_src=abc").unwrap(), link(Some("figma"), 5012345));
+        assert_eq!(parse_job_url("https://job-boards.eu.greenhouse.io/monzo/jobs/77").unwrap(), link(Some("monzo"), 77));
+        assert_eq!(parse_job_url("https://boards.greenhouse.io/embed/job_app?for=figma&token=5012345").unwrap(), link(Some("figma"), 5012345));
+        assert_eq!(parse_job_url("https://www.figma.com/careers/job/?gh_jid=5012345").unwrap(), link(None, 5012345));
+
+        assert!(parse_job_url("https://boards.greenhouse.io/figma").is_err());
+        assert!(parse_job_url("https://boards.greenhouse.io/figma/jobs/latest").is_err());
+        assert!(parse_job_url("figma 5012345").is_err());
+    }
+}
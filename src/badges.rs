@@ -0,0 +1,141 @@
+use crate::JobResult;
+use chrono::{DateTime, Utc};
+use ratatui::style::Color;
+
+// Short badges in the job list for what's usually worth knowing before
+// opening a job: where it's worked from, how senior it is and whether it was
+// posted in the last few days. All of it is guessed from the listing's
+// location, title and date; anything that can't be told is left off.
+
+// Posted within this many days counts as fresh
+const FRESH_DAYS: i64 = 3;
+
+// Title words by seniority, most senior first; the first one found wins
+const SENIORITY: &[(&[&str], &str)] = &[
+    (&["vp", "svp", "evp"], "VP"),
+    (&["head"], "HEAD"),
+    (&["director"], "DIR"),
+    (&["principal"], "PRINCIPAL"),
+    (&["staff"], "STAFF"),
+    (&["senior", "sr"], "SR"),
+    (&["lead"], "LEAD"),
+    (&["junior", "jr", "entry"], "JR"),
+    (&["intern", "internship"], "INTERN"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Badge {
+    pub label: &'static str,
+    pub color: Color,
+}
+
+// Workplace, then seniority, then freshness
+pub fn for_job(job: &JobResult, now: DateTime<Utc>) -> Vec<Badge> {
+    let mut badges = Vec::new();
+    if let Some(label) = workplace(&job.location) {
+        let color = match label {
+            "REMOTE" => Color::Green,
+            "HYBRID" => Color::Cyan,
+            _ => Color::Gray,
+        };
+        badges.push(Badge { label, color });
+    }
+    if let Some(label) = seniority(&job.title) {
+        badges.push(Badge { label, color: Color::Magenta });
+    }
+    if is_fresh(&job.date_posted, now) {
+        badges.push(Badge { label: "NEW", color: Color::Yellow });
+    }
+    badges
+}
+
+impl Badge {
+    // The badge as it's read out in the accessible browser, where "SR" or "JR"
+    // would be spelled out letter by letter
+    pub fn words(&self) -> &'static str {
+        match self.label {
+            "REMOTE" => "remote",
+            "HYBRID" => "hybrid",
+            "ONSITE" => "on-site",
+            "VP" => "vice president",
+            "HEAD" => "head",
+            "DIR" => "director",
+            "PRINCIPAL" => "principal",
+            "STAFF" => "staff",
+            "SR" => "senior",
+            "LEAD" => "lead",
+            "JR" => "junior",
+            "INTERN" => "internship",
+            "NEW" => "posted in the last few days",
+            other => other,
+        }
+    }
+}
+
+// "Remote - US" is remote, "Hybrid (NYC)" hybrid, any other named place on-site
+pub fn workplace(location: &str) -> Option<&'static str> {
+    let location = location.to_lowercase();
+    if location.trim().is_empty() {
+        None
+    } else if location.contains("hybrid") {
+        Some("HYBRID")
+    } else if location.contains("remote") || location.contains("anywhere") {
+        Some("REMOTE")
+    } else {
+        Some("ONSITE")
+    }
+}
+
+pub fn seniority(title: &str) -> Option<&'static str> {
+    let title = title.to_lowercase();
+    let words: Vec<&str> = title.split(|c: char| !c.is_alphanumeric()).filter(|word| !word.is_empty()).collect();
+    SENIORITY
+        .iter()
+        .find(|(markers, _)| words.iter().any(|word| markers.contains(word)))
+        .map(|(_, label)| *label)
+}
+
+// Unparseable dates are never fresh
+pub fn is_fresh(date_posted: &str, now: DateTime<Utc>) -> bool {
+    DateTime::parse_from_rfc3339(date_posted)
+        .is_ok_and(|posted| now.signed_duration_since(posted) < chrono::Duration::days(FRESH_DAYS))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seniority_takes_the_most_senior_title_word() {
+        assert_eq!(seniority("Senior Staff Software Engineer"), Some("STAFF"));
+        assert_eq!(seniority("Sr. Product Manager"), Some("SR"));
+        assert_eq!(seniority("Head of Design"), Some("HEAD"));
+        assert_eq!(seniority("Software Engineering Intern, Summer"), Some("INTERN"));
+        // Whole words only
+        assert_eq!(seniority("Leadership Development Manager"), None);
+        assert_eq!(seniority("Product Manager"), None);
+    }
+
+    #[test]
+    fn workplace_and_freshness_come_from_location_and_date() {
+        assert_eq!(workplace("Remote - US"), Some("REMOTE"));
+        assert_eq!(workplace("Hybrid (Remote 2 days), New York"), Some("HYBRID"));
+        assert_eq!(workplace("San Francisco, CA"), Some("ONSITE"));
+        assert_eq!(workplace("  "), None);
+
+        let now = DateTime::parse_from_rfc3339("2026-10-15T12:00:00Z").unwrap().with_timezone(&Utc);
+        assert!(is_fresh("2026-10-13T09:00:00-04:00", now));
+        assert!(!is_fresh("2026-10-12T11:00:00Z", now));
+        assert!(!is_fresh("yesterday", now));
+    }
+
+    #[test]
+    fn every_badge_has_words_for_screen_readers() {
+        let labels = SENIORITY.iter().map(|(_, label)| *label).chain(["REMOTE", "HYBRID", "ONSITE", "NEW"]);
+        for label in labels {
+            let words = Badge { label, color: Color::Gray }.words();
+            assert_ne!(words, label);
+            assert_eq!(words, words.to_lowercase());
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use crate::profile::Profile;
+use crate::JobResult;
+
+// Applying by hand in the browser, for jobs the Job Board API can't take: the
+// board doesn't publish the job's questions, or turned the submission itself
+// away. The posting opens with what can go in its query string: the gh_src
+// source token from [applications] in config.toml, and the profile's contact
+// details for hosted forms that pick them up. Everything else is typed in the
+// browser, and the application log keeps a follow-up until it's marked done.
+
+pub fn prefilled_url(job: &JobResult, profile: Option<&Profile>, gh_src: Option<&str>) -> String {
+    let Ok(mut url) = reqwest::Url::parse(&job.url) else {
+        return job.url.clone();
+    };
+    {
+        let mut query = url.query_pairs_mut();
+        if let Some(source) = gh_src {
+            query.append_pair("gh_src", source);
+        }
+        if let Some(profile) = profile {
+            query.append_pair("first_name", &profile.first_name);
+            query.append_pair("last_name", &profile.last_name);
+            query.append_pair("email", &profile.email);
+            if let Some(phone) = &profile.phone {
+                query.append_pair("phone", phone);
+            }
+        }
+    }
+    url.into()
+}
+
+// The platform's "open this URL" command, without waiting for the browser
+pub fn open(url: &str) -> Result<(), String> {
+    let mut command = if cfg!(target_os = "macos") {
+        std::process::Command::new("open")
+    } else if cfg!(windows) {
+        let mut command = std::process::Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    } else {
+        std::process::Command::new("xdg-open")
+    };
+    command
+        .arg(url)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| format!("couldn't start a browser ({}); open {} yourself", e, url))
+}
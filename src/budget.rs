@@ -0,0 +1,44 @@
+//! A ceiling on the HTTP requests one search makes (`--max-requests`).
+//!
+//! Each request takes a turn before it starts; once the turns run out nothing
+//! new starts and the search carries on with what it already has, so the
+//! results can be incomplete. Requests already under way finish. A searcher
+//! given a budget through
+//! [`GreenhouseJobSearcherBuilder::request_budget`](crate::search::GreenhouseJobSearcherBuilder::request_budget)
+//! claims a turn for every request it sends; the command shares the same
+//! budget with discovery and the passes after the search, and starts the count
+//! over with every search, so each --watch cycle gets the full allowance.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+#[derive(Debug)]
+pub struct RequestBudget {
+    max: usize,
+    used: AtomicUsize,
+}
+
+impl RequestBudget {
+    pub fn new(max: usize) -> Arc<Self> {
+        Arc::new(RequestBudget { max, used: AtomicUsize::new(0) })
+    }
+
+    pub fn max(&self) -> usize {
+        self.max
+    }
+
+    /// Claims one request, or returns false once all of them are spoken for
+    pub fn take(&self) -> bool {
+        self.used
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |used| (used < self.max).then_some(used + 1))
+            .is_ok()
+    }
+
+    pub fn exhausted(&self) -> bool {
+        self.used.load(Ordering::SeqCst) >= self.max
+    }
+
+    pub fn reset(&self) {
+        self.used.store(0, Ordering::SeqCst);
+    }
+}
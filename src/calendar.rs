@@ -0,0 +1,146 @@
+use crate::{extract, posted_at, JobResult};
+use chrono::{Datelike, NaiveDate};
+use regex::Regex;
+use std::path::Path;
+use std::sync::OnceLock;
+
+// --ics-out: an iCalendar file with an all-day event on each job's application
+// deadline, for jobs whose description states one ("Applications close July 3,
+// 2024", "Deadline: 2024-07-03"). Jobs without a recognizable deadline are left out.
+
+// Phrases that introduce a deadline; the date is looked for after the phrase,
+// or on the next line when the phrase is a heading of its own
+fn cue() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(
+            r"(?i)\b(deadline|apply by|closing date|no later than|applications? (close|closes|closing|due|will close|must be (received|submitted)|(are )?accepted (until|through))|accepting applications (until|through))\b",
+        )
+        .expect("valid regex")
+    })
+}
+
+const MONTHS: &str = "jan|feb|mar|apr|may|jun|jul|aug|sep|oct|nov|dec";
+
+fn iso_date() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| Regex::new(r"\b(\d{4})-(\d{1,2})-(\d{1,2})\b").expect("valid regex"))
+}
+
+// "July 3, 2024", "Jul. 3rd", "3 July 2024", "3rd of July" and "7/3/2024" (month first)
+fn written_date() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(&format!(
+            r"(?i)\b(?:(?P<m1>{m})[a-z]*\.?\s+(?P<d1>\d{{1,2}})(?:st|nd|rd|th)?|(?P<d2>\d{{1,2}})(?:st|nd|rd|th)?\s+(?:of\s+)?(?P<m2>{m})[a-z]*\.?)(?:,?\s+(?P<y>\d{{4}}))?\b|\b(?P<nm>\d{{1,2}})/(?P<nd>\d{{1,2}})/(?P<ny>\d{{4}})\b",
+            m = MONTHS
+        ))
+        .expect("valid regex")
+    })
+}
+
+// The first date in `text`. Without a year, the first such day on or after `after`.
+fn first_date(text: &str, after: NaiveDate) -> Option<NaiveDate> {
+    let iso = iso_date().captures(text).and_then(|caps| {
+        let start = caps.get(0)?.start();
+        let date = NaiveDate::from_ymd_opt(caps[1].parse().ok()?, caps[2].parse().ok()?, caps[3].parse().ok()?)?;
+        Some((start, date))
+    });
+    let written = written_date().captures_iter(text).find_map(|caps| {
+        let start = caps.get(0)?.start();
+        if let (Some(month), Some(day), Some(year)) = (caps.name("nm"), caps.name("nd"), caps.name("ny")) {
+            let date = NaiveDate::from_ymd_opt(year.as_str().parse().ok()?, month.as_str().parse().ok()?, day.as_str().parse().ok()?)?;
+            return Some((start, date));
+        }
+        let month_name = caps.name("m1").or_else(|| caps.name("m2"))?.as_str().to_lowercase();
+        let month = MONTHS.split('|').position(|m| month_name.starts_with(m))? as u32 + 1;
+        let day: u32 = caps.name("d1").or_else(|| caps.name("d2"))?.as_str().parse().ok()?;
+        let date = match caps.name("y") {
+            Some(year) => NaiveDate::from_ymd_opt(year.as_str().parse().ok()?, month, day)?,
+            None => {
+                let this_year = NaiveDate::from_ymd_opt(after.year(), month, day)?;
+                if this_year >= after { this_year } else { NaiveDate::from_ymd_opt(after.year() + 1, month, day)? }
+            }
+        };
+        Some((start, date))
+    });
+    [iso, written].into_iter().flatten().min_by_key(|(start, _)| *start).map(|(_, date)| date)
+}
+
+// The application deadline stated in an HTML job description, if any.
+// Dates without a year are placed on or after `posted`.
+pub fn deadline(content: &str, posted: NaiveDate) -> Option<NaiveDate> {
+    let lines = extract::plain_text(content);
+    lines.iter().enumerate().find_map(|(index, line)| {
+        let found = cue().find(line)?;
+        first_date(&line[found.end()..], posted).or_else(|| {
+            let next = lines.get(index + 1)?;
+            first_date(next, posted)
+        })
+    })
+}
+
+fn job_deadline(job: &JobResult) -> Option<NaiveDate> {
+    let content = job.raw.get("content").and_then(serde_json::Value::as_str)?;
+    let posted = posted_at(job).map(|instant| instant.date_naive()).unwrap_or_else(|| chrono::Utc::now().date_naive());
+    deadline(content, posted)
+}
+
+// Writes one event per job with a deadline and returns how many were written
+pub fn write_ics(path: &Path, jobs: &[JobResult]) -> Result<usize, String> {
+    let stamp = chrono::Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//greenhouse-job-search//Application deadlines//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+    ];
+    let mut events = 0;
+    for job in jobs {
+        let Some(date) = job_deadline(job) else {
+            continue;
+        };
+        events += 1;
+        let next_day = date.succ_opt().unwrap_or(date);
+        lines.extend([
+            "BEGIN:VEVENT".to_string(),
+            format!("UID:{}-{}@greenhouse-job-search", job.board_token, job.id),
+            format!("DTSTAMP:{}", stamp),
+            format!("DTSTART;VALUE=DATE:{}", date.format("%Y%m%d")),
+            format!("DTEND;VALUE=DATE:{}", next_day.format("%Y%m%d")),
+            format!("SUMMARY:{}", escape(&format!("Application deadline: {} at {}", job.title, job.company))),
+            format!("DESCRIPTION:{}", escape(&format!("{}\n{}", job.location, job.url))),
+            format!("URL:{}", job.url),
+            "TRANSP:TRANSPARENT".to_string(),
+            "END:VEVENT".to_string(),
+        ]);
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    let text: String = lines.iter().map(|line| fold(line) + "\r\n").collect();
+    let tmp = path.with_extension("ics.tmp");
+    std::fs::write(&tmp, text).map_err(|e| e.to_string())?;
+    std::fs::rename(&tmp, path).map_err(|e| e.to_string())?;
+    Ok(events)
+}
+
+// TEXT values escape backslashes, separators and newlines (RFC 5545 3.3.11)
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace(';', "\\;").replace(',', "\\,").replace('\n', "\\n")
+}
+
+// Lines longer than 75 octets continue on the next line after a space,
+// split on character boundaries
+fn fold(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len());
+    let mut width = 0;
+    for ch in line.chars() {
+        if width + ch.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            width = 1;
+        }
+        folded.push(ch);
+        width += ch.len_utf8();
+    }
+    folded
+}
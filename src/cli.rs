@@ -0,0 +1,722 @@
+use clap::{Parser, Subcommand, ValueEnum};
+
+#[derive(Debug, Parser)]
+#[command(name = "greenhouse-job-search", version, about = "Search Greenhouse job boards and browse the matches")]
+pub struct Cli {
+    /// Job title keywords to match (all words must match); an empty keyword matches every title.
+    /// Weight terms as term:weight, e.g. "principal:3 product manager:2" (unweighted terms count 1),
+    /// to match titles with enough of the weight and rank them by it instead
+    #[arg(long, default_value = "principal product manager")]
+    pub keyword: String,
+
+    /// With weighted keywords, the share of the total weight (in percent) a title needs to match
+    #[arg(long, value_name = "PERCENT", value_parser = clap::value_parser!(u8).range(1..=100))]
+    pub min_relevance: Option<u8>,
+
+    /// Skip title matching and list every job that passes the location and other filters
+    #[arg(long, conflicts_with = "keyword")]
+    pub all_titles: bool,
+
+    /// Location to match (remote and Bay Area listings are always included)
+    #[arg(long, default_value = "94555")]
+    pub location: String,
+
+    /// Only keep jobs with a department containing this text (any of a job's departments may match)
+    #[arg(long, value_name = "TEXT")]
+    pub department: Option<String>,
+
+    /// Treat --location as a case-insensitive regex over the job's location and offices,
+    /// e.g. "(CA|WA|OR)"; replaces the built-in remote and Bay Area matching
+    #[arg(long)]
+    pub location_regex: bool,
+
+    /// Match keywords only as whole words, not anywhere inside a word ("manager" vs "mismanager")
+    #[arg(long)]
+    pub whole_word: bool,
+
+    /// Fold keywords and titles to Unicode NFKC before matching, with ASCII dashes and quotes
+    /// and no periods, so typographic variants ("Sr." and "Sr", "ﬁ" and "fi") match
+    #[arg(long)]
+    pub normalize_unicode: bool,
+
+    /// With --normalize-unicode, also ignore accents ("Développeur" matches "developpeur")
+    #[arg(long, requires = "normalize_unicode")]
+    pub strip_diacritics: bool,
+
+    /// Keep config, data and cache together in this folder instead of the platform's standard directories
+    #[arg(long, value_name = "DIR", env = "GREENHOUSE_JOB_SEARCH_DIR")]
+    pub config_dir: Option<std::path::PathBuf>,
+
+    /// Format dates and numbers for this locale, e.g. en-GB or de-DE (defaults to the system locale)
+    #[arg(long, value_name = "TAG")]
+    pub locale: Option<String>,
+
+    /// Browse results with a plain line-by-line prompt instead of the full-screen TUI
+    #[arg(long)]
+    pub accessible: bool,
+
+    /// Show plain-text labels instead of emoji, as happens anyway when output isn't a UTF-8 terminal
+    #[arg(long)]
+    pub no_emoji: bool,
+
+    /// Show debugging details, like the overrides.toml patterns no question on a form matched
+    #[arg(long)]
+    pub debug: bool,
+
+    /// Only show matches that have never been recorded in the job store
+    #[arg(long, conflicts_with = "since")]
+    pub new_only: bool,
+
+    /// Only show matches first recorded on or after this date (YYYY-MM-DD)
+    #[arg(long, value_name = "DATE")]
+    pub since: Option<chrono::NaiveDate>,
+
+    /// Print one line per job, truncated to the terminal width
+    #[arg(long)]
+    pub compact: bool,
+
+    /// Print the results under a header per company or department, sorted by name, instead of
+    /// one flat list
+    #[arg(long, value_enum, value_name = "FIELD")]
+    pub group_by: Option<GroupBy>,
+
+    /// Show results that don't fit on the screen through $PAGER (less by default). Off when
+    /// stdout isn't a terminal and with --json-out
+    #[arg(long)]
+    pub pager: bool,
+
+    /// Print a bar chart of matches per day or week of posting
+    #[arg(long, value_enum, value_name = "BUCKET")]
+    pub histogram: Option<HistogramBucket>,
+
+    /// Print how many matches each company and each department has
+    #[arg(long)]
+    pub summary: bool,
+
+    /// Leave jobs you've already applied to out of the printed results
+    #[arg(long)]
+    pub ignore_applied: bool,
+
+    /// Keep only the most relevant of the results sharing a key: "url", "id" (the Greenhouse
+    /// job id) or both, e.g. "url,id"
+    #[arg(long, value_enum, value_name = "KEYS", value_delimiter = ',')]
+    pub dedupe: Vec<DedupeKey>,
+
+    /// Write the results, with the search parameters and metadata, to a JSON file
+    #[arg(long, value_name = "PATH")]
+    pub json_out: Option<std::path::PathBuf>,
+
+    /// With --json-out, write only the results array without the metadata envelope
+    #[arg(long, requires = "json_out")]
+    pub bare: bool,
+
+    /// Write the results to this file as CSV (.csv), Markdown (.md) or JSON (any other extension)
+    #[arg(long, value_name = "PATH")]
+    pub export: Option<std::path::PathBuf>,
+
+    /// Add your tags, application status and notes from the job store to --json-out and --export
+    #[arg(long)]
+    pub include_annotations: bool,
+
+    /// Capture a labeled section of each job description, e.g. --extract "Tech Stack" --extract Team
+    #[arg(long, value_name = "LABEL")]
+    pub extract: Vec<String>,
+
+    /// Merge matches into an Atom feed file for feed readers
+    #[arg(long, value_name = "PATH")]
+    pub feed: Option<std::path::PathBuf>,
+
+    /// Number of most recent entries kept in the --feed file
+    #[arg(long, value_name = "N", default_value_t = 200, requires = "feed")]
+    pub feed_limit: usize,
+
+    /// Write an iCalendar file with an event on each application deadline found in the descriptions
+    #[arg(long, value_name = "PATH")]
+    pub ics_out: Option<std::path::PathBuf>,
+
+    /// Replace details in everything the run prints, exports or sends, for sharing
+    /// (the key is kept in the data directory)
+    #[arg(long, value_enum, value_name = "WHAT", value_delimiter = ',')]
+    pub redact: Vec<RedactTarget>,
+
+    /// Only show jobs with this tag; repeat for several (all must match). Also applies to `history show`
+    #[arg(long, value_name = "TAG")]
+    pub tag: Vec<String>,
+
+    /// Stop collecting matches from a board once it has produced N. Speeds up broad searches,
+    /// but by design misses any further matches listed later on that board
+    #[arg(long, value_name = "N")]
+    pub max_matches_per_board: Option<std::num::NonZeroUsize>,
+
+    /// Search at most N of the discovered boards: the first N by board token, after merging
+    /// every discovery source and leaving out boards still backing off. Bounds exploratory runs
+    #[arg(long, value_name = "N")]
+    pub max_boards: Option<std::num::NonZeroUsize>,
+
+    /// Stop starting new requests once a search has made N, counting discovery and board fetches.
+    /// A safety valve for huge board lists: the boards left over aren't searched, so the results
+    /// may be incomplete. Each --watch cycle starts a fresh count
+    #[arg(long, value_name = "N")]
+    pub max_requests: Option<std::num::NonZeroUsize>,
+
+    /// After the results, break down how many jobs each filter left out (title, location,
+    /// department, age, ...), counting each job under the first filter it failed
+    #[arg(long)]
+    pub stats: bool,
+
+    /// Include jobs you marked "not interested" (x in the browser) in the results
+    #[arg(long)]
+    pub show_hidden: bool,
+
+    /// Where board tokens come from, tried in order, e.g. "boards-file,discover-from-url,google,builtin"
+    /// [default: boards-file and discover-from-url when given, then google,builtin]
+    #[arg(long, value_enum, value_name = "SOURCES", value_delimiter = ',')]
+    pub discover: Vec<DiscoverySource>,
+
+    /// With --discover, stop at the first source that finds any boards, or run them all and merge
+    #[arg(long, value_enum, value_name = "MODE", default_value = "first")]
+    pub discover_mode: DiscoverMode,
+
+    /// Fetch at most this many boards at once [default: every board at once]
+    #[arg(long, value_name = "N")]
+    pub concurrency: Option<std::num::NonZeroUsize>,
+
+    /// Start with a few boards at once and adjust to stay just under Greenhouse's rate limits
+    #[arg(long, conflicts_with = "concurrency")]
+    pub auto_concurrency: bool,
+
+    /// Make at most this many requests at once in the --verify-live and --resolve-urls passes
+    /// after the search, apart from --concurrency. They count toward --max-requests
+    #[arg(long, value_name = "N", default_value = "1")]
+    pub post_concurrency: std::num::NonZeroUsize,
+
+    /// After the search, ask Greenhouse for each match again and leave out the ones taken down since
+    #[arg(long)]
+    pub verify_live: bool,
+
+    /// After the search, follow each match's URL to where it ends up, e.g. a company career site
+    #[arg(long)]
+    pub resolve_urls: bool,
+
+    /// After discovery, choose which boards to search from a checklist
+    #[arg(long, conflicts_with_all = ["process_retry_queue", "quiet_notify"])]
+    pub pick_boards: bool,
+
+    /// File of board tokens or board URLs to search, one per line (# starts a comment);
+    /// "-" reads them from stdin, and leaves out the prompts that would read it too
+    #[arg(long, value_name = "PATH")]
+    pub boards: Option<std::path::PathBuf>,
+
+    /// File of favorite board tokens or board URLs, one per line (# starts a comment); their
+    /// matches are listed first and highlighted, each group still in the usual order
+    #[arg(long, value_name = "PATH")]
+    pub favorites: Option<std::path::PathBuf>,
+
+    /// Page to collect Greenhouse board links from; repeat for several
+    #[arg(long, value_name = "URL")]
+    pub discover_url: Vec<String>,
+
+    /// Search only the boards that failed or were rate limited in earlier runs
+    #[arg(long)]
+    pub process_retry_queue: bool,
+
+    /// Save this run's full result set under runs/ in the data directory so `history show`
+    /// can bring it back. On by default once the data directory exists
+    #[arg(long)]
+    pub save_results: bool,
+
+    /// Don't save this run's results
+    #[arg(long, conflicts_with = "save_results")]
+    pub no_save_results: bool,
+
+    /// Number of most recent saved runs to keep
+    #[arg(long, value_name = "N", default_value_t = std::num::NonZeroUsize::new(crate::runs::DEFAULT_KEEP).unwrap())]
+    pub keep_runs: std::num::NonZeroUsize,
+
+    /// Keep a self-updating HTML dashboard (index.html plus its data file) in this directory
+    #[arg(long, value_name = "DIR")]
+    pub dashboard: Option<std::path::PathBuf>,
+
+    /// Extra query parameters appended to every board's jobs URL, e.g. "for=acme&internal=false"
+    #[arg(long, value_name = "QUERY", value_parser = parse_board_query)]
+    pub board_query: Option<String>,
+
+    /// List boards without descriptions, departments and offices, and fetch those only for the
+    /// jobs that pass the title and location filters. Faster on broad searches, but
+    /// --location-regex can't match office names before the fetch
+    #[arg(long)]
+    pub lite: bool,
+
+    /// Send notifications about new matches; repeat for several targets
+    /// (desktop quiet hours and webhook URLs can be set in config.toml)
+    #[arg(long, value_enum, value_name = "TARGET")]
+    pub notify: Vec<NotifyTarget>,
+
+    /// Slack incoming webhook URL used by --notify slack
+    #[arg(long, env = "SLACK_WEBHOOK_URL", value_name = "URL", hide_env_values = true)]
+    pub slack_webhook_url: Option<String>,
+
+    /// Discord webhook URL used by --notify discord
+    #[arg(long, env = "DISCORD_WEBHOOK_URL", value_name = "URL", hide_env_values = true)]
+    pub discord_webhook_url: Option<String>,
+
+    /// For cron: search for unseen matches, send them to the --notify targets and write the
+    /// output to quiet_notify.log in the data directory instead of stdout. Skips the run if the
+    /// previous one is still going. Exits 0 on success, 1 on failure, 2 for invalid options,
+    /// 3 when skipped
+    #[arg(long, requires = "notify", conflicts_with_all = ["since", "notify_test", "set_credential"])]
+    pub quiet_notify: bool,
+
+    /// Endpoint that --notify webhook POSTs new matches to as versioned JSON
+    #[arg(long, env = "WEBHOOK_URL", value_name = "URL", hide_env_values = true)]
+    pub webhook_url: Option<String>,
+
+    /// Shared secret for signing --notify webhook requests (HMAC-SHA256 in the X-Signature-256 header)
+    #[arg(long, env = "WEBHOOK_SECRET", value_name = "SECRET", hide_env_values = true)]
+    pub webhook_secret: Option<String>,
+
+    /// Attempts per --notify webhook request before giving up; server errors are retried with backoff
+    #[arg(long, value_name = "N", default_value_t = 3, value_parser = clap::value_parser!(u32).range(1..=10))]
+    pub webhook_attempts: u32,
+
+    /// Send a sample notification to each --notify target and exit
+    #[arg(long, requires = "notify")]
+    pub notify_test: bool,
+
+    /// Send this resume (.pdf or .docx) with applications, instead of the profile's resume
+    /// and any per-board [resumes] entry in config.toml
+    #[arg(long, value_name = "PATH")]
+    pub resume: Option<std::path::PathBuf>,
+
+    /// Apply as this named profile from profile.toml's [profiles.<name>] tables instead of the
+    /// main one; the confirm screen can switch per job
+    #[arg(long, value_name = "NAME")]
+    pub profile_name: Option<String>,
+
+    /// Go through every step of applying but write what would be sent to a file instead
+    /// of submitting it (`d` on the confirm screen switches this in the TUI)
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Attach the cover letter as plain text, or as a PDF with your name and contact details at the
+    /// top, to forms that take it as a file [default: format under [cover_letter] in config.toml, else txt]
+    #[arg(long, value_enum, value_name = "FORMAT")]
+    pub letter_format: Option<crate::cover_letter::LetterFormat>,
+
+    /// Lift the cap on applications submitted per 24 hours (daily_limit in config.toml) for this
+    /// session, after typing a confirmation. Not meant for regular use
+    #[arg(long)]
+    pub override_rate_limit: bool,
+
+    /// Store a secret for an authenticated board in the system keychain and exit
+    #[arg(long, value_name = "NAME")]
+    pub set_credential: Option<String>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+impl Cli {
+    // The keyword actually searched for; empty means every title matches
+    pub fn title_keyword(&self) -> &str {
+        if self.all_titles {
+            ""
+        } else {
+            &self.keyword
+        }
+    }
+
+    // `--boards -`: stdin carries board tokens, so nothing may prompt on it
+    pub fn boards_from_stdin(&self) -> bool {
+        self.boards.as_deref() == Some(std::path::Path::new("-"))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum HistogramBucket {
+    Day,
+    Week,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum DedupeKey {
+    Url,
+    Id,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum GroupBy {
+    Company,
+    Department,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum DiscoverySource {
+    BoardsFile,
+    DiscoverFromUrl,
+    Google,
+    Builtin,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum DiscoverMode {
+    First,
+    Union,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum RedactTarget {
+    /// Company names, board tokens and job links become "Company A", "Company B", ...
+    Companies,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum StatsFormat {
+    Text,
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum NotifyTarget {
+    Desktop,
+    Slack,
+    Discord,
+    Email,
+    Webhook,
+}
+
+// Accepts `key=value` pairs joined by `&`; values may be empty but keys may not
+fn parse_board_query(raw: &str) -> Result<String, String> {
+    let query = raw.trim().trim_start_matches('?');
+    if query.is_empty() {
+        return Err("query string is empty".to_string());
+    }
+
+    for pair in query.split('&') {
+        let (key, value) = pair
+            .split_once('=')
+            .ok_or_else(|| format!("\"{}\" is not a key=value pair", pair))?;
+        if key.is_empty() {
+            return Err(format!("\"{}\" has an empty key", pair));
+        }
+        if let Some(c) = pair.chars().find(|c| c.is_whitespace() || matches!(c, '?' | '#' | '/')) {
+            return Err(format!("\"{}\" contains '{}'; URL-encode it first", pair, c));
+        }
+        if value.contains('=') {
+            return Err(format!("\"{}\" contains more than one '='", pair));
+        }
+    }
+
+    Ok(query.to_string())
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Inspect the local job store
+    Db {
+        #[command(subcommand)]
+        action: DbCommand,
+    },
+    /// Review the log of jobs you've applied to
+    Applications {
+        #[command(subcommand)]
+        action: ApplicationsCommand,
+    },
+    /// Apply to one job from its link without the job browser: the form is fetched and filled
+    /// from the profile and overrides.toml, shown, and sent once confirmed
+    Apply {
+        /// The posting's link: boards.greenhouse.io/<token>/jobs/<id>, job-boards.greenhouse.io,
+        /// or a company careers page with a gh_jid parameter
+        #[arg(long, value_name = "URL")]
+        job_url: String,
+        /// The job's board, for careers pages that don't say and jobs the job store doesn't know
+        #[arg(long, value_name = "TOKEN")]
+        board: Option<String>,
+        /// Apply as this profile from profile.toml instead of --profile-name or the main one
+        #[arg(long, value_name = "NAME")]
+        profile: Option<String>,
+        /// Go through every step but write what would be sent to a file instead of submitting it
+        #[arg(long)]
+        dry_run: bool,
+        /// Don't ask before a dry run; a real submission always asks
+        #[arg(long)]
+        yes: bool,
+    },
+    /// Queue jobs to apply to, then go through them one at a time with `queue process`
+    Queue {
+        #[command(subcommand)]
+        action: QueueCommand,
+    },
+    /// Move the local state (job store, application log, settings) between machines
+    State {
+        #[command(subcommand)]
+        action: StateCommand,
+    },
+    /// Print where config, data and cache files are kept
+    Paths,
+    /// Check the setup: the Greenhouse API and Google are reachable, config.toml, profile.toml
+    /// and --boards parse, resumes exist, the job store opens and the terminal can run the
+    /// job browser. Exits non-zero when a critical check fails
+    Doctor,
+    /// List recent searches with their parameters and outcomes
+    History {
+        /// How many runs to list
+        #[arg(long, value_name = "N", default_value_t = 20)]
+        limit: usize,
+        #[command(subcommand)]
+        action: Option<HistoryCommand>,
+    },
+    /// Compare two files written by --json-out or saved under runs/: jobs added, removed and changed
+    Diff {
+        old: std::path::PathBuf,
+        new: std::path::PathBuf,
+        /// Print the differences as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Browse a saved result set (from runs/ in the data directory or --json-out) offline,
+    /// with your tags and applications from the job store
+    Browse {
+        #[arg(value_name = "FILE", required_unless_present = "last")]
+        file: Option<std::path::PathBuf>,
+        /// Browse the most recently saved run
+        #[arg(long, conflicts_with = "file")]
+        last: bool,
+    },
+    /// Report on your own pipeline: applications per week and by company, how many tagged
+    /// jobs you applied to, and how long applying took
+    Stats {
+        #[arg(long, value_enum, default_value = "text")]
+        format: StatsFormat,
+    },
+    /// Create the applicant profile used to fill in application forms
+    Profile {
+        #[command(subcommand)]
+        action: ProfileCommand,
+    },
+    /// List or unhide the jobs marked "not interested"
+    Hidden {
+        #[command(subcommand)]
+        action: HiddenCommand,
+    },
+    /// Read the postings archived when you tag or apply to a job
+    Archive {
+        #[command(subcommand)]
+        action: ArchiveCommand,
+    },
+    /// Compare your resume with a recorded job's description: the match percentage and the
+    /// description's prominent terms your resume doesn't mention
+    Analyze {
+        #[arg(long)]
+        job_id: u64,
+        /// Board the job belongs to, needed only when several boards share the id
+        #[arg(long, value_name = "TOKEN")]
+        board: Option<String>,
+        /// Analyze this resume (.pdf, .txt or .md) instead of the one applying would send
+        #[arg(long, value_name = "PATH")]
+        against: Option<std::path::PathBuf>,
+    },
+    /// Check that every tracked job (tagged or applied to) is still listed on its board
+    /// and mark the ones that have been taken down as closed
+    CheckTracked,
+    /// Re-run the search periodically and report only new matches.
+    /// Search flags go before the subcommand: `--keyword "staff pm" watch --interval 30m`
+    Watch {
+        /// Time between searches, e.g. 45s, 30m, 2h or 1d
+        #[arg(long, default_value = "30m", value_parser = parse_interval)]
+        interval: std::time::Duration,
+    },
+    /// Run the search and serve the latest results as JSON on /results, /results?new=true,
+    /// /stats and /healthz. Search flags go before the subcommand, as with watch
+    Serve {
+        #[arg(long, default_value_t = 7878)]
+        port: u16,
+        /// Address to listen on; anything other than localhost exposes your results to the network
+        #[arg(long, default_value = "127.0.0.1")]
+        bind: std::net::IpAddr,
+        /// Repeat the search this often, e.g. 30m, instead of searching once
+        #[arg(long, value_parser = parse_interval)]
+        interval: Option<std::time::Duration>,
+    },
+}
+
+// `<number><unit>` with unit s, m, h or d
+pub fn parse_interval(raw: &str) -> Result<std::time::Duration, String> {
+    let raw = raw.trim();
+    let split = raw.find(|c: char| !c.is_ascii_digit()).unwrap_or(raw.len());
+    let (number, unit) = raw.split_at(split);
+    let number: u64 = number
+        .parse()
+        .map_err(|_| format!("\"{}\" should look like 45s, 30m, 2h or 1d", raw))?;
+    let seconds = match unit {
+        "s" => number,
+        "m" | "" => number * 60,
+        "h" => number * 60 * 60,
+        "d" => number * 60 * 60 * 24,
+        other => return Err(format!("unknown unit \"{}\"; use s, m, h or d", other)),
+    };
+    if seconds == 0 {
+        return Err("interval must be greater than zero".to_string());
+    }
+    Ok(std::time::Duration::from_secs(seconds))
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ApplicationsCommand {
+    /// Print the application history
+    List {
+        #[command(flatten)]
+        filter: ApplicationFilterArgs,
+    },
+    /// Write the application history as JSON, or CSV for spreadsheets
+    Export {
+        #[command(flatten)]
+        filter: ApplicationFilterArgs,
+        /// Emit CSV instead of JSON
+        #[arg(long)]
+        csv: bool,
+        /// Write to this file instead of stdout
+        #[arg(long, short, value_name = "PATH")]
+        output: Option<std::path::PathBuf>,
+    },
+    /// List jobs opened in the browser to apply by hand that aren't marked done yet
+    FollowUps,
+    /// Mark a browser follow-up done, logging the job as applied to by hand
+    Complete {
+        /// The follow-up's entry number, as `applications follow-ups` shows it
+        id: i64,
+    },
+    /// Record what happened after applying, such as a screen or a rejection, and when to follow up
+    /// (`u` in the job browser does the same). Earlier updates stay in the application's history
+    #[command(group(clap::ArgGroup::new("change").required(true).multiple(true)))]
+    Update {
+        /// The entry number, as `applications list` shows it
+        id: i64,
+        #[arg(long, group = "change")]
+        status: Option<crate::store::ApplicationStatus>,
+        /// What happened, kept in the history with the status
+        #[arg(long, group = "change")]
+        note: Option<String>,
+        /// Remind me to follow up on this date (YYYY-MM-DD); watch mode sends the reminder
+        #[arg(long, value_name = "DATE", group = "change")]
+        follow_up: Option<chrono::NaiveDate>,
+        /// Drop the follow-up reminder
+        #[arg(long, group = "change", conflicts_with = "follow_up")]
+        no_follow_up: bool,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum QueueCommand {
+    /// Add a recorded job to the end of the queue (`+` in the job browser does the same)
+    Add {
+        #[arg(long)]
+        job_id: u64,
+        /// Board the job belongs to, needed only when several boards share the id
+        #[arg(long, value_name = "TOKEN")]
+        board: Option<String>,
+    },
+    /// Print the queue with each job's status
+    List,
+    /// Show each waiting job's filled-in form and submit it once you confirm. Failed jobs
+    /// stay queued for the next run; an interrupted run resumes where it stopped
+    Process,
+    /// Take a job out of the queue
+    Remove {
+        /// The entry number, as `queue list` shows it
+        id: i64,
+    },
+    /// Drop the submitted and skipped entries
+    Clear,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct ApplicationFilterArgs {
+    /// Only applications on or after this date (YYYY-MM-DD)
+    #[arg(long, value_name = "DATE")]
+    pub since: Option<chrono::NaiveDate>,
+    /// Only applications on or before this date (YYYY-MM-DD)
+    #[arg(long, value_name = "DATE")]
+    pub until: Option<chrono::NaiveDate>,
+    /// Only applications whose company contains this text
+    #[arg(long)]
+    pub company: Option<String>,
+    /// Include dry runs, which were never submitted
+    #[arg(long)]
+    pub dry_runs: bool,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum StateCommand {
+    /// Package every state file into a .tar.gz bundle
+    Export { bundle: std::path::PathBuf },
+    /// Restore a bundle made by `state export`, replacing the files it contains
+    Import { bundle: std::path::PathBuf },
+    /// Delete the local state after listing exactly what goes: every file under the config,
+    /// data and cache directories, or only the parts named
+    Wipe {
+        /// The application log with its history and reminders, drafts, the application queue
+        /// and saved dry runs
+        #[arg(long)]
+        applications: bool,
+        /// Cached board data, such as school and degree lists
+        #[arg(long)]
+        cache: bool,
+        /// Tags and hidden jobs
+        #[arg(long)]
+        annotations: bool,
+        /// Delete without asking
+        #[arg(long)]
+        yes: bool,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum HistoryCommand {
+    /// Print one run in full, including its saved results
+    Show {
+        id: i64,
+        /// Open the saved results in the interactive browser instead of printing them
+        #[arg(long)]
+        browse: bool,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum HiddenCommand {
+    /// Print every hidden job with its URL
+    List,
+    /// Show one job again in future searches
+    Unhide { url: String },
+    /// Show every hidden job again
+    UnhideAll,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ProfileCommand {
+    /// Answer a few questions to write profile.toml in the config directory
+    Init {
+        /// Replace an existing profile
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ArchiveCommand {
+    /// Print the newest archived Greenhouse record of a job as JSON
+    Show {
+        job_id: u64,
+        /// Board the job belongs to, needed only when several boards share the id
+        #[arg(long, value_name = "TOKEN")]
+        board: Option<String>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum DbCommand {
+    /// Print where the job store database lives
+    Path,
+    /// Summarize the jobs and searches recorded so far
+    Stats,
+}
@@ -0,0 +1,57 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+// Copying text to the system clipboard through the command the platform has
+// for it: pbcopy on macOS, clip on Windows, and on Linux wl-copy under Wayland
+// or xclip / xsel under X11, trying each in turn until one takes it.
+
+type Candidate = (&'static str, &'static [&'static str]);
+
+const MACOS: &[Candidate] = &[("pbcopy", &[])];
+const WINDOWS: &[Candidate] = &[("clip", &[])];
+const LINUX: &[Candidate] = &[
+    ("wl-copy", &[]),
+    ("xclip", &["-selection", "clipboard"]),
+    ("xsel", &["--clipboard", "--input"]),
+];
+
+// The name of the command that took the text
+pub fn copy(text: &str) -> Result<&'static str, String> {
+    let candidates = if cfg!(target_os = "macos") {
+        MACOS
+    } else if cfg!(windows) {
+        WINDOWS
+    } else {
+        LINUX
+    };
+    // wl-copy is often installed without a Wayland session to copy into, so
+    // a failure only counts once nothing after it works either
+    let mut failure = None;
+    for (program, args) in candidates {
+        match pipe(program, args, text) {
+            Ok(()) => return Ok(program),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => failure = Some(format!("{} failed: {}", program, e)),
+        }
+    }
+    let names: Vec<&str> = candidates.iter().map(|(program, _)| *program).collect();
+    Err(failure.unwrap_or_else(|| format!("no clipboard command found; install {}", names.join(" or "))))
+}
+
+fn pipe(program: &str, args: &[&str], text: &str) -> std::io::Result<()> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(text.as_bytes())?;
+    }
+    let status = child.wait()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(std::io::Error::other(format!("exited with {}", status)))
+    }
+}
@@ -0,0 +1,57 @@
+//! Display names for board tokens. Capitalizing the token is right for most
+//! boards ("stripe" -> "Stripe") but not all ("newrelic" -> "New Relic"), so
+//! a built-in map covers the common exceptions and [`set_overrides`] can add
+//! or override entries.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+const DEFAULT_NAMES: &[(&str, &str)] = &[
+    ("doordash", "DoorDash"),
+    ("github", "GitHub"),
+    ("gitlab", "GitLab"),
+    ("goodrx", "GoodRx"),
+    ("hubspot", "HubSpot"),
+    ("launchdarkly", "LaunchDarkly"),
+    ("linkedin", "LinkedIn"),
+    ("mongodb", "MongoDB"),
+    ("nerdwallet", "NerdWallet"),
+    ("newrelic", "New Relic"),
+    ("openai", "OpenAI"),
+    ("pagerduty", "PagerDuty"),
+    ("scaleai", "Scale AI"),
+    ("sendgrid", "SendGrid"),
+    ("servicetitan", "ServiceTitan"),
+    ("sofi", "SoFi"),
+    ("surveymonkey", "SurveyMonkey"),
+    ("ziprecruiter", "ZipRecruiter"),
+];
+
+static OVERRIDES: OnceLock<HashMap<String, String>> = OnceLock::new();
+
+/// Board token -> display name, over the built-in names. Only the first call
+/// counts, so make it before any name is looked up.
+pub fn set_overrides(names: HashMap<String, String>) {
+    let _ = OVERRIDES.set(names.into_iter().map(|(token, name)| (token.to_lowercase(), name)).collect());
+}
+
+/// The mapped name for a board token, or `None` to let the caller decide
+pub fn mapped_name(board_token: &str) -> Option<&'static str> {
+    let token = board_token.to_lowercase();
+    if let Some(name) = OVERRIDES.get().and_then(|overrides| overrides.get(&token)) {
+        return Some(name);
+    }
+    DEFAULT_NAMES.iter().find(|(known, _)| *known == token).map(|(_, name)| *name)
+}
+
+/// The mapped name if there is one, otherwise the token with its first letter capitalized
+pub fn display_company_name(board_token: &str) -> String {
+    if let Some(name) = mapped_name(board_token) {
+        return name.to_string();
+    }
+    let mut chars = board_token.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
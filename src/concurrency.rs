@@ -0,0 +1,115 @@
+use std::sync::{Arc, Mutex};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+// How many boards are fetched at once: a fixed --concurrency, or the adaptive
+// --auto-concurrency. The adaptive limit grows by one slot after a full round
+// of boards at the current level comes back without rate limiting, and halves
+// when a board is rate limited. Slots taken before a cut don't count against
+// the new level, so one burst of 429s only halves it once. The searcher keeps
+// the limiter, so later watch cycles start where the last one settled.
+
+pub const AUTO_START: usize = 4;
+pub const AUTO_MAX: usize = 64;
+
+pub struct Limiter {
+    semaphore: Arc<Semaphore>,
+    // The --concurrency value; the adaptive limit lives in `adaptive`
+    fixed: usize,
+    adaptive: Option<Mutex<Adaptive>>,
+}
+
+struct Adaptive {
+    limit: usize,
+    // Boards finished without rate limiting since the limit last changed
+    successes: usize,
+    // Permits to retire as they come back, after the limit was cut
+    excess: usize,
+    // Bumped on every cut
+    generation: u64,
+    peak: usize,
+    cuts: usize,
+}
+
+// One board's turn; hand it back with `finish`
+pub struct Slot {
+    permit: OwnedSemaphorePermit,
+    generation: u64,
+}
+
+impl Limiter {
+    pub fn fixed(limit: usize) -> Arc<Self> {
+        Arc::new(Limiter { semaphore: Arc::new(Semaphore::new(limit)), fixed: limit, adaptive: None })
+    }
+
+    pub fn adaptive() -> Arc<Self> {
+        let state = Adaptive { limit: AUTO_START, successes: 0, excess: 0, generation: 0, peak: AUTO_START, cuts: 0 };
+        Arc::new(Limiter { semaphore: Arc::new(Semaphore::new(AUTO_START)), fixed: 0, adaptive: Some(Mutex::new(state)) })
+    }
+
+    fn state(&self) -> Option<std::sync::MutexGuard<'_, Adaptive>> {
+        self.adaptive.as_ref().map(|state| state.lock().unwrap_or_else(|poisoned| poisoned.into_inner()))
+    }
+
+    pub async fn acquire(&self) -> Slot {
+        let permit = self.semaphore.clone().acquire_owned().await.expect("the semaphore is never closed");
+        let generation = self.state().map_or(0, |state| state.generation);
+        Slot { permit, generation }
+    }
+
+    pub fn finish(&self, slot: Slot, rate_limited: bool) {
+        let Some(mut state) = self.state() else {
+            return;
+        };
+        if rate_limited {
+            if slot.generation == state.generation && state.limit > 1 {
+                let cut = state.limit - state.limit / 2;
+                state.limit -= cut;
+                state.excess += cut;
+                state.generation += 1;
+                state.cuts += 1;
+                state.successes = 0;
+            }
+        } else {
+            state.successes += 1;
+            if state.successes >= state.limit && state.limit < AUTO_MAX {
+                state.limit += 1;
+                state.peak = state.peak.max(state.limit);
+                state.successes = 0;
+                // Keep a permit that was due to be retired rather than add one
+                if state.excess > 0 {
+                    state.excess -= 1;
+                } else {
+                    self.semaphore.add_permits(1);
+                }
+            }
+        }
+        if state.excess > 0 {
+            state.excess -= 1;
+            slot.permit.forget();
+        }
+    }
+
+    pub fn is_adaptive(&self) -> bool {
+        self.adaptive.is_some()
+    }
+
+    // Fixed or current adaptive limit
+    pub fn limit(&self) -> usize {
+        match self.state() {
+            Some(state) => state.limit,
+            None => self.fixed,
+        }
+    }
+
+    // "settled at 12 (peak 16, halved 2 times after rate limiting)", for adaptive limiters
+    pub fn report(&self) -> Option<String> {
+        let state = self.state()?;
+        Some(match state.cuts {
+            0 => format!("settled at {} board(s) at once (never rate limited)", state.limit),
+            cuts => format!(
+                "settled at {} board(s) at once (peak {}, halved {} time(s) after rate limiting)",
+                state.limit, state.peak, cuts
+            ),
+        })
+    }
+}
@@ -0,0 +1,188 @@
+use crate::cover_letter::{ContactField, LetterFormat};
+use crate::{icons, paths, quota};
+use chrono::NaiveTime;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+// Optional settings read from config.toml in the data directory. A missing
+// file means defaults; a malformed one is reported and ignored.
+
+#[derive(Debug, Default, Deserialize)]
+pub struct Config {
+    #[serde(default)]
+    pub notifications: NotificationConfig,
+    #[serde(default)]
+    pub tui: TuiConfig,
+    #[serde(default)]
+    pub applications: ApplicationsConfig,
+    #[serde(default)]
+    pub cover_letter: CoverLetterConfig,
+    #[serde(default)]
+    pub storage: StorageConfig,
+    /// Board token -> name of the credential sent with that board's requests
+    #[serde(default)]
+    pub board_credentials: HashMap<String, String>,
+    /// Plaintext fallback secrets by credential name; prefer the keychain
+    #[serde(default)]
+    pub credentials: HashMap<String, String>,
+    /// Board token -> resume sent to that board instead of the profile's
+    #[serde(default)]
+    pub resumes: HashMap<String, PathBuf>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct NotificationConfig {
+    /// Local time window, e.g. "22:00-07:00", during which notifications are held back
+    #[serde(default)]
+    pub quiet_hours: Option<String>,
+    /// Slack incoming webhook URL, used when --slack-webhook-url isn't given
+    #[serde(default)]
+    pub slack_webhook_url: Option<String>,
+    /// Discord webhook URL, used when --discord-webhook-url isn't given
+    #[serde(default)]
+    pub discord_webhook_url: Option<String>,
+    /// Generic webhook endpoint and signing secret, used when --webhook-url / --webhook-secret aren't given
+    #[serde(default)]
+    pub webhook_url: Option<String>,
+    #[serde(default)]
+    pub webhook_secret: Option<String>,
+    #[serde(default)]
+    pub email: EmailConfig,
+}
+
+// SMTP settings for --notify email; the password is read from SMTP_PASSWORD
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct EmailConfig {
+    pub smtp_host: Option<String>,
+    /// 465 for implicit TLS, otherwise STARTTLS (default 587)
+    pub smtp_port: Option<u16>,
+    pub from: Option<String>,
+    #[serde(default)]
+    pub to: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TuiConfig {
+    /// Show the current time and search duration in the footer
+    #[serde(default = "enabled")]
+    pub clock: bool,
+    /// Where `e` in the job browser writes its exports; "exports" in the data directory by default
+    #[serde(default)]
+    pub export_dir: Option<PathBuf>,
+}
+
+impl Default for TuiConfig {
+    fn default() -> Self {
+        TuiConfig { clock: true, export_dir: None }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StorageConfig {
+    /// Keep the job store, sessions, drafts, saved runs and caches between runs; false leaves
+    /// nothing behind in the config, data or cache directories
+    #[serde(default = "enabled")]
+    pub persist: bool,
+}
+
+impl Default for StorageConfig {
+    fn default() -> Self {
+        StorageConfig { persist: true }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ApplicationsConfig {
+    /// Most applications submitted through the API in any 24 hours
+    #[serde(default = "default_daily_limit")]
+    pub daily_limit: usize,
+    /// Greenhouse source token added as ?gh_src= when a job is opened in the browser to apply
+    #[serde(default)]
+    pub gh_src: Option<String>,
+    /// Country (ISO code, e.g. "US") of profile phone numbers written without a +country code
+    #[serde(default)]
+    pub phone_country: Option<String>,
+}
+
+impl Default for ApplicationsConfig {
+    fn default() -> Self {
+        ApplicationsConfig { daily_limit: quota::DEFAULT_DAILY_LIMIT, gh_src: None, phone_country: None }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CoverLetterConfig {
+    /// How a letter is attached to forms that take it as a file: txt or pdf; --letter-format overrides it
+    #[serde(default)]
+    pub format: LetterFormat,
+    /// The name at the top of a PDF letter, instead of the profile's first and last name
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Profile fields on the contact line under the name, in order; ones the profile leaves blank are skipped
+    #[serde(default = "default_contact")]
+    pub contact: Vec<ContactField>,
+}
+
+impl Default for CoverLetterConfig {
+    fn default() -> Self {
+        CoverLetterConfig { format: LetterFormat::default(), name: None, contact: default_contact() }
+    }
+}
+
+fn default_contact() -> Vec<ContactField> {
+    vec![ContactField::Email, ContactField::Phone, ContactField::Location]
+}
+
+fn default_daily_limit() -> usize {
+    quota::DEFAULT_DAILY_LIMIT
+}
+
+fn enabled() -> bool {
+    true
+}
+
+impl Config {
+    pub fn load() -> Self {
+        let path = paths::config_path();
+        let Ok(text) = std::fs::read_to_string(&path) else {
+            return Config::default();
+        };
+        match toml::from_str::<Config>(&text) {
+            Ok(config) => {
+                if let Some(Err(e)) = config.notifications.quiet_hours.as_deref().map(parse_quiet_hours) {
+                    eprintln!("{}Ignoring quiet_hours in {}: {}", icons::WARNING, path.display(), e);
+                }
+                config
+            }
+            Err(e) => {
+                eprintln!("{}Ignoring {}: {}", icons::WARNING, path.display(), e);
+                Config::default()
+            }
+        }
+    }
+}
+
+impl NotificationConfig {
+    pub fn in_quiet_hours(&self, now: NaiveTime) -> bool {
+        let Some(Ok((start, end))) = self.quiet_hours.as_deref().map(parse_quiet_hours) else {
+            return false;
+        };
+        if start <= end {
+            start <= now && now < end
+        } else {
+            // The window wraps past midnight
+            now >= start || now < end
+        }
+    }
+}
+
+fn parse_quiet_hours(raw: &str) -> Result<(NaiveTime, NaiveTime), String> {
+    let (start, end) = raw
+        .split_once('-')
+        .ok_or_else(|| format!("\"{}\" should look like 22:00-07:00", raw))?;
+    let parse = |t: &str| {
+        NaiveTime::parse_from_str(t.trim(), "%H:%M").map_err(|_| format!("\"{}\" is not an HH:MM time", t.trim()))
+    };
+    Ok((parse(start)?, parse(end)?))
+}
@@ -0,0 +1,293 @@
+use reqwest::Url;
+use std::fmt;
+
+// Contact fields the way Greenhouse forms take them. Phone numbers become
+// E.164 (+14155550100), using `phone_country` under [applications] in
+// config.toml for numbers written without a country code. LinkedIn and
+// GitHub links must be on their own site and are rewritten to https with the
+// query string (tracking parameters, mostly) dropped; other links lose only
+// their utm_ and click-id parameters. What can't be fixed without guessing
+// comes back as `Invalid`, with the likely fix when there is one.
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Invalid {
+    pub message: String,
+    pub suggestion: Option<String>,
+}
+
+impl Invalid {
+    fn new(message: impl Into<String>) -> Self {
+        Invalid { message: message.into(), suggestion: None }
+    }
+
+    fn suggesting(message: impl Into<String>, suggestion: impl Into<String>) -> Self {
+        Invalid { message: message.into(), suggestion: Some(suggestion.into()) }
+    }
+}
+
+impl From<String> for Invalid {
+    fn from(message: String) -> Self {
+        Invalid::new(message)
+    }
+}
+
+impl fmt::Display for Invalid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.suggestion {
+            Some(suggestion) => write!(f, "{}; did you mean {}?", self.message, suggestion),
+            None => f.write_str(&self.message),
+        }
+    }
+}
+
+// Calling code by ISO 3166 country code, and whether the national number's
+// leading 0 stays after it (Italy's does)
+const COUNTRIES: &[(&str, &str, bool)] = &[
+    ("US", "1", false), ("CA", "1", false), ("GB", "44", false), ("IE", "353", false),
+    ("DE", "49", false), ("FR", "33", false), ("ES", "34", false), ("IT", "39", true),
+    ("NL", "31", false), ("BE", "32", false), ("CH", "41", false), ("AT", "43", false),
+    ("SE", "46", false), ("NO", "47", false), ("DK", "45", false), ("FI", "358", false),
+    ("PL", "48", false), ("PT", "351", false), ("IL", "972", false), ("AE", "971", false),
+    ("ZA", "27", false), ("IN", "91", false), ("SG", "65", false), ("HK", "852", false),
+    ("JP", "81", false), ("KR", "82", false), ("CN", "86", false), ("AU", "61", false),
+    ("NZ", "64", false), ("BR", "55", false), ("MX", "52", false), ("AR", "54", false),
+];
+
+// Misspellings of the big mail providers, which still pass as addresses
+const EMAIL_DOMAIN_TYPOS: &[(&str, &str)] = &[
+    ("gmial.com", "gmail.com"), ("gmai.com", "gmail.com"), ("gamil.com", "gmail.com"), ("gmail.co", "gmail.com"),
+    ("gmail.con", "gmail.com"), ("hotmial.com", "hotmail.com"), ("hotmail.co", "hotmail.com"), ("outlok.com", "outlook.com"),
+    ("yahoo.co", "yahoo.com"), ("yaho.com", "yahoo.com"), ("iclod.com", "icloud.com"), ("icloud.co", "icloud.com"),
+];
+
+// Query parameters that only say where a click came from
+const TRACKING_PARAMETERS: &[&str] = &["fbclid", "gclid", "msclkid", "mc_cid", "mc_eid", "ref", "trk", "trackingid", "lipi"];
+
+// The address with its domain lowercased; a misspelled provider gets the fix suggested
+pub fn email(raw: &str) -> Result<String, Invalid> {
+    let raw = raw.trim();
+    let (local, domain) = raw.rsplit_once('@').ok_or_else(|| Invalid::new(format!("\"{}\" has no @", raw)))?;
+    let domain = domain.to_lowercase();
+    if let Some((_, fixed)) = EMAIL_DOMAIN_TYPOS.iter().find(|(typo, _)| *typo == domain) {
+        return Err(Invalid::suggesting(format!("\"{}\" looks misspelled", domain), format!("{}@{}", local, fixed)));
+    }
+    Ok(format!("{}@{}", local, domain))
+}
+
+pub fn phone(raw: &str, country: Option<&str>) -> Result<String, Invalid> {
+    let raw = raw.trim();
+    if let Some(bad) = raw.chars().find(|c| !(c.is_ascii_digit() || " +-().".contains(*c))) {
+        return Err(Invalid::new(format!("\"{}\" contains '{}'; use digits, spaces, + - ( ) and .", raw, bad)));
+    }
+    let digits: String = raw.chars().filter(char::is_ascii_digit).collect();
+    let international = if raw.starts_with('+') {
+        digits
+    } else if let Some(rest) = digits.strip_prefix("00") {
+        rest.to_string()
+    } else {
+        national_to_international(raw, &digits, country)?
+    };
+    // E.164 allows 15 digits, country code included
+    if !(8..=15).contains(&international.len()) {
+        return Err(Invalid::new(format!(
+            "\"{}\" has {} digits with its country code; phone numbers have 8 to 15",
+            raw,
+            international.len()
+        )));
+    }
+    Ok(format!("+{}", international))
+}
+
+fn national_to_international(raw: &str, digits: &str, country: Option<&str>) -> Result<String, Invalid> {
+    let Some(country) = country else {
+        let message = format!(
+            "\"{}\" has no country code; start it with + and the code, or set phone_country under [applications] in config.toml",
+            raw
+        );
+        // Ten digits is almost always a North American number
+        return Err(match digits.len() {
+            10 => Invalid::suggesting(message, format!("+1{}", digits)),
+            _ => Invalid::new(message),
+        });
+    };
+    let (code, keeps_zero) = calling_code(country)
+        .ok_or_else(|| Invalid::new(format!("phone_country \"{}\" isn't a country code this knows; start the number with +", country)))?;
+    if code == "1" {
+        return match digits.len() {
+            10 => Ok(format!("1{}", digits)),
+            11 if digits.starts_with('1') => Ok(digits.to_string()),
+            _ => Err(Invalid::new(format!("\"{}\" isn't a 10-digit North American number", raw))),
+        };
+    }
+    let national = if keeps_zero { digits } else { digits.strip_prefix('0').unwrap_or(digits) };
+    Ok(format!("{}{}", code, national))
+}
+
+fn calling_code(country: &str) -> Option<(&'static str, bool)> {
+    let country = country.trim().to_uppercase();
+    let country = if country == "UK" { "GB".to_string() } else { country };
+    COUNTRIES.iter().find(|(iso, _, _)| *iso == country).map(|(_, code, keeps_zero)| (*code, *keeps_zero))
+}
+
+pub fn linkedin(raw: &str) -> Result<String, Invalid> {
+    let url = profile_url(raw, "LinkedIn", |handle| format!("https://www.linkedin.com/in/{}", handle))?;
+    let host = url.host_str().unwrap_or_default();
+    if !is_host(host, "linkedin.com") {
+        return Err(wrong_site(raw, host, "LinkedIn"));
+    }
+    let segments: Vec<&str> = path_segments(&url);
+    match segments.as_slice() {
+        ["in" | "pub", handle, ..] => Ok(format!("https://www.linkedin.com/in/{}", handle)),
+        ["company" | "school" | "jobs" | "feed", ..] => Err(Invalid::new(format!(
+            "\"{}\" isn't a profile; yours is the linkedin.com/in/... address on your profile page",
+            raw
+        ))),
+        [handle] => Err(Invalid::suggesting(
+            format!("\"{}\" isn't a profile address", raw),
+            format!("https://www.linkedin.com/in/{}", handle),
+        )),
+        _ => Err(Invalid::new(format!("\"{}\" has no profile in it; yours is the linkedin.com/in/... address", raw))),
+    }
+}
+
+pub fn github(raw: &str) -> Result<String, Invalid> {
+    let url = profile_url(raw, "GitHub", |handle| format!("https://github.com/{}", handle))?;
+    let host = url.host_str().unwrap_or_default();
+    if !is_host(host, "github.com") {
+        return Err(wrong_site(raw, host, "GitHub"));
+    }
+    let segments = path_segments(&url);
+    if segments.is_empty() {
+        return Err(Invalid::new(format!("\"{}\" has no username; use https://github.com/<username>", raw)));
+    }
+    Ok(format!("https://github.com/{}", segments.join("/")))
+}
+
+// Any http(s) page, with the scheme added when it's left off
+pub fn website(raw: &str) -> Result<String, Invalid> {
+    let raw = raw.trim();
+    let mut url = parse_with_scheme(raw).ok_or_else(|| Invalid::new(format!("\"{}\" isn't a web address", raw)))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(Invalid::new(format!("\"{}\" isn't a web page; use an http or https address", raw)));
+    }
+    if !url.host_str().is_some_and(|host| host.contains('.')) {
+        return Err(Invalid::new(format!("\"{}\" has no domain", raw)));
+    }
+    let kept: Vec<(String, String)> = url
+        .query_pairs()
+        .filter(|(name, _)| {
+            let name = name.to_lowercase();
+            !name.starts_with("utm_") && !TRACKING_PARAMETERS.contains(&name.as_str())
+        })
+        .map(|(name, value)| (name.into_owned(), value.into_owned()))
+        .collect();
+    if kept.is_empty() {
+        url.set_query(None);
+    } else {
+        url.query_pairs_mut().clear().extend_pairs(kept);
+    }
+    url.set_fragment(None);
+    Ok(url.into())
+}
+
+// A link to a profile on `site`, with https and without query or fragment.
+// A bare username gets the full address suggested.
+fn profile_url(raw: &str, site: &str, address: impl Fn(&str) -> String) -> Result<Url, Invalid> {
+    let raw = raw.trim();
+    let handle = raw.trim_start_matches('@');
+    if !handle.is_empty() && handle.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_') {
+        return Err(Invalid::suggesting(format!("\"{}\" is a username, not a {} address", raw, site), address(handle)));
+    }
+    let mut url = parse_with_scheme(raw).ok_or_else(|| Invalid::new(format!("\"{}\" isn't a web address", raw)))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(Invalid::new(format!("\"{}\" isn't a web page", raw)));
+    }
+    let _ = url.set_scheme("https");
+    url.set_query(None);
+    url.set_fragment(None);
+    Ok(url)
+}
+
+// "mailto:..." keeps its scheme; "janedoe.dev" is read as https
+fn parse_with_scheme(raw: &str) -> Option<Url> {
+    Url::parse(raw).ok().or_else(|| Url::parse(&format!("https://{}", raw)).ok())
+}
+
+fn path_segments(url: &Url) -> Vec<&str> {
+    url.path_segments().map(|segments| segments.filter(|segment| !segment.is_empty()).collect()).unwrap_or_default()
+}
+
+// The domain itself or one of its subdomains (www., uk. and the like)
+fn is_host(host: &str, domain: &str) -> bool {
+    let host = host.to_lowercase();
+    host == domain || host.ends_with(&format!(".{}", domain))
+}
+
+fn wrong_site(raw: &str, host: &str, site: &str) -> Invalid {
+    let other = if is_host(host, "linkedin.com") {
+        Some("linkedin")
+    } else if is_host(host, "github.com") {
+        Some("github")
+    } else {
+        None
+    };
+    match other {
+        Some(field) => Invalid::new(format!("\"{}\" isn't a {} address; it belongs under {} in profile.toml", raw, site, field)),
+        None => Invalid::new(format!("\"{}\" isn't a {} address", raw, site)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn phone_numbers_become_e164() {
+        assert_eq!(phone("+44 20 7946 0958", None), Ok("+442079460958".to_string()));
+        assert_eq!(phone("0044 20 7946 0958", None), Ok("+442079460958".to_string()));
+        assert_eq!(phone("(415) 555-0100", Some("US")), Ok("+14155550100".to_string()));
+        assert_eq!(phone("1-415-555-0100", Some("us")), Ok("+14155550100".to_string()));
+        // The trunk 0 goes, except in Italy
+        assert_eq!(phone("020 7946 0958", Some("UK")), Ok("+442079460958".to_string()));
+        assert_eq!(phone("06 1234 5678", Some("IT")), Ok("+390612345678".to_string()));
+    }
+
+    #[test]
+    fn phone_without_a_country_suggests_one_when_it_can() {
+        let invalid = phone("(415) 555-0100", None).unwrap_err();
+        assert_eq!(invalid.suggestion.as_deref(), Some("+14155550100"));
+        assert_eq!(phone("020 7946 0958", None).unwrap_err().suggestion, None);
+        assert!(phone("415 555 01", Some("US")).is_err());
+        assert!(phone("+1 415 555 0100 ext", None).is_err());
+    }
+
+    #[test]
+    fn misspelled_mail_providers_are_caught() {
+        assert_eq!(email(" Jane.Doe@Example.COM "), Ok("Jane.Doe@example.com".to_string()));
+        assert_eq!(email("jane@gmial.com").unwrap_err().suggestion.as_deref(), Some("jane@gmail.com"));
+    }
+
+    #[test]
+    fn linkedin_urls_are_normalized_or_get_a_suggestion() {
+        assert_eq!(
+            linkedin("http://uk.linkedin.com/in/janedoe/?trk=public_profile&utm_source=share#about"),
+            Ok("https://www.linkedin.com/in/janedoe".to_string())
+        );
+        assert_eq!(linkedin("linkedin.com/in/janedoe"), Ok("https://www.linkedin.com/in/janedoe".to_string()));
+        let suggestion = |raw: &str| linkedin(raw).unwrap_err().suggestion;
+        assert_eq!(suggestion("linkedin.com/janedoe").as_deref(), Some("https://www.linkedin.com/in/janedoe"));
+        assert_eq!(suggestion("@janedoe").as_deref(), Some("https://www.linkedin.com/in/janedoe"));
+        assert_eq!(suggestion("https://www.linkedin.com/company/acme"), None);
+        assert!(linkedin("https://github.com/janedoe").unwrap_err().message.contains("under github"));
+    }
+
+    #[test]
+    fn github_and_website_links_lose_tracking() {
+        assert_eq!(github("http://www.github.com/janedoe?tab=repositories"), Ok("https://github.com/janedoe".to_string()));
+        assert_eq!(github("janedoe").unwrap_err().suggestion.as_deref(), Some("https://github.com/janedoe"));
+        assert!(github("https://github.com/").is_err());
+        assert_eq!(website("janedoe.dev/work?utm_source=cv&page=2#top"), Ok("https://janedoe.dev/work?page=2".to_string()));
+        assert_eq!(website("http://janedoe.dev/"), Ok("http://janedoe.dev/".to_string()));
+        assert!(website("mailto:jane@example.com").is_err());
+    }
+}
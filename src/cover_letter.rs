@@ -0,0 +1,206 @@
+use crate::config::CoverLetterConfig;
+use crate::profile::Profile;
+use crate::{paths, pdf, JobResult};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::io;
+
+// The cover letter template, cover_letter.txt in the config directory,
+// rendered for each job when applying:
+//
+//   Dear {{hiring_team}},
+//
+//   I'd like to apply for the {{title}} role at {{company}}.
+//
+//   {{custom_paragraph}}
+//
+// {{company}} and {{title}} come from the job; {{hiring_team}} is the job's
+// first department's team, or the company's hiring team; {{custom_paragraph}}
+// is the company's entry under [cover_letter_paragraphs] in profile.toml, keyed
+// by board token or company name. A placeholder without a value fails the
+// render, so a letter never goes out with "{{company}}" in it.
+//
+// Forms that take the letter as an upload get it as cover_letter.txt, or with
+// --letter-format pdf (or `format = "pdf"` under [cover_letter] in
+// config.toml) as cover_letter.pdf, with the applicant's name and contact
+// details at the top. Either way the log records the file's SHA-256.
+
+pub const PLACEHOLDERS: &[&str] = &["company", "title", "hiring_team", "custom_paragraph"];
+
+// None when there's no template
+pub fn load_template() -> Result<Option<String>, String> {
+    let path = paths::cover_letter_path();
+    match std::fs::read_to_string(&path) {
+        Ok(text) => Ok(Some(text)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(format!("{}: {}", path.display(), e)),
+    }
+}
+
+pub fn render_for(template: &str, job: &JobResult, profile: &Profile) -> Result<String, String> {
+    render(template, &values(job, profile)).map_err(|e| match e {
+        RenderError::Missing(name) if name == "custom_paragraph" => format!(
+            "{{{{custom_paragraph}}}} has no value for {}; add one under [cover_letter_paragraphs] in profile.toml",
+            job.company
+        ),
+        RenderError::Missing(name) => format!("{{{{{}}}}} has no value for this job", name),
+        RenderError::Invalid(message) => format!("{}: {}", paths::cover_letter_path().display(), message),
+    })
+}
+
+fn values(job: &JobResult, profile: &Profile) -> BTreeMap<&'static str, String> {
+    let mut values = BTreeMap::new();
+    values.insert("company", job.company.clone());
+    values.insert("title", job.title.clone());
+    let hiring_team = match job.departments.first() {
+        Some(department) => format!("{} team", department),
+        None => format!("{} hiring team", job.company),
+    };
+    values.insert("hiring_team", hiring_team);
+    let paragraph = profile
+        .cover_letter_paragraphs
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(&job.board_token) || key.eq_ignore_ascii_case(&job.company));
+    if let Some((_, paragraph)) = paragraph {
+        values.insert("custom_paragraph", paragraph.trim().to_string());
+    }
+    values
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize, Serialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum LetterFormat {
+    #[default]
+    Txt,
+    Pdf,
+}
+
+// Profile fields for the contact line of a PDF letter
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ContactField {
+    Email,
+    Phone,
+    Location,
+    Linkedin,
+    Github,
+    Website,
+}
+
+// A rendered letter as it's attached to an application
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Letter {
+    pub text: String,
+    pub format: LetterFormat,
+    // The PDF's header; plain text goes without one
+    pub name: String,
+    pub contact: Vec<String>,
+}
+
+// The letter as it went out, for the application log; pasted letters have no file
+#[derive(Debug, Clone)]
+pub struct SentLetter {
+    pub text: String,
+    pub file_name: Option<String>,
+    pub sha256: Option<String>,
+}
+
+impl Letter {
+    pub fn new(text: String, profile: &Profile, settings: &CoverLetterConfig) -> Letter {
+        let name = settings.name.clone().unwrap_or_else(|| format!("{} {}", profile.first_name, profile.last_name).trim().to_string());
+        let contact = settings
+            .contact
+            .iter()
+            .filter_map(|field| match field {
+                ContactField::Email => Some(&profile.email).filter(|email| !email.is_empty()),
+                ContactField::Phone => profile.phone.as_ref(),
+                ContactField::Location => profile.location.as_ref(),
+                ContactField::Linkedin => profile.linkedin.as_ref(),
+                ContactField::Github => profile.github.as_ref(),
+                ContactField::Website => profile.website.as_ref(),
+            })
+            .cloned()
+            .collect();
+        Letter { text, format: settings.format, name, contact }
+    }
+
+    pub fn file_name(&self) -> &'static str {
+        match self.format {
+            LetterFormat::Txt => "cover_letter.txt",
+            LetterFormat::Pdf => "cover_letter.pdf",
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self.format {
+            LetterFormat::Txt => "text/plain",
+            LetterFormat::Pdf => "application/pdf",
+        }
+    }
+
+    // The same letter always makes the same bytes
+    pub fn bytes(&self) -> Vec<u8> {
+        match self.format {
+            LetterFormat::Txt => self.text.as_bytes().to_vec(),
+            LetterFormat::Pdf => pdf::letter(&self.name, &self.contact, &self.text),
+        }
+    }
+
+    pub fn sent(&self) -> SentLetter {
+        let sha256 = Sha256::digest(self.bytes()).iter().map(|byte| format!("{:02x}", byte)).collect();
+        SentLetter { text: self.text.clone(), file_name: Some(self.file_name().to_string()), sha256: Some(sha256) }
+    }
+}
+
+impl SentLetter {
+    pub fn pasted(text: &str) -> SentLetter {
+        SentLetter { text: text.to_string(), file_name: None, sha256: None }
+    }
+}
+
+enum RenderError {
+    // A known placeholder with no value for this job
+    Missing(String),
+    // A template mistake: an unknown placeholder or unclosed braces
+    Invalid(String),
+}
+
+// Replaces each `{{ name }}`; every placeholder must be known and have a value
+fn render(template: &str, values: &BTreeMap<&str, String>) -> Result<String, RenderError> {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let line = template[..template.len() - rest.len() + start].matches('\n').count() + 1;
+        let after = &rest[start + 2..];
+        let end = after.find("}}").ok_or_else(|| RenderError::Invalid(format!("the {{{{ on line {} is never closed", line)))?;
+        let name = after[..end].trim();
+        if !PLACEHOLDERS.contains(&name) {
+            let known: Vec<String> = PLACEHOLDERS.iter().map(|name| format!("{{{{{}}}}}", name)).collect();
+            return Err(RenderError::Invalid(format!("{{{{{}}}}} on line {} isn't a placeholder; use {}", name, line, known.join(", "))));
+        }
+        out.push_str(values.get(name).ok_or_else(|| RenderError::Missing(name.to_string()))?);
+        rest = &after[end + 2..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+// Opens the letter in $VISUAL or $EDITOR (vi without either) and returns what was saved
+pub fn edit(text: &str, job: &JobResult) -> Result<String, String> {
+    let editor = std::env::var("VISUAL").or_else(|_| std::env::var("EDITOR")).unwrap_or_else(|_| "vi".to_string());
+    let path = std::env::temp_dir().join(format!("cover-letter-{}-{}-{}.txt", job.board_token, job.id, std::process::id()));
+    std::fs::write(&path, text).map_err(|e| format!("can't write {}: {}", path.display(), e))?;
+    // The editor may come with arguments, e.g. "code --wait"
+    let mut words = editor.split_whitespace();
+    let program = words.next().unwrap_or("vi");
+    let status = std::process::Command::new(program).args(words).arg(&path).status();
+    let edited = match status {
+        Ok(status) if status.success() => std::fs::read_to_string(&path).map_err(|e| format!("can't read {}: {}", path.display(), e)),
+        Ok(status) => Err(format!("{} exited with {}; the letter is unchanged", editor, status)),
+        Err(e) => Err(format!("can't run {}: {}", editor, e)),
+    };
+    let _ = std::fs::remove_file(&path);
+    edited
+}
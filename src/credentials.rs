@@ -0,0 +1,88 @@
+use crate::config::Config;
+use crate::icons;
+use std::collections::HashMap;
+use std::error::Error;
+use std::io::IsTerminal;
+
+// Secrets for authenticated boards, kept in the OS keychain and referenced by
+// name from config.toml:
+//
+//   [board_credentials]
+//   acme-private = "acme-token"    # board token -> credential name
+//
+// Lookup order is the keychain, then GREENHOUSE_CREDENTIAL_<NAME>, then a
+// plaintext `[credentials]` entry in config.toml, which earns a warning.
+
+const KEYRING_SERVICE: &str = "greenhouse-job-search";
+
+// Secrets for every board listed in `[board_credentials]` that could be resolved
+pub fn board_credentials(config: &Config) -> HashMap<String, String> {
+    let mut secrets = HashMap::new();
+    for (board, name) in &config.board_credentials {
+        match resolve(name, config) {
+            Some(secret) => {
+                secrets.insert(board.clone(), secret);
+            }
+            None => eprintln!(
+                "{}No credential named \"{}\" for board {}; run --set-credential {} to store one",
+                icons::WARNING, name, board, name
+            ),
+        }
+    }
+    secrets
+}
+
+fn resolve(name: &str, config: &Config) -> Option<String> {
+    match keyring::Entry::new(KEYRING_SERVICE, name).and_then(|entry| entry.get_password()) {
+        Ok(secret) => return Some(secret),
+        Err(keyring::Error::NoEntry) => {}
+        Err(e) => eprintln!("{}Keychain unavailable for \"{}\" ({}); trying the environment and config", icons::WARNING, name, e),
+    }
+
+    if let Ok(secret) = std::env::var(env_var(name)) {
+        return Some(secret);
+    }
+
+    let secret = config.credentials.get(name)?;
+    eprintln!(
+        "{}Credential \"{}\" is stored in plaintext in config.toml; move it to the keychain with --set-credential {}",
+        icons::WARNING, name, name
+    );
+    Some(secret.clone())
+}
+
+// "acme-token" -> GREENHOUSE_CREDENTIAL_ACME_TOKEN
+fn env_var(name: &str) -> String {
+    let suffix: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_uppercase() } else { '_' })
+        .collect();
+    format!("GREENHOUSE_CREDENTIAL_{}", suffix)
+}
+
+// --set-credential: prompt for the secret without echoing it and save it to the keychain
+pub fn set_interactive(name: &str) -> Result<(), Box<dyn Error>> {
+    // Piped input is read as-is so the secret can be scripted
+    let secret = if std::io::stdin().is_terminal() {
+        rpassword::prompt_password(format!("Secret for \"{}\": ", name))?
+    } else {
+        let mut line = String::new();
+        std::io::stdin().read_line(&mut line)?;
+        line.trim_end_matches(['\r', '\n']).to_string()
+    };
+    if secret.is_empty() {
+        return Err("no secret entered; nothing stored".into());
+    }
+
+    let stored = keyring::Entry::new(KEYRING_SERVICE, name).and_then(|entry| entry.set_password(&secret));
+    match stored {
+        Ok(()) => {
+            println!("{}Stored \"{}\" in the system keychain", icons::KEYCHAIN, name);
+            Ok(())
+        }
+        Err(e) => Err(format!(
+            "couldn't store \"{}\" in the keychain ({}); set {} instead",
+            name, e, env_var(name)
+        ).into()),
+    }
+}
@@ -0,0 +1,160 @@
+use crate::report::{self, escape};
+use crate::{locale, JobResult};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+// A static dashboard kept in a directory across runs: index.html is rebuilt
+// from dashboard.json after every search. Both files are written to a
+// temporary name and renamed into place, so a browser refreshing the page
+// never sees a half-written file.
+
+// Runs of history kept for the sparkline
+const MAX_HISTORY: usize = 120;
+const SPARKLINE_WIDTH: f64 = 360.0;
+const SPARKLINE_HEIGHT: f64 = 48.0;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct DashboardData {
+    #[serde(default)]
+    history: Vec<RunPoint>,
+    // Matches from the latest run, with `first_seen` always filled in
+    #[serde(default)]
+    latest: Vec<JobResult>,
+    // Every match first seen in the last day, gathered across runs
+    #[serde(default)]
+    recent: Vec<JobResult>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct RunPoint {
+    at: DateTime<Utc>,
+    matches: usize,
+    new: usize,
+}
+
+pub fn update(dir: &Path, jobs: &[JobResult]) -> Result<(), String> {
+    std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    let data_path = dir.join("dashboard.json");
+    let mut data: DashboardData = match std::fs::read_to_string(&data_path) {
+        Ok(text) => serde_json::from_str(&text).map_err(|e| format!("{}: {}", data_path.display(), e))?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => DashboardData::default(),
+        Err(e) => return Err(e.to_string()),
+    };
+
+    let now = Utc::now();
+    data.latest = jobs.to_vec();
+    for job in &mut data.latest {
+        job.first_seen.get_or_insert(now);
+    }
+    data.history.push(RunPoint {
+        at: now,
+        matches: jobs.len(),
+        new: jobs.iter().filter(|job| job.first_seen.is_none()).count(),
+    });
+    let excess = data.history.len().saturating_sub(MAX_HISTORY);
+    data.history.drain(..excess);
+
+    let yesterday = now - chrono::Duration::days(1);
+    for job in &data.latest {
+        data.recent.retain(|recent| recent.url != job.url);
+        data.recent.push(job.clone());
+    }
+    data.recent.retain(|job| job.first_seen.is_some_and(|seen| seen >= yesterday));
+    crate::sort_results(&mut data.recent);
+
+    let json = serde_json::to_string_pretty(&data).map_err(|e| e.to_string())?;
+    write_atomically(&data_path, &json)?;
+    write_atomically(&dir.join("index.html"), &render(&data, now))
+}
+
+fn write_atomically(path: &Path, contents: &str) -> Result<(), String> {
+    let tmp = path.with_extension("tmp");
+    std::fs::write(&tmp, contents).map_err(|e| format!("{}: {}", tmp.display(), e))?;
+    std::fs::rename(&tmp, path).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+fn render(data: &DashboardData, now: DateTime<Utc>) -> String {
+    let locale = locale::current();
+    let latest: Vec<&JobResult> = data.latest.iter().collect();
+    let recent: Vec<&JobResult> = data.recent.iter().collect();
+
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str("<title>Job search dashboard</title>\n</head>\n");
+    html.push_str(&format!("<body style=\"{}\">\n", report::BODY_STYLE));
+    html.push_str("<h1>Job search dashboard</h1>\n");
+    html.push_str(&format!("<p>Updated {}</p>\n", escape(&locale.date_time(now))));
+
+    html.push_str("<h2>Matches per run</h2>\n");
+    html.push_str(&sparkline(&data.history));
+
+    html.push_str(&format!("<h2>New since yesterday ({})</h2>\n", locale.count(recent.len())));
+    html.push_str(&report::job_table(&recent));
+
+    html.push_str(&format!("<h2>Latest run ({})</h2>\n", locale.count(latest.len())));
+    html.push_str(&report::job_table(&latest));
+
+    html.push_str("<h2>By company</h2>\n");
+    html.push_str(&company_counts(&latest));
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+fn sparkline(history: &[RunPoint]) -> String {
+    let (Some(first), Some(last)) = (history.first(), history.last()) else {
+        return "<p>No runs recorded yet.</p>\n".to_string();
+    };
+    let locale = locale::current();
+    let max = history.iter().map(|point| point.matches).max().unwrap_or(0).max(1) as f64;
+    let step = if history.len() > 1 { SPARKLINE_WIDTH / (history.len() - 1) as f64 } else { 0.0 };
+    let points: Vec<String> = history
+        .iter()
+        .enumerate()
+        .map(|(i, point)| {
+            let y = SPARKLINE_HEIGHT - (point.matches as f64 / max) * (SPARKLINE_HEIGHT - 2.0) - 1.0;
+            format!("{:.1},{:.1}", i as f64 * step, y)
+        })
+        .collect();
+
+    format!(
+        "<svg width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\" role=\"img\" aria-label=\"Matches per run\">\
+         <polyline fill=\"none\" stroke=\"#2a7ae2\" stroke-width=\"2\" points=\"{}\"/></svg>\n\
+         <p>{} run(s) from {} to {}; latest {} match(es), {} new, peak {}</p>\n",
+        points.join(" "),
+        locale.count(history.len()),
+        escape(&locale.date_time(first.at)),
+        escape(&locale.date_time(last.at)),
+        locale.count(last.matches),
+        locale.count(last.new),
+        locale.count(max as usize),
+        w = SPARKLINE_WIDTH,
+        h = SPARKLINE_HEIGHT,
+    )
+}
+
+fn company_counts(jobs: &[&JobResult]) -> String {
+    if jobs.is_empty() {
+        return "<p>No matching jobs.</p>\n".to_string();
+    }
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for job in jobs {
+        *counts.entry(job.company.as_str()).or_default() += 1;
+    }
+    let mut counts: Vec<(&str, usize)> = counts.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+
+    let locale = locale::current();
+    let mut html = String::from("<table style=\"border-collapse: collapse;\">\n");
+    for (company, count) in counts {
+        html.push_str(&format!(
+            "<tr><td style=\"padding: 2px 12px 2px 0;\">{}</td><td style=\"text-align: right;\">{}</td></tr>\n",
+            escape(company),
+            locale.count(count)
+        ));
+    }
+    html.push_str("</table>\n");
+    html
+}
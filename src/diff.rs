@@ -0,0 +1,170 @@
+use crate::{icons, locale};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::path::Path;
+
+// Compares two files written by --json-out. Jobs are keyed by board and job
+// id, falling back to the URL, and only the fields every export version has
+// are read, so files from older or newer versions of the tool compare fine.
+
+#[derive(Debug, Clone, Serialize)]
+struct DiffJob {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<u64>,
+    title: String,
+    company: String,
+    location: String,
+    url: String,
+    #[serde(skip)]
+    key: String,
+}
+
+#[derive(Debug, Serialize)]
+struct Change {
+    before: DiffJob,
+    after: DiffJob,
+    fields: Vec<&'static str>,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct CompanyCounts {
+    added: usize,
+    removed: usize,
+    changed: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct ResultDiff {
+    added: Vec<DiffJob>,
+    removed: Vec<DiffJob>,
+    changed: Vec<Change>,
+    companies: BTreeMap<String, CompanyCounts>,
+}
+
+pub fn run(old: &Path, new: &Path, json: bool) -> Result<(), Box<dyn Error>> {
+    let diff = compare(load(old)?, load(new)?);
+    if json {
+        println!("{}", serde_json::to_string_pretty(&diff)?);
+    } else {
+        print_diff(&diff);
+    }
+    Ok(())
+}
+
+// Accepts both the metadata envelope and a bare results array (--bare)
+fn load(path: &Path) -> Result<Vec<DiffJob>, Box<dyn Error>> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let value: Value = serde_json::from_str(&text).map_err(|e| format!("{}: not valid JSON ({})", path.display(), e))?;
+    let results = match &value {
+        Value::Array(results) => results,
+        Value::Object(envelope) => match envelope.get("results") {
+            Some(Value::Array(results)) => results,
+            _ => return Err(format!("{}: no \"results\" array found", path.display()).into()),
+        },
+        _ => return Err(format!("{}: expected an exported result set", path.display()).into()),
+    };
+
+    results
+        .iter()
+        .enumerate()
+        .map(|(i, result)| {
+            parse_job(result).ok_or_else(|| format!("{}: result {} has no id or url", path.display(), i + 1).into())
+        })
+        .collect()
+}
+
+fn parse_job(value: &Value) -> Option<DiffJob> {
+    let text = |field: &str| value.get(field).and_then(Value::as_str).unwrap_or_default().to_string();
+    let id = value.get("id").and_then(Value::as_u64).filter(|&id| id != 0);
+    let url = text("url");
+    let key = match id {
+        Some(id) => format!("{}/{}", text("board_token"), id),
+        None if !url.is_empty() => url.clone(),
+        None => return None,
+    };
+    Some(DiffJob { id, title: text("title"), company: text("company"), location: text("location"), url, key })
+}
+
+fn compare(old: Vec<DiffJob>, new: Vec<DiffJob>) -> ResultDiff {
+    let mut remaining: HashMap<String, DiffJob> = old.into_iter().map(|job| (job.key.clone(), job)).collect();
+    let mut diff = ResultDiff { added: Vec::new(), removed: Vec::new(), changed: Vec::new(), companies: BTreeMap::new() };
+
+    for job in new {
+        // An id can be missing on one side only if the formats differ, so try the URL too
+        let before = remaining.remove(&job.key).or_else(|| {
+            let key = remaining.iter().find(|(_, old)| !old.url.is_empty() && old.url == job.url)?.0.clone();
+            remaining.remove(&key)
+        });
+        match before {
+            None => {
+                diff.companies.entry(job.company.clone()).or_default().added += 1;
+                diff.added.push(job);
+            }
+            Some(before) => {
+                let mut fields = Vec::new();
+                if before.title != job.title {
+                    fields.push("title");
+                }
+                if before.location != job.location {
+                    fields.push("location");
+                }
+                if !fields.is_empty() {
+                    diff.companies.entry(job.company.clone()).or_default().changed += 1;
+                    diff.changed.push(Change { before, after: job, fields });
+                }
+            }
+        }
+    }
+
+    let mut removed: Vec<DiffJob> = remaining.into_values().collect();
+    removed.sort_by(|a, b| a.company.cmp(&b.company).then_with(|| a.title.cmp(&b.title)));
+    for job in &removed {
+        diff.companies.entry(job.company.clone()).or_default().removed += 1;
+    }
+    diff.removed = removed;
+    diff
+}
+
+fn print_diff(diff: &ResultDiff) {
+    let locale = locale::current();
+    println!(
+        "{}{} added, {} removed, {} changed",
+        icons::RESULTS, locale.count(diff.added.len()),
+        locale.count(diff.removed.len()),
+        locale.count(diff.changed.len())
+    );
+
+    if !diff.added.is_empty() {
+        println!("\n{}Added:", icons::ADDED);
+        for job in &diff.added {
+            println!("   [{}] {} ({})\n      {}", job.company, job.title, job.location, job.url);
+        }
+    }
+    if !diff.removed.is_empty() {
+        println!("\n{}Removed:", icons::REMOVED);
+        for job in &diff.removed {
+            println!("   [{}] {} ({})\n      {}", job.company, job.title, job.location, job.url);
+        }
+    }
+    if !diff.changed.is_empty() {
+        println!("\n{}Changed:", icons::CHANGED);
+        for change in &diff.changed {
+            println!("   [{}] {}", change.after.company, change.after.title);
+            if change.fields.contains(&"title") {
+                println!("      title: {} → {}", change.before.title, change.after.title);
+            }
+            if change.fields.contains(&"location") {
+                println!("      location: {} → {}", change.before.location, change.after.location);
+            }
+        }
+    }
+
+    if !diff.companies.is_empty() {
+        println!("\n{}By company (added / removed / changed):", icons::COMPANY);
+        for (company, counts) in &diff.companies {
+            println!("   {:>4} {:>4} {:>4}  {}", counts.added, counts.removed, counts.changed, company);
+        }
+    }
+}
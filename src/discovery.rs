@@ -0,0 +1,134 @@
+//! Finding board tokens: board lists, pages that link to boards, a Google
+//! search and a built-in list of well-known boards.
+
+use scraper::{Html, Selector};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// Somewhere to find board tokens. A searcher's sources are tried in order by
+/// [`GreenhouseJobSearcher::discover_tokens`](crate::GreenhouseJobSearcher::discover_tokens).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenSource {
+    /// A board list file, see [`from_file`]
+    File(PathBuf),
+    /// Pages that link to boards, see [`from_url`]
+    Urls(Vec<String>),
+    /// See [`from_google`]
+    Google,
+    /// See [`builtin`]
+    Builtin,
+}
+
+impl TokenSource {
+    /// Google, then the built-in list
+    pub fn defaults() -> Vec<TokenSource> {
+        vec![TokenSource::Google, TokenSource::Builtin]
+    }
+}
+
+/// One token per line; blank lines and `#` comments are skipped, and board URLs
+/// are reduced to their token.
+///
+/// ```
+/// use greenhouse_job_search::discovery::parse_board_list;
+///
+/// let boards = parse_board_list("stripe\n# payments\nhttps://boards.greenhouse.io/airbnb/jobs/123\n\n");
+/// assert_eq!(boards, ["stripe", "airbnb"]);
+/// ```
+pub fn parse_board_list(text: &str) -> Vec<String> {
+    text.lines()
+        .map(|line| line.split('#').next().unwrap_or("").trim())
+        .filter(|line| !line.is_empty())
+        .map(|line| tokens_in(line).into_iter().next().unwrap_or_else(|| line.to_string()))
+        .collect()
+}
+
+/// A board list file as [`parse_board_list`] reads it; `-` reads the list from stdin
+pub fn from_file(path: &Path) -> Result<HashSet<String>, String> {
+    let text = if path == Path::new("-") {
+        std::io::read_to_string(std::io::stdin()).map_err(|e| format!("failed to read board tokens from stdin: {}", e))?
+    } else {
+        std::fs::read_to_string(path).map_err(|e| format!("failed to read {}: {}", path.display(), e))?
+    };
+    Ok(parse_board_list(&text).into_iter().collect())
+}
+
+/// Every Greenhouse board linked from a page, e.g. a curated list of companies
+pub async fn from_url(client: &reqwest::Client, url: &str) -> Result<HashSet<String>, String> {
+    let response = client.get(url).send().await.map_err(|e| format!("network error: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("{} returned status {}", url, response.status()));
+    }
+    let body = response.text().await.map_err(|e| format!("unexpected response: {}", e))?;
+    Ok(tokens_in(&body).into_iter().collect())
+}
+
+/// Boards linked from the first page of a Google search for Greenhouse boards
+pub async fn from_google(client: &reqwest::Client) -> Result<HashSet<String>, String> {
+    let search_query = "site:boards.greenhouse.io";
+    let google_url = format!("https://www.google.com/search?q={}&num=100", urlencoding::encode(search_query));
+
+    let response = client.get(&google_url).send().await.map_err(|e| format!("network error: {}", e))?;
+    let html = response.text().await.map_err(|e| format!("unexpected response: {}", e))?;
+    let document = Html::parse_document(&html);
+    let link_selector = Selector::parse("a[href*='boards.greenhouse.io']").map_err(|_| "Failed to parse CSS selector")?;
+
+    let mut tokens = HashSet::new();
+    for element in document.select(&link_selector) {
+        if let Some(href) = element.value().attr("href") {
+            if let Some(token) = extract_board_token(href) {
+                tokens.insert(token);
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+/// Well-known boards, for when nothing else turns up any
+pub fn builtin() -> HashSet<String> {
+    [
+        "stripe", "uber", "airbnb", "shopify", "atlassian",
+        "mongodb", "snowflake", "databricks", "plaid", "twilio",
+        "coinbase", "square", "dropbox", "slack", "zoom",
+        "figma", "notion", "airtable", "zapier", "hubspot",
+        "asana", "gitlab", "newrelic", "datadog", "sendgrid",
+        "doordash", "instacart", "reddit", "discord", "spotify",
+        "pinterest", "robinhood", "lyft", "github", "palantir",
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect()
+}
+
+/// The board token in a greenhouse URL, a board page or the API's
+/// boards-api.greenhouse.io/v1/boards/{token}/...
+pub fn extract_board_token(url: &str) -> Option<String> {
+    if let Some((_, rest)) = url.split_once("boards-api.greenhouse.io/v1/boards/") {
+        let token = rest.split(['/', '?']).next()?;
+        return (!token.is_empty()).then(|| token.to_string());
+    }
+    if url.contains("boards.greenhouse.io/") {
+        let parts: Vec<&str> = url.split("boards.greenhouse.io/").collect();
+        if parts.len() > 1 {
+            let token_part = parts[1].split('/').next()?;
+            if !token_part.is_empty() && token_part != "embed" {
+                return Some(token_part.to_string());
+            }
+        }
+    }
+    None
+}
+
+// Board tokens in any boards.greenhouse.io or job-boards.greenhouse.io links in `text`
+fn tokens_in(text: &str) -> Vec<String> {
+    static PATTERN: std::sync::OnceLock<regex::Regex> = std::sync::OnceLock::new();
+    let pattern = PATTERN.get_or_init(|| {
+        regex::Regex::new(r"(?:job-)?boards(?:\.eu)?\.greenhouse\.io/(?:embed/job_board\?for=)?([A-Za-z0-9_-]+)")
+            .expect("board link pattern is valid")
+    });
+    pattern
+        .captures_iter(text)
+        .map(|captures| captures[1].to_string())
+        .filter(|token| token != "embed")
+        .collect()
+}
@@ -0,0 +1,226 @@
+use crate::cli::Cli;
+use crate::{config, discovery, icons, overrides, paths, profile, store};
+use std::error::Error;
+use std::io::IsTerminal;
+use std::time::{Duration, Instant};
+
+// `doctor`: one pass over everything a search or an application depends on,
+// printed as a report. Failing critical checks make the command exit non-zero;
+// the rest only warn, since the tool works around them (discovery falls back
+// to the built-in boards, --accessible replaces the TUI).
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+// Any board will do; the API answering at all is what's checked
+const PROBE_BOARD: &str = "stripe";
+
+enum Outcome {
+    Pass(String),
+    Warn(String),
+    Fail(String),
+}
+
+struct Report {
+    failed: usize,
+    warned: usize,
+}
+
+impl Report {
+    fn print(&mut self, name: &str, outcome: Outcome) {
+        match outcome {
+            Outcome::Pass(detail) => println!("{}{}: {}", icons::PASS, name, detail),
+            Outcome::Warn(detail) => {
+                self.warned += 1;
+                println!("{}{}: {}", icons::WARNING, name, detail);
+            }
+            Outcome::Fail(detail) => {
+                self.failed += 1;
+                println!("{}{}: {}", icons::FAIL, name, detail);
+            }
+        }
+    }
+}
+
+pub async fn run(cli: &Cli) -> Result<(), Box<dyn Error>> {
+    println!("{}Checking the setup\n", icons::INSPECT);
+    let client = reqwest::Client::builder()
+        .timeout(TIMEOUT)
+        .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36")
+        .build()?;
+    let mut report = Report { failed: 0, warned: 0 };
+    report.print("Greenhouse API", greenhouse_api(&client).await);
+    report.print("Google discovery", google(&client).await);
+    let (outcome, config) = config_file();
+    report.print("config.toml", outcome);
+    if let Some(path) = cli.boards.as_deref().filter(|path| *path != std::path::Path::new("-")) {
+        report.print("Boards file", boards_file(path));
+    }
+    report.print("profile.toml", profiles(&config));
+    report.print("overrides.toml", answer_overrides());
+    for (name, outcome) in resumes(cli, config) {
+        report.print(&name, outcome);
+    }
+    report.print("Job store", job_store());
+    report.print("Terminal", terminal());
+
+    println!();
+    if report.failed > 0 {
+        return Err(format!("{} critical check(s) failed", report.failed).into());
+    }
+    if report.warned > 0 {
+        println!("{}Usable, with {} warning(s)", icons::DONE, report.warned);
+    } else {
+        println!("{}Everything checks out", icons::DONE);
+    }
+    Ok(())
+}
+
+async fn greenhouse_api(client: &reqwest::Client) -> Outcome {
+    let url = format!("https://boards-api.greenhouse.io/v1/boards/{}", PROBE_BOARD);
+    let started = Instant::now();
+    match client.get(&url).send().await {
+        // A missing board still means the API answered
+        Ok(response) if response.status().is_success() || response.status() == reqwest::StatusCode::NOT_FOUND => {
+            Outcome::Pass(format!("boards-api.greenhouse.io answered in {} ms", started.elapsed().as_millis()))
+        }
+        Ok(response) => Outcome::Fail(format!("boards-api.greenhouse.io returned status {}", response.status())),
+        Err(e) => Outcome::Fail(format!("can't reach boards-api.greenhouse.io: {}", e)),
+    }
+}
+
+// Google answers automated searches with a CAPTCHA page ("/sorry/") or a 429
+async fn google(client: &reqwest::Client) -> Outcome {
+    let url = format!("https://www.google.com/search?q={}&num=100", urlencoding::encode("site:boards.greenhouse.io"));
+    let fallback = "discovery falls back to the built-in boards; --boards or --discover-url avoid it";
+    let response = match client.get(&url).send().await {
+        Ok(response) => response,
+        Err(e) => return Outcome::Warn(format!("can't reach Google ({}); {}", e, fallback)),
+    };
+    let status = response.status();
+    let redirected = response.url().path().starts_with("/sorry");
+    let body = response.text().await.unwrap_or_default();
+    if status == reqwest::StatusCode::TOO_MANY_REQUESTS || redirected || body.contains("unusual traffic") {
+        Outcome::Warn(format!("blocked (status {}); {}", status, fallback))
+    } else if !status.is_success() {
+        Outcome::Warn(format!("returned status {}; {}", status, fallback))
+    } else if !body.contains("boards.greenhouse.io") {
+        Outcome::Warn(format!("answered without any board links; {}", fallback))
+    } else {
+        Outcome::Pass("searches return board links".to_string())
+    }
+}
+
+// The settings the other checks use: the defaults unless config.toml parses
+fn config_file() -> (Outcome, config::Config) {
+    let path = paths::config_path();
+    let text = match std::fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return (Outcome::Pass(format!("none at {}; the defaults apply", path.display())), config::Config::default())
+        }
+        Err(e) => return (Outcome::Fail(format!("{}: {}", path.display(), e)), config::Config::default()),
+    };
+    match toml::from_str::<config::Config>(&text) {
+        Ok(config) => (Outcome::Pass(format!("{} parses", path.display())), config),
+        // The first line has the position; the rest is a drawing of it
+        Err(e) => {
+            let error = e.to_string();
+            let summary = error.lines().next().unwrap_or_default();
+            (Outcome::Fail(format!("{} is ignored until it's fixed: {}", path.display(), summary)), config::Config::default())
+        }
+    }
+}
+
+fn boards_file(path: &std::path::Path) -> Outcome {
+    match discovery::from_file(path) {
+        Ok(tokens) if tokens.is_empty() => Outcome::Fail(format!("{} has no board tokens", path.display())),
+        Ok(tokens) => Outcome::Pass(format!("{} lists {} board(s)", path.display(), tokens.len())),
+        Err(e) => Outcome::Fail(e),
+    }
+}
+
+fn profiles(config: &config::Config) -> Outcome {
+    let profiles = match profile::Profiles::load() {
+        Ok(Some(profiles)) => profiles,
+        Ok(None) => return Outcome::Warn("there isn't one yet; applying needs it, and `profile init` writes it".to_string()),
+        Err(e) => return Outcome::Fail(e),
+    };
+    let phone_country = config.applications.phone_country.as_deref();
+    let mut problems = Vec::new();
+    let mut warnings = Vec::new();
+    for name in profiles.names() {
+        let which = if name == profile::MAIN_PROFILE { String::new() } else { format!("[{}] ", name) };
+        match profiles.get(Some(name)) {
+            Ok(profile) => {
+                problems.extend(profile.problems().into_iter().map(|problem| format!("{}{}", which, problem)));
+                warnings.extend(profile.warnings(phone_country).into_iter().map(|warning| format!("{}{}", which, warning)));
+            }
+            Err(e) => problems.push(e),
+        }
+    }
+    if !problems.is_empty() {
+        Outcome::Fail(problems.join("; "))
+    } else if !warnings.is_empty() {
+        Outcome::Warn(warnings.join("; "))
+    } else {
+        Outcome::Pass(format!("{} profile(s), all usable", profiles.names().len()))
+    }
+}
+
+// Not critical: a broken file only means forms are filled from the profile alone
+fn answer_overrides() -> Outcome {
+    match overrides::Overrides::load() {
+        Ok(overrides) => match overrides.counts() {
+            (0, _) => Outcome::Pass("none; forms are filled from the profile".to_string()),
+            (boards, patterns) => Outcome::Pass(format!("{} pattern(s) for {} board(s)", patterns, boards)),
+        },
+        Err(e) => Outcome::Warn(format!("{}; it's ignored until it's fixed", e)),
+    }
+}
+
+// --resume and the per-board resumes in config.toml; the profiles' own are checked with them
+fn resumes(cli: &Cli, config: config::Config) -> Vec<(String, Outcome)> {
+    let mut resumes: Vec<(String, std::path::PathBuf)> = Vec::new();
+    if let Some(path) = &cli.resume {
+        resumes.push(("--resume".to_string(), path.clone()));
+    }
+    let mut by_board: Vec<(String, std::path::PathBuf)> = config.resumes.into_iter().collect();
+    by_board.sort();
+    resumes.extend(by_board.into_iter().map(|(board, path)| (format!("Resume for {}", board), path)));
+    resumes
+        .into_iter()
+        .map(|(name, path)| {
+            let outcome = match profile::check_resume(&path) {
+                Ok(()) => Outcome::Pass(format!("{} is ready to send", path.display())),
+                Err(e) => Outcome::Fail(e),
+            };
+            (name, outcome)
+        })
+        .collect()
+}
+
+fn job_store() -> Outcome {
+    let path = paths::database_path();
+    match store::JobStore::open(&path) {
+        Ok(_) => Outcome::Pass(format!("{} opens", path.display())),
+        Err(e) => Outcome::Fail(format!("{}: {}", path.display(), e)),
+    }
+}
+
+fn terminal() -> Outcome {
+    let accessible = "the job browser needs an interactive terminal; --accessible works without one";
+    if !std::io::stdout().is_terminal() || !std::io::stdin().is_terminal() {
+        return Outcome::Warn(format!("not a terminal; {}", accessible));
+    }
+    if std::env::var("TERM").is_ok_and(|term| term == "dumb") {
+        return Outcome::Warn(format!("TERM=dumb; {}", accessible));
+    }
+    let size = match crossterm::terminal::size() {
+        Ok(size) => size,
+        Err(e) => return Outcome::Warn(format!("its size can't be read ({}); {}", e, accessible)),
+    };
+    if let Err(e) = crossterm::terminal::enable_raw_mode().and_then(|()| crossterm::terminal::disable_raw_mode()) {
+        return Outcome::Warn(format!("raw mode isn't available ({}); {}", e, accessible));
+    }
+    Outcome::Pass(format!("{}x{}, raw mode works", size.0, size.1))
+}
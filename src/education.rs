@@ -0,0 +1,278 @@
+use crate::application::{ApplicationForm, Question, QuestionField};
+use crate::paths;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
+// Education questions answer with ids from the board's own lists:
+//
+//   GET https://boards-api.greenhouse.io/v1/boards/{token}/education/schools?page=N
+//
+// and the same for `degrees` and `disciplines`, a page of `{id, text}` items at
+// a time. Each list a form needs is fetched whole, every page, and cached per
+// board under the cache directory for a week, unless state isn't kept. A list that can't be had leaves
+// its field as free text.
+
+const CACHE_DAYS: u64 = 7;
+// The school list runs to a few thousand entries; anything past this is a runaway
+const MAX_PAGES: u32 = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum List {
+    Schools,
+    Degrees,
+    Disciplines,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Item {
+    pub id: u64,
+    pub text: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Page {
+    #[serde(default)]
+    items: Vec<Item>,
+    #[serde(default)]
+    meta: Option<Meta>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Meta {
+    #[serde(default)]
+    total_count: Option<usize>,
+}
+
+// The lists one board's forms use, as far as they could be loaded
+#[derive(Debug, Clone, Default)]
+pub struct Lists {
+    lists: BTreeMap<List, Vec<Item>>,
+    // List -> why it couldn't be loaded
+    pub unavailable: Vec<(List, String)>,
+}
+
+impl List {
+    const ALL: [List; 3] = [List::Schools, List::Degrees, List::Disciplines];
+
+    fn path(self) -> &'static str {
+        match self {
+            List::Schools => "schools",
+            List::Degrees => "degrees",
+            List::Disciplines => "disciplines",
+        }
+    }
+
+    pub fn describe(self) -> &'static str {
+        match self {
+            List::Schools => "school",
+            List::Degrees => "degree",
+            List::Disciplines => "discipline",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            List::Schools => "School",
+            List::Degrees => "Degree",
+            List::Disciplines => "Discipline",
+        }
+    }
+
+    fn field_name(self) -> &'static str {
+        match self {
+            List::Schools => "educations[][school_name_id]",
+            List::Degrees => "educations[][degree_id]",
+            List::Disciplines => "educations[][discipline_id]",
+        }
+    }
+
+    // `school_name_id`, `educations[][school_name_id]` and the like
+    pub fn for_field(name: &str) -> Option<List> {
+        let key = name.trim_end_matches(']').rsplit('[').next().unwrap_or(name);
+        match key {
+            "school_name_id" | "school_id" => Some(List::Schools),
+            "degree_id" => Some(List::Degrees),
+            "discipline_id" => Some(List::Disciplines),
+            _ => None,
+        }
+    }
+}
+
+impl Lists {
+    // None when the list didn't load, so the field takes free text
+    pub fn for_field(&self, field: &QuestionField) -> Option<&[Item]> {
+        List::for_field(&field.name).and_then(|list| self.lists.get(&list)).map(Vec::as_slice)
+    }
+}
+
+// Boards that switch education on say so with `education` ("education_optional"
+// or "education_required") rather than listing the fields; those get a School,
+// Degree and Discipline question each, unless the form already has them
+pub fn add_questions(form: &mut ApplicationForm) {
+    let required = match form.education.as_deref() {
+        Some("education_required") => true,
+        Some("education_optional") => false,
+        _ => return,
+    };
+    let present = needed(form);
+    for list in List::ALL.into_iter().filter(|list| !present.contains(list)) {
+        form.questions.push(Question {
+            label: list.label().to_string(),
+            required,
+            description: None,
+            fields: vec![QuestionField { name: list.field_name().to_string(), field_type: "input_text".to_string(), values: Vec::new() }],
+        });
+    }
+}
+
+// The lists the form's fields take ids from
+pub fn needed(form: &ApplicationForm) -> Vec<List> {
+    let mut lists: Vec<List> =
+        form.questions.iter().flat_map(|question| &question.fields).filter_map(|field| List::for_field(&field.name)).collect();
+    lists.sort();
+    lists.dedup();
+    lists
+}
+
+// Each list the form needs, from the cache while it's fresh and from the board otherwise
+pub async fn load(client: &reqwest::Client, board_token: &str, form: &ApplicationForm) -> Lists {
+    let mut loaded = Lists::default();
+    for list in needed(form) {
+        let items = match cached(board_token, list) {
+            Some(items) => Ok(items),
+            None => fetch(client, board_token, list).await,
+        };
+        match items {
+            Ok(items) if items.is_empty() => loaded.unavailable.push((list, "the board's list is empty".to_string())),
+            Ok(items) => {
+                loaded.lists.insert(list, items);
+            }
+            Err(e) => loaded.unavailable.push((list, e)),
+        }
+    }
+    loaded
+}
+
+async fn fetch(client: &reqwest::Client, board_token: &str, list: List) -> Result<Vec<Item>, String> {
+    let mut items = Vec::new();
+    for page in 1..=MAX_PAGES {
+        let url = format!("https://boards-api.greenhouse.io/v1/boards/{}/education/{}?page={}", board_token, list.path(), page);
+        let response = client.get(&url).send().await.map_err(|e| format!("network error: {}", e))?;
+        if !response.status().is_success() {
+            return Err(format!("board returned status {}", response.status()));
+        }
+        let page: Page = response.json().await.map_err(|e| format!("unexpected response: {}", e))?;
+        let last = page.items.is_empty();
+        items.extend(page.items);
+        if last || page.meta.and_then(|meta| meta.total_count).is_some_and(|total| items.len() >= total) {
+            break;
+        }
+    }
+    // A list that can't be cached is still good for this run
+    if let (true, Ok(text)) = (paths::persistent(), serde_json::to_string(&items)) {
+        let _ = std::fs::create_dir_all(cache_dir(board_token)).and_then(|()| std::fs::write(cache_path(board_token, list), text));
+    }
+    Ok(items)
+}
+
+fn cached(board_token: &str, list: List) -> Option<Vec<Item>> {
+    let path = cache_path(board_token, list);
+    let age = std::fs::metadata(&path).and_then(|metadata| metadata.modified()).ok()?.elapsed().unwrap_or(Duration::ZERO);
+    if age > Duration::from_secs(CACHE_DAYS * 24 * 60 * 60) {
+        return None;
+    }
+    serde_json::from_str(&std::fs::read_to_string(path).ok()?).ok()
+}
+
+fn cache_dir(board_token: &str) -> PathBuf {
+    paths::education_cache_dir().join(board_token)
+}
+
+fn cache_path(board_token: &str, list: List) -> PathBuf {
+    cache_dir(board_token).join(format!("{}.json", list.path()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn education_fields_name_their_lists() {
+        assert_eq!(List::for_field("educations[][school_name_id]"), Some(List::Schools));
+        assert_eq!(List::for_field("school_name_id"), Some(List::Schools));
+        assert_eq!(List::for_field("educations[][degree_id]"), Some(List::Degrees));
+        assert_eq!(List::for_field("discipline_id"), Some(List::Disciplines));
+        assert_eq!(List::for_field("question_31604873"), None);
+
+        let mut form: ApplicationForm = serde_json::from_value(serde_json::json!({
+            "questions": [{ "label": "Degree", "required": false, "fields": [{ "name": "educations[][degree_id]", "type": "input_text" }] }],
+            "education": "education_required",
+        }))
+        .unwrap();
+        add_questions(&mut form);
+        let labels: Vec<(&str, bool)> = form.questions.iter().map(|question| (question.label.as_str(), question.required)).collect();
+        assert_eq!(labels, [("Degree", false), ("School", true), ("Discipline", true)]);
+        assert_eq!(needed(&form), [List::Schools, List::Degrees, List::Disciplines]);
+    }
+
+    #[test]
+    fn cached_lists_are_used_without_fetching() {
+        let board = format!("education_cache_{}", std::process::id());
+        let items = vec![Item { id: 1, text: "MIT".to_string() }, Item { id: 2, text: "Stanford".to_string() }];
+        std::fs::create_dir_all(cache_dir(&board)).unwrap();
+        std::fs::write(cache_path(&board, List::Schools), serde_json::to_string(&items).unwrap()).unwrap();
+
+        let form: ApplicationForm = serde_json::from_value(serde_json::json!({
+            "questions": [{ "label": "School", "fields": [{ "name": "educations[][school_name_id]", "type": "input_text" }] }],
+        }))
+        .unwrap();
+        let lists = tokio::runtime::Runtime::new().unwrap().block_on(load(&reqwest::Client::new(), &board, &form));
+        assert_eq!(lists.for_field(&form.questions[0].fields[0]), Some(items.as_slice()));
+        assert!(lists.unavailable.is_empty());
+        std::fs::remove_dir_all(cache_dir(&board)).unwrap();
+    }
+
+    #[test]
+    fn the_form_sends_the_id_of_the_school_picked() {
+        use crate::form_editor::FormEditor;
+        use crate::submit::{Answer, FilledForm};
+        use crossterm::event::KeyCode;
+
+        let mut form = ApplicationForm { questions: Vec::new(), compliance: Vec::new(), education: Some("education_required".to_string()) };
+        add_questions(&mut form);
+        form.questions.truncate(1);
+        let schools = ["University of Washington", "Stanford University", "Washington University in St. Louis"];
+        let lists = Lists {
+            lists: BTreeMap::from([(List::Schools, schools.iter().zip(1..).map(|(text, id)| Item { id, text: text.to_string() }).collect())]),
+            unavailable: Vec::new(),
+        };
+        let mut editor = FormEditor::new(&form, &FilledForm::default(), Some(&lists));
+
+        "washington".chars().for_each(|c| editor.handle_key(KeyCode::Char(c)));
+        // Typed but not picked: nothing is sent, and the form says why
+        let filled = editor.filled();
+        assert!(filled.answers.is_empty());
+        assert_eq!(filled.problems.len(), 1);
+        assert!(filled.problems[0].message.contains("school list"), "{}", filled.problems[0].message);
+
+        // Names starting with what's typed come first
+        editor.handle_key(KeyCode::Down);
+        editor.handle_key(KeyCode::Enter);
+        let filled = editor.filled();
+        assert!(filled.problems.is_empty());
+        match &filled.answers[..] {
+            [(name, Answer::Choice { label, value })] => {
+                assert_eq!(name, "educations[][school_name_id]");
+                assert_eq!((label.as_str(), value.as_str()), ("University of Washington", "1"));
+            }
+            answers => panic!("{:?}", answers),
+        }
+
+        // Without the list the field is free text
+        let mut editor = FormEditor::new(&form, &FilledForm::default(), None);
+        "MIT".chars().for_each(|c| editor.handle_key(KeyCode::Char(c)));
+        assert!(matches!(&editor.filled().answers[..], [(_, Answer::Text(text))] if text == "MIT"));
+    }
+}
@@ -0,0 +1,305 @@
+use crate::store::{self, ApplicationRecord, JobStore, PriorApplication};
+use crate::{locale, JobResult};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+// Bumped whenever the envelope layout changes so readers can adapt
+pub const EXPORT_SCHEMA_VERSION: u32 = 1;
+
+// Self-describing export: the results plus the context needed to interpret them
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchExport<R = JobResult> {
+    pub schema_version: u32,
+    pub tool_version: String,
+    pub exported_at: chrono::DateTime<chrono::Utc>,
+    pub search: SearchParameters,
+    pub board_count: usize,
+    pub results: Vec<R>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchParameters {
+    pub keyword: String,
+    pub location: String,
+    #[serde(default)]
+    pub new_only: bool,
+    #[serde(default)]
+    pub since: Option<chrono::NaiveDate>,
+}
+
+impl SearchExport {
+    pub fn new(search: SearchParameters, board_count: usize, results: Vec<JobResult>) -> Self {
+        Self {
+            schema_version: EXPORT_SCHEMA_VERSION,
+            tool_version: env!("CARGO_PKG_VERSION").to_string(),
+            exported_at: chrono::Utc::now(),
+            search,
+            board_count,
+            results,
+        }
+    }
+}
+
+// The user's own state for a job (tags, application status and notes),
+// merged from the job store at export time. JSON nests it under
+// "annotations" on each result so consumers can ignore it in one place.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Annotations {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    // "applied" or "possibly applied"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub applied_at: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub notes: Vec<String>,
+}
+
+// A result with its annotations appended, for --include-annotations
+#[derive(Serialize)]
+struct AnnotatedResult<'a> {
+    #[serde(flatten)]
+    job: &'a JobResult,
+    annotations: &'a Annotations,
+}
+
+impl Annotations {
+    fn is_empty(&self) -> bool {
+        self.tags.is_empty() && self.status.is_none() && self.notes.is_empty()
+    }
+}
+
+// One entry per job, in the same order
+pub fn load_annotations(store: &JobStore, jobs: &[JobResult]) -> rusqlite::Result<Vec<Annotations>> {
+    let applications = store.applications(&store::ApplicationFilter::default())?;
+    jobs.iter()
+        .map(|job| {
+            let (status, applied_at) = match store::find_prior_application(&applications, job) {
+                Some(PriorApplication::Applied(record)) => (Some("applied"), Some(record.applied_at.clone())),
+                Some(PriorApplication::PossiblyApplied(record)) => {
+                    (Some("possibly applied"), Some(record.applied_at.clone()))
+                }
+                Some(PriorApplication::FollowUp(record)) => (Some("follow-up"), Some(record.applied_at.clone())),
+                None => (None, None),
+            };
+            let notes = applications
+                .iter()
+                .filter(|record| record.board_token == job.board_token && record.job_id == job.id)
+                .filter_map(|record| record.notes.clone())
+                .filter(|note| !note.trim().is_empty())
+                .collect();
+            Ok(Annotations { tags: store.tags_for(job)?, status: status.map(str::to_string), applied_at, notes })
+        })
+        .collect()
+}
+
+// RFC 4180 quoting: wrap in quotes when needed and double any embedded quotes
+pub fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+pub fn csv_row(fields: &[&str]) -> String {
+    fields.iter().map(|f| csv_field(f)).collect::<Vec<_>>().join(",")
+}
+
+pub fn applications_csv(records: &[ApplicationRecord]) -> String {
+    let mut out = csv_row(&["id", "applied_at", "board_token", "job_id", "title", "company", "url", "method", "notes", "resume", "resume_sha256", "cover_letter", "cover_letter_file", "cover_letter_sha256", "profile", "status", "status_at", "remind_on"]);
+    out.push('\n');
+    for record in records {
+        out.push_str(&csv_row(&[
+            &record.id.to_string(),
+            &record.applied_at,
+            &record.board_token,
+            &record.job_id.to_string(),
+            &record.title,
+            &record.company,
+            &record.url,
+            &record.method,
+            record.notes.as_deref().unwrap_or(""),
+            record.resume.as_deref().unwrap_or(""),
+            record.resume_sha256.as_deref().unwrap_or(""),
+            record.cover_letter.as_deref().unwrap_or(""),
+            record.cover_letter_file.as_deref().unwrap_or(""),
+            record.cover_letter_sha256.as_deref().unwrap_or(""),
+            record.profile.as_deref().unwrap_or(""),
+            if record.went_out() { record.current_status().as_str() } else { "" },
+            record.status_at.as_deref().unwrap_or(""),
+            record.remind_on.as_deref().unwrap_or(""),
+        ]));
+        out.push('\n');
+    }
+    out
+}
+
+// Write the envelope, or just the results array when `bare` is set
+pub fn write_json(path: &Path, export: &SearchExport, bare: bool, annotations: Option<&[Annotations]>)
+    -> Result<(), Box<dyn std::error::Error>> {
+    std::fs::write(path, results_json(export, bare, annotations)?)?;
+    Ok(())
+}
+
+fn results_json(export: &SearchExport, bare: bool, annotations: Option<&[Annotations]>)
+    -> serde_json::Result<String> {
+    let Some(annotations) = annotations else {
+        return if bare { serde_json::to_string_pretty(&export.results) } else { serde_json::to_string_pretty(export) };
+    };
+    let annotated = SearchExport {
+        schema_version: export.schema_version,
+        tool_version: export.tool_version.clone(),
+        exported_at: export.exported_at,
+        search: export.search.clone(),
+        board_count: export.board_count,
+        results: export
+            .results
+            .iter()
+            .zip(annotations)
+            .map(|(job, annotations)| AnnotatedResult { job, annotations })
+            .collect(),
+    };
+    if bare {
+        serde_json::to_string_pretty(&annotated.results)
+    } else {
+        serde_json::to_string_pretty(&annotated)
+    }
+}
+
+// --export: the format follows the extension, .csv, .md or JSON for anything else
+pub fn write_export(path: &Path, export: &SearchExport, annotations: Option<&[Annotations]>)
+    -> Result<(), Box<dyn std::error::Error>> {
+    let extension = path.extension().and_then(|e| e.to_str()).unwrap_or_default().to_lowercase();
+    let text = match extension.as_str() {
+        "csv" => results_csv(&export.results, annotations),
+        "md" | "markdown" => results_markdown(export, annotations),
+        _ => results_json(export, false, annotations)?,
+    };
+    std::fs::write(path, text)?;
+    Ok(())
+}
+
+// `e` in the job browser: the jobs the list shows, in a new file each time
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SnapshotFormat {
+    Csv,
+    Json,
+    Markdown,
+}
+
+impl SnapshotFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            SnapshotFormat::Csv => "csv",
+            SnapshotFormat::Json => "json",
+            SnapshotFormat::Markdown => "md",
+        }
+    }
+}
+
+// `results-YYYYMMDD-HHMMSS.<ext>` in `dir`, named for the export time and
+// created along with `dir` if needed; a second export within the same second
+// gets a `-2` suffix rather than replacing the first
+pub fn write_snapshot(dir: &Path, format: SnapshotFormat, export: &SearchExport, annotations: Option<&[Annotations]>)
+    -> Result<PathBuf, Box<dyn std::error::Error>> {
+    std::fs::create_dir_all(dir)?;
+    let stem = format!("results-{}", export.exported_at.with_timezone(&chrono::Local).format("%Y%m%d-%H%M%S"));
+    let mut path = dir.join(format!("{}.{}", stem, format.extension()));
+    let mut copy = 1;
+    while path.exists() {
+        copy += 1;
+        path = dir.join(format!("{}-{}.{}", stem, copy, format.extension()));
+    }
+    let text = match format {
+        SnapshotFormat::Csv => results_csv(&export.results, annotations),
+        SnapshotFormat::Json => results_json(export, false, annotations)?,
+        SnapshotFormat::Markdown => results_markdown(export, annotations),
+    };
+    std::fs::write(&path, text)?;
+    Ok(path)
+}
+
+pub fn results_csv(jobs: &[JobResult], annotations: Option<&[Annotations]>) -> String {
+    let mut header = vec!["id", "board_token", "title", "company", "location", "date_posted", "url", "first_seen"];
+    if annotations.is_some() {
+        header.extend(["tags", "status", "applied_at", "notes"]);
+    }
+    let mut out = csv_row(&header);
+    out.push('\n');
+    for (i, job) in jobs.iter().enumerate() {
+        let id = job.id.to_string();
+        let first_seen = job.first_seen.map(|seen| seen.to_rfc3339()).unwrap_or_default();
+        let mut fields = vec![
+            id.as_str(), &job.board_token, &job.title, &job.company, &job.location, &job.date_posted, &job.url,
+            &first_seen,
+        ];
+        let (tags, notes);
+        if let Some(annotation) = annotations.and_then(|all| all.get(i)) {
+            tags = annotation.tags.join(" ");
+            // Multi-line notes stay in one quoted field
+            notes = annotation.notes.join("\n\n");
+            fields.extend([
+                tags.as_str(),
+                annotation.status.as_deref().unwrap_or(""),
+                annotation.applied_at.as_deref().unwrap_or(""),
+                notes.as_str(),
+            ]);
+        } else if annotations.is_some() {
+            fields.extend(["", "", "", ""]);
+        }
+        out.push_str(&csv_row(&fields));
+        out.push('\n');
+    }
+    out
+}
+
+pub fn results_markdown(export: &SearchExport, annotations: Option<&[Annotations]>) -> String {
+    let locale = locale::current();
+    let mut out = String::from("# Job search results\n\n");
+    out.push_str(&format!(
+        "{} job(s) for \"{}\" in {}, exported {}\n",
+        locale.count(export.results.len()),
+        markdown_escape(&export.search.keyword),
+        markdown_escape(&export.search.location),
+        locale.date_time(export.exported_at)
+    ));
+    for (i, job) in export.results.iter().enumerate() {
+        out.push_str(&format!("\n## {}. [{}]({})\n\n", i + 1, markdown_escape(&job.title), job.url.replace(' ', "%20")));
+        out.push_str(&format!("- **Company:** {}\n", markdown_escape(&job.company)));
+        out.push_str(&format!("- **Location:** {}\n", markdown_escape(&job.location)));
+        out.push_str(&format!("- **Posted:** {}\n", locale.timestamp_date(&job.date_posted)));
+        out.push_str(&format!("- **Job ID:** {} ({})\n", job.id, markdown_escape(&job.board_token)));
+        let Some(annotation) = annotations.and_then(|all| all.get(i)).filter(|a| !a.is_empty()) else {
+            continue;
+        };
+        if !annotation.tags.is_empty() {
+            let tags: Vec<String> = annotation.tags.iter().map(|tag| format!("`{}`", tag)).collect();
+            out.push_str(&format!("- **Tags:** {}\n", tags.join(" ")));
+        }
+        if let Some(status) = &annotation.status {
+            let date = annotation.applied_at.as_deref().map(|at| locale.timestamp_date(at)).unwrap_or_default();
+            out.push_str(&format!("- **Status:** {} {}\n", status, date));
+        }
+        for note in &annotation.notes {
+            out.push('\n');
+            for line in note.lines() {
+                out.push_str(&format!("> {}\n", line).replace("> \n", ">\n"));
+            }
+        }
+    }
+    out
+}
+
+// Backslash the characters that would otherwise start Markdown formatting
+fn markdown_escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '#' | '|') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
@@ -0,0 +1,130 @@
+use scraper::{ElementRef, Html, Node};
+use std::collections::BTreeMap;
+
+// Pulls labeled sections ("Team: Payments", or a "Tech Stack" heading
+// followed by a list) out of a job's HTML description.
+
+const BLOCK_ELEMENTS: &[&str] = &[
+    "p", "div", "br", "li", "ul", "ol", "h1", "h2", "h3", "h4", "h5", "h6", "tr", "section", "blockquote",
+];
+const EMPHASIS_ELEMENTS: &[&str] = &["strong", "b", "h1", "h2", "h3", "h4", "h5", "h6"];
+
+struct TextLine {
+    text: String,
+    // Every word on the line was bold or in a heading, so it likely starts a section
+    heading: bool,
+}
+
+#[derive(Default)]
+struct LineBuilder {
+    lines: Vec<TextLine>,
+    text: String,
+    has_plain_text: bool,
+}
+
+impl LineBuilder {
+    fn push(&mut self, text: &str, emphasized: bool) {
+        if !emphasized && !text.trim().is_empty() {
+            self.has_plain_text = true;
+        }
+        self.text.push_str(text);
+    }
+
+    fn flush(&mut self) {
+        let text = self.text.split_whitespace().collect::<Vec<_>>().join(" ");
+        if !text.is_empty() {
+            self.lines.push(TextLine { text, heading: !self.has_plain_text });
+        }
+        self.text.clear();
+        self.has_plain_text = false;
+    }
+}
+
+pub fn extract_fields(content: &str, labels: &[String]) -> BTreeMap<String, String> {
+    let mut fields = BTreeMap::new();
+    if labels.is_empty() || content.is_empty() {
+        return fields;
+    }
+
+    let lines = text_lines(content);
+    for label in labels {
+        if let Some(value) = find_section(&lines, label) {
+            fields.insert(label.clone(), value);
+        }
+    }
+    fields
+}
+
+// The description as readable lines, one per paragraph or list item
+pub fn plain_text(content: &str) -> Vec<String> {
+    text_lines(content).into_iter().map(|line| line.text).collect()
+}
+
+fn text_lines(content: &str) -> Vec<TextLine> {
+    // The boards API returns `content` with its HTML entity-escaped
+    let html = if content.contains("&lt;") {
+        Html::parse_fragment(content).root_element().text().collect::<String>()
+    } else {
+        content.to_string()
+    };
+
+    let fragment = Html::parse_fragment(&html);
+    let mut builder = LineBuilder::default();
+    walk(fragment.root_element(), false, &mut builder);
+    builder.flush();
+    builder.lines
+}
+
+fn walk(element: ElementRef<'_>, emphasized: bool, builder: &mut LineBuilder) {
+    let name = element.value().name();
+    let block = BLOCK_ELEMENTS.contains(&name);
+    let emphasized = emphasized || EMPHASIS_ELEMENTS.contains(&name);
+    if block {
+        builder.flush();
+    }
+    for child in element.children() {
+        if let Node::Text(text) = child.value() {
+            builder.push(text, emphasized);
+        } else if let Some(child) = ElementRef::wrap(child) {
+            walk(child, emphasized, builder);
+        }
+    }
+    if block {
+        builder.flush();
+    }
+}
+
+// Either "Label: value" on one line, or a "Label" line followed by the lines
+// up to the next heading
+fn find_section(lines: &[TextLine], label: &str) -> Option<String> {
+    let label = label.trim();
+    for (i, line) in lines.iter().enumerate() {
+        let Some(rest) = strip_label(&line.text, label) else {
+            continue;
+        };
+        let inline = rest.trim_start_matches(':').trim();
+        if !inline.is_empty() {
+            return Some(inline.to_string());
+        }
+
+        let section: Vec<&str> = lines[i + 1..]
+            .iter()
+            .take_while(|l| !l.heading && !l.text.ends_with(':'))
+            .map(|l| l.text.as_str())
+            .collect();
+        if !section.is_empty() {
+            return Some(section.join("; "));
+        }
+    }
+    None
+}
+
+fn strip_label<'a>(text: &'a str, label: &str) -> Option<&'a str> {
+    if label.is_empty() || text.len() < label.len() || !text.is_char_boundary(label.len()) {
+        return None;
+    }
+    let (head, rest) = text.split_at(label.len());
+    let rest = rest.trim_start();
+    let labeled = rest.is_empty() || rest.starts_with(':');
+    (head.to_lowercase() == label.to_lowercase() && labeled).then_some(rest)
+}
@@ -0,0 +1,80 @@
+use crate::{posted_at, report, JobResult};
+use atom_syndication::{Content, Entry, Feed, Link, Person, Text};
+use std::path::Path;
+
+// Atom feed of matches for feed readers. Each run merges its matches into the
+// existing file (entries are keyed by job URL) and keeps only the newest
+// `limit` entries, so the file can be served by any static file server.
+
+pub fn update_feed(path: &Path, jobs: &[JobResult], limit: usize) -> Result<usize, String> {
+    let mut feed = match std::fs::read_to_string(path) {
+        Ok(text) => text
+            .parse::<Feed>()
+            // Refuse to overwrite a file we can't read back rather than lose its entries
+            .map_err(|e| format!("{} is not an Atom feed ({}); leaving it untouched", path.display(), e))?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => new_feed(path),
+        Err(e) => return Err(e.to_string()),
+    };
+
+    let now = chrono::Utc::now().fixed_offset();
+    let mut entries: Vec<Entry> = feed.entries().to_vec();
+    for job in jobs {
+        match entries.iter_mut().find(|existing| existing.id() == job.url) {
+            Some(existing) => *existing = job_entry(job, *existing.updated()),
+            None => entries.push(job_entry(job, now)),
+        }
+    }
+    entries.sort_by(|a, b| b.updated().cmp(a.updated()).then_with(|| a.id().cmp(b.id())));
+    entries.truncate(limit);
+
+    let count = entries.len();
+    feed.set_entries(entries);
+    feed.set_updated(now);
+
+    let tmp = path.with_extension("xml.tmp");
+    let file = std::fs::File::create(&tmp).map_err(|e| e.to_string())?;
+    feed.write_to(std::io::BufWriter::new(file)).map_err(|e| e.to_string())?;
+    std::fs::rename(&tmp, path).map_err(|e| e.to_string())?;
+    Ok(count)
+}
+
+fn new_feed(path: &Path) -> Feed {
+    let name = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or("jobs");
+    let mut feed = Feed::default();
+    feed.set_title(Text::plain("Greenhouse job matches"));
+    feed.set_id(format!("urn:greenhouse-job-search:feed:{}", name));
+    feed.set_authors(vec![Person { name: "greenhouse-job-search".to_string(), ..Default::default() }]);
+    feed
+}
+
+// Atom requires `updated`; for an unreadable posting date fall back to when
+// the job was first seen, then to `fallback`
+fn job_entry(job: &JobResult, fallback: atom_syndication::FixedDateTime) -> Entry {
+    let updated = posted_at(job)
+        .or(job.first_seen)
+        .map(|instant| instant.fixed_offset())
+        .unwrap_or(fallback);
+
+    let mut entry = Entry::default();
+    entry.set_id(job.url.clone());
+    entry.set_title(Text::plain(format!("{} at {}", job.title, job.company)));
+    entry.set_updated(updated);
+    entry.set_links(vec![Link {
+        href: job.url.clone(),
+        rel: "alternate".to_string(),
+        ..Default::default()
+    }]);
+    entry.set_summary(Some(Text::plain(format!("{} · {}", job.company, job.location))));
+    entry.set_content(Some(Content {
+        content_type: Some("html".to_string()),
+        value: Some(format!(
+            "<p><strong>{}</strong> at {}</p><p>{}</p><p><a href=\"{}\">View the posting</a></p>",
+            report::escape(&job.title),
+            report::escape(&job.company),
+            report::escape(&job.location),
+            report::escape(&job.url),
+        )),
+        ..Default::default()
+    }));
+    entry
+}
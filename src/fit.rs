@@ -0,0 +1,515 @@
+use crate::extract;
+use flate2::read::ZlibDecoder;
+use std::collections::{HashMap, HashSet};
+use std::io::Read;
+use std::path::Path;
+
+// Resume fit: which of a description's prominent terms the resume never
+// mentions. Terms are weighted by how often the description uses them
+// (damped, so repeating a word doesn't drown out the rest) and how rare they
+// are across the descriptions at hand (the board's postings, or the results
+// being browsed). Terms every one of those uses are the company's boilerplate
+// and don't count as requirements. The score is the share of the prominent
+// terms' weight the resume covers.
+//
+// Resumes are read as plain text (.txt, .md) or from a PDF's text operators.
+// PDFs whose fonts don't map to readable bytes (some CID fonts) and scanned
+// ones have no text to find; a plain-text copy works for those.
+
+// How many of the description's heaviest terms are checked against the resume
+const PROMINENT_TERMS: usize = 25;
+
+const MIN_BOILERPLATE_DOCUMENTS: usize = 3;
+
+// Common English, plus what every posting says whatever the job is
+const STOPWORDS: &[&str] = &[
+    "a", "about", "above", "across", "after", "all", "also", "am", "an", "and", "any", "are", "as", "at", "be",
+    "because", "been", "being", "both", "but", "by", "can", "could", "did", "do", "does", "doing", "during", "each",
+    "either", "etc", "every", "few", "for", "from", "further", "had", "has", "have", "having", "he", "her", "here",
+    "hers", "him", "his", "how", "however", "if", "in", "into", "is", "it", "its", "itself", "just", "least", "less",
+    "like", "may", "me", "might", "more", "most", "much", "must", "my", "no", "nor", "not", "now", "of", "off", "on",
+    "once", "one", "only", "or", "other", "our", "ours", "out", "over", "own", "per", "plus", "same", "shall", "she",
+    "should", "so", "some", "such", "than", "that", "the", "their", "theirs", "them", "then", "there", "these",
+    "they", "this", "those", "through", "to", "too", "under", "until", "up", "upon", "us", "very", "via", "was", "we",
+    "were", "what", "when", "where", "whether", "which", "while", "who", "whom", "why", "will", "with", "within",
+    "without", "would", "you", "your", "yours", "yourself",
+    // Posting filler
+    "ability", "able", "apply", "applicants", "benefits", "best", "candidate", "candidates", "company", "day",
+    "environment", "equal", "employer", "experience", "help", "ideal", "including", "join", "looking", "new",
+    "opportunity", "preferred", "qualifications", "required", "requirements", "responsibilities", "role",
+    "strong", "team", "teams", "well", "work", "working", "year", "years",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Analysis {
+    // Share of the prominent terms' weight found in the resume, 0 to 1
+    pub score: f64,
+    // Heaviest first
+    pub matched: Vec<String>,
+    pub missing: Vec<String>,
+}
+
+// How many descriptions use each term, for weighting
+#[derive(Debug, Default)]
+pub struct Corpus {
+    documents: usize,
+    frequencies: HashMap<String, usize>,
+}
+
+impl Corpus {
+    pub fn new<'a>(descriptions: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut corpus = Corpus::default();
+        for description in descriptions {
+            corpus.documents += 1;
+            for term in terms(description).into_iter().collect::<HashSet<_>>() {
+                *corpus.frequencies.entry(term).or_default() += 1;
+            }
+        }
+        corpus
+    }
+
+    fn frequency(&self, term: &str) -> usize {
+        self.frequencies.get(term).copied().unwrap_or(0)
+    }
+
+    // Smoothed, so a term no description uses still weighs something and an empty corpus weighs by count alone
+    fn idf(&self, term: &str) -> f64 {
+        ((1 + self.documents) as f64 / (1 + self.frequency(term)) as f64).ln() + 1.0
+    }
+
+    // Too few descriptions can't tell boilerplate from a shared requirement
+    fn boilerplate(&self, term: &str) -> bool {
+        self.documents >= MIN_BOILERPLATE_DOCUMENTS && self.frequency(term) == self.documents
+    }
+}
+
+// Lowercased words without stopwords or numbers; "c++" and "c#" keep their symbols
+pub fn terms(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|c: char| !(c.is_alphanumeric() || c == '+' || c == '#'))
+        .map(|word| word.trim_start_matches(['+', '#']))
+        .filter(|word| word.chars().count() > 1 && word.chars().any(char::is_alphabetic))
+        .filter(|word| !STOPWORDS.contains(word))
+        .map(str::to_string)
+        .collect()
+}
+
+pub fn analyze(resume: &str, description: &str, corpus: &Corpus) -> Analysis {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for term in terms(description) {
+        *counts.entry(term).or_default() += 1;
+    }
+    let mut weighted: Vec<(String, f64)> = counts
+        .into_iter()
+        .filter(|(term, _)| !corpus.boilerplate(term))
+        .map(|(term, count)| {
+            let weight = (1.0 + (count as f64).ln()) * corpus.idf(&term);
+            (term, weight)
+        })
+        .collect();
+    weighted.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    weighted.truncate(PROMINENT_TERMS);
+
+    let resume_terms: HashSet<String> = terms(resume).into_iter().collect();
+    let mut analysis = Analysis { score: 0.0, matched: Vec::new(), missing: Vec::new() };
+    let (mut covered, mut total) = (0.0, 0.0);
+    for (term, weight) in weighted {
+        total += weight;
+        if mentions(&resume_terms, &term) {
+            covered += weight;
+            analysis.matched.push(term);
+        } else {
+            analysis.missing.push(term);
+        }
+    }
+    if total > 0.0 {
+        analysis.score = covered / total;
+    }
+    analysis
+}
+
+// "api" and "apis" are the same requirement
+fn mentions(resume_terms: &HashSet<String>, term: &str) -> bool {
+    resume_terms.contains(term)
+        || resume_terms.contains(&format!("{}s", term))
+        || term.strip_suffix('s').is_some_and(|singular| resume_terms.contains(singular))
+}
+
+// A posting's title and description as plain text; None when the record has no description
+pub fn description(raw: &serde_json::Value) -> Option<String> {
+    let content = raw.get("content").and_then(serde_json::Value::as_str)?;
+    let title = raw.get("title").and_then(serde_json::Value::as_str).unwrap_or_default();
+    Some(format!("{}\n{}", title, extract::plain_text(content).join("\n")))
+}
+
+pub fn resume_text(path: &Path) -> Result<String, String> {
+    let extension = path.extension().and_then(|ext| ext.to_str()).map(str::to_lowercase).unwrap_or_default();
+    let bytes = std::fs::read(path).map_err(|e| format!("can't read {}: {}", path.display(), e))?;
+    match extension.as_str() {
+        "pdf" => pdf_text(&bytes).map_err(|e| format!("{}: {}", path.display(), e)),
+        "txt" | "text" | "md" => Ok(String::from_utf8_lossy(&bytes).into_owned()),
+        _ => Err(format!(
+            "can't read text from {}; save it as a PDF, or as plain text for `analyze --against`",
+            path.display()
+        )),
+    }
+}
+
+// The text shown by a PDF's content streams, a line per text line. Only what
+// the text operators draw is read: images, fonts and metadata are skipped.
+pub fn pdf_text(bytes: &[u8]) -> Result<String, String> {
+    if !bytes.starts_with(b"%PDF") {
+        return Err("not a PDF file".to_string());
+    }
+    let mut text = String::new();
+    let mut from = 0;
+    while let Some(found) = find(&bytes[from..], b"stream") {
+        let keyword = from + found;
+        from = keyword + b"stream".len();
+        if keyword >= 3 && &bytes[keyword - 3..keyword] == b"end" {
+            continue;
+        }
+        let dictionary = &bytes[rfind(&bytes[..keyword], b"obj").unwrap_or(0)..keyword];
+        let mut start = from;
+        if bytes.get(start) == Some(&b'\r') {
+            start += 1;
+        }
+        if bytes.get(start) == Some(&b'\n') {
+            start += 1;
+        }
+        let Some(length) = find(&bytes[start..], b"endstream") else {
+            break;
+        };
+        from = start + length;
+        if let Some(content) = stream_content(dictionary, &bytes[start..start + length]) {
+            content_text(&content, &mut text);
+        }
+    }
+
+    let text: Vec<String> = text
+        .lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|line| !line.is_empty())
+        .collect();
+    let text = text.join("\n");
+    if text.chars().filter(|c| c.is_alphabetic()).count() < 20 {
+        return Err("no readable text in the PDF (it may be scanned, or its fonts don't map to text)".to_string());
+    }
+    Ok(text)
+}
+
+// The decoded stream when it could hold page text
+fn stream_content(dictionary: &[u8], data: &[u8]) -> Option<Vec<u8>> {
+    let skipped: [&[u8]; 4] = [b"/Image", b"/Length1", b"/XRef", b"/Metadata"];
+    if skipped.iter().any(|key| find(dictionary, key).is_some()) {
+        return None;
+    }
+    if find(dictionary, b"/FlateDecode").is_some() {
+        let mut decoded = Vec::new();
+        ZlibDecoder::new(data).read_to_end(&mut decoded).ok()?;
+        return Some(decoded);
+    }
+    // Any other filter (DCT, LZW, ...) isn't text we can read
+    if find(dictionary, b"/Filter").is_some() {
+        return None;
+    }
+    Some(data.to_vec())
+}
+
+enum Operand {
+    Text(Vec<u8>),
+    Number(f64),
+    Array(Vec<Operand>),
+    Other,
+}
+
+// Runs the text operators in one content stream: Tj, TJ, ' and " draw strings,
+// and Td, TD, T*, Tm and ET move to another line
+fn content_text(content: &[u8], text: &mut String) {
+    let mut operands: Vec<Operand> = Vec::new();
+    let mut arrays: Vec<Vec<Operand>> = Vec::new();
+    let mut i = 0;
+    while i < content.len() {
+        let byte = content[i];
+        let operand = match byte {
+            b'(' => {
+                let (string, end) = literal_string(content, i + 1);
+                i = end;
+                Operand::Text(string)
+            }
+            b'<' if content.get(i + 1) == Some(&b'<') => {
+                i += 2;
+                continue;
+            }
+            b'<' => {
+                let end = content[i..].iter().position(|&b| b == b'>').map_or(content.len(), |p| i + p);
+                let string = hex_string(&content[i + 1..end]);
+                i = end + 1;
+                Operand::Text(string)
+            }
+            b'[' => {
+                arrays.push(Vec::new());
+                i += 1;
+                continue;
+            }
+            b']' => {
+                i += 1;
+                match arrays.pop() {
+                    Some(items) => Operand::Array(items),
+                    None => continue,
+                }
+            }
+            b'%' => {
+                i = content[i..].iter().position(|&b| b == b'\n' || b == b'\r').map_or(content.len(), |p| i + p);
+                continue;
+            }
+            b'/' => {
+                i += 1 + word_length(&content[i + 1..]);
+                Operand::Other
+            }
+            b'0'..=b'9' | b'-' | b'+' | b'.' => {
+                let length = word_length(&content[i..]).max(1);
+                let number = std::str::from_utf8(&content[i..i + length]).ok().and_then(|n| n.parse().ok());
+                i += length;
+                number.map_or(Operand::Other, Operand::Number)
+            }
+            _ if byte.is_ascii_whitespace() || byte == b'>' || byte == b'{' || byte == b'}' || byte == b')' => {
+                i += 1;
+                continue;
+            }
+            _ => {
+                let length = word_length(&content[i..]).max(1);
+                let operator = &content[i..i + length];
+                i += length;
+                if operator == b"BI" {
+                    // Inline image data runs to EI and isn't text
+                    i = find(&content[i..], b"EI").map_or(content.len(), |p| i + p + 2);
+                } else {
+                    apply(operator, &operands, text);
+                }
+                operands.clear();
+                continue;
+            }
+        };
+        match arrays.last_mut() {
+            Some(array) => array.push(operand),
+            None => operands.push(operand),
+        }
+    }
+}
+
+fn apply(operator: &[u8], operands: &[Operand], text: &mut String) {
+    match operator {
+        b"Tj" | b"'" | b"\"" => {
+            if operator != b"Tj" {
+                text.push('\n');
+            }
+            if let Some(Operand::Text(string)) = operands.last() {
+                text.push_str(&decode(string));
+            }
+        }
+        b"TJ" => {
+            let Some(Operand::Array(items)) = operands.last() else {
+                return;
+            };
+            for item in items {
+                match item {
+                    Operand::Text(string) => text.push_str(&decode(string)),
+                    // A wide negative adjustment is how many PDFs space words
+                    Operand::Number(adjustment) if *adjustment < -200.0 => text.push(' '),
+                    _ => {}
+                }
+            }
+        }
+        b"Td" | b"TD" => {
+            let moves_down = matches!(operands.last(), Some(Operand::Number(dy)) if *dy != 0.0);
+            text.push(if moves_down { '\n' } else { ' ' });
+        }
+        b"T*" | b"Tm" | b"ET" => text.push('\n'),
+        _ => {}
+    }
+}
+
+fn word_length(bytes: &[u8]) -> usize {
+    bytes
+        .iter()
+        .position(|&b| b.is_ascii_whitespace() || b"()<>[]{}/%".contains(&b))
+        .unwrap_or(bytes.len())
+}
+
+// The bytes of a (string), with its escapes resolved; returns where it ends
+fn literal_string(content: &[u8], start: usize) -> (Vec<u8>, usize) {
+    let mut string = Vec::new();
+    let mut depth = 1;
+    let mut i = start;
+    while i < content.len() {
+        let byte = content[i];
+        i += 1;
+        match byte {
+            b'\\' => {
+                let Some(&escaped) = content.get(i) else {
+                    break;
+                };
+                i += 1;
+                match escaped {
+                    b'n' => string.push(b'\n'),
+                    b'r' => string.push(b'\r'),
+                    b't' => string.push(b'\t'),
+                    b'b' | b'f' => {}
+                    b'0'..=b'7' => {
+                        let mut value = u32::from(escaped - b'0');
+                        for _ in 0..2 {
+                            match content.get(i) {
+                                Some(&digit @ b'0'..=b'7') => {
+                                    value = value * 8 + u32::from(digit - b'0');
+                                    i += 1;
+                                }
+                                _ => break,
+                            }
+                        }
+                        string.push(value as u8);
+                    }
+                    // A backslash before a line break continues the string on the next line
+                    b'\r' => {
+                        if content.get(i) == Some(&b'\n') {
+                            i += 1;
+                        }
+                    }
+                    b'\n' => {}
+                    other => string.push(other),
+                }
+            }
+            b'(' => {
+                depth += 1;
+                string.push(byte);
+            }
+            b')' => {
+                depth -= 1;
+                if depth == 0 {
+                    break;
+                }
+                string.push(byte);
+            }
+            _ => string.push(byte),
+        }
+    }
+    (string, i)
+}
+
+fn hex_string(hex: &[u8]) -> Vec<u8> {
+    let digits: Vec<u8> = hex.iter().filter_map(|&b| (b as char).to_digit(16)).map(|d| d as u8).collect();
+    // An odd final digit is padded with 0
+    digits.chunks(2).map(|pair| pair[0] << 4 | pair.get(1).copied().unwrap_or(0)).collect()
+}
+
+// UTF-16 when the string says so, else the single-byte encodings simple fonts use
+fn decode(string: &[u8]) -> String {
+    if let Some(utf16) = string.strip_prefix(&[0xfe, 0xff]) {
+        let units: Vec<u16> = utf16.chunks(2).map(|pair| u16::from_be_bytes([pair[0], pair.get(1).copied().unwrap_or(0)])).collect();
+        return String::from_utf16_lossy(&units);
+    }
+    string
+        .iter()
+        .filter_map(|&byte| match byte {
+            0x91 | 0x92 => Some('\''),
+            0x93 | 0x94 => Some('"'),
+            0x95 => Some('•'),
+            0x96 | 0x97 => Some('-'),
+            b'\t' | b'\n' | b'\r' => Some(' '),
+            byte if byte < 0x20 || byte == 0x7f => None,
+            byte => Some(char::from(byte)),
+        })
+        .collect()
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|window| window == needle)
+}
+
+fn rfind(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).rposition(|window| window == needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture_path(name: &str) -> std::path::PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/resume_fit").join(name)
+    }
+
+    // Postings from one board in the shape the boards API returns, sharing the company's boilerplate
+    fn posting(name: &str) -> String {
+        let path = fixture_path(&format!("{}.json", name));
+        let text = std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+        let raw: serde_json::Value = serde_json::from_str(&text).unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+        description(&raw).expect("fixture has a description")
+    }
+
+    fn board() -> (String, Corpus) {
+        let postings: Vec<String> = ["platform_engineer", "data_analyst", "product_designer"].into_iter().map(posting).collect();
+        let corpus = Corpus::new(postings.iter().map(String::as_str));
+        (postings[0].clone(), corpus)
+    }
+
+    #[test]
+    fn pdf_text_reads_compressed_content_and_skips_font_files() {
+        let text = resume_text(&fixture_path("resume.pdf")).expect("readable PDF");
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], "Jane Doe");
+        // Kerning wide enough to be a word space, and not otherwise
+        assert_eq!(lines[1], "Senior Software Engineer");
+        assert!(text.contains("Rust, Go, Python, PostgreSQL"));
+        // Escaped parentheses and an octal en dash
+        assert!(text.contains("Backend Engineer, Globex (2020-2026)"));
+        // Strings inside the embedded font program aren't page text
+        assert!(!text.contains("Kubernetes"));
+    }
+
+    #[test]
+    fn pdf_without_text_is_an_error() {
+        let error = resume_text(&fixture_path("scanned.pdf")).expect_err("no text layer");
+        assert!(error.contains("no readable text"), "{}", error);
+        assert!(pdf_text(b"PK\x03\x04 a docx").is_err());
+    }
+
+    #[test]
+    fn terms_drop_stopwords_and_numbers_but_keep_language_names() {
+        assert_eq!(terms("You will write C++, C# and Go for 5+ years"), ["write", "c++", "c#", "go"]);
+    }
+
+    #[test]
+    fn missing_terms_are_the_prominent_ones_the_resume_lacks() {
+        let resume = std::fs::read_to_string(fixture_path("resume.txt")).expect("fixture");
+        let (description, corpus) = board();
+        let analysis = analyze(&resume, &description, &corpus);
+
+        // Named twice in the posting and nowhere else on the board
+        assert_eq!(&analysis.missing[..2], ["kubernetes", "terraform"]);
+        for term in ["rust", "go", "postgresql", "kafka", "aws", "apis"] {
+            assert!(analysis.matched.iter().any(|matched| matched == term), "{} should match", term);
+        }
+        // Company boilerplate is on every posting, so it isn't a requirement
+        assert!(!analysis.missing.iter().chain(&analysis.matched).any(|term| term == "acme" || term == "insurance"));
+        assert!((0.3..0.8).contains(&analysis.score), "{}", analysis.score);
+    }
+
+    #[test]
+    fn pdf_and_text_resumes_score_the_same() {
+        let (description, corpus) = board();
+        let from_text = analyze(&std::fs::read_to_string(fixture_path("resume.txt")).expect("fixture"), &description, &corpus);
+        let from_pdf = analyze(&resume_text(&fixture_path("resume.pdf")).expect("readable PDF"), &description, &corpus);
+        assert_eq!(from_pdf, from_text);
+    }
+
+    #[test]
+    fn unrelated_resume_scores_low_and_plurals_match() {
+        let (description, corpus) = board();
+        let designer = analyze("Product designer: Figma, prototyping, user research", &description, &corpus);
+        assert!(designer.score < 0.1, "{}", designer.score);
+        // "API" on the resume covers "APIs" in the posting
+        let api = analyze("Designed an API", "Build APIs", &Corpus::default());
+        assert_eq!(api.matched, ["apis"]);
+        assert_eq!(api.missing, ["build"]);
+    }
+}
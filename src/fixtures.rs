@@ -0,0 +1,43 @@
+// Job records for tests, shared by the library's tests and the binary's
+// (which compiles this same file), so every test builds them the same way
+
+use crate::search::{Department, Job, JobLocation, JobResult};
+use std::collections::BTreeMap;
+
+// A match at Acme, as a search returns it
+pub(crate) fn job(url: &str, date_posted: &str) -> JobResult {
+    JobResult {
+        id: 0,
+        board_token: "acme".to_string(),
+        title: "Product Manager".to_string(),
+        company: "Acme".to_string(),
+        location: "Remote".to_string(),
+        date_posted: date_posted.to_string(),
+        url: url.to_string(),
+        raw: serde_json::Value::Null,
+        first_seen: None,
+        extras: BTreeMap::new(),
+        departments: Vec::new(),
+        tags: Vec::new(),
+        closed_at: None,
+        relevance: None,
+        favorite: false,
+    }
+}
+
+// A job as its board lists it
+pub(crate) fn listing(id: u64, title: &str, location: &str, departments: &[&str]) -> Job {
+    Job {
+        id,
+        title: title.to_string(),
+        updated_at: "2024-06-01T12:00:00Z".to_string(),
+        location: JobLocation { name: location.to_string() },
+        absolute_url: format!("https://example.com/jobs/{}", id),
+        departments: Some(departments.iter().map(|name| Department { id: 0, name: name.to_string() }).collect()),
+        extra: serde_json::Map::new(),
+    }
+}
+
+pub(crate) fn ids(jobs: &[JobResult]) -> Vec<u64> {
+    jobs.iter().map(|job| job.id).collect()
+}
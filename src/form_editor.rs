@@ -0,0 +1,670 @@
+use crate::application::{ApplicationForm, QuestionField};
+use crate::education::{self, Item};
+use crate::inputs::{PickList, TextInput, Typeahead, YesNo};
+use crate::submit::{self, Answer, AnswerSource, FieldError, FilledForm};
+use crate::{icons, locale, AppView, JobApplicationSystem, JobResult};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame,
+};
+use std::collections::{BTreeMap, HashMap};
+use std::ops::Range;
+use std::path::PathBuf;
+
+// 'a' from the details view: the job's application form as one input per
+// field, prefilled from the board's overrides, the profile and the cover letter
+// template, then from the job's saved draft. Each field says which of those its
+// answer came from, or that it was entered by hand. Drafts are saved with Ctrl-S or when leaving the form
+// and deleted once the application is logged. What the form holds is what
+// gets submitted; the confirm screen shows it once more before anything is sent.
+// Self-identification sections are collapsed to a summary until Ctrl-E opens
+// them, or until one of their answers needs attention. Education fields search
+// the board's school, degree and discipline lists as they're typed in and send
+// the id of the one picked; without the list they take free text.
+
+const FOOTER: &str = "Tab/Shift-Tab: Next/Previous Field | Ctrl-S: Save Draft | Ctrl-R: Review & Submit | Esc: Save & Back";
+
+pub struct FormEditor {
+    entries: Vec<Entry>,
+    focus: usize,
+    // Field name -> what's wrong with its answer, from validation or from Greenhouse
+    errors: HashMap<String, String>,
+    // Whether the self-identification sections show their fields
+    self_id_expanded: bool,
+    // From the prefill, for --debug
+    unused_overrides: Vec<String>,
+}
+
+struct Entry {
+    // Shown above the first question of each compliance section
+    heading: Option<String>,
+    label: String,
+    // What kind of answer this field takes, for questions with more than one field
+    detail: Option<&'static str>,
+    // Position of the question in the form; the fields of one question share it
+    question: usize,
+    // Which self-identification section the field is in; None for the job's own questions
+    section: Option<usize>,
+    required: bool,
+    field: QuestionField,
+    input: Input,
+    // Where the answer came from; None while there's none
+    source: Option<AnswerSource>,
+}
+
+enum Input {
+    Text(TextInput),
+    Pick(PickList),
+    YesNo(YesNo),
+    // A path to attach, or the rendered cover letter sent while the path is empty
+    File { path: TextInput, document: Option<Answer> },
+    // One of the board's education list entries
+    Search { input: Typeahead, items: Vec<Item> },
+}
+
+// Single selects offering exactly "Yes" and "No"
+fn is_yes_no(field: &QuestionField) -> bool {
+    field.values.len() == 2 && field.values.iter().all(|option| yes_no(&option.label).is_some())
+}
+
+fn yes_no(label: &str) -> Option<bool> {
+    match label.trim().to_lowercase().as_str() {
+        "yes" => Some(true),
+        "no" => Some(false),
+        _ => None,
+    }
+}
+
+impl Input {
+    fn new(field: &QuestionField, answers: &[&Answer]) -> Input {
+        let labels: Vec<String> = answers
+            .iter()
+            .map(|answer| match answer {
+                Answer::Text(text) | Answer::Choice { label: text, .. } => text.clone(),
+                Answer::File(path) => path.display().to_string(),
+                Answer::Document(letter) => letter.file_name().to_string(),
+            })
+            .collect();
+        let first = labels.first().map(String::as_str).unwrap_or_default();
+        match field.field_type.as_str() {
+            "input_file" => {
+                let path = answers.iter().find_map(|answer| match answer {
+                    Answer::File(path) => Some(path.display().to_string()),
+                    _ => None,
+                });
+                let document = answers.iter().find(|answer| matches!(answer, Answer::Document(_))).map(|&answer| answer.clone());
+                Input::File { path: TextInput::new(&path.unwrap_or_default(), false), document }
+            }
+            "multi_value_single_select" if is_yes_no(field) => Input::YesNo(YesNo::new(yes_no(first))),
+            "multi_value_single_select" | "multi_value_multi_select" if !field.values.is_empty() => {
+                let options = field.values.iter().map(|option| option.label.clone()).collect();
+                let mut list = PickList::new(options, field.field_type.ends_with("multi_select"));
+                list.choose(&labels);
+                Input::Pick(list)
+            }
+            "textarea" => Input::Text(TextInput::new(first, true)),
+            _ => Input::Text(TextInput::new(first, false)),
+        }
+    }
+
+    // Prefilled from a saved choice's id, or from text naming an entry
+    fn search(items: &[Item], answers: &[&Answer]) -> Input {
+        let value = match answers.first() {
+            Some(Answer::Choice { label, value }) => {
+                items.iter().find(|item| item.id.to_string() == *value).map_or(label.as_str(), |item| item.text.as_str())
+            }
+            Some(Answer::Text(text)) => text.as_str(),
+            _ => "",
+        };
+        let input = Typeahead::new(items.iter().map(|item| item.text.clone()).collect(), value);
+        Input::Search { input, items: items.to_vec() }
+    }
+
+    fn handle_key(&mut self, code: KeyCode) -> bool {
+        match self {
+            Input::Text(text) | Input::File { path: text, .. } => text.handle_key(code),
+            Input::Pick(list) => list.handle_key(code),
+            Input::YesNo(yes_no) => yes_no.handle_key(code),
+            Input::Search { input, .. } => input.handle_key(code),
+        }
+    }
+
+    // As saved in a draft
+    fn values(&self) -> Vec<String> {
+        match self {
+            Input::Text(text) | Input::File { path: text, .. } => vec![text.value().to_string()],
+            Input::Pick(list) => list.chosen().into_iter().map(str::to_string).collect(),
+            Input::YesNo(yes_no) => yes_no.value().map(|yes| if yes { "Yes" } else { "No" }.to_string()).into_iter().collect(),
+            Input::Search { input, .. } => vec![input.value().to_string()],
+        }
+    }
+
+    fn restore(&mut self, values: &[String]) {
+        let first = values.first().map(String::as_str).unwrap_or_default();
+        match self {
+            Input::Text(text) | Input::File { path: text, .. } => text.set_value(first),
+            Input::Pick(list) => list.choose(values),
+            Input::YesNo(input) => *input = YesNo::new(yes_no(first)),
+            Input::Search { input, .. } => input.set_value(first),
+        }
+    }
+
+    fn answers(&self, field: &QuestionField) -> Vec<Answer> {
+        let option = |label: &str| field.values.iter().find(|option| option.label == label).map(submit::option_answer);
+        match self {
+            Input::Text(text) if text.value().trim().is_empty() => Vec::new(),
+            Input::Text(text) => vec![Answer::Text(text.value().to_string())],
+            Input::Pick(list) => list.chosen().into_iter().filter_map(option).collect(),
+            Input::YesNo(input) => input
+                .value()
+                .and_then(|yes| field.values.iter().find(|option| yes_no(&option.label) == Some(yes)))
+                .map(submit::option_answer)
+                .into_iter()
+                .collect(),
+            Input::File { path, document } if path.value().trim().is_empty() => document.iter().cloned().collect(),
+            Input::File { path, .. } => vec![Answer::File(PathBuf::from(path.value().trim()))],
+            Input::Search { input, items } => input
+                .chosen()
+                .map(|i| Answer::Choice { label: items[i].text.clone(), value: items[i].id.to_string() })
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    // Text typed into a list search without picking anything from it
+    fn unpicked(&self) -> Option<&str> {
+        match self {
+            Input::Search { input, .. } if input.chosen().is_none() && !input.value().trim().is_empty() => Some(input.value()),
+            _ => None,
+        }
+    }
+
+    fn lines(&self, focused: bool) -> Vec<Line<'static>> {
+        let mut lines = match self {
+            Input::Text(text) | Input::File { path: text, .. } => text.lines(focused),
+            Input::Pick(list) => list.lines(focused),
+            Input::YesNo(yes_no) => yes_no.lines(focused),
+            Input::Search { input, .. } => input.lines(focused),
+        };
+        if let Input::File { path, document: Some(Answer::Document(letter)) } = self {
+            if path.value().trim().is_empty() {
+                // Typing goes on the line above the note while focused
+                let note = Line::from(Span::styled(
+                    format!("{}, from your cover letter template; type a path to attach a file instead", letter.file_name()),
+                    Style::default().fg(Color::DarkGray),
+                ));
+                if focused {
+                    lines.push(note);
+                } else {
+                    lines = vec![note];
+                }
+            }
+        }
+        lines
+    }
+}
+
+impl FormEditor {
+    // Every field the applicant can answer, prefilled with `prefill`'s answers;
+    // education fields search `education`'s lists where it has them
+    pub fn new(form: &ApplicationForm, prefill: &FilledForm, education: Option<&education::Lists>) -> Self {
+        let sections = std::iter::once((None, None, &form.questions)).chain(form.compliance.iter().enumerate().map(|(i, section)| {
+            (Some(i), Some(format!("Voluntary self-identification ({})", section.kind.to_uppercase())), &section.questions)
+        }));
+        let mut entries = Vec::new();
+        let mut question_number = 0;
+        for (section, mut heading, questions) in sections {
+            for question in questions {
+                let fields: Vec<&QuestionField> = question.fields.iter().filter(|field| field.field_type != "input_hidden").collect();
+                for &field in &fields {
+                    let answers: Vec<&Answer> =
+                        prefill.answers.iter().filter(|(name, _)| name == &field.name).map(|(_, answer)| answer).collect();
+                    let detail = (fields.len() > 1).then_some(match field.field_type.as_str() {
+                        "input_file" => "attach a file",
+                        "textarea" => "or paste it",
+                        _ => "text",
+                    });
+                    entries.push(Entry {
+                        heading: heading.take(),
+                        label: question.label.clone(),
+                        detail,
+                        question: question_number,
+                        section,
+                        required: question.required,
+                        field: field.clone(),
+                        input: match education.and_then(|lists| lists.for_field(field)) {
+                            Some(items) => Input::search(items, &answers),
+                            None => Input::new(field, &answers),
+                        },
+                        source: prefill.sources.get(&field.name).copied().filter(|_| !answers.is_empty()),
+                    });
+                }
+                question_number += 1;
+            }
+        }
+        FormEditor { entries, focus: 0, errors: HashMap::new(), self_id_expanded: false, unused_overrides: prefill.unused_overrides.clone() }
+    }
+
+    pub fn has_self_identification(&self) -> bool {
+        self.entries.iter().any(|entry| entry.section.is_some())
+    }
+
+    fn is_shown(&self, entry: &Entry) -> bool {
+        entry.section.is_none() || self.self_id_expanded
+    }
+
+    // Ctrl-E: show or hide the self-identification fields
+    pub fn toggle_self_identification(&mut self) {
+        self.self_id_expanded = !self.self_id_expanded;
+        if !self.entries.get(self.focus).is_none_or(|entry| self.is_shown(entry)) {
+            self.focus = self.entries.iter().rposition(|entry| entry.section.is_none()).unwrap_or(0);
+        }
+    }
+
+    // Field name -> the answer as typed or chosen, for the job store
+    pub fn draft(&self) -> BTreeMap<String, Vec<String>> {
+        self.entries.iter().map(|entry| (entry.field.name.clone(), entry.input.values())).collect()
+    }
+
+    // Fields the draft doesn't mention keep their prefilled answers, as do
+    // those it saved unchanged
+    pub fn restore(&mut self, draft: &BTreeMap<String, Vec<String>>) {
+        for entry in &mut self.entries {
+            if let Some(values) = draft.get(&entry.field.name).filter(|&values| *values != entry.input.values()) {
+                entry.input.restore(values);
+                entry.source = Some(AnswerSource::Manual);
+            }
+        }
+    }
+
+    // The answers as they stand, with a problem for each required question left
+    // blank and each file that isn't there
+    pub fn filled(&self) -> FilledForm {
+        let mut filled = FilledForm::default();
+        let mut answered = vec![false; self.entries.last().map_or(0, |entry| entry.question + 1)];
+        for entry in &self.entries {
+            let answers = entry.input.answers(&entry.field);
+            for answer in &answers {
+                if let Answer::File(path) = answer {
+                    if !path.is_file() {
+                        filled.problems.push(FieldError { field: entry.field.name.clone(), message: format!("there's no file at {}", path.display()) });
+                    }
+                }
+            }
+            if let Some(text) = entry.input.unpicked() {
+                let list = education::List::for_field(&entry.field.name).map_or("", |list| list.describe());
+                filled.problems.push(FieldError {
+                    field: entry.field.name.clone(),
+                    message: format!("\"{}\" isn't picked from the board's {} list; choose one of its matches", text, list),
+                });
+            }
+            // Text that wasn't picked has its own problem, so it isn't also called unanswered
+            answered[entry.question] |= !answers.is_empty() || entry.input.unpicked().is_some();
+            if let Some(source) = entry.source.filter(|_| !answers.is_empty()) {
+                filled.sources.insert(entry.field.name.clone(), source);
+            }
+            filled.answers.extend(answers.into_iter().map(|answer| (entry.field.name.clone(), answer)));
+        }
+        for (question, &answered) in answered.iter().enumerate() {
+            let Some(entry) = self.entries.iter().find(|entry| entry.question == question) else {
+                continue;
+            };
+            if entry.required && !answered {
+                filled.problems.push(FieldError { field: entry.field.name.clone(), message: format!("\"{}\" is required", entry.label) });
+            }
+        }
+        filled.unused_overrides = self.unused_overrides.clone();
+        filled
+    }
+
+    // Marks every problem on its field and moves to the first; returns how many there are
+    pub fn validate(&mut self) -> usize {
+        let problems = self.filled().problems;
+        self.set_errors(&problems);
+        problems.len()
+    }
+
+    // Errors from Greenhouse or validation, replacing the previous ones
+    pub fn set_errors(&mut self, errors: &[FieldError]) {
+        self.errors = errors.iter().map(|error| (error.field.clone(), error.message.clone())).collect();
+        if let Some(first) = self.entries.iter().position(|entry| self.errors.contains_key(&entry.field.name)) {
+            self.focus = first;
+            self.self_id_expanded |= self.entries[first].section.is_some();
+        }
+    }
+
+    // After the letter is edited elsewhere; a letter pasted into the form wins over the attachment
+    pub fn set_cover_letter(&mut self, letter: &str) {
+        for entry in &mut self.entries {
+            match (entry.field.name.as_str(), &mut entry.input) {
+                ("cover_letter_text", Input::Text(text)) => text.set_value(letter),
+                ("cover_letter", Input::File { document: Some(Answer::Document(document)), .. }) => document.text = letter.to_string(),
+                _ => continue,
+            }
+            entry.source = Some(AnswerSource::Manual);
+        }
+    }
+
+    // Tab and Shift-Tab move between fields, as do Up/Down and Enter when the input doesn't use them
+    pub fn handle_key(&mut self, code: KeyCode) {
+        let Some(entry) = self.entries.get_mut(self.focus) else {
+            return;
+        };
+        match code {
+            KeyCode::Tab => self.move_focus(true),
+            KeyCode::BackTab => self.move_focus(false),
+            code => {
+                let before = entry.input.values();
+                if entry.input.handle_key(code) {
+                    self.errors.remove(&entry.field.name);
+                    // Moving the cursor or the highlight isn't an edit
+                    if entry.input.values() != before {
+                        entry.source = Some(AnswerSource::Manual);
+                    }
+                } else if matches!(code, KeyCode::Down | KeyCode::Enter) {
+                    self.move_focus(true);
+                } else if code == KeyCode::Up {
+                    self.move_focus(false);
+                }
+            }
+        }
+    }
+
+    // To the next shown field, skipping collapsed sections
+    fn move_focus(&mut self, forward: bool) {
+        let len = self.entries.len();
+        for _ in 0..len {
+            self.focus = if forward { (self.focus + 1) % len } else { (self.focus + len - 1) % len };
+            if self.is_shown(&self.entries[self.focus]) {
+                break;
+            }
+        }
+    }
+
+    // "3 questions, 3 answered" for a collapsed section
+    fn section_summary(&self, section: usize) -> String {
+        let filled = self.filled();
+        let entries: Vec<&Entry> = self.entries.iter().filter(|entry| entry.section == Some(section)).collect();
+        let mut questions: Vec<usize> = entries.iter().map(|entry| entry.question).collect();
+        questions.dedup();
+        let answered = questions
+            .iter()
+            .filter(|&&question| {
+                entries
+                    .iter()
+                    .filter(|entry| entry.question == question)
+                    .any(|entry| filled.answers.iter().any(|(name, _)| name == &entry.field.name))
+            })
+            .count();
+        format!("{} question(s), {} answered. Ctrl-E: Show", questions.len(), answered)
+    }
+
+    // The form's lines and which of them belong to the focused field
+    fn lines(&self) -> (Vec<Line<'static>>, Range<usize>) {
+        let mut lines = Vec::new();
+        let mut focused = 0..0;
+        for (i, entry) in self.entries.iter().enumerate() {
+            if let Some(heading) = &entry.heading {
+                lines.push(Line::from(Span::styled(heading.clone(), Style::default().fg(Color::Cyan))));
+                if let (Some(section), false) = (entry.section, self.self_id_expanded) {
+                    lines.push(Line::from(Span::styled(format!("  {}", self.section_summary(section)), Style::default().fg(Color::DarkGray))));
+                }
+                lines.push(Line::from(""));
+            }
+            if !self.is_shown(entry) {
+                continue;
+            }
+            let start = lines.len();
+            let is_focused = i == self.focus;
+            let label_style = if is_focused {
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::Yellow)
+            };
+            let mut label = vec![Span::raw(if is_focused { "▶ " } else { "  " }), Span::styled(entry.label.clone(), label_style)];
+            if let Some(detail) = entry.detail {
+                label.push(Span::raw(format!(" ({})", detail)));
+            }
+            if entry.required {
+                label.push(Span::styled(" *", Style::default().fg(Color::Red)));
+            }
+            if let Some(source) = entry.source {
+                label.push(Span::styled(format!("  {}", source.describe()), Style::default().fg(Color::DarkGray)));
+            }
+            lines.push(Line::from(label));
+            for line in entry.input.lines(is_focused) {
+                lines.push(Line::from(std::iter::once(Span::raw("    ")).chain(line.spans).collect::<Vec<_>>()));
+            }
+            if let Some(error) = self.errors.get(&entry.field.name) {
+                lines.push(Line::from(Span::styled(format!("    {}{}", icons::CROSS, error), Style::default().fg(Color::Red))));
+            }
+            if is_focused {
+                focused = start..lines.len();
+            }
+            lines.push(Line::from(""));
+        }
+        (lines, focused)
+    }
+}
+
+// First row to show so the focused field fits, counting wrapped lines
+fn scroll_offset(lines: &[Line], focused: &Range<usize>, width: usize, height: usize) -> u16 {
+    let rows = |lines: &[Line]| -> usize { lines.iter().map(|line| line.width().div_ceil(width.max(1)).max(1)).sum() };
+    let start = rows(&lines[..focused.start]);
+    let end = rows(&lines[..focused.end]);
+    let offset = if end <= height {
+        0
+    } else if end - start > height {
+        start
+    } else {
+        end - height
+    };
+    offset.min(u16::MAX as usize) as u16
+}
+
+impl JobApplicationSystem {
+    // 'a' from the details or form view: the form to fill in, or the confirm
+    // screen when there's none (offline, not published, failed to load)
+    pub fn open_application_form(&mut self) {
+        self.profiles = crate::profile::Profiles::load();
+        self.cover_letter_template = crate::cover_letter::load_template();
+        self.answer_overrides = crate::overrides::Overrides::load();
+        let Some(job) = self.selected_job_index.and_then(|i| self.jobs.get(i)) else {
+            return;
+        };
+        let form = match self.application_forms.get(&job.id) {
+            Some(Ok(form)) if !form.questions.is_empty() && self.offline_since.is_none() => form,
+            _ => return self.confirm_application(),
+        };
+        let job_id = job.id;
+        if !self.form_editors.contains_key(&job_id) {
+            let (editor, notice) = self.prefilled_editor(job, form);
+            self.form_editors.insert(job_id, editor);
+            self.notice = notice;
+        }
+        self.current_view = AppView::ApplicationForm;
+    }
+
+    fn prefilled_editor(&self, job: &JobResult, form: &ApplicationForm) -> (FormEditor, Option<String>) {
+        let (prefill, mut notice) = self.profile_prefill(job, form);
+        let mut editor = FormEditor::new(form, &prefill, self.education_lists.get(&job.board_token));
+        match self.store.as_ref().map(|store| store.draft(job)) {
+            Some(Ok(Some(draft))) => {
+                editor.restore(&draft.answers);
+                notice = Some(format!("{}Restored your draft from {}", icons::FORM, locale::current().timestamp_date_time(&draft.saved_at)));
+            }
+            Some(Err(e)) => notice = Some(format!("{}Failed to read the saved draft: {}", icons::WARNING, e)),
+            _ => {}
+        }
+        (editor, notice)
+    }
+
+    // What the job's profile and cover letter answer, and why that's less than it could be
+    fn profile_prefill(&self, job: &JobResult, form: &ApplicationForm) -> (FilledForm, Option<String>) {
+        let mut notice = None;
+        let prefill = match self.applicant_profile(job) {
+            Ok(Some(profile)) => {
+                let letter = match self.cover_letter(job, form, &profile) {
+                    Some(Err(e)) => {
+                        notice = Some(format!("{}The cover letter can't be rendered: {}", icons::WARNING, e));
+                        None
+                    }
+                    letter => letter.and_then(Result::ok),
+                };
+                self.fill(job, form, &profile, letter.as_deref())
+            }
+            Ok(None) => FilledForm::default(),
+            Err(e) => {
+                notice = Some(format!("{}{}; nothing was filled in from the profile", icons::WARNING, e));
+                FilledForm::default()
+            }
+        };
+        let notice = notice.or_else(|| self.override_notes(Some(&prefill)).into_iter().next());
+        (prefill, notice)
+    }
+
+    // After switching profiles: the new profile's answers, and what was entered
+    // by hand for the fields it leaves blank
+    pub fn refill_application_form(&mut self, job: &JobResult) {
+        let (Some(editor), Some(Ok(form))) = (self.form_editors.get(&job.id), self.application_forms.get(&job.id)) else {
+            return;
+        };
+        let (prefill, notice) = self.profile_prefill(job, form);
+        let mut kept = editor.draft();
+        kept.retain(|field, _| !prefill.answers.iter().any(|(name, _)| name == field));
+        let mut editor = FormEditor::new(form, &prefill, self.education_lists.get(&job.board_token));
+        editor.restore(&kept);
+        self.form_editors.insert(job.id, editor);
+        if notice.is_some() {
+            self.notice = notice;
+        }
+    }
+
+    pub fn has_form_editor(&self) -> bool {
+        self.selected_job_index.and_then(|i| self.jobs.get(i)).is_some_and(|job| self.form_editors.contains_key(&job.id))
+    }
+
+    // 'f' after a rejected submission: back to the form, on the first field Greenhouse complained about
+    pub fn reopen_application_form(&mut self) {
+        if self.has_form_editor() {
+            self.current_view = AppView::ApplicationForm;
+        }
+    }
+
+    pub fn handle_form_key(&mut self, key: KeyEvent) {
+        let control = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Char('s') if control => self.save_form_draft(),
+            KeyCode::Char('r') if control => self.review_form(),
+            KeyCode::Char('e') if control => {
+                if let Some(editor) = self.selected_job_index.and_then(|i| self.jobs.get(i)).and_then(|job| self.form_editors.get_mut(&job.id)) {
+                    editor.toggle_self_identification();
+                }
+            }
+            KeyCode::Esc => {
+                self.save_form_draft();
+                self.select_current_job();
+            }
+            _ if control => {}
+            code => {
+                let Some(job) = self.selected_job_index.and_then(|i| self.jobs.get(i)) else {
+                    return;
+                };
+                if let Some(editor) = self.form_editors.get_mut(&job.id) {
+                    editor.handle_key(code);
+                }
+            }
+        }
+    }
+
+    fn save_form_draft(&mut self) {
+        let Some(job) = self.selected_job_index.and_then(|i| self.jobs.get(i)) else {
+            return;
+        };
+        let Some(editor) = self.form_editors.get(&job.id) else {
+            return;
+        };
+        self.notice = Some(match &self.store {
+            Some(store) => match store.save_draft(job, &editor.draft()) {
+                Ok(()) => format!("{}Draft saved; it's filled back in the next time you apply", icons::SAVED),
+                Err(e) => format!("{}Failed to save the draft: {}", icons::WARNING, e),
+            },
+            None => format!("{}Job store unavailable; the draft is kept only until you quit", icons::WARNING),
+        });
+    }
+
+    // Ctrl-R: on to the confirm screen once every required question is answered
+    fn review_form(&mut self) {
+        let Some(job) = self.selected_job_index.and_then(|i| self.jobs.get(i)) else {
+            return;
+        };
+        let Some(editor) = self.form_editors.get_mut(&job.id) else {
+            return;
+        };
+        match editor.validate() {
+            0 => self.confirm_application(),
+            problems => self.notice = Some(format!("{}{} answer(s) need attention before submitting", icons::WARNING, problems)),
+        }
+    }
+
+    // Once applied, the draft has no further use
+    pub fn discard_form(&mut self, job: &JobResult) {
+        self.form_editors.remove(&job.id);
+        if let Some(store) = &self.store {
+            if let Err(e) = store.delete_draft(job) {
+                eprintln!("{}Failed to delete the application draft: {}", icons::WARNING, e);
+            }
+        }
+    }
+
+    pub fn render_application_form(&mut self, f: &mut Frame) {
+        let Some(job) = self.selected_job_index.and_then(|i| self.jobs.get(i)) else {
+            return;
+        };
+        let Some(editor) = self.form_editors.get(&job.id) else {
+            return;
+        };
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Min(0),
+                Constraint::Length(3),
+            ])
+            .split(f.area());
+
+        let title = Paragraph::new(format!("{}APPLY: {} at {}", icons::WRITE, job.title, job.company))
+            .block(Block::default().borders(Borders::ALL))
+            .style(Style::default().fg(Color::Cyan));
+        f.render_widget(title, chunks[0]);
+
+        let (lines, focused) = editor.lines();
+        let inner = chunks[1].inner(ratatui::layout::Margin { horizontal: 1, vertical: 1 });
+        let scroll = scroll_offset(&lines, &focused, inner.width as usize, inner.height as usize);
+        let shown: Vec<usize> = (0..editor.entries.len()).filter(|&i| editor.is_shown(&editor.entries[i])).collect();
+        let number = shown.iter().position(|&i| i == editor.focus).map_or(0, |position| position + 1);
+        let position = format!(" Field {} of {} (* required) ", number, shown.len());
+        let form = Paragraph::new(lines)
+            .block(Block::default().borders(Borders::ALL).title(position))
+            .wrap(Wrap { trim: false })
+            .scroll((scroll, 0));
+        f.render_widget(form, chunks[1]);
+
+        let footer = match &self.notice {
+            Some(notice) => notice.clone(),
+            None if editor.has_self_identification() => {
+                self.footer(&format!("{} | Ctrl-E: {} Self-ID", FOOTER, if editor.self_id_expanded { "Hide" } else { "Show" }))
+            }
+            None => self.footer(FOOTER),
+        };
+        let controls = Paragraph::new(footer)
+            .block(Block::default().borders(Borders::ALL))
+            .style(Style::default().fg(Color::Gray));
+        f.render_widget(controls, chunks[2]);
+    }
+}
@@ -0,0 +1,91 @@
+use crate::{icons, locale, paths, JobResult};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+// Jobs marked "not interested", keyed by URL so a job stays hidden across
+// runs. Hidden jobs are left out of the TUI list and of search results unless
+// --show-hidden is given.
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct HiddenJobs {
+    #[serde(default)]
+    jobs: BTreeMap<String, HiddenJob>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HiddenJob {
+    pub title: String,
+    pub company: String,
+    pub hidden_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl HiddenJobs {
+    pub fn load() -> Self {
+        std::fs::read_to_string(paths::hidden_jobs_path())
+            .ok()
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_default()
+    }
+
+    // Write to a temporary file and rename so a crash never leaves half a file
+    pub fn save(&self) -> std::io::Result<()> {
+        if !paths::persistent() {
+            return Ok(());
+        }
+        let path = paths::hidden_jobs_path();
+        if self.jobs.is_empty() {
+            return match std::fs::remove_file(&path) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+                _ => Ok(()),
+            };
+        }
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_string_pretty(self)?)?;
+        std::fs::rename(tmp, path)
+    }
+
+    pub fn len(&self) -> usize {
+        self.jobs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.jobs.is_empty()
+    }
+
+    pub fn contains(&self, url: &str) -> bool {
+        self.jobs.contains_key(url)
+    }
+
+    // Sorted by URL
+    pub fn entries(&self) -> impl Iterator<Item = (&String, &HiddenJob)> {
+        self.jobs.iter()
+    }
+
+    pub fn hide(&mut self, job: &JobResult) {
+        self.jobs.insert(
+            job.url.clone(),
+            HiddenJob { title: job.title.clone(), company: job.company.clone(), hidden_at: chrono::Utc::now() },
+        );
+    }
+
+    pub fn unhide(&mut self, url: &str) -> bool {
+        self.jobs.remove(url).is_some()
+    }
+
+    pub fn clear(&mut self) {
+        self.jobs.clear();
+    }
+}
+
+pub fn print_hidden(hidden: &HiddenJobs) {
+    if hidden.is_empty() {
+        println!("{}No hidden jobs.", icons::EMPTY);
+        return;
+    }
+
+    println!("{}Hidden jobs ({}):\n", icons::HIDDEN, locale::current().count(hidden.len()));
+    for (url, job) in hidden.entries() {
+        println!("{} — {}  (hidden {})", job.company, job.title, locale::current().date(job.hidden_at));
+        println!("   {}{}", icons::LINK, url);
+    }
+}
@@ -0,0 +1,72 @@
+use crate::cli::HistogramBucket;
+use crate::{icons, locale, posted_at, JobResult};
+use chrono::{Datelike, Duration, NaiveDate};
+use std::collections::BTreeMap;
+
+// Text bar chart of matches per day or week of posting, to show how fast
+// matching roles are appearing. Empty buckets between the first and last
+// posting are kept so gaps in hiring are visible.
+
+const BAR_WIDTH: usize = 40;
+// Older buckets beyond this are summarized rather than printed
+const MAX_ROWS: usize = 90;
+
+pub fn print_histogram(jobs: &[JobResult], bucket: HistogramBucket) {
+    println!("\n{}MATCHES BY {}", icons::RISING, match bucket {
+        HistogramBucket::Day => "DAY POSTED",
+        HistogramBucket::Week => "WEEK POSTED",
+    });
+
+    let mut counts: BTreeMap<NaiveDate, usize> = BTreeMap::new();
+    let mut unparseable = 0;
+    for job in jobs {
+        match posted_at(job) {
+            Some(instant) => {
+                let day = instant.with_timezone(&chrono::Local).date_naive();
+                *counts.entry(bucket_start(day, bucket)).or_default() += 1;
+            }
+            None => unparseable += 1,
+        }
+    }
+
+    if let (Some(&first), Some(&last)) = (counts.keys().next(), counts.keys().next_back()) {
+        let step = match bucket {
+            HistogramBucket::Day => Duration::days(1),
+            HistogramBucket::Week => Duration::weeks(1),
+        };
+        let mut rows = Vec::new();
+        let mut start = first;
+        while start <= last {
+            rows.push((start, counts.get(&start).copied().unwrap_or(0)));
+            start += step;
+        }
+
+        let omitted = rows.len().saturating_sub(MAX_ROWS);
+        if omitted > 0 {
+            let older: usize = rows[..omitted].iter().map(|(_, count)| count).sum();
+            println!("   … {} older bucket(s) with {} job(s) not shown", omitted, older);
+        }
+
+        let locale = locale::current();
+        let max = rows.iter().map(|&(_, count)| count).max().unwrap_or(0).max(1);
+        for &(start, count) in &rows[omitted..] {
+            let label = start.format(locale.date_pattern()).to_string();
+            let bar = "█".repeat((count * BAR_WIDTH).div_ceil(max));
+            println!("   {:>10} │{} {}", label, bar, locale.count(count));
+        }
+    } else {
+        println!("   No results with a posting date to chart.");
+    }
+
+    if unparseable > 0 {
+        println!("   ({} job(s) with an unreadable posting date left out)", unparseable);
+    }
+}
+
+// Weeks start on Monday
+pub fn bucket_start(day: NaiveDate, bucket: HistogramBucket) -> NaiveDate {
+    match bucket {
+        HistogramBucket::Day => day,
+        HistogramBucket::Week => day - Duration::days(day.weekday().num_days_from_monday().into()),
+    }
+}
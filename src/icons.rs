@@ -0,0 +1,154 @@
+use std::fmt;
+use std::io::IsTerminal;
+use std::sync::OnceLock;
+
+// Every emoji the tool prints, in one place. Each shows as its emoji, or as a
+// plain-text label with --no-emoji and when stdout isn't a UTF-8 terminal
+// (piped output, the C locale), where emoji come out garbled or get read out
+// by screen readers. Most labels are empty because the text after the emoji
+// already says what it is. The emoji carry their trailing space, so
+// `format!("{}Saved", icons::SAVED)` reads right either way.
+
+#[derive(Debug, Clone, Copy)]
+pub struct Icon {
+    emoji: &'static str,
+    label: &'static str,
+}
+
+const fn icon(emoji: &'static str, label: &'static str) -> Icon {
+    Icon { emoji, label }
+}
+
+pub const WARNING: Icon = icon("⚠️  ", "Warning: ");
+pub const ERROR: Icon = icon("❌ ", "Error: ");
+pub const INFO: Icon = icon("ℹ️  ", "Note: ");
+pub const KEYS: Icon = icon("🎮 ", "Keys: ");
+pub const NEW: Icon = icon("🆕 ", "");
+pub const DONE: Icon = icon("✅ ", "");
+pub const MAYBE: Icon = icon("❔ ", "");
+pub const CLOSED: Icon = icon("🚫 ", "");
+pub const CHECK: Icon = icon("✔ ", "");
+pub const CROSS: Icon = icon("✘ ", "");
+pub const PASS: Icon = icon("✅ ", "PASS: ");
+pub const FAIL: Icon = icon("❌ ", "FAIL: ");
+
+// Startup, discovery and searching
+pub const APP: Icon = icon("🌱 ", "");
+pub const START: Icon = icon("🚀 ", "");
+pub const TARGET: Icon = icon("🎯 ", "");
+pub const SEARCH: Icon = icon("🔍 ", "");
+pub const INSPECT: Icon = icon("🔎 ", "");
+pub const SEARCHING: Icon = icon("🔄 ", "");
+pub const WEB: Icon = icon("🌐 ", "");
+pub const LOCATION: Icon = icon("📍 ", "");
+pub const RETRY: Icon = icon("🔁 ", "");
+pub const PAUSED: Icon = icon("⏸️  ", "");
+pub const GAVE_UP: Icon = icon("🪦 ", "");
+pub const SETTINGS: Icon = icon("⚙️  ", "");
+pub const CUT: Icon = icon("✂️  ", "");
+pub const MATCH: Icon = icon("🎉 ", "");
+pub const SLOW: Icon = icon("🐢 ", "");
+pub const BYE: Icon = icon("👋 ", "");
+
+// Results
+pub const RESULTS: Icon = icon("📊 ", "");
+pub const EMPTY: Icon = icon("📭 ", "");
+pub const JOB: Icon = icon("📋 ", "");
+pub const COMPANY: Icon = icon("🏢 ", "");
+pub const TITLE: Icon = icon("📌 ", "");
+pub const DEPARTMENT: Icon = icon("🏷️  ", "");
+pub const TAGS: Icon = icon("🔖 ", "");
+pub const RELEVANCE: Icon = icon("⭐ ", "");
+pub const FAVORITE: Icon = icon("💛 ", "");
+pub const CALENDAR: Icon = icon("📅 ", "");
+pub const LINK: Icon = icon("🔗 ", "");
+pub const ID: Icon = icon("🆔 ", "");
+pub const SIMILAR: Icon = icon("🔀 ", "");
+pub const ARCHIVE: Icon = icon("📦 ", "");
+pub const HIDDEN: Icon = icon("🙈 ", "");
+pub const UNHIDDEN: Icon = icon("👀 ", "");
+pub const REDACTED: Icon = icon("🕶️  ", "");
+pub const EXCLUDED: Icon = icon("📉 ", "");
+pub const RISING: Icon = icon("📈 ", "");
+pub const ADDED: Icon = icon("➕ ", "");
+pub const REMOVED: Icon = icon("➖ ", "");
+pub const CHANGED: Icon = icon("✏️  ", "");
+pub const REOPENED: Icon = icon("♻️  ", "");
+
+// Applying
+pub const FORM: Icon = icon("📝 ", "");
+pub const WRITE: Icon = icon("✍️  ", "");
+pub const CONFIRM: Icon = icon("🤔 ", "");
+pub const APPLICATIONS: Icon = icon("📨 ", "");
+pub const ATTACHMENT: Icon = icon("📎 ", "");
+pub const LETTER: Icon = icon("✉️  ", "");
+pub const DRY_RUN: Icon = icon("🧪 ", "");
+pub const PROFILE: Icon = icon("👤 ", "");
+pub const QUEUE: Icon = icon("🗂️  ", "");
+pub const STATUS: Icon = icon("🚦 ", "");
+
+// Files, storage and services
+pub const SAVED: Icon = icon("💾 ", "");
+pub const FOLDER: Icon = icon("📁 ", "");
+pub const OPEN_FOLDER: Icon = icon("📂 ", "");
+pub const STORE: Icon = icon("🗄️  ", "");
+pub const HISTORY: Icon = icon("🕘 ", "");
+pub const IMPORT: Icon = icon("📥 ", "");
+pub const FEED: Icon = icon("📰 ", "");
+pub const KEYCHAIN: Icon = icon("🔐 ", "");
+pub const SERVING: Icon = icon("🔌 ", "");
+pub const BROADCAST: Icon = icon("📡 ", "");
+pub const WEBHOOK: Icon = icon("📮 ", "");
+
+// Time
+pub const CLOCK: Icon = icon("🕒 ", "");
+pub const TIMER: Icon = icon("⏱️  ", "");
+pub const ALARM: Icon = icon("⏰ ", "");
+pub const REMINDER: Icon = icon("🔔 ", "Reminder: ");
+pub const QUIET: Icon = icon("🔕 ", "");
+pub const SLEEP: Icon = icon("💤 ", "");
+pub const STOP: Icon = icon("🛑 ", "");
+pub const SKIPPED: Icon = icon("⏭️  ", "");
+
+static ENABLED: OnceLock<bool> = OnceLock::new();
+
+// --no-emoji turns them off; otherwise they're on for UTF-8 terminals
+pub fn init(no_emoji: bool) {
+    let _ = ENABLED.set(!no_emoji && std::io::stdout().is_terminal() && utf8_locale());
+}
+
+pub fn enabled() -> bool {
+    *ENABLED.get_or_init(|| true)
+}
+
+// The first of LC_ALL, LC_CTYPE and LANG that's set decides, as it does for the
+// C library; with none set the locale is plain C on Unix
+fn utf8_locale() -> bool {
+    let value = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty());
+    match value {
+        Some(value) => {
+            let value = value.to_lowercase();
+            value.contains("utf-8") || value.contains("utf8")
+        }
+        None => cfg!(windows),
+    }
+}
+
+impl Icon {
+    pub fn as_str(&self) -> &'static str {
+        if enabled() {
+            self.emoji
+        } else {
+            self.label
+        }
+    }
+}
+
+impl fmt::Display for Icon {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
@@ -0,0 +1,334 @@
+use crossterm::event::KeyCode;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+
+// Input components for full-screen forms. Each one takes the keys it
+// understands (returning false for the rest, so the form can use them to move
+// between fields) and renders itself as lines, with a cursor or highlight only
+// while focused.
+
+fn cursor_style() -> Style {
+    Style::default().add_modifier(Modifier::REVERSED)
+}
+
+// Editable text with a cursor; multiline inputs take Enter as a line break
+#[derive(Debug, Clone, Default)]
+pub struct TextInput {
+    value: String,
+    // Byte offset into `value`, always on a character boundary
+    cursor: usize,
+    multiline: bool,
+}
+
+impl TextInput {
+    pub fn new(value: &str, multiline: bool) -> Self {
+        TextInput { value: value.to_string(), cursor: value.len(), multiline }
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    pub fn set_value(&mut self, value: &str) {
+        self.value = value.to_string();
+        self.cursor = self.value.len();
+    }
+
+    fn previous_boundary(&self) -> usize {
+        self.value[..self.cursor].char_indices().next_back().map_or(0, |(i, _)| i)
+    }
+
+    fn next_boundary(&self) -> usize {
+        self.value[self.cursor..].chars().next().map_or(self.cursor, |c| self.cursor + c.len_utf8())
+    }
+
+    fn line_start(&self) -> usize {
+        self.value[..self.cursor].rfind('\n').map_or(0, |i| i + 1)
+    }
+
+    fn line_end(&self) -> usize {
+        self.value[self.cursor..].find('\n').map_or(self.value.len(), |i| self.cursor + i)
+    }
+
+    pub fn handle_key(&mut self, code: KeyCode) -> bool {
+        match code {
+            KeyCode::Char(c) => {
+                self.value.insert(self.cursor, c);
+                self.cursor += c.len_utf8();
+            }
+            KeyCode::Enter if self.multiline => {
+                self.value.insert(self.cursor, '\n');
+                self.cursor += 1;
+            }
+            KeyCode::Backspace if self.cursor > 0 => {
+                let start = self.previous_boundary();
+                self.value.replace_range(start..self.cursor, "");
+                self.cursor = start;
+            }
+            KeyCode::Delete if self.cursor < self.value.len() => {
+                let end = self.next_boundary();
+                self.value.replace_range(self.cursor..end, "");
+            }
+            KeyCode::Left => self.cursor = self.previous_boundary(),
+            KeyCode::Right => self.cursor = self.next_boundary(),
+            KeyCode::Home => self.cursor = self.line_start(),
+            KeyCode::End => self.cursor = self.line_end(),
+            // Nothing to delete is still the input's key, not the form's
+            KeyCode::Backspace | KeyCode::Delete => {}
+            _ => return false,
+        }
+        true
+    }
+
+    pub fn lines(&self, focused: bool) -> Vec<Line<'static>> {
+        if !focused {
+            if self.value.is_empty() {
+                return vec![Line::from(Span::styled("(empty)", Style::default().fg(Color::DarkGray)))];
+            }
+            return self.value.split('\n').map(|line| Line::from(line.to_string())).collect();
+        }
+        // The cursor is drawn over the character it sits before, or past the end of its line
+        let mut lines = Vec::new();
+        let mut offset = 0;
+        for line in self.value.split('\n') {
+            let end = offset + line.len();
+            if (offset..=end).contains(&self.cursor) {
+                let at = self.cursor - offset;
+                let under = line[at..].chars().next();
+                let after = at + under.map_or(0, char::len_utf8);
+                lines.push(Line::from(vec![
+                    Span::raw(line[..at].to_string()),
+                    Span::styled(under.map_or(" ".to_string(), String::from), cursor_style()),
+                    Span::raw(line[after..].to_string()),
+                ]));
+            } else {
+                lines.push(Line::from(line.to_string()));
+            }
+            offset = end + 1;
+        }
+        lines
+    }
+}
+
+// A list of choices; single-choice lists select with Enter or Space, multi-choice lists toggle
+#[derive(Debug, Clone)]
+pub struct PickList {
+    options: Vec<String>,
+    chosen: Vec<bool>,
+    highlighted: usize,
+    multiple: bool,
+}
+
+impl PickList {
+    pub fn new(options: Vec<String>, multiple: bool) -> Self {
+        let chosen = vec![false; options.len()];
+        PickList { options, chosen, highlighted: 0, multiple }
+    }
+
+    // Chooses the options with these labels, ignoring case; unknown labels are skipped
+    pub fn choose(&mut self, labels: &[String]) {
+        for (option, chosen) in self.options.iter().zip(&mut self.chosen) {
+            *chosen = labels.iter().any(|label| label.eq_ignore_ascii_case(option));
+        }
+        if !self.multiple {
+            // Keep only the first match
+            if let Some(first) = self.chosen.iter().position(|&chosen| chosen) {
+                self.chosen.iter_mut().skip(first + 1).for_each(|chosen| *chosen = false);
+                self.highlighted = first;
+            }
+        }
+    }
+
+    pub fn chosen(&self) -> Vec<&str> {
+        self.options.iter().zip(&self.chosen).filter(|(_, &chosen)| chosen).map(|(option, _)| option.as_str()).collect()
+    }
+
+    pub fn handle_key(&mut self, code: KeyCode) -> bool {
+        match code {
+            KeyCode::Up if self.highlighted > 0 => self.highlighted -= 1,
+            KeyCode::Down if self.highlighted + 1 < self.options.len() => self.highlighted += 1,
+            KeyCode::Enter | KeyCode::Char(' ') if !self.options.is_empty() => {
+                let was = self.chosen[self.highlighted];
+                if !self.multiple {
+                    self.chosen.iter_mut().for_each(|chosen| *chosen = false);
+                }
+                self.chosen[self.highlighted] = !was || !self.multiple;
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    pub fn lines(&self, focused: bool) -> Vec<Line<'static>> {
+        let (on, off) = if self.multiple { ("[x]", "[ ]") } else { ("(•)", "( )") };
+        // Unfocused lists show only what's chosen
+        let mut lines: Vec<Line<'static>> = self
+            .options
+            .iter()
+            .zip(&self.chosen)
+            .enumerate()
+            .filter(|(_, (_, &chosen))| focused || chosen)
+            .map(|(i, (option, &chosen))| {
+                let text = format!("{} {}", if chosen { on } else { off }, option);
+                if focused && i == self.highlighted {
+                    Line::from(Span::styled(text, cursor_style()))
+                } else {
+                    Line::from(text)
+                }
+            })
+            .collect();
+        if lines.is_empty() {
+            lines.push(Line::from(Span::styled("(nothing chosen)", Style::default().fg(Color::DarkGray))));
+        }
+        lines
+    }
+}
+
+// A yes/no question: y or n, or Left/Right to switch
+#[derive(Debug, Clone, Default)]
+pub struct YesNo {
+    value: Option<bool>,
+}
+
+impl YesNo {
+    pub fn new(value: Option<bool>) -> Self {
+        YesNo { value }
+    }
+
+    pub fn value(&self) -> Option<bool> {
+        self.value
+    }
+
+    pub fn handle_key(&mut self, code: KeyCode) -> bool {
+        match code {
+            KeyCode::Char('y') | KeyCode::Char('Y') => self.value = Some(true),
+            KeyCode::Char('n') | KeyCode::Char('N') => self.value = Some(false),
+            KeyCode::Left | KeyCode::Right | KeyCode::Char(' ') => self.value = Some(!self.value.unwrap_or(false)),
+            _ => return false,
+        }
+        true
+    }
+
+    pub fn lines(&self, focused: bool) -> Vec<Line<'static>> {
+        let option = |label: &str, selected: bool| {
+            let text = format!("{} {}", if selected { "(•)" } else { "( )" }, label);
+            if focused && selected {
+                Span::styled(text, cursor_style())
+            } else {
+                Span::raw(text)
+            }
+        };
+        vec![Line::from(vec![option("Yes", self.value == Some(true)), Span::raw("   "), option("No", self.value == Some(false))])]
+    }
+}
+
+// Free text narrowed to a long list of choices: typing filters the list
+// (names starting with what's typed first), Up/Down move through the matches
+// and Enter picks one. Only a picked option counts as chosen.
+#[derive(Debug, Clone)]
+pub struct Typeahead {
+    query: TextInput,
+    options: Vec<String>,
+    // Index into `options`
+    chosen: Option<usize>,
+    // Position among the matches
+    highlighted: usize,
+}
+
+// Matches shown under the input at once
+const TYPEAHEAD_ROWS: usize = 8;
+
+impl Typeahead {
+    // Picks the option `value` names, ignoring case, if there is one
+    pub fn new(options: Vec<String>, value: &str) -> Self {
+        let mut input = Typeahead { query: TextInput::default(), options, chosen: None, highlighted: 0 };
+        input.set_value(value);
+        input
+    }
+
+    pub fn set_value(&mut self, value: &str) {
+        self.query.set_value(value);
+        self.chosen = self.options.iter().position(|option| option.eq_ignore_ascii_case(value.trim()));
+        self.highlighted = 0;
+    }
+
+    pub fn value(&self) -> &str {
+        self.query.value()
+    }
+
+    pub fn chosen(&self) -> Option<usize> {
+        self.chosen
+    }
+
+    // Indexes of the options containing the query, those starting with it first
+    pub fn matches(&self) -> Vec<usize> {
+        let query = self.query.value().trim().to_lowercase();
+        if query.is_empty() {
+            return Vec::new();
+        }
+        let mut matches: Vec<(bool, usize)> = self
+            .options
+            .iter()
+            .enumerate()
+            .filter_map(|(i, option)| {
+                let option = option.to_lowercase();
+                option.find(&query).map(|at| (at != 0, i))
+            })
+            .collect();
+        matches.sort();
+        matches.into_iter().map(|(_, i)| i).collect()
+    }
+
+    pub fn handle_key(&mut self, code: KeyCode) -> bool {
+        let matches = self.matches();
+        match code {
+            KeyCode::Up if self.highlighted > 0 => self.highlighted -= 1,
+            KeyCode::Down if self.highlighted + 1 < matches.len().min(TYPEAHEAD_ROWS) && self.chosen.is_none() => {
+                self.highlighted += 1
+            }
+            KeyCode::Enter if self.chosen.is_none() && !matches.is_empty() => {
+                let chosen = matches[self.highlighted.min(matches.len() - 1)];
+                self.query.set_value(&self.options[chosen]);
+                self.chosen = Some(chosen);
+                self.highlighted = 0;
+            }
+            code => {
+                let before = self.query.value().to_string();
+                if !self.query.handle_key(code) {
+                    return false;
+                }
+                if self.query.value() != before {
+                    self.chosen = None;
+                    self.highlighted = 0;
+                }
+            }
+        }
+        true
+    }
+
+    pub fn lines(&self, focused: bool) -> Vec<Line<'static>> {
+        let mut lines = self.query.lines(focused);
+        if !focused {
+            if self.chosen.is_none() && !self.query.value().trim().is_empty() {
+                lines.push(Line::from(Span::styled("(not picked from the list)", Style::default().fg(Color::DarkGray))));
+            }
+            return lines;
+        }
+        if self.chosen.is_some() {
+            return lines;
+        }
+        let matches = self.matches();
+        for (row, &i) in matches.iter().take(TYPEAHEAD_ROWS).enumerate() {
+            let text = format!("  {}", self.options[i]);
+            lines.push(if row == self.highlighted { Line::from(Span::styled(text, cursor_style())) } else { Line::from(text) });
+        }
+        let hint = match matches.len() {
+            0 if self.query.value().trim().is_empty() => "Type to search the list".to_string(),
+            0 => "Nothing on the list matches".to_string(),
+            n if n > TYPEAHEAD_ROWS => format!("{} more; keep typing to narrow it down", n - TYPEAHEAD_ROWS),
+            _ => "Enter: Pick".to_string(),
+        };
+        lines.push(Line::from(Span::styled(format!("  {}", hint), Style::default().fg(Color::DarkGray))));
+        lines
+    }
+}
@@ -0,0 +1,98 @@
+//! The keyword spec: whitespace-separated terms, each optionally weighted
+//! as `term:weight`, e.g. "principal:3 product:1 manager:2". Terms without a
+//! weight count 1. Without any weights every term must be in the title; with
+//! weights a title matches once the terms it contains carry at least
+//! `min_relevance` of the total weight, and results rank by that share.
+
+use crate::normalize::{self, Normalization};
+
+#[derive(Debug, Clone, Default)]
+pub struct KeywordSpec {
+    terms: Vec<Term>,
+    weighted: bool,
+    /// Share of the total weight (0.0 to 1.0) a title needs when weighted
+    pub min_relevance: f64,
+    // Applied to the terms here and to titles in `fold_title`
+    normalization: Option<Normalization>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Term {
+    pub text: String,
+    pub weight: f64,
+}
+
+impl KeywordSpec {
+    pub fn parse(raw: &str) -> Result<Self, String> {
+        let mut spec = KeywordSpec { min_relevance: 0.5, ..KeywordSpec::default() };
+        for word in raw.split_whitespace() {
+            let (text, weight) = match word.rsplit_once(':') {
+                Some((text, weight)) => {
+                    let weight: f64 = weight
+                        .parse()
+                        .ok()
+                        .filter(|w: &f64| w.is_finite() && *w > 0.0)
+                        .ok_or_else(|| format!("\"{}\" needs a positive number after the ':'", word))?;
+                    spec.weighted = true;
+                    (text, weight)
+                }
+                None => (word, 1.0),
+            };
+            if text.is_empty() {
+                return Err(format!("\"{}\" has a weight but no term", word));
+            }
+            spec.terms.push(Term { text: text.to_lowercase(), weight });
+        }
+        Ok(spec)
+    }
+
+    pub fn normalize(&mut self, normalization: Normalization) {
+        for term in &mut self.terms {
+            term.text = normalize::fold(&term.text, normalization);
+        }
+        self.normalization = Some(normalization);
+    }
+
+    /// The title as the terms should be looked for in it
+    pub fn fold_title(&self, title: &str) -> String {
+        match self.normalization {
+            Some(normalization) => normalize::fold(title, normalization),
+            None => title.to_lowercase(),
+        }
+    }
+
+    /// `fold_title` into a buffer kept across titles; ASCII titles, most of
+    /// them, are lowercased in place without allocating
+    pub fn fold_title_into(&self, title: &str, folded: &mut String) {
+        folded.clear();
+        match self.normalization {
+            None if title.is_ascii() => {
+                folded.push_str(title);
+                folded.make_ascii_lowercase();
+            }
+            _ => folded.push_str(&self.fold_title(title)),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    pub fn is_weighted(&self) -> bool {
+        self.weighted
+    }
+
+    pub fn terms(&self) -> &[Term] {
+        &self.terms
+    }
+
+    /// Share of the total weight carried by the terms `has` accepts
+    pub fn relevance(&self, has: impl Fn(&str) -> bool) -> f64 {
+        let total: f64 = self.terms.iter().map(|term| term.weight).sum();
+        if total == 0.0 {
+            return 1.0;
+        }
+        let found: f64 = self.terms.iter().filter(|term| has(&term.text)).map(|term| term.weight).sum();
+        found / total
+    }
+}
@@ -0,0 +1,64 @@
+//! Searching Greenhouse job boards from Rust.
+//!
+//! The `greenhouse-job-search` command is a CLI and TUI over this library:
+//! [`GreenhouseJobSearcher`] fetches boards from the public Job Board API,
+//! [`SearchQuery`] says which of their jobs match, and [`discovery`] finds
+//! board tokens to search. Nothing in the library prints or keeps state on
+//! disk; what went wrong with a board comes back in its
+//! [`BoardOutcome`](search::BoardOutcome).
+//!
+//! Searching a few boards for product manager jobs in Seattle (remote jobs
+//! and the Bay Area always count):
+//!
+//! ```no_run
+//! use greenhouse_job_search::{GreenhouseJobSearcher, SearchQuery};
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), String> {
+//!     let searcher = GreenhouseJobSearcher::new();
+//!     let query = SearchQuery::new("product manager", "Seattle")?;
+//!     for job in searcher.search(&["stripe", "airbnb", "figma"], &query).await {
+//!         println!("{} at {} ({}): {}", job.title, job.company, job.location, job.url);
+//!     }
+//!     Ok(())
+//! }
+//! ```
+//!
+//! Weighted keywords rank the matches, and one board at a time says how the
+//! board went:
+//!
+//! ```no_run
+//! use greenhouse_job_search::search::{BoardStatus, GreenhouseJobSearcher, SearchQuery};
+//!
+//! # async fn run() -> Result<(), String> {
+//! let searcher = GreenhouseJobSearcher::new();
+//! let mut query = SearchQuery::new("principal:3 product:1 manager:2", "Remote")?;
+//! query.keywords.min_relevance = 0.6;
+//! query.department = Some("product".to_string());
+//!
+//! let outcome = searcher.search_board("stripe", &query).await;
+//! match (outcome.status, &outcome.problem) {
+//!     (BoardStatus::Searched, _) => {
+//!         for job in &outcome.matches {
+//!             println!("{:.0}% {}", job.relevance.unwrap_or(0.0) * 100.0, job.title);
+//!         }
+//!         println!("{} job(s) left out", outcome.excluded.total());
+//!     }
+//!     (status, problem) => println!("{:?}: {:?}", status, problem),
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod budget;
+pub mod companies;
+pub mod discovery;
+pub mod keywords;
+mod matching;
+pub mod normalize;
+pub mod search;
+
+#[cfg(test)]
+mod fixtures;
+
+pub use search::{GreenhouseJobSearcher, JobResult, SearchQuery};
@@ -0,0 +1,117 @@
+use crate::icons;
+use chrono::{DateTime, Local, Utc};
+use std::sync::OnceLock;
+
+// Display conventions for dates and large numbers. Only output is affected:
+// Greenhouse timestamps are always parsed as ISO 8601 regardless of locale.
+// Without --locale the system locale (LC_ALL, LC_TIME, LANG) is used, and
+// anything unrecognized falls back to the US-style defaults.
+
+#[derive(Debug, Clone)]
+pub struct Locale {
+    date: &'static str,
+    date_time: &'static str,
+    thousands: &'static str,
+}
+
+static CURRENT: OnceLock<Locale> = OnceLock::new();
+
+pub fn init(requested: Option<&str>) {
+    let locale = match requested {
+        Some(tag) => Locale::from_tag(tag).unwrap_or_else(|| {
+            eprintln!("{}Unknown locale \"{}\"; using en-US formatting", icons::WARNING, tag);
+            Locale::default()
+        }),
+        None => system_tag().and_then(|tag| Locale::from_tag(&tag)).unwrap_or_default(),
+    };
+    let _ = CURRENT.set(locale);
+}
+
+pub fn current() -> &'static Locale {
+    CURRENT.get_or_init(Locale::default)
+}
+
+fn system_tag() -> Option<String> {
+    ["LC_ALL", "LC_TIME", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty())
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Locale { date: "%Y-%m-%d", date_time: "%Y-%m-%d %H:%M", thousands: "," }
+    }
+}
+
+impl Locale {
+    // Accepts BCP 47 tags ("de-DE") as well as POSIX names ("de_DE.UTF-8")
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let normalized = tag.split(['.', '@']).next().unwrap_or_default().replace('_', "-").to_lowercase();
+        let (language, region) = match normalized.split_once('-') {
+            Some((language, region)) => (language, region),
+            None => (normalized.as_str(), ""),
+        };
+
+        let (date, date_time, thousands) = match (language, region) {
+            ("c" | "posix", _) | ("en", "" | "us") => return Some(Locale::default()),
+            ("en", "ca") => ("%Y-%m-%d", "%Y-%m-%d %H:%M", ","),
+            ("en", _) => ("%d/%m/%Y", "%d/%m/%Y %H:%M", ","),
+            ("de", "ch") => ("%d.%m.%Y", "%d.%m.%Y %H:%M", "’"),
+            ("de", _) => ("%d.%m.%Y", "%d.%m.%Y %H:%M", "."),
+            ("fr", "ch") => ("%d.%m.%Y", "%d.%m.%Y %H:%M", "\u{202f}"),
+            ("fr", _) => ("%d/%m/%Y", "%d/%m/%Y %H:%M", "\u{202f}"),
+            ("nl", _) => ("%d-%m-%Y", "%d-%m-%Y %H:%M", "."),
+            ("es" | "it" | "pt" | "da" | "id" | "tr", _) => ("%d/%m/%Y", "%d/%m/%Y %H:%M", "."),
+            ("sv", _) => ("%Y-%m-%d", "%Y-%m-%d %H:%M", "\u{a0}"),
+            ("fi" | "nb" | "no" | "pl" | "cs" | "ru" | "uk", _) => ("%d.%m.%Y", "%d.%m.%Y %H:%M", "\u{a0}"),
+            ("ja" | "zh", _) => ("%Y/%m/%d", "%Y/%m/%d %H:%M", ","),
+            ("ko", _) => ("%Y.%m.%d", "%Y.%m.%d %H:%M", ","),
+            _ => return None,
+        };
+        Some(Locale { date, date_time, thousands })
+    }
+
+    // strftime pattern for a calendar date, for callers that already have a local date
+    pub fn date_pattern(&self) -> &'static str {
+        self.date
+    }
+
+    pub fn date(&self, instant: DateTime<Utc>) -> String {
+        instant.with_timezone(&Local).format(self.date).to_string()
+    }
+
+    pub fn date_time(&self, instant: DateTime<Utc>) -> String {
+        instant.with_timezone(&Local).format(self.date_time).to_string()
+    }
+
+    // Formats an RFC 3339 timestamp from the API or the store; anything
+    // unparseable is shown as-is
+    pub fn timestamp_date(&self, raw: &str) -> String {
+        parse_timestamp(raw).map_or_else(|| raw.to_string(), |instant| self.date(instant))
+    }
+
+    pub fn timestamp_date_time(&self, raw: &str) -> String {
+        parse_timestamp(raw).map_or_else(|| raw.to_string(), |instant| self.date_time(instant))
+    }
+
+    pub fn count(&self, n: impl std::fmt::Display) -> String {
+        let digits = n.to_string();
+        let (sign, digits) = match digits.strip_prefix('-') {
+            Some(rest) => ("-", rest),
+            None => ("", digits.as_str()),
+        };
+        let mut grouped = String::new();
+        for (i, digit) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i) % 3 == 0 {
+                grouped.push_str(self.thousands);
+            }
+            grouped.push(digit);
+        }
+        format!("{}{}", sign, grouped)
+    }
+}
+
+fn parse_timestamp(raw: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(raw).ok().map(|dt| dt.with_timezone(&Utc))
+}
@@ -1,13 +1,81 @@
-use reqwest;
-use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
-use tokio;
-use scraper::{Html, Selector};
+mod accessible;
+mod application;
+mod apply;
+mod badges;
+mod browser;
+mod calendar;
+mod cli;
+mod clipboard;
+mod concurrency;
+mod config;
+mod contact;
+mod cover_letter;
+mod credentials;
+mod dashboard;
+mod diff;
+mod doctor;
+mod education;
+mod export;
+mod extract;
+mod feed;
+mod fit;
+mod form_editor;
+mod hidden;
+mod histogram;
+mod icons;
+mod inputs;
+mod locale;
+mod notify;
+mod overrides;
+mod pager;
+mod passes;
+mod paths;
+mod pdf;
+mod picker;
+mod pipeline;
+mod profile;
+mod queue;
+mod quota;
+mod quiet;
+mod redact;
+mod report;
+mod resume;
+mod retry;
+mod runs;
+mod serve;
+mod session;
+mod sources;
+mod state;
+mod store;
+mod submit;
+mod summary;
+mod tags;
+mod tracked;
+mod watch;
+
+// The library's test fixtures, compiled into the binary's tests too; they name
+// the library's types through `crate::search`
+#[cfg(test)]
+#[path = "fixtures.rs"]
+mod fixtures;
+#[cfg(test)]
+use greenhouse_job_search::search;
+
+use application::ApplicationForm;
+use greenhouse_job_search::discovery::{self, TokenSource};
+use greenhouse_job_search::{budget, companies, keywords, normalize};
+use greenhouse_job_search::search::{
+    listed_job, posted_at, sort_results, BoardOutcome, BoardProblem, BoardStatus, Exclusions, GreenhouseJobSearcher, Job,
+    JobResult, SearchQuery,
+};
+use clap::Parser;
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::error::Error;
+use std::sync::Arc;
 use std::time::Duration;
-use std::io::{self, Write};
+use std::io::{self, IsTerminal, Write};
 use crossterm::{
-    event::{self, Event, KeyCode},
+    event::{self, Event, KeyCode, KeyModifiers},
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
     ExecutableCommand,
 };
@@ -20,57 +88,174 @@ use ratatui::{
     Terminal, Frame,
 };
 
-#[derive(Debug, Deserialize, Serialize)]
-struct Job {
-    id: u64,
-    title: String,
-    updated_at: String,
-    location: JobLocation,
-    absolute_url: String,
-    departments: Option<Vec<Department>>, // Make this optional
+struct JobApplicationSystem {
+    jobs: Vec<JobResult>,
+    list_state: ListState,
+    current_view: AppView,
+    selected_job_index: Option<usize>,
+    client: reqwest::Client,
+    // Application forms fetched lazily per job id
+    application_forms: HashMap<u64, Result<ApplicationForm, String>>,
+    // School, degree and discipline lists per board token, for forms that ask for them
+    education_lists: HashMap<String, education::Lists>,
+    // Archived copies of postings whose live fetch failed, per job id
+    archived_postings: HashMap<u64, store::ArchivedJob>,
+    // Case-insensitive text filter over title, company and location; #words filter by tag
+    filter: Option<String>,
+    // Text being typed at the bottom of the screen, if any
+    prompt: Option<Prompt>,
+    // 'e' in the list: the export formats are offered in the footer
+    export_menu: bool,
+    // [tui] export_dir in config.toml, or the default under the data directory
+    export_dir: std::path::PathBuf,
+    // The search behind these results and how many boards it covered, for exports
+    search: Option<(export::SearchParameters, usize)>,
+    // Every tag in the job store, for autocompletion
+    known_tags: Vec<String>,
+    // One-off message shown in the list footer until the next key press
+    notice: Option<String>,
+    // Latest resume fit analysis per job id, from the job store and 'r' in the details view
+    resume_fits: HashMap<u64, store::ResumeFit>,
+    // Term frequencies across the results, built the first time a job is analyzed
+    fit_corpus: Option<fit::Corpus>,
+    // 's' in the list: analyzed jobs first, best fit first
+    sort_by_fit: bool,
+    // Jobs marked "not interested", left out of the list unless `show_hidden`
+    hidden: hidden::HiddenJobs,
+    show_hidden: bool,
+    // Selection in the hidden jobs view
+    hidden_list_state: ListState,
+    store: Option<store::JobStore>,
+    // Application log, used to flag jobs that were already applied to
+    applications: Vec<store::ApplicationRecord>,
+    // Set after the first 'y' on a job that was already applied to
+    duplicate_confirmed: bool,
+    // Outcome of the last action, shown on the next screen
+    status_message: Option<String>,
+    // Who applications are submitted as, read from profile.toml when confirming
+    profiles: Result<Option<profile::Profiles>, String>,
+    // --profile-name, for jobs without a profile picked on the confirm screen
+    profile_name: Option<String>,
+    // Profiles picked on the confirm screen, by job id
+    job_profiles: HashMap<u64, String>,
+    // Job Board API keys by board token, for boards that require one to apply
+    board_credentials: HashMap<String, String>,
+    // --resume and [resumes] in config.toml, over the profile's resume
+    resumes: resume::Resumes,
+    // cover_letter.txt, read with the profile
+    cover_letter_template: Result<Option<String>, String>,
+    // overrides.toml, read with the profile
+    answer_overrides: Result<overrides::Overrides, String>,
+    // [cover_letter] in config.toml, with --letter-format applied
+    letter_settings: config::CoverLetterConfig,
+    // Letters edited in $EDITOR, by job id; they replace the rendered template
+    edited_cover_letters: HashMap<u64, String>,
+    // Forms being filled in, by job id; what they hold is what gets submitted
+    form_editors: HashMap<u64, form_editor::FormEditor>,
+    // Result of the last submission; None when the application was only logged
+    submission: Option<Result<(), submit::SubmitError>>,
+    // --dry-run, or `d` on the confirm screen: applying stops short of sending
+    dry_run: bool,
+    // daily_limit in config.toml, checked against the application log before each submission
+    daily_limit: usize,
+    // --override-rate-limit, once confirmed
+    override_rate_limit: bool,
+    // What the last dry run would have sent
+    dry_run_result: Option<submit::DryRun>,
+    // Greenhouse's answer to submissions it turned away outright, by job id
+    api_refusals: HashMap<u64, String>,
+    // The URL the last browser fallback opened, or why it couldn't
+    browser_result: Option<Result<String, String>>,
+    // gh_src under [applications] in config.toml, for applications opened in the browser
+    gh_src: Option<String>,
+    // phone_country under [applications], for profile phone numbers without a country code
+    phone_country: Option<String>,
+    // --debug: previews also list the overrides no question on the form matched
+    debug: bool,
+    // Footer clock, on unless `[tui] clock = false` in config.toml
+    show_clock: bool,
+    // How long the search behind these results took
+    search_elapsed: Option<Duration>,
+    // When the results being replayed were saved; nothing is fetched while set
+    offline_since: Option<chrono::DateTime<chrono::Utc>>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
-struct JobLocation {
-    name: String,
-}
+// How often the TUI redraws without input, for the footer clock
+const FOOTER_TICK: Duration = Duration::from_secs(1);
+
+// Suggestions in the details view: at most this many, sharing at least this
+// share of their title words with the job being viewed
+const SIMILAR_JOBS: usize = 5;
+const MIN_TITLE_OVERLAP: f64 = 0.25;
+
+// Missing terms listed under a job's resume fit in the details view
+const RESUME_FIT_MISSING_SHOWN: usize = 8;
 
-#[derive(Debug, Deserialize, Serialize)]
-struct Department {
-    id: u64,
-    name: String,
+struct Prompt {
+    kind: PromptKind,
+    text: String,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
-struct JobsResponse {
-    jobs: Vec<Job>,
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PromptKind {
+    // '/': filter text, with #tag words
+    Filter,
+    // 't': the tags of the job at this index
+    Tags(usize),
+    // 'u': a status update for the logged application with this id
+    Status(i64),
 }
 
-#[derive(Debug, Clone)]
-struct JobResult {
-    title: String,
-    company: String,
-    date_posted: String,
-    url: String,
+// What the status prompt asks to record: an optional status word first, then
+// a note, with `@YYYY-MM-DD` anywhere setting the follow-up day and `@-`
+// dropping it, e.g. "interview Panel on Friday @2026-10-20"
+#[derive(Debug, PartialEq)]
+struct StatusUpdate {
+    status: Option<store::ApplicationStatus>,
+    note: Option<String>,
+    remind_on: Option<Option<chrono::NaiveDate>>,
 }
 
-struct JobApplicationSystem {
-    jobs: Vec<JobResult>,
-    list_state: ListState,
-    current_view: AppView,
-    selected_job_index: Option<usize>,
+fn parse_status_update(text: &str) -> Result<StatusUpdate, String> {
+    let mut words: Vec<&str> = Vec::new();
+    let mut remind_on = None;
+    for word in text.split_whitespace() {
+        match word.strip_prefix('@') {
+            Some("-") => remind_on = Some(None),
+            Some(day) => {
+                let day = day.parse().map_err(|_| format!("\"{}\" isn't a date; follow-up days look like @2026-10-20", day))?;
+                remind_on = Some(Some(day));
+            }
+            None => words.push(word),
+        }
+    }
+    let status = words.first().and_then(|word| store::ApplicationStatus::parse(&word.to_lowercase()));
+    if status.is_some() {
+        words.remove(0);
+    }
+    let note = (!words.is_empty()).then(|| words.join(" "));
+    if status.is_none() && note.is_none() && remind_on.is_none() {
+        return Err("nothing to record; start with screening, interview, offer or rejected".to_string());
+    }
+    Ok(StatusUpdate { status, note, remind_on })
 }
 
 #[derive(Debug, Clone, PartialEq)]
 enum AppView {
     JobList,
     JobDetails,
+    // 'f' from the details view: every question on the job's application form
+    FormPreview,
+    // 'H' from the list: jobs marked "not interested"
+    HiddenJobs,
+    // 'a' from the details view: the application form's fields, to fill in
+    ApplicationForm,
     ConfirmApplication,
     ApplicationComplete,
 }
 
 impl JobApplicationSystem {
-    fn new(jobs: Vec<JobResult>) -> Self {
+    fn new(jobs: Vec<JobResult>, client: reqwest::Client) -> Self {
         let mut list_state = ListState::default();
         if !jobs.is_empty() {
             list_state.select(Some(0));
@@ -81,276 +266,1776 @@ impl JobApplicationSystem {
             list_state,
             current_view: AppView::JobList,
             selected_job_index: None,
+            client,
+            application_forms: HashMap::new(),
+            education_lists: HashMap::new(),
+            archived_postings: HashMap::new(),
+            filter: None,
+            prompt: None,
+            export_menu: false,
+            export_dir: paths::exports_dir(),
+            search: None,
+            known_tags: Vec::new(),
+            notice: None,
+            resume_fits: HashMap::new(),
+            fit_corpus: None,
+            sort_by_fit: false,
+            hidden: hidden::HiddenJobs::default(),
+            show_hidden: false,
+            hidden_list_state: ListState::default(),
+            store: None,
+            applications: Vec::new(),
+            duplicate_confirmed: false,
+            status_message: None,
+            profiles: Ok(None),
+            profile_name: None,
+            job_profiles: HashMap::new(),
+            board_credentials: HashMap::new(),
+            resumes: resume::Resumes::default(),
+            cover_letter_template: Ok(None),
+            answer_overrides: Ok(overrides::Overrides::default()),
+            letter_settings: config::CoverLetterConfig::default(),
+            edited_cover_letters: HashMap::new(),
+            form_editors: HashMap::new(),
+            submission: None,
+            dry_run: false,
+            dry_run_result: None,
+            api_refusals: HashMap::new(),
+            browser_result: None,
+            gh_src: None,
+            phone_country: None,
+            debug: false,
+            daily_limit: quota::DEFAULT_DAILY_LIMIT,
+            override_rate_limit: false,
+            show_clock: true,
+            search_elapsed: None,
+            offline_since: None,
         }
     }
 
-    fn next(&mut self) {
-        let i = match self.list_state.selected() {
-            Some(i) => {
-                if i >= self.jobs.len() - 1 {
-                    0
-                } else {
-                    i + 1
-                }
-            }
-            None => 0,
-        };
-        self.list_state.select(Some(i));
+    fn attach_store(&mut self, store: store::JobStore) {
+        match store.applications(&store::ApplicationFilter::default()) {
+            Ok(records) => self.applications = records,
+            Err(e) => eprintln!("{}Failed to read the application log: {}", icons::WARNING, e),
+        }
+        self.known_tags = store.all_tags().unwrap_or_default();
+        self.resume_fits = store.resume_fits(&self.jobs).unwrap_or_default();
+        self.store = Some(store);
     }
 
-    fn previous(&mut self) {
-        let i = match self.list_state.selected() {
-            Some(i) => {
-                if i == 0 {
-                    self.jobs.len() - 1
-                } else {
-                    i - 1
-                }
-            }
-            None => 0,
-        };
-        self.list_state.select(Some(i));
+    fn prior_application(&self, job: &JobResult) -> Option<store::PriorApplication<'_>> {
+        store::find_prior_application(&self.applications, job)
     }
 
-    fn select_current_job(&mut self) {
-        self.selected_job_index = self.list_state.selected();
-        self.current_view = AppView::JobDetails;
+    fn quota(&self) -> quota::Quota {
+        quota::quota(self.daily_limit, &self.applications, chrono::Utc::now())
     }
 
-    fn back_to_list(&mut self) {
-        self.current_view = AppView::JobList;
+    // Why a submission can't go out right now, if the daily limit stops it
+    fn rate_limited(&self) -> Option<String> {
+        let quota = self.quota();
+        (quota.reached() && !self.override_rate_limit).then(|| quota.describe())
     }
 
-    fn confirm_application(&mut self) {
-        self.current_view = AppView::ConfirmApplication;
+    // 'y' on the confirm screen. Exact duplicates need a second 'y' to go through.
+    fn confirm_yes(&mut self) {
+        if self.dry_run {
+            self.dry_run_application();
+            return;
+        }
+        // Stays on the confirm screen, so the application can still be dry run or logged
+        if let Some(reason) = self.rate_limited() {
+            self.notice = Some(format!("{}{}", icons::PAUSED, reason));
+            return;
+        }
+        let already_applied = self.selected_job_index
+            .and_then(|i| self.jobs.get(i))
+            .is_some_and(|job| matches!(self.prior_application(job), Some(store::PriorApplication::Applied(_))));
+
+        if already_applied && !self.duplicate_confirmed {
+            self.duplicate_confirmed = true;
+        } else {
+            self.apply_to_job();
+        }
     }
 
-    fn apply_to_job(&mut self) {
-        self.current_view = AppView::ApplicationComplete;
+    fn visible_job_indices(&self) -> Vec<usize> {
+        let (wanted_tags, text) = match &self.filter {
+            Some(filter) => tags::split_filter(filter),
+            None => (Vec::new(), None),
+        };
+        let needle = text.map(|text| text.to_lowercase());
+        self.jobs
+            .iter()
+            .enumerate()
+            .filter(|(_, job)| self.show_hidden || !self.hidden.contains(&job.url))
+            .filter(|(_, job)| tags::has_all(job, &wanted_tags))
+            .filter(|(_, job)| match &needle {
+                Some(needle) => [&job.title, &job.company, &job.location]
+                    .iter()
+                    .any(|field| field.to_lowercase().contains(needle.as_str())),
+                None => true,
+            })
+            .map(|(i, _)| i)
+            .collect()
     }
 
-    fn render(&mut self, f: &mut Frame) {
-        match self.current_view {
-            AppView::JobList => self.render_job_list(f),
-            AppView::JobDetails => self.render_job_details(f),
-            AppView::ConfirmApplication => self.render_confirm_application(f),
-            AppView::ApplicationComplete => self.render_application_complete(f),
+    // Change the filter, moving the cursor to the first match if the current job is filtered out
+    fn set_filter(&mut self, filter: Option<String>) {
+        self.filter = filter;
+        self.keep_selection_visible();
+    }
+
+    fn keep_selection_visible(&mut self) {
+        let visible = self.visible_job_indices();
+        if !self.list_state.selected().is_some_and(|i| visible.contains(&i)) {
+            self.list_state.select(visible.first().copied());
         }
     }
 
-    fn render_job_list(&mut self, f: &mut Frame) {
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .margin(1)
-            .constraints([
-                Constraint::Length(3),
-                Constraint::Min(0),
-                Constraint::Length(3),
-            ])
-            .split(f.area());
+    // Loaded when browsing starts, after the session restored the last selection
+    fn load_hidden(&mut self) {
+        self.hidden = hidden::HiddenJobs::load();
+        self.keep_selection_visible();
+    }
 
-        // Title
-        let title = Paragraph::new("🎯 JOB BROWSER - Interactive Mode")
-            .block(Block::default().borders(Borders::ALL))
-            .style(Style::default().fg(Color::Cyan));
-        f.render_widget(title, chunks[0]);
+    // Mark the job "not interested" and move on to the next one in the list.
+    // It stays hidden for this session even if saving fails.
+    fn hide_job(&mut self, index: usize) -> Result<(), String> {
+        let Some(job) = self.jobs.get(index) else {
+            return Ok(());
+        };
+        self.hidden.hide(job);
+        let saved = self.hidden.save().map_err(|e| format!("saving the hidden list failed: {}", e));
+        if !self.show_hidden {
+            let visible = self.visible_job_indices();
+            let next = visible.iter().find(|&&i| i > index).or(visible.last()).copied();
+            self.list_state.select(next);
+        }
+        saved
+    }
 
-        // Job list
-        let items: Vec<ListItem> = self.jobs
-            .iter()
-            .enumerate()
-            .map(|(_i, job)| {
-                let content = vec![
-                    Line::from(vec![
-                        Span::styled("📋 ", Style::default().fg(Color::Blue)),
-                        Span::raw(&job.title),
-                    ]),
-                    Line::from(vec![
-                        Span::raw("   🏢 "),
-                        Span::styled(&job.company, Style::default().fg(Color::Green)),
-                    ]),
-                ];
-                ListItem::new(content)
-            })
-            .collect();
+    // 'x' in the list or details view
+    fn hide_with_notice(&mut self, index: usize) {
+        let Some(title) = self.jobs.get(index).map(|job| job.title.clone()) else {
+            return;
+        };
+        self.notice = Some(match self.hide_job(index) {
+            Ok(()) => format!("{}Hid \"{}\" (H: manage hidden jobs)", icons::HIDDEN, title),
+            Err(e) => format!("{}Hid \"{}\" for now, but {}", icons::WARNING, title, e),
+        });
+    }
 
-        let jobs_list = List::new(items)
-            .block(Block::default().borders(Borders::ALL).title("Jobs"))
-            .highlight_style(Style::default().bg(Color::LightBlue).fg(Color::Black).add_modifier(Modifier::BOLD))
-            .highlight_symbol("→ ");
+    // The logged application a status can be recorded for, when the job has one that went out
+    fn status_record(&self, job: &JobResult) -> Option<&store::ApplicationRecord> {
+        match self.prior_application(job) {
+            Some(store::PriorApplication::Applied(record)) if record.went_out() => Some(record),
+            _ => None,
+        }
+    }
 
-        f.render_stateful_widget(jobs_list, chunks[1], &mut self.list_state);
+    fn save_status(&mut self, id: i64, update: StatusUpdate) -> Result<String, String> {
+        let store = self.store.as_ref().ok_or("the job store isn't available, so the status can't be saved")?;
+        let saved = store
+            .update_application(id, update.status, update.note.as_deref(), update.remind_on)
+            .map_err(|e| format!("failed to save the status: {}", e))?;
+        if !saved {
+            return Err(format!("#{} isn't an application that went out", id));
+        }
+        self.applications = store.applications(&store::ApplicationFilter::default()).map_err(|e| e.to_string())?;
+        let Some(record) = self.applications.iter().find(|record| record.id == id) else {
+            return Ok(format!("{}Saved", icons::DONE));
+        };
+        let reminder = match record.remind_on() {
+            Some(day) => format!("; follow up on {}", day.format(locale::current().date_pattern())),
+            None => String::new(),
+        };
+        Ok(format!("{}{}: {}{}", icons::STATUS, record.company, record.status_line(), reminder))
+    }
 
-        // Controls
-        let controls = Paragraph::new("🎮 ↑/↓: Navigate | Enter: View Details | q: Quit")
-            .block(Block::default().borders(Borders::ALL))
-            .style(Style::default().fg(Color::Gray));
-        f.render_widget(controls, chunks[2]);
+    // '+' in the list or details view: saved for `queue process`
+    fn queue_with_notice(&mut self, index: usize) {
+        let Some(job) = self.jobs.get(index) else {
+            return;
+        };
+        self.notice = Some(match self.store.as_ref().map(|store| store.queue_job(job)) {
+            Some(Ok(true)) => format!("{}Queued \"{}\"; apply to the queue with `queue process`", icons::QUEUE, job.title),
+            Some(Ok(false)) => format!("{}\"{}\" is already queued", icons::INFO, job.title),
+            Some(Err(e)) => format!("{}Failed to queue \"{}\": {}", icons::ERROR, job.title, e),
+            None => format!("{}The job store isn't available, so nothing can be queued", icons::WARNING),
+        });
     }
 
-    fn render_job_details(&mut self, f: &mut Frame) {
-        if let Some(index) = self.selected_job_index {
-            if let Some(job) = self.jobs.get(index) {
-                let chunks = Layout::default()
-                    .direction(Direction::Vertical)
-                    .margin(1)
-                    .constraints([
-                        Constraint::Length(3),
-                        Constraint::Min(0),
-                        Constraint::Length(3),
-                    ])
-                    .split(f.area());
+    fn open_hidden_view(&mut self) {
+        self.hidden_list_state.select((!self.hidden.is_empty()).then_some(0));
+        self.current_view = AppView::HiddenJobs;
+    }
 
-                // Title
-                let title = Paragraph::new("📋 JOB DETAILS")
-                    .block(Block::default().borders(Borders::ALL))
-                    .style(Style::default().fg(Color::Cyan));
-                f.render_widget(title, chunks[0]);
+    // 'u' and 'U' in the hidden jobs view; `None` unhides everything
+    fn unhide(&mut self, position: Option<usize>) {
+        match position {
+            Some(position) => {
+                let Some(url) = self.hidden.entries().nth(position).map(|(url, _)| url.clone()) else {
+                    return;
+                };
+                self.hidden.unhide(&url);
+            }
+            None => self.hidden.clear(),
+        }
+        if let Err(e) = self.hidden.save() {
+            self.notice = Some(format!("{}Failed to save the hidden list: {}", icons::WARNING, e));
+        }
+        let last = self.hidden.len().checked_sub(1);
+        self.hidden_list_state.select(position.zip(last).map(|(position, last)| position.min(last)));
+        self.keep_selection_visible();
+    }
 
-                // Job details
-                let details = vec![
-                    Line::from(vec![
-                        Span::styled("📌 Title: ", Style::default().fg(Color::Yellow)),
-                        Span::raw(&job.title),
-                    ]),
-                    Line::from(""),
-                    Line::from(vec![
-                        Span::styled("🏢 Company: ", Style::default().fg(Color::Green)),
-                        Span::raw(&job.company),
-                    ]),
-                    Line::from(""),
-                    Line::from(vec![
-                        Span::styled("📅 Date Posted: ", Style::default().fg(Color::Blue)),
-                        Span::raw(&job.date_posted),
-                    ]),
-                    Line::from(""),
-                    Line::from(vec![
-                        Span::styled("🔗 URL: ", Style::default().fg(Color::Magenta)),
-                        Span::raw(&job.url),
-                    ]),
-                ];
+    // Replace a job's tags in the store and in the loaded results
+    fn save_tags(&mut self, index: usize, tags: Vec<String>) -> Result<(), String> {
+        let Some(job) = self.jobs.get(index) else {
+            return Ok(());
+        };
+        let Some(store) = &self.store else {
+            return Err("job store unavailable; tags were not saved".to_string());
+        };
+        store.set_tags(job, &tags).map_err(|e| format!("failed to save tags: {}", e))?;
+        self.known_tags.extend(tags.iter().cloned());
+        self.known_tags.sort();
+        self.known_tags.dedup();
+        let tagged = !tags.is_empty();
+        self.jobs[index].tags = tags;
+        if tagged {
+            store.archive_job(&self.jobs[index]).map_err(|e| format!("tags saved, but archiving the posting failed: {}", e))?;
+        }
+        Ok(())
+    }
 
-                let details_paragraph = Paragraph::new(details)
-                    .block(Block::default().borders(Borders::ALL))
-                    .wrap(ratatui::widgets::Wrap { trim: true });
-                f.render_widget(details_paragraph, chunks[1]);
+    fn open_prompt(&mut self, kind: PromptKind) {
+        let text = match kind {
+            PromptKind::Filter => self.filter.clone().unwrap_or_default(),
+            PromptKind::Tags(index) => match self.jobs.get(index) {
+                Some(job) if !job.tags.is_empty() => job.tags.join(" ") + " ",
+                Some(_) => String::new(),
+                None => return,
+            },
+            PromptKind::Status(_) => String::new(),
+        };
+        self.prompt = Some(Prompt { kind, text });
+    }
 
-                // Controls
-                let controls = Paragraph::new("🎮 a: Apply | b: Back to List | q: Quit")
-                    .block(Block::default().borders(Borders::ALL))
-                    .style(Style::default().fg(Color::Gray));
-                f.render_widget(controls, chunks[2]);
+    fn handle_prompt_key(&mut self, code: KeyCode) {
+        let Some(prompt) = &mut self.prompt else {
+            return;
+        };
+        match code {
+            KeyCode::Esc => self.prompt = None,
+            KeyCode::Backspace => {
+                prompt.text.pop();
+            }
+            KeyCode::Tab => {
+                // In the filter only #words complete, since the rest is free text
+                let completes = match prompt.kind {
+                    PromptKind::Filter => prompt.text.split_whitespace().last().is_some_and(|w| w.starts_with('#')),
+                    PromptKind::Tags(_) => true,
+                    // Only the status word at the start
+                    PromptKind::Status(_) => {
+                        if !prompt.text.contains(' ') {
+                            let typed = prompt.text.to_lowercase();
+                            let mut matching = store::ApplicationStatus::ALL[1..].iter().filter(|status| status.as_str().starts_with(&typed));
+                            if let (Some(status), None) = (matching.next(), matching.next()) {
+                                prompt.text = format!("{} ", status.as_str());
+                            }
+                        }
+                        false
+                    }
+                };
+                if completes {
+                    if let Some(completed) = tags::complete(&prompt.text, &self.known_tags) {
+                        prompt.text = completed;
+                    }
+                }
+            }
+            KeyCode::Char(c) => prompt.text.push(c),
+            KeyCode::Enter => {
+                let Some(prompt) = self.prompt.take() else {
+                    return;
+                };
+                match prompt.kind {
+                    PromptKind::Filter => {
+                        let text = prompt.text.trim();
+                        self.set_filter((!text.is_empty()).then(|| text.to_string()));
+                    }
+                    PromptKind::Tags(index) => {
+                        if let Err(e) = self.save_tags(index, tags::parse(&prompt.text)) {
+                            self.notice = Some(format!("{}{}", icons::WARNING, e));
+                        }
+                    }
+                    PromptKind::Status(id) => {
+                        let saved = parse_status_update(&prompt.text).and_then(|update| self.save_status(id, update));
+                        self.notice = Some(saved.unwrap_or_else(|e| format!("{}{}", icons::WARNING, e)));
+                    }
+                }
             }
+            _ => {}
         }
     }
 
-    fn render_confirm_application(&mut self, f: &mut Frame) {
-        if let Some(index) = self.selected_job_index {
-            if let Some(job) = self.jobs.get(index) {
-                let chunks = Layout::default()
-                    .direction(Direction::Vertical)
-                    .margin(1)
-                    .constraints([
-                        Constraint::Length(3),
-                        Constraint::Min(0),
-                        Constraint::Length(3),
-                    ])
-                    .split(f.area());
-
-                // Title
-                let title = Paragraph::new("🤔 CONFIRM APPLICATION")
-                    .block(Block::default().borders(Borders::ALL))
-                    .style(Style::default().fg(Color::Red));
-                f.render_widget(title, chunks[0]);
+    // A key after 'e': the jobs the list shows, in its order, to a new file in
+    // the export directory or to the clipboard as Markdown. Anything else
+    // closes the menu.
+    fn handle_export_key(&mut self, code: KeyCode) {
+        self.export_menu = false;
+        let format = match code {
+            KeyCode::Char('c') => Some(export::SnapshotFormat::Csv),
+            KeyCode::Char('j') => Some(export::SnapshotFormat::Json),
+            KeyCode::Char('m') => Some(export::SnapshotFormat::Markdown),
+            KeyCode::Char('y') => None,
+            _ => return,
+        };
+        let jobs: Vec<JobResult> = self.visible_job_indices().into_iter().map(|i| self.jobs[i].clone()).collect();
+        if jobs.is_empty() {
+            self.notice = Some(format!("{}Nothing to export; no job passes the filter", icons::EMPTY));
+            return;
+        }
+        let annotations = self.store.as_ref().and_then(|store| export::load_annotations(store, &jobs).ok());
+        let (search, board_count) = self.search.clone().unwrap_or_else(|| {
+            let boards: HashSet<&str> = jobs.iter().map(|job| job.board_token.as_str()).collect();
+            let search = export::SearchParameters { keyword: String::new(), location: String::new(), new_only: false, since: None };
+            (search, boards.len())
+        });
+        let export = export::SearchExport::new(search, board_count, jobs);
+        let count = export.results.len();
+        let exported = match format {
+            Some(format) => export::write_snapshot(&self.export_dir, format, &export, annotations.as_deref())
+                .map(|path| format!("{}Exported {} job(s) to {}", icons::SAVED, count, path.display()))
+                .map_err(|e| e.to_string()),
+            None => clipboard::copy(&export::results_markdown(&export, annotations.as_deref()))
+                .map(|program| format!("{}Copied {} job(s) to the clipboard as Markdown ({})", icons::SAVED, count, program)),
+        };
+        self.notice = Some(exported.unwrap_or_else(|e| format!("{}Export failed: {}", icons::WARNING, e)));
+    }
 
-                // Confirmation details
-                let details = vec![
-                    Line::from(""),
-                    Line::from(vec![
-                        Span::styled("📋 ", Style::default().fg(Color::Blue)),
-                        Span::styled(&job.title, Style::default().add_modifier(Modifier::BOLD)),
-                    ]),
-                    Line::from(""),
-                    Line::from(vec![
-                        Span::styled("🏢 ", Style::default().fg(Color::Green)),
-                        Span::raw(&job.company),
-                    ]),
-                    Line::from(""),
-                    Line::from(vec![
-                        Span::styled("🔗 ", Style::default().fg(Color::Magenta)),
-                        Span::raw(&job.url),
-                    ]),
-                    Line::from(""),
-                    Line::from(""),
-                    Line::from(vec![
-                        Span::styled("Do you want to apply to this position?", Style::default().fg(Color::Yellow)),
-                    ]),
-                ];
+    // The prompt line with its key hints, replacing the usual controls while typing
+    fn prompt_line(&self) -> Option<String> {
+        let prompt = self.prompt.as_ref()?;
+        let label = match prompt.kind {
+            PromptKind::Filter => format!("{}Filter (#tag to match tags)", icons::SEARCH),
+            PromptKind::Tags(_) => format!("{}Tags", icons::TAGS),
+            PromptKind::Status(_) => format!("{}Status (screening/interview/offer/rejected), note, @YYYY-MM-DD to follow up", icons::STATUS),
+        };
+        Some(format!("{}: {}▏  Tab: Complete | Enter: Save | Esc: Cancel", label, prompt.text))
+    }
 
-                let details_paragraph = Paragraph::new(details)
-                    .block(Block::default().borders(Borders::ALL))
-                    .wrap(ratatui::widgets::Wrap { trim: true });
-                f.render_widget(details_paragraph, chunks[1]);
+    // Put the cursor back on the job selected when the last session ended
+    fn restore_session(&mut self) {
+        let session = session::SessionState::load();
+        let Some(url) = session.last_selected_url else {
+            return;
+        };
+        if let Some(index) = self.jobs.iter().position(|job| job.url == url) {
+            *self.list_state.offset_mut() = session.scroll_offset.min(index);
+            self.list_state.select(Some(index));
+        }
+    }
 
-                // Controls
-                let controls = Paragraph::new("🎮 y: Yes, Apply | n: No, Go Back")
-                    .block(Block::default().borders(Borders::ALL))
-                    .style(Style::default().fg(Color::Gray));
-                f.render_widget(controls, chunks[2]);
+    // Writes out what the session only holds in memory: the list position and
+    // the forms being filled in, as drafts. Every way out of the browser calls
+    // it, a panic included. Hidden jobs, tags and the application log are
+    // written as they change, so there's nothing of theirs to flush.
+    fn save_state(&self) {
+        self.save_session();
+        let Some(store) = &self.store else {
+            return;
+        };
+        for job in self.jobs.iter().filter(|job| self.form_editors.contains_key(&job.id)) {
+            if let Err(e) = store.save_draft(job, &self.form_editors[&job.id].draft()) {
+                eprintln!("{}Failed to save the application draft for {}: {}", icons::WARNING, job.title, e);
             }
         }
     }
 
-    fn render_application_complete(&mut self, f: &mut Frame) {
-        if let Some(index) = self.selected_job_index {
-            if let Some(job) = self.jobs.get(index) {
-                let chunks = Layout::default()
-                    .direction(Direction::Vertical)
-                    .margin(1)
-                    .constraints([
-                        Constraint::Length(3),
-                        Constraint::Min(0),
-                        Constraint::Length(3),
-                    ])
-                    .split(f.area());
-
-                // Title
-                let title = Paragraph::new("✅ JOB SELECTED FOR APPLICATION")
-                    .block(Block::default().borders(Borders::ALL))
-                    .style(Style::default().fg(Color::Green));
-                f.render_widget(title, chunks[0]);
+    fn save_session(&self) {
+        let session = session::SessionState {
+            last_selected_url: self.list_state.selected().and_then(|i| self.jobs.get(i)).map(|job| job.url.clone()),
+            scroll_offset: self.list_state.offset(),
+        };
+        if let Err(e) = session.save() {
+            eprintln!("{}Failed to save session state: {}", icons::WARNING, e);
+        }
+    }
 
-                // Success message
-                let details = vec![
+    fn next(&mut self) {
+        self.step_visible(true);
+    }
+
+    fn previous(&mut self) {
+        self.step_visible(false);
+    }
+
+    // Move to the next or previous job that passes the filter, wrapping around
+    fn step_visible(&mut self, forward: bool) {
+        let visible = self.visible_job_indices();
+        if visible.is_empty() {
+            return;
+        }
+        let position = self.list_state.selected()
+            .and_then(|current| visible.iter().position(|&i| i == current));
+        let next = match (position, forward) {
+            (None, _) => 0,
+            (Some(p), true) => (p + 1) % visible.len(),
+            (Some(p), false) => (p + visible.len() - 1) % visible.len(),
+        };
+        self.list_state.select(Some(visible[next]));
+    }
+
+    // Hop to the first job of the next company group
+    fn next_company(&mut self) {
+        let visible = self.visible_job_indices();
+        let Some(position) = self.list_state.selected().and_then(|current| visible.iter().position(|&i| i == current)) else {
+            return;
+        };
+        let company = &self.jobs[visible[position]].company;
+        if let Some(offset) = visible[position..].iter().position(|&i| &self.jobs[i].company != company) {
+            self.list_state.select(Some(visible[position + offset]));
+        }
+    }
+
+    // Hop to the first job of the previous company group
+    fn previous_company(&mut self) {
+        let visible = self.visible_job_indices();
+        let Some(position) = self.list_state.selected().and_then(|current| visible.iter().position(|&i| i == current)) else {
+            return;
+        };
+        let company = &self.jobs[visible[position]].company;
+        let Some(target) = visible[..position].iter().rposition(|&i| &self.jobs[i].company != company) else {
+            return;
+        };
+        let target_company = &self.jobs[visible[target]].company;
+        let group_start = visible[..target]
+            .iter()
+            .rposition(|&i| &self.jobs[i].company != target_company)
+            .map_or(0, |p| p + 1);
+        self.list_state.select(Some(visible[group_start]));
+    }
+
+    fn select_current_job(&mut self) {
+        self.selected_job_index = self.list_state.selected();
+        self.current_view = AppView::JobDetails;
+    }
+
+    // Other visible jobs whose titles share the most words with this one's, most similar first
+    fn similar_jobs(&self, index: usize) -> Vec<(usize, f64)> {
+        let words = store::title_words(&self.jobs[index].title);
+        let mut similar: Vec<(usize, f64)> = self
+            .visible_job_indices()
+            .into_iter()
+            .filter(|&other| other != index)
+            .map(|other| (other, store::word_overlap(&words, &store::title_words(&self.jobs[other].title))))
+            .filter(|&(_, overlap)| overlap >= MIN_TITLE_OVERLAP)
+            .collect();
+        similar.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        similar.truncate(SIMILAR_JOBS);
+        similar
+    }
+
+    // 1-5 in the details view: jump to that suggestion
+    fn open_similar(&mut self, rank: usize) {
+        let Some(index) = self.selected_job_index else {
+            return;
+        };
+        if let Some(&(other, _)) = self.similar_jobs(index).get(rank) {
+            self.list_state.select(Some(other));
+            self.select_current_job();
+        }
+    }
+
+    // True when the details or form view is showing a job whose form hasn't been fetched yet
+    fn needs_application_form(&self) -> bool {
+        if !matches!(
+            self.current_view,
+            AppView::JobDetails | AppView::FormPreview | AppView::ApplicationForm | AppView::ConfirmApplication
+        ) {
+            return false;
+        }
+        match self.selected_job_index.and_then(|i| self.jobs.get(i)) {
+            Some(job) => !self.application_forms.contains_key(&job.id),
+            None => false,
+        }
+    }
+
+    fn load_application_form(&mut self) {
+        let Some(job) = self.selected_job_index.and_then(|i| self.jobs.get(i)) else {
+            return;
+        };
+
+        let job_id = job.id;
+        // The TUI loop is synchronous, so block on the fetch from within the runtime
+        let form = if self.offline_since.is_some() {
+            Err("not fetched while browsing saved results".to_string())
+        } else {
+            tokio::task::block_in_place(|| {
+                tokio::runtime::Handle::current().block_on(application::get_application_form(&self.client, job))
+            })
+        };
+
+        // Loaded once per board, the first time one of its forms asks for them
+        if let Some(form) = form.as_ref().ok().filter(|form| !education::needed(form).is_empty()) {
+            if !self.education_lists.contains_key(&job.board_token) {
+                let lists = tokio::task::block_in_place(|| {
+                    tokio::runtime::Handle::current().block_on(education::load(&self.client, &job.board_token, form))
+                });
+                if let Some((list, e)) = lists.unavailable.first() {
+                    self.notice = Some(format!("{}The board's {} list couldn't be loaded ({}); type the answer in instead", icons::WARNING, list.describe(), e));
+                }
+                self.education_lists.insert(job.board_token.clone(), lists);
+            }
+        }
+
+        // A posting that's been taken down can still be shown from the archive
+        if form.is_err() {
+            if let Some(Ok(Some(archived))) = self.store.as_ref().map(|store| store.latest_archive(job)) {
+                self.archived_postings.insert(job_id, archived);
+            }
+        }
+        self.application_forms.insert(job_id, form);
+    }
+
+    // The archived description, when the live posting couldn't be fetched
+    fn archived_posting(&self, job: &JobResult) -> Option<(String, Vec<String>)> {
+        let archived = self.archived_postings.get(&job.id)?;
+        let content = archived.raw.get("content").and_then(serde_json::Value::as_str).unwrap_or_default();
+        let reason = if self.offline_since.is_some() { "browsing offline" } else { "the live posting couldn't be loaded" };
+        let heading = format!(
+            "Archived posting from {} ({})",
+            locale::current().timestamp_date(&archived.archived_at),
+            reason
+        );
+        Some((heading, extract::plain_text(content)))
+    }
+
+    // Plain-text summary of the job's application form, shared by the TUI and accessible mode
+    fn application_requirements(&self, job: &JobResult) -> Vec<String> {
+        let mut lines = Vec::new();
+
+        match self.application_forms.get(&job.id) {
+            None => lines.push("Loading application form...".to_string()),
+            Some(Err(e)) => lines.push(format!("Application questions not available ({})", e)),
+            Some(Ok(form)) if form.questions.is_empty() => {
+                lines.push("This board doesn't publish its application questions".to_string());
+            }
+            Some(Ok(form)) => {
+                let required: Vec<&str> = form.required_questions().map(|q| q.label.as_str()).collect();
+                lines.push(format!(
+                    "{} question(s), {} required, {} essay question(s)",
+                    form.questions.len(), required.len(), form.essay_question_count()
+                ));
+
+                for (label, field) in [("Resume", "resume"), ("Cover Letter", "cover_letter")] {
+                    let status = match form.question_with_field(field) {
+                        Some(q) if q.required => "required",
+                        Some(_) => "optional",
+                        None => "not requested",
+                    };
+                    lines.push(format!("{}: {}", label, status));
+                }
+
+                if !required.is_empty() {
+                    lines.push(format!("Required fields: {}", required.join(", ")));
+                }
+            }
+        }
+
+        lines
+    }
+
+    // Every question on the form with its fields, shared by the TUI and accessible mode
+    fn application_questions(&self, job: &JobResult) -> Vec<String> {
+        let form = match self.application_forms.get(&job.id) {
+            None => return vec!["Loading application form...".to_string()],
+            Some(Err(e)) => return vec![
+                format!("The application form isn't available: {}.", e),
+                format!("Open the posting to see what it asks for: {}", job.url),
+            ],
+            Some(Ok(form)) if form.questions.is_empty() => return vec![
+                "This board doesn't publish its application questions.".to_string(),
+                format!("Open the posting to see what it asks for: {}", job.url),
+            ],
+            Some(Ok(form)) => form,
+        };
+
+        // With a profile, each question shows what it would be answered with
+        let mut letter_problem = None;
+        let filled = match self.applicant_profile(job) {
+            Ok(Some(profile)) => {
+                let letter = match self.cover_letter(job, form, &profile) {
+                    Some(Ok(letter)) => Some(letter),
+                    Some(Err(e)) => {
+                        letter_problem = Some(format!("{}The cover letter can't be rendered: {}", icons::WARNING, e));
+                        None
+                    }
+                    None => None,
+                };
+                Some(self.fill(job, form, &profile, letter.as_deref()))
+            }
+            _ => None,
+        };
+        let mut summary = format!("{} question(s), {} required", form.questions.len(), form.required_questions().count());
+        match &filled {
+            Some(filled) => {
+                let unanswered = form.required_questions().filter(|q| filled.answers_for(q).next().is_none()).count();
+                summary.push_str(&format!(", {} not answered by your profile", unanswered));
+            }
+            None => summary.push_str("; create a profile with `profile init` to see what would be filled in"),
+        }
+        let mut lines = vec![summary];
+        lines.extend(letter_problem);
+        lines.extend(self.override_notes(filled.as_ref()));
+        for (number, question) in form.questions.iter().enumerate() {
+            push_question(&mut lines, number + 1, question, filled.as_ref());
+        }
+        for section in &form.compliance {
+            lines.push(String::new());
+            lines.push(format!("Voluntary self-identification ({})", section.kind.to_uppercase()));
+            lines.extend(section.description.iter().flat_map(|text| extract::plain_text(text)));
+            for (number, question) in section.questions.iter().enumerate() {
+                push_question(&mut lines, number + 1, question, filled.as_ref());
+            }
+        }
+        lines
+    }
+
+    // The job's title and description, from the archive when the result doesn't carry one
+    fn job_description(&self, job: &JobResult) -> Option<String> {
+        fit::description(&job.raw)
+            .or_else(|| self.archived_postings.get(&job.id).and_then(|archived| fit::description(&archived.raw)))
+            .or_else(|| {
+                let archived = self.store.as_ref()?.latest_archive(job).ok()??;
+                fit::description(&archived.raw)
+            })
+    }
+
+    // 'r' in the details view: how well the resume this job would be sent covers its description
+    fn analyze_resume_fit(&mut self) {
+        let Some(job) = self.selected_job_index.and_then(|i| self.jobs.get(i)).cloned() else {
+            return;
+        };
+        self.profiles = profile::Profiles::load();
+        let resume = match self.applicant_profile(&job) {
+            Ok(Some(profile)) => profile.resume,
+            Ok(None) => self.resumes.profile_for(&job.board_token, &profile::Profile::default()).resume,
+            Err(e) => {
+                self.notice = Some(format!("{}{}", icons::ERROR, e));
+                return;
+            }
+        };
+        let Some(resume) = resume else {
+            self.notice = Some(format!("{}No resume to analyze; set resume in profile.toml or pass --resume", icons::INFO));
+            return;
+        };
+        let text = match fit::resume_text(&resume) {
+            Ok(text) => text,
+            Err(e) => {
+                self.notice = Some(format!("{}{}", icons::ERROR, e));
+                return;
+            }
+        };
+        let Some(description) = self.job_description(&job) else {
+            self.notice = Some(format!("{}This job has no description to compare with", icons::INFO));
+            return;
+        };
+        if self.fit_corpus.is_none() {
+            let descriptions: Vec<String> = self.jobs.iter().filter_map(|job| fit::description(&job.raw)).collect();
+            self.fit_corpus = Some(fit::Corpus::new(descriptions.iter().map(String::as_str)));
+        }
+        let corpus = self.fit_corpus.as_ref().expect("corpus was just built");
+        let fit = store::ResumeFit::new(&resume, &fit::analyze(&text, &description, corpus));
+        if let Some(Err(e)) = self.store.as_ref().map(|store| store.save_resume_fit(&job, &fit)) {
+            self.notice = Some(format!("{}Failed to save the resume fit: {}", icons::WARNING, e));
+        }
+        self.resume_fits.insert(job.id, fit);
+    }
+
+    // 's' in the list: toggles between best resume fit first and the search's order
+    fn toggle_fit_sort(&mut self) {
+        if self.resume_fits.is_empty() && !self.sort_by_fit {
+            self.notice = Some(format!("{}No job has a resume fit yet; press r in a job's details", icons::INFO));
+            return;
+        }
+        let selected = self.list_state.selected().and_then(|i| self.jobs.get(i)).map(|job| job.url.clone());
+        self.sort_by_fit = !self.sort_by_fit;
+        sort_results(&mut self.jobs);
+        if self.sort_by_fit {
+            // Stable, so jobs without an analysis keep the search's order after the rest
+            let fits = &self.resume_fits;
+            self.jobs.sort_by(|a, b| {
+                let score = |job: &JobResult| fits.get(&job.id).map_or(-1.0, |fit| fit.score);
+                b.favorite.cmp(&a.favorite).then_with(|| score(b).total_cmp(&score(a)))
+            });
+        }
+        if let Some(url) = selected {
+            self.list_state.select(self.jobs.iter().position(|job| job.url == url));
+        }
+    }
+
+    fn preview_application_form(&mut self) {
+        self.current_view = AppView::FormPreview;
+        self.profiles = profile::Profiles::load();
+        self.cover_letter_template = cover_letter::load_template();
+        self.answer_overrides = overrides::Overrides::load();
+    }
+
+    fn selected_api_unavailable(&self) -> bool {
+        self.selected_job_index.and_then(|i| self.jobs.get(i)).is_some_and(|job| self.api_unavailable(job).is_some())
+    }
+
+    fn back_to_list(&mut self) {
+        self.current_view = AppView::JobList;
+    }
+
+    fn confirm_application(&mut self) {
+        self.current_view = AppView::ConfirmApplication;
+        self.duplicate_confirmed = false;
+        self.profiles = profile::Profiles::load();
+        self.cover_letter_template = cover_letter::load_template();
+        self.answer_overrides = overrides::Overrides::load();
+    }
+
+    // The filled-in form for the selected job, or why it can't be submitted
+    fn filled_form(&self, job: &JobResult) -> Result<(&ApplicationForm, submit::FilledForm), String> {
+        let form = match self.application_forms.get(&job.id) {
+            None => return Err("the application form hasn't loaded".to_string()),
+            Some(Err(e)) => return Err(format!("the application form isn't available: {}", e)),
+            Some(Ok(form)) if form.questions.is_empty() => {
+                return Err("this board doesn't take applications through the API".to_string())
+            }
+            Some(Ok(form)) => form,
+        };
+        if let Some(editor) = self.form_editors.get(&job.id) {
+            return Ok((form, editor.filled()));
+        }
+        let profile = match self.applicant_profile(job) {
+            Ok(Some(profile)) => profile,
+            Ok(None) => return Err("there's no applicant profile yet; create one with `profile init`".to_string()),
+            Err(e) => return Err(e),
+        };
+        let problems = profile.problems();
+        if !problems.is_empty() {
+            return Err(format!("fix profile.toml first ({})", problems.join("; ")));
+        }
+        let letter = match self.cover_letter(job, form, &profile) {
+            Some(Err(e)) => return Err(format!("the cover letter can't be rendered: {}", e)),
+            letter => letter.and_then(Result::ok),
+        };
+        Ok((form, self.fill(job, form, &profile, letter.as_deref())))
+    }
+
+    // The profile picked for this job on the confirm screen, else --profile-name, else the main one
+    fn profile_name(&self, job: &JobResult) -> &str {
+        self.job_profiles.get(&job.id).or(self.profile_name.as_ref()).map_or(profile::MAIN_PROFILE, String::as_str)
+    }
+
+    // Who this job's application goes out as, with the resume it gets and its
+    // contact fields normalized; None without profile.toml
+    fn applicant_profile(&self, job: &JobResult) -> Result<Option<profile::Profile>, String> {
+        let Some(profiles) = self.profiles.as_ref().map_err(Clone::clone)? else {
+            return Ok(None);
+        };
+        let profile = profiles.get(Some(self.profile_name(job)))?;
+        Ok(Some(self.resumes.profile_for(&job.board_token, profile).normalized(self.phone_country.as_deref())))
+    }
+
+    // The form answered from the job's board overrides, then the profile; a
+    // broken overrides.toml counts as none, and the previews say why
+    fn fill(&self, job: &JobResult, form: &ApplicationForm, profile: &profile::Profile, cover_letter: Option<&str>) -> submit::FilledForm {
+        let rules = self.answer_overrides.as_ref().map_or(&[][..], |overrides| overrides.for_board(&job.board_token));
+        let letter = cover_letter.map(|text| cover_letter::Letter::new(text.to_string(), profile, &self.letter_settings));
+        submit::fill(form, profile, letter.as_ref(), rules)
+    }
+
+    // What the previews add about overrides.toml: why it's ignored, and with
+    // --debug, which of the board's patterns this form left unused
+    fn override_notes(&self, filled: Option<&submit::FilledForm>) -> Vec<String> {
+        match (&self.answer_overrides, filled) {
+            (Err(e), _) => vec![format!("{}overrides.toml is ignored until it's fixed: {}", icons::WARNING, e)],
+            (Ok(_), Some(filled)) if self.debug && !filled.unused_overrides.is_empty() => {
+                vec![format!("{}Debug: overrides.toml patterns that matched no question: {}", icons::INFO, filled.unused_overrides.join(", "))]
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    // Every profile in profile.toml, main first; empty without one
+    fn profile_names(&self) -> Vec<String> {
+        match &self.profiles {
+            Ok(Some(profiles)) => profiles.names().into_iter().map(str::to_string).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    // `p` on the confirm screen: the next profile for this job
+    fn next_profile(&mut self) {
+        let Some(job) = self.selected_job_index.and_then(|i| self.jobs.get(i)).cloned() else {
+            return;
+        };
+        let names = self.profile_names();
+        if names.len() < 2 {
+            self.notice = Some(format!("{}There's only one profile; add [profiles.<name>] tables to profile.toml for more", icons::INFO));
+            return;
+        }
+        let current = names.iter().position(|name| name == self.profile_name(&job)).unwrap_or(0);
+        self.job_profiles.insert(job.id, names[(current + 1) % names.len()].clone());
+        self.refill_application_form(&job);
+    }
+
+    // The picker on the confirm screen: each profile with the resume and links it
+    // would send to this job, the one in use marked
+    fn profile_choices(&self, job: &JobResult) -> Vec<String> {
+        let Ok(Some(profiles)) = &self.profiles else {
+            return Vec::new();
+        };
+        let chosen = self.profile_name(job);
+        let mut lines = vec!["Apply as:".to_string()];
+        for name in profiles.names() {
+            let marker = if name == chosen { "→" } else { " " };
+            let details = match profiles.get(Some(name)) {
+                Ok(profile) => {
+                    let profile = self.resumes.profile_for(&job.board_token, profile);
+                    let resume = profile.resume.as_ref().map_or("no resume".to_string(), |path| {
+                        path.file_name().unwrap_or(path.as_os_str()).to_string_lossy().into_owned()
+                    });
+                    let links: Vec<&str> = [&profile.linkedin, &profile.github, &profile.website].into_iter().flatten().map(String::as_str).collect();
+                    let problems = profile.problems();
+                    let mut details = format!("{}{}", resume, links.iter().map(|link| format!(", {}", link)).collect::<String>());
+                    if !problems.is_empty() {
+                        details.push_str(&format!(" ({} problem(s) in profile.toml)", problems.len()));
+                    }
+                    details
+                }
+                Err(e) => format!("can't be used: {}", e),
+            };
+            lines.push(format!("  {} {}: {}", marker, name, details));
+        }
+        lines
+    }
+
+    // The letter for this job, edited or rendered from the template; None
+    // without a template, or when the form doesn't ask for a cover letter
+    fn cover_letter(&self, job: &JobResult, form: &ApplicationForm, profile: &profile::Profile) -> Option<Result<String, String>> {
+        form.question_with_field("cover_letter").or_else(|| form.question_with_field("cover_letter_text"))?;
+        if let Some(letter) = self.edited_cover_letters.get(&job.id) {
+            return Some(Ok(letter.clone()));
+        }
+        match &self.cover_letter_template {
+            Ok(Some(template)) => Some(cover_letter::render_for(template, job, profile)),
+            Ok(None) => None,
+            Err(e) => Some(Err(e.clone())),
+        }
+    }
+
+    // `e` on the confirm screen: the letter as it stands, opened in the user's editor
+    fn edit_cover_letter(&mut self) {
+        let Some(job) = self.selected_job_index.and_then(|index| self.jobs.get(index)) else {
+            return;
+        };
+        let letter = match self.filled_form(job) {
+            Ok((_, filled)) => filled.cover_letter().map(str::to_string),
+            Err(e) => {
+                self.notice = Some(format!("{}Can't edit the cover letter: {}", icons::WARNING, e));
+                return;
+            }
+        };
+        let Some(letter) = letter else {
+            self.notice = Some(format!("{}No cover letter to edit; the form doesn't ask for one or {} doesn't exist", icons::WARNING, paths::cover_letter_path().display()));
+            return;
+        };
+        match cover_letter::edit(&letter, job) {
+            Ok(edited) => {
+                if let Some(editor) = self.form_editors.get_mut(&job.id) {
+                    editor.set_cover_letter(&edited);
+                }
+                self.edited_cover_letters.insert(job.id, edited);
+            }
+            Err(e) => self.notice = Some(format!("{}{}", icons::WARNING, e)),
+        }
+    }
+
+    // What confirming would send, for the confirmation screen
+    fn submission_preview(&self, job: &JobResult) -> Vec<String> {
+        let filled = match self.filled_form(job) {
+            Ok((_, filled)) => filled,
+            Err(e) => return vec![format!("Can't submit: {}.", e), "Press m if you applied yourself, to log it.".to_string()],
+        };
+        let mut lines = vec!["This will be sent to Greenhouse:".to_string()];
+        lines.extend(submit::preview(&filled).into_iter().map(|line| format!("  {}", line)));
+        let notes = self.override_notes(Some(&filled));
+        if !notes.is_empty() {
+            lines.push(String::new());
+            lines.extend(notes);
+        }
+        if let Some(letter) = filled.cover_letter() {
+            lines.push(String::new());
+            lines.push("Cover letter:".to_string());
+            lines.extend(letter.lines().map(|line| format!("  {}", line)));
+        }
+        if !filled.problems.is_empty() {
+            lines.push(String::new());
+            lines.push("Not ready to submit:".to_string());
+            lines.extend(filled.problems.iter().map(|problem| format!("  {}: {}", problem.field, problem.message)));
+        }
+        let warnings = match self.applicant_profile(job) {
+            Ok(Some(profile)) => profile.warnings(self.phone_country.as_deref()),
+            _ => Vec::new(),
+        };
+        if !warnings.is_empty() {
+            lines.push(String::new());
+            lines.push("Check these in profile.toml; the form may reject them:".to_string());
+            lines.extend(warnings.iter().map(|warning| format!("  {}", warning)));
+        }
+        if !self.dry_run {
+            lines.push(String::new());
+            let quota = self.quota();
+            if self.override_rate_limit {
+                lines.push(format!("{} (not enforced: --override-rate-limit)", quota.describe()));
+            } else {
+                lines.push(quota.describe());
+            }
+        }
+        lines
+    }
+
+    // Submits the selected job's application and logs it when Greenhouse accepts it
+    fn apply_to_job(&mut self) {
+        self.current_view = AppView::ApplicationComplete;
+        self.status_message = None;
+        self.dry_run_result = None;
+        self.browser_result = None;
+        let Some(index) = self.selected_job_index.filter(|&i| i < self.jobs.len()) else {
+            return;
+        };
+        if let Some(reason) = self.rate_limited() {
+            self.submission = Some(Err(submit::SubmitError::general(reason)));
+            return;
+        }
+        let job = &self.jobs[index];
+        let mut sent_resume = None;
+        let mut sent_cover_letter = None;
+        let mut sent = None;
+        let outcome = match self.filled_form(job) {
+            Err(e) => Err(submit::SubmitError::general(e)),
+            Ok((_, filled)) if !filled.problems.is_empty() => {
+                Err(submit::SubmitError::with_fields("some required questions aren't answered", filled.problems))
+            }
+            Ok((form, filled)) => {
+                // Hashed now, so the log records exactly the file that goes out
+                let resume = filled.answers.iter().find_map(|(name, answer)| match answer {
+                    submit::Answer::File(path) if name == "resume" => Some(resume::Resume::open(path)),
+                    _ => None,
+                });
+                match resume.transpose() {
+                    Err(e) => Err(submit::SubmitError::with_fields(
+                        format!("the resume can't be sent: {}", e),
+                        vec![submit::FieldError { field: "resume".to_string(), message: e }],
+                    )),
+                    Ok(resume) => {
+                        sent_resume = resume;
+                        sent_cover_letter = filled.sent_letter();
+                        let api_key = self.board_credentials.get(&job.board_token).map(String::as_str);
+                        let outcome = tokio::task::block_in_place(|| {
+                            tokio::runtime::Handle::current().block_on(submit::submit(&self.client, job, form, &filled.answers, api_key))
+                        });
+                        sent = Some(submit::SavedPayload {
+                            answers: filled.answers,
+                            form: form.clone(),
+                            profile: self.profile_name(job).to_string(),
+                        });
+                        outcome
+                    }
+                }
+            }
+        };
+        match &outcome {
+            Ok(()) => self.record_application(index, store::ApplicationMethod::Api, sent_resume.as_ref(), sent_cover_letter.as_ref()),
+            // Shown against the fields when the form is reopened
+            Err(e) => {
+                if e.api_refused {
+                    self.api_refusals.insert(self.jobs[index].id, e.message.clone());
+                }
+                if let Some(editor) = self.form_editors.get_mut(&self.jobs[index].id) {
+                    editor.set_errors(&e.fields);
+                }
+                if let Some(sent) = sent.filter(|_| e.transient) {
+                    self.queue_for_retry(index, &e.message, &sent);
+                }
+            }
+        }
+        self.submission = Some(outcome);
+    }
+
+    // A submission that didn't get through goes in the queue as it was sent,
+    // for `queue process` to send again
+    fn queue_for_retry(&mut self, index: usize, reason: &str, sent: &submit::SavedPayload) {
+        let Some(store) = &self.store else {
+            return;
+        };
+        let queued = serde_json::to_string(sent)
+            .map_err(|e| e.to_string())
+            .and_then(|payload| store.queue_retry(&self.jobs[index], reason, &payload).map_err(|e| e.to_string()));
+        self.status_message = Some(match queued {
+            Ok(()) => "Queued with these exact answers; `queue process` sends them again".to_string(),
+            Err(e) => format!("Failed to queue it to send again: {}", e),
+        });
+    }
+
+    // Everything `apply_to_job` does up to the POST, written to a file and
+    // logged as a dry run; the draft stays and the posting isn't archived
+    fn dry_run_application(&mut self) {
+        self.current_view = AppView::ApplicationComplete;
+        self.status_message = None;
+        self.dry_run_result = None;
+        self.browser_result = None;
+        let Some(job) = self.selected_job_index.and_then(|i| self.jobs.get(i)) else {
+            return;
+        };
+        let filled = match self.filled_form(job) {
+            Ok((_, filled)) => filled,
+            Err(e) => {
+                self.submission = Some(Err(submit::SubmitError::general(e)));
+                return;
+            }
+        };
+        let authenticated = self.board_credentials.contains_key(&job.board_token);
+        let mut report = tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(submit::dry_run(job, &filled, authenticated))
+        });
+        let resume = filled.answers.iter().find_map(|(name, answer)| match answer {
+            submit::Answer::File(path) if name == "resume" => Some(resume::Resume::open(path)),
+            _ => None,
+        });
+        let resume = match resume.transpose() {
+            Ok(resume) => resume,
+            Err(e) => {
+                report.problems.push(submit::FieldError { field: "resume".to_string(), message: e });
+                None
+            }
+        };
+
+        let saved = match paths::persistent().then(|| report.save()) {
+            None => "Not saved; state isn't kept (persist = false in config.toml)".to_string(),
+            Some(Ok(path)) => format!("Saved to {}", path.display()),
+            Some(Err(e)) => format!("Failed to save the dry run: {}", e),
+        };
+        let notes = if report.would_submit() {
+            format!("Dry run, not submitted: {} part(s) would have been sent", report.parts.len())
+        } else {
+            format!("Dry run, not submitted: {} problem(s) would have stopped it", report.problems.len())
+        };
+        let logged = match &self.store {
+            Some(store) => match store.record_application(job, store::ApplicationMethod::DryRun, Some(&notes), resume.as_ref(),
+                filled.sent_letter().as_ref(), Some(self.profile_name(job))) {
+                Ok(_) => "Logged as a dry run, not as an application".to_string(),
+                Err(e) => format!("Failed to log the dry run: {}", e),
+            },
+            None => "Application log unavailable; this dry run was not logged".to_string(),
+        };
+        self.status_message = Some(format!("{}. {}.", saved, logged));
+        self.submission = None;
+        self.dry_run_result = Some(report);
+    }
+
+    // Why the Job Board API can't take this application, from fetching its form
+    // or from Greenhouse turning a submission away; None while it still might
+    fn api_unavailable(&self, job: &JobResult) -> Option<String> {
+        if let Some(refusal) = self.api_refusals.get(&job.id) {
+            return Some(refusal.clone());
+        }
+        match self.application_forms.get(&job.id)? {
+            Err(e) if e == application::NOT_PUBLISHED => Some(e.clone()),
+            Ok(form) if form.questions.is_empty() => Some("this board doesn't publish the job's application questions".to_string()),
+            _ => None,
+        }
+    }
+
+    // `o` when the API can't take the application: open the posting with what
+    // the query string can prefill and log it as a follow-up. The form and its
+    // draft stay, and nothing counts as applied until the follow-up is done.
+    fn apply_in_browser(&mut self) {
+        self.current_view = AppView::ApplicationComplete;
+        self.submission = None;
+        self.dry_run_result = None;
+        self.status_message = None;
+        let Some(job) = self.selected_job_index.and_then(|i| self.jobs.get(i)) else {
+            return;
+        };
+        let profile = self.applicant_profile(job).ok().flatten();
+        let url = browser::prefilled_url(job, profile.as_ref(), self.gh_src.as_deref());
+        if let Err(e) = browser::open(&url) {
+            self.browser_result = Some(Err(e));
+            return;
+        }
+        let reason = self.api_unavailable(job).unwrap_or_else(|| "the API couldn't take it".to_string());
+        let notes = format!("Opened for manual application: {}", reason);
+        self.status_message = Some(match &self.store {
+            Some(store) => match store.record_application(job, store::ApplicationMethod::Browser, Some(&notes), None, None, None) {
+                Ok(id) => {
+                    if let Ok(records) = store.applications(&store::ApplicationFilter::default()) {
+                        self.applications = records;
+                    }
+                    format!("Logged as follow-up #{}; mark it done with m on the confirm screen or `applications complete {}`", id, id)
+                }
+                Err(e) => format!("Failed to log the follow-up: {}", e),
+            },
+            None => "Application log unavailable; this follow-up was not logged".to_string(),
+        });
+        self.browser_result = Some(Ok(url));
+    }
+
+    // For applications made outside the tool: only the log entry
+    fn log_application(&mut self) {
+        self.current_view = AppView::ApplicationComplete;
+        self.submission = None;
+        self.dry_run_result = None;
+        self.browser_result = None;
+        if let Some(index) = self.selected_job_index.filter(|&i| i < self.jobs.len()) {
+            self.record_application(index, store::ApplicationMethod::Manual, None, None);
+        }
+    }
+
+    fn record_application(&mut self, index: usize, method: store::ApplicationMethod, resume: Option<&resume::Resume>,
+        cover_letter: Option<&cover_letter::SentLetter>) {
+        let job = &self.jobs[index];
+        // Only submissions go out as a profile
+        let profile = (method == store::ApplicationMethod::Api).then(|| self.profile_name(job));
+        self.status_message = Some(match &self.store {
+            Some(store) => match store.record_application(job, method, None, resume, cover_letter, profile) {
+                Ok(_) => {
+                    if let Ok(records) = store.applications(&store::ApplicationFilter::default()) {
+                        self.applications = records;
+                    }
+                    match store.archive_job(job) {
+                        Ok(_) => "Recorded in your application log".to_string(),
+                        Err(e) => format!("Recorded in your application log, but archiving the posting failed: {}", e),
+                    }
+                }
+                Err(e) => format!("Failed to record in the application log: {}", e),
+            },
+            None => "Application log unavailable; this application was not recorded".to_string(),
+        });
+        self.discard_form(&self.jobs[index].clone());
+    }
+
+    // Heading and lines for the screen after applying
+    fn application_outcome(&self, job: &JobResult) -> (String, Vec<String>) {
+        let logged = self.status_message.clone().into_iter();
+        if let Some(report) = &self.dry_run_result {
+            let mut lines = vec!["Nothing was sent. A real submission would have made this request:".to_string()];
+            lines.extend(report.lines());
+            lines.push(String::new());
+            lines.extend(logged);
+            return (format!("{}DRY RUN, NOT SUBMITTED", icons::DRY_RUN), lines);
+        }
+        match &self.browser_result {
+            Some(Ok(url)) => {
+                let mut lines = vec![
+                    "Finish the application in your browser; it opened at:".to_string(),
+                    format!("  {}", url),
+                    String::new(),
+                ];
+                lines.extend(logged);
+                return (format!("{}OPENED IN YOUR BROWSER", icons::WEB), lines);
+            }
+            Some(Err(e)) => {
+                return (format!("{}BROWSER DIDN'T OPEN", icons::ERROR), vec![format!("{}.", e)]);
+            }
+            None => {}
+        }
+        match &self.submission {
+            Some(Ok(())) => (format!("{}APPLICATION SUBMITTED", icons::DONE), std::iter::once("Greenhouse accepted your application.".to_string()).chain(logged).collect()),
+            Some(Err(e)) => {
+                let mut lines = vec![format!("Not submitted: {}.", e.message)];
+                lines.extend(e.fields.iter().map(|field| format!("  {}: {}", field.field, field.message)));
+                lines.push(String::new());
+                if e.api_refused {
+                    lines.push("The board doesn't take this application through the API; press o to apply in the browser".to_string());
+                } else if e.transient {
+                    lines.push("Greenhouse may take it later.".to_string());
+                    lines.extend(logged);
+                } else {
+                    lines.push(format!("You can apply manually at {}", job.url));
+                }
+                (format!("{}APPLICATION NOT SUBMITTED", icons::ERROR), lines)
+            }
+            None => (format!("{}LOGGED AS APPLIED", icons::FORM), logged.collect()),
+        }
+    }
+
+    fn render(&mut self, f: &mut Frame) {
+        match self.current_view {
+            AppView::JobList => self.render_job_list(f),
+            AppView::JobDetails => self.render_job_details(f),
+            AppView::FormPreview => self.render_form_preview(f),
+            AppView::HiddenJobs => self.render_hidden_jobs(f),
+            AppView::ApplicationForm => self.render_application_form(f),
+            AppView::ConfirmApplication => self.render_confirm_application(f),
+            AppView::ApplicationComplete => self.render_application_complete(f),
+        }
+    }
+
+    fn render_job_list(&mut self, f: &mut Frame) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Min(0),
+                Constraint::Length(3),
+            ])
+            .split(f.area());
+
+        // Title
+        let title = Paragraph::new(match self.offline_description() {
+            Some(offline) => format!("{}JOB BROWSER - {}", icons::TARGET, offline),
+            None => format!("{}JOB BROWSER - Interactive Mode", icons::TARGET),
+        })
+            .block(Block::default().borders(Borders::ALL))
+            .style(Style::default().fg(Color::Cyan));
+        f.render_widget(title, chunks[0]);
+
+        // Job list, showing only the jobs that pass the filter
+        let visible = self.visible_job_indices();
+        let now = chrono::Utc::now();
+        let items: Vec<ListItem> = visible
+            .iter()
+            .map(|&i| &self.jobs[i])
+            .map(|job| {
+                let mut title_line = vec![
+                    Span::styled(icons::JOB.as_str(), Style::default().fg(Color::Blue)),
+                    Span::raw(&job.title),
+                ];
+                if job.first_seen.is_none() {
+                    title_line.push(Span::styled(format!(" {}UNSEEN", icons::NEW), Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)));
+                }
+                match self.prior_application(job) {
+                    Some(store::PriorApplication::Applied(_)) => {
+                        title_line.push(Span::styled(format!(" {}APPLIED", icons::DONE), Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)));
+                    }
+                    Some(store::PriorApplication::PossiblyApplied(_)) => {
+                        title_line.push(Span::styled(format!(" {}APPLIED?", icons::MAYBE), Style::default().fg(Color::Yellow)));
+                    }
+                    Some(store::PriorApplication::FollowUp(_)) => {
+                        title_line.push(Span::styled(format!(" {}FOLLOW UP", icons::WEB), Style::default().fg(Color::Yellow)));
+                    }
+                    None => {}
+                }
+                if job.closed_at.is_some() {
+                    title_line.push(Span::styled(format!(" {}CLOSED", icons::CLOSED), Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)));
+                }
+                if let Some(fit) = self.resume_fits.get(&job.id) {
+                    title_line.push(Span::styled(format!(" {}% fit", fit.percent()), Style::default().fg(Color::DarkGray)));
+                }
+                let company_style = if job.favorite {
+                    Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(Color::Green)
+                };
+                let mut company_line = vec![
+                    Span::raw(format!("   {}", icons::COMPANY)),
+                    Span::styled(&job.company, company_style),
+                ];
+                if job.favorite {
+                    company_line.push(Span::styled(format!(" {}FAVORITE", icons::FAVORITE), Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)));
+                }
+                let badges = badges::for_job(job, now);
+                if !badges.is_empty() {
+                    company_line.push(Span::raw("  "));
+                    company_line.extend(badge_chips(&badges));
+                }
+                if !job.tags.is_empty() {
+                    company_line.push(Span::raw("  "));
+                    company_line.extend(tag_chips(&job.tags));
+                }
+                let content = vec![Line::from(title_line), Line::from(company_line)];
+                ListItem::new(content)
+            })
+            .collect();
+
+        let mut list_title = match &self.filter {
+            Some(filter) => format!("Jobs ({} of {} match \"{}\")", visible.len(), self.jobs.len(), filter),
+            None if visible.len() < self.jobs.len() => format!("Jobs ({} hidden)", self.jobs.len() - visible.len()),
+            None => "Jobs".to_string(),
+        };
+        if self.sort_by_fit {
+            list_title.push_str(", best resume fit first");
+        }
+        let jobs_list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(list_title))
+            .highlight_style(Style::default().bg(Color::LightBlue).fg(Color::Black).add_modifier(Modifier::BOLD))
+            .highlight_symbol("→ ");
+
+        // The list only holds visible jobs, so translate the selection into and back out of it
+        let mut state = ListState::default()
+            .with_offset(self.list_state.offset())
+            .with_selected(self.list_state.selected().and_then(|current| visible.iter().position(|&i| i == current)));
+        f.render_stateful_widget(jobs_list, chunks[1], &mut state);
+        *self.list_state.offset_mut() = state.offset();
+
+        // Controls
+        let footer = match (self.prompt_line(), &self.notice) {
+            (Some(prompt), _) => prompt,
+            (None, _) if self.export_menu => format!(
+                "{}Export {} job(s): c: CSV | j: JSON | m: Markdown | y: Copy to Clipboard | Esc: Cancel",
+                icons::SAVED,
+                visible.len()
+            ),
+            (None, Some(notice)) => notice.clone(),
+            (None, None) => {
+                let sort = if self.sort_by_fit { "s: Search Order" } else { "s: Sort by Fit" };
+                self.footer(&format!(
+                    "↑/↓: Navigate | {{/}}: Prev/Next Company | Enter: View Details | /: Filter | t: Tags | +: Queue | x: Hide | H: Hidden | e: Export | {} | q: Quit",
+                    sort
+                ))
+            }
+        };
+        let controls = Paragraph::new(footer)
+            .block(Block::default().borders(Borders::ALL))
+            .style(Style::default().fg(Color::Gray));
+        f.render_widget(controls, chunks[2]);
+    }
+
+    // "Saved results from 2024-07-03 09:12 (2 days old), offline" when replaying
+    fn offline_description(&self) -> Option<String> {
+        let saved_at = self.offline_since?;
+        Some(format!(
+            "Saved results from {} ({} old), offline",
+            locale::current().timestamp_date_time(&saved_at.to_rfc3339()),
+            age(chrono::Utc::now() - saved_at)
+        ))
+    }
+
+    // Controls followed by the clock and search time, when enabled
+    fn footer(&self, controls: &str) -> String {
+        let mut footer = format!("{}{}", icons::KEYS, controls);
+        if !self.show_clock {
+            return footer;
+        }
+        footer.push_str(&format!("  |  {}{}", icons::CLOCK, chrono::Local::now().format("%H:%M:%S")));
+        if let Some(elapsed) = self.search_elapsed {
+            let seconds = elapsed.as_secs();
+            footer.push_str(&format!("  |  {}search took {}:{:02}", icons::TIMER, seconds / 60, seconds % 60));
+        }
+        footer
+    }
+
+    fn render_job_details(&mut self, f: &mut Frame) {
+        if let Some(index) = self.selected_job_index {
+            if let Some(job) = self.jobs.get(index) {
+                let chunks = Layout::default()
+                    .direction(Direction::Vertical)
+                    .margin(1)
+                    .constraints([
+                        Constraint::Length(3),
+                        Constraint::Min(0),
+                        Constraint::Length(3),
+                    ])
+                    .split(f.area());
+
+                // Title
+                let title = Paragraph::new(format!("{}JOB DETAILS", icons::JOB))
+                    .block(Block::default().borders(Borders::ALL))
+                    .style(Style::default().fg(Color::Cyan));
+                f.render_widget(title, chunks[0]);
+
+                // Job details
+                let mut details = vec![
+                    Line::from(vec![
+                        Span::styled(format!("{}Title: ", icons::TITLE), Style::default().fg(Color::Yellow)),
+                        Span::raw(&job.title),
+                    ]),
+                    Line::from(""),
+                    Line::from(vec![
+                        Span::styled(format!("{}Company: ", icons::COMPANY), Style::default().fg(Color::Green)),
+                        Span::raw(&job.company),
+                    ]),
+                    Line::from(""),
+                    Line::from(vec![
+                        Span::styled(format!("{}Departments: ", icons::DEPARTMENT), Style::default().fg(Color::Green)),
+                        Span::raw(if job.departments.is_empty() { "—".to_string() } else { job.departments.join(", ") }),
+                    ]),
+                    Line::from(""),
+                    Line::from(
+                        std::iter::once(Span::styled(format!("{}Tags: ", icons::TAGS), Style::default().fg(Color::Green)))
+                            .chain(if job.tags.is_empty() { vec![Span::raw("—")] } else { tag_chips(&job.tags) })
+                            .collect::<Vec<_>>(),
+                    ),
+                    Line::from(""),
+                ];
+                if let Some(closed_at) = &job.closed_at {
+                    details.push(Line::from(Span::styled(
+                        format!("{}Closed: no longer listed on the board since {}", icons::CLOSED, locale::current().timestamp_date(closed_at)),
+                        Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                    )));
+                    details.push(Line::from(""));
+                }
+                if let Some(relevance) = job.relevance {
+                    details.push(Line::from(vec![
+                        Span::styled(format!("{}Relevance: ", icons::RELEVANCE), Style::default().fg(Color::Yellow)),
+                        Span::raw(format!("{:.0}%", relevance * 100.0)),
+                    ]));
+                    details.push(Line::from(""));
+                }
+                if let Some(record) = self.status_record(job) {
+                    let reminder = match record.remind_on() {
+                        Some(day) => format!("; follow up on {}", day.format(locale::current().date_pattern())),
+                        None => String::new(),
+                    };
+                    details.push(Line::from(vec![
+                        Span::styled(format!("{}Applied: ", icons::STATUS), Style::default().fg(Color::Yellow)),
+                        Span::raw(format!("{}, {}{}", record.applied_date(), record.status_line(), reminder)),
+                    ]));
+                    details.push(Line::from(""));
+                }
+                if let Some(fit) = self.resume_fits.get(&job.id) {
+                    details.push(Line::from(vec![
+                        Span::styled(format!("{}Resume Fit: ", icons::ATTACHMENT), Style::default().fg(Color::Yellow)),
+                        Span::raw(format!("{}% ({}, {})", fit.percent(), fit.resume, locale::current().timestamp_date(&fit.analyzed_at))),
+                    ]));
+                    if !fit.missing.is_empty() {
+                        let missing: Vec<&str> = fit.missing.iter().take(RESUME_FIT_MISSING_SHOWN).map(String::as_str).collect();
+                        details.push(Line::from(format!("   Missing: {}", missing.join(", "))));
+                    }
+                    details.push(Line::from(""));
+                }
+                details.extend([
+                    Line::from(vec![
+                        Span::styled(format!("{}Date Posted: ", icons::CALENDAR), Style::default().fg(Color::Blue)),
+                        Span::raw(locale::current().timestamp_date_time(&job.date_posted)),
+                    ]),
+                    Line::from(""),
+                    Line::from(vec![
+                        Span::styled(format!("{}URL: ", icons::LINK), Style::default().fg(Color::Magenta)),
+                        Span::raw(&job.url),
+                    ]),
+                    Line::from(""),
+                    Line::from(vec![
+                        Span::styled(format!("{}Job ID: ", icons::ID), Style::default().fg(Color::Magenta)),
+                        Span::raw(format!("{} on board {}", job.id, job.board_token)),
+                    ]),
+                    Line::from(""),
+                ]);
+                for (label, value) in &job.extras {
+                    details.push(Line::from(vec![
+                        Span::styled(format!("{}{}: ", icons::INSPECT, label), Style::default().fg(Color::Yellow)),
+                        Span::raw(value),
+                    ]));
+                    details.push(Line::from(""));
+                }
+                let similar = self.similar_jobs(index);
+                if !similar.is_empty() {
+                    details.push(Line::from(Span::styled(format!("{}Similar Jobs:", icons::SIMILAR), Style::default().fg(Color::Cyan))));
+                    for (rank, (other, overlap)) in similar.iter().enumerate() {
+                        let other = &self.jobs[*other];
+                        details.push(Line::from(vec![
+                            Span::styled(format!("   {}. ", rank + 1), Style::default().fg(Color::Yellow)),
+                            Span::raw(format!("{} at {}", other.title, other.company)),
+                            Span::styled(format!(" ({:.0}% title match)", overlap * 100.0), Style::default().fg(Color::DarkGray)),
+                        ]));
+                    }
+                    details.push(Line::from(""));
+                }
+                details.push(Line::from(Span::styled(format!("{}Application Requirements:", icons::FORM), Style::default().fg(Color::Cyan))));
+                details.extend(self.application_requirements(job).into_iter().map(|l| Line::from(format!("   {}", l))));
+                if let Some((heading, lines)) = self.archived_posting(job) {
+                    details.push(Line::from(""));
+                    details.push(Line::from(Span::styled(format!("{}{}:", icons::ARCHIVE, heading), Style::default().fg(Color::Cyan))));
+                    details.extend(lines.into_iter().map(|l| Line::from(format!("   {}", l))));
+                }
+
+                let details_paragraph = Paragraph::new(details)
+                    .block(Block::default().borders(Borders::ALL))
+                    .wrap(ratatui::widgets::Wrap { trim: true });
+                f.render_widget(details_paragraph, chunks[1]);
+
+                // Controls
+                let status_key = if self.status_record(job).is_some() { "u: Update Status | " } else { "" };
+                let footer = match (self.prompt_line(), &self.notice) {
+                    (Some(prompt), _) => prompt,
+                    (None, Some(notice)) => notice.clone(),
+                    (None, None) if self.offline_since.is_some() => self.footer(&format!(
+                        "a: Apply | +: Queue | r: Resume Fit | 1-5: Similar Job | {}t: Tags | x: Hide | b: Back to List | q: Quit", status_key
+                    )),
+                    (None, None) => self.footer(&format!(
+                        "a: Apply | +: Queue | f: Form Questions | r: Resume Fit | 1-5: Similar Job | {}t: Tags | x: Hide | b: Back to List | q: Quit",
+                        status_key
+                    )),
+                };
+                let controls = Paragraph::new(footer)
+                    .block(Block::default().borders(Borders::ALL))
+                    .style(Style::default().fg(Color::Gray));
+                f.render_widget(controls, chunks[2]);
+            }
+        }
+    }
+
+    fn render_form_preview(&mut self, f: &mut Frame) {
+        let Some(job) = self.selected_job_index.and_then(|i| self.jobs.get(i)) else {
+            return;
+        };
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Min(0),
+                Constraint::Length(3),
+            ])
+            .split(f.area());
+
+        let title = Paragraph::new(format!("{}APPLICATION FORM: {} at {}", icons::FORM, job.title, job.company))
+            .block(Block::default().borders(Borders::ALL))
+            .style(Style::default().fg(Color::Cyan));
+        f.render_widget(title, chunks[0]);
+
+        let questions: Vec<Line> = self
+            .application_questions(job)
+            .into_iter()
+            .map(|line| {
+                if line.ends_with("(required)") {
+                    Line::from(Span::styled(line, Style::default().fg(Color::Yellow)))
+                } else {
+                    Line::from(line)
+                }
+            })
+            .collect();
+        let questions = Paragraph::new(questions)
+            .block(Block::default().borders(Borders::ALL))
+            .wrap(ratatui::widgets::Wrap { trim: false });
+        f.render_widget(questions, chunks[1]);
+
+        let controls = Paragraph::new(self.footer("a: Apply | b: Back to Details | q: Quit"))
+            .block(Block::default().borders(Borders::ALL))
+            .style(Style::default().fg(Color::Gray));
+        f.render_widget(controls, chunks[2]);
+    }
+
+    fn render_confirm_application(&mut self, f: &mut Frame) {
+        if let Some(index) = self.selected_job_index {
+            if let Some(job) = self.jobs.get(index) {
+                let chunks = Layout::default()
+                    .direction(Direction::Vertical)
+                    .margin(1)
+                    .constraints([
+                        Constraint::Length(3),
+                        Constraint::Min(0),
+                        Constraint::Length(3),
+                    ])
+                    .split(f.area());
+
+                // Title
+                let heading = if self.dry_run { "CONFIRM APPLICATION (DRY RUN)" } else { "CONFIRM APPLICATION" };
+                let title = Paragraph::new(format!("{}{}", icons::CONFIRM, heading))
+                    .block(Block::default().borders(Borders::ALL))
+                    .style(Style::default().fg(Color::Red));
+                f.render_widget(title, chunks[0]);
+
+                // Confirmation details
+                let mut details = vec![
                     Line::from(""),
                     Line::from(vec![
-                        Span::styled("📋 ", Style::default().fg(Color::Blue)),
+                        Span::styled(icons::JOB.as_str(), Style::default().fg(Color::Blue)),
                         Span::styled(&job.title, Style::default().add_modifier(Modifier::BOLD)),
                     ]),
                     Line::from(""),
                     Line::from(vec![
-                        Span::styled("🏢 ", Style::default().fg(Color::Green)),
+                        Span::styled(icons::COMPANY.as_str(), Style::default().fg(Color::Green)),
                         Span::raw(&job.company),
                     ]),
                     Line::from(""),
+                    Line::from(vec![
+                        Span::styled(icons::LINK.as_str(), Style::default().fg(Color::Magenta)),
+                        Span::raw(&job.url),
+                    ]),
+                    Line::from(""),
+                    Line::from(""),
+                ];
+
+                let warning = Style::default().fg(Color::Red).add_modifier(Modifier::BOLD);
+                match self.prior_application(job) {
+                    Some(store::PriorApplication::Applied(record)) => {
+                        details.push(Line::from(Span::styled(
+                            format!("{}You already applied to this job on {}", icons::WARNING, record.applied_date()),
+                            warning,
+                        )));
+                        if self.duplicate_confirmed {
+                            details.push(Line::from(Span::styled("Press y again to apply anyway.", warning)));
+                        }
+                        details.push(Line::from(""));
+                    }
+                    Some(store::PriorApplication::PossiblyApplied(record)) => {
+                        details.push(Line::from(Span::styled(
+                            format!("{}Possibly applied before: \"{}\" at {} on {}", icons::MAYBE, record.title, record.company, record.applied_date()),
+                            Style::default().fg(Color::Yellow),
+                        )));
+                        details.push(Line::from(""));
+                    }
+                    Some(store::PriorApplication::FollowUp(record)) => {
+                        details.push(Line::from(Span::styled(
+                            format!("{}Opened in your browser on {}; press m once you've sent it to mark it done", icons::WEB, record.applied_date()),
+                            Style::default().fg(Color::Yellow),
+                        )));
+                        details.push(Line::from(""));
+                    }
+                    None => {}
+                }
+                if let Some(reason) = self.api_unavailable(job) {
+                    details.push(Line::from(Span::styled(
+                        format!("{}The API can't take this application: {}. Press o to apply in your browser.", icons::WEB, reason),
+                        Style::default().fg(Color::Yellow),
+                    )));
+                    details.push(Line::from(""));
+                }
+                let profile_choices = self.profile_choices(job);
+                if profile_choices.len() > 2 {
+                    details.extend(profile_choices.into_iter().map(Line::from));
+                    details.push(Line::from(""));
+                }
+                details.extend(self.submission_preview(job).into_iter().map(Line::from));
+                details.push(Line::from(""));
+                let question = if self.dry_run {
+                    "Dry run this application? Nothing will be sent to Greenhouse."
+                } else {
+                    "Submit this application to Greenhouse?"
+                };
+                details.push(Line::from(vec![Span::styled(question, Style::default().fg(Color::Yellow))]));
+
+                let details_paragraph = Paragraph::new(details)
+                    .block(Block::default().borders(Borders::ALL))
+                    .wrap(ratatui::widgets::Wrap { trim: false });
+                f.render_widget(details_paragraph, chunks[1]);
+
+                // Controls
+                let mut controls = vec![if self.dry_run { "y: Yes, Dry Run" } else { "y: Yes, Submit" }];
+                controls.push(if self.dry_run { "d: Dry Run Off" } else { "d: Dry Run" });
+                if self.profile_names().len() > 1 {
+                    controls.push("p: Switch Profile");
+                }
+                if self.api_unavailable(job).is_some() {
+                    controls.push("o: Apply in Browser");
+                }
+                controls.push("e: Edit Cover Letter");
+                if self.has_form_editor() {
+                    controls.push("b: Back to Form");
+                }
+                controls.extend(["m: I Applied Myself (log only)", "n: No, Go Back"]);
+                let footer = match &self.notice {
+                    Some(notice) => notice.clone(),
+                    None => self.footer(&controls.join(" | ")),
+                };
+                let controls = Paragraph::new(footer)
+                    .block(Block::default().borders(Borders::ALL))
+                    .style(Style::default().fg(Color::Gray));
+                f.render_widget(controls, chunks[2]);
+            }
+        }
+    }
+
+    fn render_hidden_jobs(&mut self, f: &mut Frame) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Min(0),
+                Constraint::Length(3),
+            ])
+            .split(f.area());
+
+        let title = Paragraph::new(format!("{}HIDDEN JOBS ({})", icons::HIDDEN, self.hidden.len()))
+            .block(Block::default().borders(Borders::ALL))
+            .style(Style::default().fg(Color::Cyan));
+        f.render_widget(title, chunks[0]);
+
+        let items: Vec<ListItem> = self
+            .hidden
+            .entries()
+            .map(|(url, job)| {
+                ListItem::new(vec![
+                    Line::from(vec![Span::styled(icons::JOB.as_str(), Style::default().fg(Color::Blue)), Span::raw(job.title.clone())]),
+                    Line::from(vec![
+                        Span::raw(format!("   {}", icons::COMPANY)),
+                        Span::styled(job.company.clone(), Style::default().fg(Color::Green)),
+                        Span::raw(format!("  hidden {}  {}", locale::current().date(job.hidden_at), url)),
+                    ]),
+                ])
+            })
+            .collect();
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(if self.hidden.is_empty() { "Nothing hidden" } else { "Hidden" }))
+            .highlight_style(Style::default().bg(Color::LightBlue).fg(Color::Black).add_modifier(Modifier::BOLD))
+            .highlight_symbol("→ ");
+        f.render_stateful_widget(list, chunks[1], &mut self.hidden_list_state);
+
+        let footer = match &self.notice {
+            Some(notice) => notice.clone(),
+            None => self.footer("↑/↓: Navigate | u: Unhide | U: Unhide All | b: Back to List | q: Quit"),
+        };
+        let controls = Paragraph::new(footer)
+            .block(Block::default().borders(Borders::ALL))
+            .style(Style::default().fg(Color::Gray));
+        f.render_widget(controls, chunks[2]);
+    }
+
+    fn render_application_complete(&mut self, f: &mut Frame) {
+        if let Some(index) = self.selected_job_index {
+            if let Some(job) = self.jobs.get(index) {
+                let chunks = Layout::default()
+                    .direction(Direction::Vertical)
+                    .margin(1)
+                    .constraints([
+                        Constraint::Length(3),
+                        Constraint::Min(0),
+                        Constraint::Length(3),
+                    ])
+                    .split(f.area());
+
+                let (heading, outcome) = self.application_outcome(job);
+                let color = match (&self.submission, &self.dry_run_result) {
+                    (Some(Err(_)), _) => Color::Red,
+                    (_, Some(_)) => Color::Yellow,
+                    _ => Color::Green,
+                };
+                let title = Paragraph::new(heading)
+                    .block(Block::default().borders(Borders::ALL))
+                    .style(Style::default().fg(color));
+                f.render_widget(title, chunks[0]);
+
+                let mut details = vec![
                     Line::from(""),
                     Line::from(vec![
-                        Span::styled("🚧 Phase 2 (Browser Automation) coming soon...", Style::default().fg(Color::Yellow)),
+                        Span::styled(icons::JOB.as_str(), Style::default().fg(Color::Blue)),
+                        Span::styled(&job.title, Style::default().add_modifier(Modifier::BOLD)),
                     ]),
                     Line::from(""),
-                    Line::from("For now, you can manually apply at:"),
                     Line::from(vec![
-                        Span::styled(&job.url, Style::default().fg(Color::Blue).add_modifier(Modifier::UNDERLINED)),
+                        Span::styled(icons::COMPANY.as_str(), Style::default().fg(Color::Green)),
+                        Span::raw(&job.company),
                     ]),
+                    Line::from(""),
                 ];
+                details.extend(outcome.into_iter().map(Line::from));
 
                 let details_paragraph = Paragraph::new(details)
                     .block(Block::default().borders(Borders::ALL))
-                    .wrap(ratatui::widgets::Wrap { trim: true });
+                    .wrap(ratatui::widgets::Wrap { trim: false });
                 f.render_widget(details_paragraph, chunks[1]);
 
                 // Controls
-                let controls = Paragraph::new("🎮 Press any key to continue...")
+                let browser_fallback = self.browser_result.is_none() && self.api_unavailable(job).is_some();
+                let controls = match (self.has_form_editor(), browser_fallback) {
+                    (true, true) => self.footer("o: Apply in Browser | f: Fix the Form | any other key: Continue"),
+                    (true, false) => self.footer("f: Fix the Form | any other key: Continue"),
+                    (false, true) => self.footer("o: Apply in Browser | any other key: Continue"),
+                    (false, false) => self.footer("Press any key to continue..."),
+                };
+                let controls = Paragraph::new(controls)
                     .block(Block::default().borders(Borders::ALL))
                     .style(Style::default().fg(Color::Gray));
                 f.render_widget(controls, chunks[2]);
@@ -365,32 +2050,95 @@ impl JobApplicationSystem {
         let backend = CrosstermBackend::new(io::stdout());
         let mut terminal = Terminal::new(backend)?;
 
-        let result = self.run_app(&mut terminal);
+        self.restore_session();
+        self.load_hidden();
+
+        // A panic puts the terminal back before its message is printed, then
+        // unwinds to here so the session is still saved
+        let previous_hook = std::sync::Arc::new(std::panic::take_hook());
+        let hook = previous_hook.clone();
+        std::panic::set_hook(Box::new(move |info| {
+            let _ = disable_raw_mode();
+            let _ = io::stdout().execute(LeaveAlternateScreen);
+            hook(info);
+        }));
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| self.run_app(&mut terminal)));
+        drop(std::panic::take_hook());
+        if let Ok(previous_hook) = std::sync::Arc::try_unwrap(previous_hook) {
+            std::panic::set_hook(previous_hook);
+        }
 
         // Cleanup
         disable_raw_mode()?;
         io::stdout().execute(LeaveAlternateScreen)?;
+        self.save_state();
 
-        result
+        result.unwrap_or_else(|panic| std::panic::resume_unwind(panic))
     }
 
     fn run_app(&mut self, terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<(), Box<dyn Error>> {
         if self.jobs.is_empty() {
-            println!("❌ No jobs available for application.");
+            println!("{}No jobs available for application.", icons::ERROR);
             return Ok(());
         }
 
         loop {
             terminal.draw(|f| self.render(f))?;
 
+            // Fetch after drawing so the loading state is visible, then redraw
+            if self.needs_application_form() {
+                self.load_application_form();
+                continue;
+            }
+
+            // Wake up periodically so the footer clock keeps ticking
+            if !event::poll(FOOTER_TICK)? {
+                continue;
+            }
             if let Event::Key(key) = event::read()? {
+                self.notice = None;
+                // Raw mode turns Ctrl-C into a key press; it quits from anywhere, like q
+                if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+                    return Ok(());
+                }
+                if self.prompt.is_some() {
+                    self.handle_prompt_key(key.code);
+                    continue;
+                }
+                if self.export_menu {
+                    self.handle_export_key(key.code);
+                    continue;
+                }
                 match self.current_view {
                     AppView::JobList => {
+                        let selected_visible = self.list_state.selected()
+                            .filter(|i| self.visible_job_indices().contains(i));
                         match key.code {
                             KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
                             KeyCode::Down => self.next(),
                             KeyCode::Up => self.previous(),
-                            KeyCode::Enter => self.select_current_job(),
+                            KeyCode::Char('}') => self.next_company(),
+                            KeyCode::Char('{') => self.previous_company(),
+                            KeyCode::Char('/') => self.open_prompt(PromptKind::Filter),
+                            KeyCode::Char('t') => {
+                                if let Some(index) = selected_visible {
+                                    self.open_prompt(PromptKind::Tags(index));
+                                }
+                            }
+                            KeyCode::Char('x') => {
+                                if let Some(index) = selected_visible {
+                                    self.hide_with_notice(index);
+                                }
+                            }
+                            KeyCode::Char('+') => {
+                                if let Some(index) = selected_visible {
+                                    self.queue_with_notice(index);
+                                }
+                            }
+                            KeyCode::Char('H') => self.open_hidden_view(),
+                            KeyCode::Char('e') => self.export_menu = true,
+                            KeyCode::Char('s') => self.toggle_fit_sort(),
+                            KeyCode::Enter if selected_visible.is_some() => self.select_current_job(),
                             _ => {}
                         }
                     }
@@ -398,15 +2146,80 @@ impl JobApplicationSystem {
                         match key.code {
                             KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
                             KeyCode::Char('b') => self.back_to_list(),
-                            KeyCode::Char('a') => self.confirm_application(),
+                            KeyCode::Char('a') => self.open_application_form(),
+                            KeyCode::Char('f') if self.offline_since.is_none() => self.preview_application_form(),
+                            KeyCode::Char(digit @ '1'..='5') => self.open_similar(digit as usize - '1' as usize),
+                            KeyCode::Char('r') => self.analyze_resume_fit(),
+                            KeyCode::Char('+') => {
+                                if let Some(index) = self.selected_job_index {
+                                    self.queue_with_notice(index);
+                                }
+                            }
+                            KeyCode::Char('x') => {
+                                if let Some(index) = self.selected_job_index {
+                                    self.hide_with_notice(index);
+                                    self.back_to_list();
+                                }
+                            }
+                            KeyCode::Char('t') => {
+                                if let Some(index) = self.selected_job_index {
+                                    self.open_prompt(PromptKind::Tags(index));
+                                }
+                            }
+                            KeyCode::Char('u') => {
+                                let record = self.selected_job_index.and_then(|index| self.status_record(&self.jobs[index]));
+                                if let Some(id) = record.map(|record| record.id) {
+                                    self.open_prompt(PromptKind::Status(id));
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                    AppView::HiddenJobs => {
+                        let position = self.hidden_list_state.selected();
+                        match key.code {
+                            KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                            KeyCode::Char('b') => self.back_to_list(),
+                            KeyCode::Down if !self.hidden.is_empty() => {
+                                self.hidden_list_state.select(Some(position.map_or(0, |p| (p + 1) % self.hidden.len())));
+                            }
+                            KeyCode::Up if !self.hidden.is_empty() => {
+                                let len = self.hidden.len();
+                                self.hidden_list_state.select(Some(position.map_or(0, |p| (p + len - 1) % len)));
+                            }
+                            KeyCode::Char('u') if position.is_some() => self.unhide(position),
+                            KeyCode::Char('U') => self.unhide(None),
+                            _ => {}
+                        }
+                    }
+                    AppView::FormPreview => {
+                        match key.code {
+                            KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                            KeyCode::Char('b') => self.select_current_job(),
+                            KeyCode::Char('a') => self.open_application_form(),
                             _ => {}
                         }
                     }
+                    AppView::ApplicationForm => self.handle_form_key(key),
                     AppView::ConfirmApplication => {
                         match key.code {
                             KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
-                            KeyCode::Char('y') => self.apply_to_job(),
+                            KeyCode::Char('y') => self.confirm_yes(),
+                            KeyCode::Char('m') => self.log_application(),
+                            KeyCode::Char('d') => self.dry_run = !self.dry_run,
+                            KeyCode::Char('p') => self.next_profile(),
+                            KeyCode::Char('o') if self.selected_api_unavailable() => self.apply_in_browser(),
                             KeyCode::Char('n') => self.back_to_list(),
+                            KeyCode::Char('b') => self.reopen_application_form(),
+                            KeyCode::Char('e') => {
+                                // The editor gets the terminal until it exits
+                                disable_raw_mode()?;
+                                io::stdout().execute(LeaveAlternateScreen)?;
+                                self.edit_cover_letter();
+                                enable_raw_mode()?;
+                                io::stdout().execute(EnterAlternateScreen)?;
+                                terminal.clear()?;
+                            }
                             _ => {}
                         }
                     }
@@ -414,6 +2227,10 @@ impl JobApplicationSystem {
                         // Any key to continue browsing or quit
                         match key.code {
                             KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                            KeyCode::Char('f') if self.has_form_editor() => self.reopen_application_form(),
+                            KeyCode::Char('o') if self.browser_result.is_none() && self.selected_api_unavailable() => {
+                                self.apply_in_browser()
+                            }
                             _ => self.back_to_list(),
                         }
                     }
@@ -423,331 +2240,1617 @@ impl JobApplicationSystem {
     }
 }
 
-struct GreenhouseJobSearcher {
-    client: reqwest::Client,
-    board_tokens: HashSet<String>,
+// A search as the command line runs it: the boards to search, fetched through
+// `api`, and everything around the fetching, from discovery and the job store
+// to what gets printed
+struct Searcher {
+    // Fetches and matches each board, and finds `board_tokens` from its token
+    // sources when the first search needs them
+    api: GreenhouseJobSearcher,
+    board_tokens: HashSet<String>,
+    // --discover-mode for the token sources
+    discover_mode: cli::DiscoverMode,
+    store: Option<store::JobStore>,
+    new_filter: Option<NewFilter>,
+    // Print one line per job instead of the multi-line listing
+    compact_output: bool,
+    group_by: Option<cli::GroupBy>,
+    // Lowercase board tokens from --favorites
+    favorites: HashSet<String>,
+    // Leave jobs from the application log out of the console output
+    ignore_applied: bool,
+    // --dedupe: results sharing any of these keys collapse to the first
+    dedupe: Vec<cli::DedupeKey>,
+    // Match keywords only as whole words rather than substrings
+    whole_word: bool,
+    // --normalize-unicode, folding keywords and titles before they're compared
+    normalization: Option<normalize::Normalization>,
+    // Replaces the built-in location matching when set
+    location_regex: Option<regex::Regex>,
+    // Section labels to pull out of each matching job's description
+    extract_labels: Vec<String>,
+    // Keep only jobs with a department containing this text
+    department: Option<String>,
+    // Save the full result set under runs/ and point the history entry at it
+    save_results: bool,
+    // How many saved runs to keep
+    keep_runs: usize,
+    // Keep only jobs carrying every one of these tags
+    tags: Vec<String>,
+    // Search only the boards in the retry queue
+    retry_queue_only: bool,
+    // Let the user choose among the discovered boards before the first search
+    pick_boards: bool,
+    // --redact companies: shared across cycles so a company keeps its label
+    redactor: Option<redact::Redactor>,
+    // --concurrency / --auto-concurrency; every board at once without one
+    limiter: Option<Arc<concurrency::Limiter>>,
+    // --post-concurrency, for the requests made after the search
+    post_limiter: Arc<concurrency::Limiter>,
+    // --verify-live and --resolve-urls, the passes over the matches after the search
+    verify_live: bool,
+    resolve_urls: bool,
+    // Share of the weighted keywords' total weight a title needs
+    min_relevance: f64,
+    // Early exit for broad searches; later-listed matches on a board are skipped
+    max_matches_per_board: Option<usize>,
+    // --max-boards: the discovered boards past the first N by token aren't searched
+    max_boards: Option<usize>,
+    // --max-requests, shared by discovery and every board's fetch
+    request_budget: Option<Arc<budget::RequestBudget>>,
+    // Print why jobs were excluded after the results
+    show_stats: bool,
+    // --pager, for a one-off search; never set for --watch or serve
+    pager: bool,
+    // Keep jobs marked "not interested" in the results
+    show_hidden: bool,
+    // Tallies from the most recent search
+    last_run: RunSummary,
+    // Tracked jobs the most recent search found missing from their boards
+    closed_tracked: Vec<JobResult>,
+    // The most recent search's matches before the new-only filter, for the
+    // dashboard, which tells the new ones apart by first_seen
+    cycle_matches: Vec<JobResult>,
+}
+
+// "1,200 by title, 340 by location, 50 by age", largest first
+fn exclusion_breakdown(excluded: &Exclusions) -> String {
+    excluded
+        .reasons()
+        .iter()
+        .map(|(count, reason)| format!("{} {}", locale::current().count(*count), reason))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[derive(Debug, Clone, Default)]
+struct RunSummary {
+    boards_searched: usize,
+    boards_succeeded: usize,
+    boards_not_found: usize,
+    boards_rate_limited: usize,
+    boards_failed: usize,
+    // Never fetched because --max-requests ran out
+    boards_skipped: usize,
+    jobs_scanned: usize,
+    // Why scanned jobs didn't make it into the results
+    excluded: Exclusions,
+    // Wall-clock time from the start of the search until every board answered
+    elapsed: Duration,
+}
+
+impl RunSummary {
+    fn record(&mut self, outcome: &BoardOutcome) {
+        match outcome.status {
+            BoardStatus::Searched => self.boards_succeeded += 1,
+            BoardStatus::NotFound => self.boards_not_found += 1,
+            BoardStatus::RateLimited => self.boards_rate_limited += 1,
+            BoardStatus::Failed => self.boards_failed += 1,
+        }
+        self.jobs_scanned += outcome.jobs_scanned;
+        self.excluded.add(&outcome.excluded);
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum NewFilter {
+    // Only jobs the store had never recorded before this run
+    Unseen,
+    // Jobs first recorded at or after this instant (unseen jobs count as now)
+    Since(chrono::DateTime<chrono::Utc>),
+}
+
+impl NewFilter {
+    fn keeps(&self, job: &JobResult) -> bool {
+        match (self, job.first_seen) {
+            (_, None) => true,
+            (NewFilter::Unseen, Some(_)) => false,
+            (NewFilter::Since(since), Some(first_seen)) => first_seen >= *since,
+        }
+    }
+}
+
+impl Searcher {
+    fn new(api: GreenhouseJobSearcher) -> Self {
+        Self {
+            api,
+            board_tokens: HashSet::new(),
+            discover_mode: cli::DiscoverMode::First,
+            store: None,
+            new_filter: None,
+            compact_output: false,
+            group_by: None,
+            favorites: HashSet::new(),
+            ignore_applied: false,
+            dedupe: Vec::new(),
+            whole_word: false,
+            normalization: None,
+            location_regex: None,
+            extract_labels: Vec::new(),
+            department: None,
+            save_results: false,
+            keep_runs: runs::DEFAULT_KEEP,
+            tags: Vec::new(),
+            retry_queue_only: false,
+            pick_boards: false,
+            redactor: None,
+            limiter: None,
+            post_limiter: concurrency::Limiter::fixed(1),
+            verify_live: false,
+            resolve_urls: false,
+            min_relevance: 0.5,
+            max_matches_per_board: None,
+            max_boards: None,
+            request_budget: None,
+            show_stats: false,
+            pager: false,
+            show_hidden: false,
+            last_run: RunSummary::default(),
+            closed_tracked: Vec::new(),
+            cycle_matches: Vec::new(),
+        }
+    }
+
+    // Run the discovery chain, stopping early in `first` mode once a source finds boards
+    async fn discover_board_tokens(&mut self) -> Result<(), Box<dyn Error>> {
+        let locale = locale::current();
+        let sources = self.api.token_sources().to_vec();
+        for (position, source) in sources.iter().enumerate() {
+            let found = match source {
+                TokenSource::Urls(urls) => {
+                    let mut found = HashSet::new();
+                    for url in urls {
+                        if !self.take_request() {
+                            println!("{}Not reading {}: --max-requests has run out", icons::WARNING, url);
+                            continue;
+                        }
+                        println!("{}Collecting board links from {}...", icons::WEB, url);
+                        match discovery::from_url(self.api.client(), url).await {
+                            Ok(tokens) => found.extend(tokens),
+                            Err(e) => println!("{}Couldn't read {}: {}", icons::WARNING, url, e),
+                        }
+                    }
+                    Ok(found)
+                }
+                TokenSource::Google if !self.take_request() => Err("--max-requests has run out".to_string()),
+                TokenSource::Google => {
+                    println!("{}Searching Google for Greenhouse board tokens...", icons::SEARCH);
+                    self.api.discover(source).await
+                }
+                TokenSource::File(_) | TokenSource::Builtin => self.api.discover(source).await,
+            };
+            let name = sources::source_name(source);
+            match found {
+                Ok(tokens) => {
+                    println!("{}Found {} board tokens from {}", icons::JOB, locale.count(tokens.len()), name);
+                    self.board_tokens.extend(tokens);
+                }
+                Err(e) => println!("{}{} discovery failed: {}", icons::WARNING, name, e),
+            }
+
+            if self.discover_mode == cli::DiscoverMode::First && !self.board_tokens.is_empty() {
+                break;
+            }
+            if self.board_tokens.is_empty() && position + 1 < sources.len() {
+                println!("{}No tokens found via {}. Trying the next source.", icons::WARNING, name);
+            }
+        }
+
+        println!("{}Total board tokens to search: {}", icons::JOB, locale.count(self.board_tokens.len()));
+        
+        // Print some of the tokens we'll be using
+        if !self.board_tokens.is_empty() {
+            println!("{}Sample board tokens: {:?}",
+                    icons::TARGET, self.board_tokens.iter().take(10).collect::<Vec<_>>());
+        }
+        
+        Ok(())
+    }
+
+    // Claims a request from --max-requests, if there is one; false means don't send it
+    fn take_request(&self) -> bool {
+        self.request_budget.as_ref().is_none_or(|budget| budget.take())
+    }
+
+    fn requests_exhausted(&self) -> bool {
+        self.request_budget.as_ref().is_some_and(|budget| budget.exhausted())
+    }
+
+    // What happened on a board, printed as its task finishes. Failed requests
+    // are only sampled, since a broad search runs into plenty of missing boards.
+    fn print_board_outcome(board_token: &str, outcome: &BoardOutcome) {
+        let board_token = outcome.renamed_to.as_deref().unwrap_or(board_token);
+        match &outcome.problem {
+            Some(problem @ (BoardProblem::Redirect(_) | BoardProblem::Api(_))) => {
+                println!("\n{}{}: {}", icons::WARNING, board_token, problem);
+            }
+            Some(BoardProblem::Status(status)) if *status == 404 && rand::random::<f32>() < 0.2 => {
+                println!("\n{}Debug: {} returned status {} (board doesn't exist)", icons::SEARCH, board_token, status);
+            }
+            Some(problem) if rand::random::<f32>() < 0.1 => println!("\n{}Debug: {} {}", icons::SEARCH, board_token, problem),
+            Some(_) => {}
+            None => {
+                // Always print successful API calls with job counts
+                let total_jobs = outcome.jobs_scanned + outcome.excluded.unscanned;
+                if total_jobs > 0 {
+                    println!("\n{}{}: {} jobs found", icons::DONE, board_token, locale::current().count(total_jobs));
+                }
+            }
+        }
+        for job in &outcome.matches {
+            println!("\n{}MATCH FOUND: '{}' at {} ({})", icons::MATCH, job.title, job.company, job.location);
+        }
+        if outcome.excluded.unscanned > 0 {
+            println!("{}{}: stopped after {} match(es), {} job(s) left unscanned", icons::CUT, board_token, outcome.matches.len(), outcome.excluded.unscanned);
+        }
+    }
+
+    fn print_pass_report(&self, report: &passes::PassReport, changed: &str) {
+        if report.changed > 0 {
+            println!("{}{} match(es) {}", icons::CHANGED, report.changed, changed);
+        }
+        if report.failed > 0 {
+            println!("{}{} match(es) couldn't be checked and were kept as they are", icons::WARNING, report.failed);
+        }
+        if report.out_of_requests > 0 {
+            println!("{}Not checking {} match(es): --max-requests has run out", icons::SKIPPED, report.out_of_requests);
+        }
+    }
+
+    // Write the run's results to their own file and prune old ones; failures only warn.
+    // Returns the file's name within runs/, which is what the history records
+    fn save_run(&self, keyword: &str, location: &str, board_count: usize, jobs: &[JobResult]) -> Option<std::path::PathBuf> {
+        let search = export::SearchParameters {
+            keyword: keyword.to_string(),
+            location: location.to_string(),
+            new_only: matches!(self.new_filter, Some(NewFilter::Unseen)),
+            since: match self.new_filter {
+                Some(NewFilter::Since(since)) => Some(since.with_timezone(&chrono::Local).date_naive()),
+                _ => None,
+            },
+        };
+        let path = match runs::save(&export::SearchExport::new(search, board_count, jobs.to_vec())) {
+            Ok(path) => path,
+            Err(e) => {
+                eprintln!("{}Failed to save this run's results: {}", icons::WARNING, e);
+                return None;
+            }
+        };
+        if let Err(e) = runs::prune(self.keep_runs) {
+            eprintln!("{}Failed to prune old saved runs: {}", icons::WARNING, e);
+        }
+        Some(path)
+    }
+
+    // Boards queued for a retry go first; boards still backing off sit this run out
+    fn boards_to_search(&self, retry_queue: &retry::RetryQueue) -> Vec<String> {
+        let now = chrono::Utc::now();
+        let mut eligible: Vec<&String> = Vec::new();
+        let mut backing_off = 0;
+        for board in &self.board_tokens {
+            match retry_queue.standing(board, now) {
+                retry::Standing::BackingOff => backing_off += 1,
+                _ => eligible.push(board),
+            }
+        }
+        if backing_off > 0 {
+            println!("{}Skipping {} board(s) still backing off after failing", icons::PAUSED, backing_off);
+        }
+        // The cap keeps the same boards from run to run, whatever order discovery found them in
+        eligible.sort();
+        if let Some(max) = self.max_boards.filter(|&max| max < eligible.len()) {
+            println!("{}Searching the first {} of {} board(s) by token (--max-boards)", icons::CUT, max, eligible.len());
+            eligible.truncate(max);
+        }
+        let (mut due, rest): (Vec<String>, Vec<String>) = eligible
+            .into_iter()
+            .cloned()
+            .partition(|board| retry_queue.standing(board, now) == retry::Standing::Due);
+        if !due.is_empty() {
+            println!("{}Retrying {} board(s) that failed last time first", icons::RETRY, due.len());
+        }
+        due.extend(rest);
+        due
+    }
+
+    // Main search function - now returns jobs for application interface
+    async fn search_jobs(&mut self, keyword: &str, location: &str) -> Result<Vec<JobResult>, Box<dyn Error>> {
+        let started = std::time::Instant::now();
+        println!("{}Starting job search...", icons::START);
+        if keyword.trim().is_empty() {
+            println!("{}Keyword: (none; every title matches)", icons::SEARCH);
+        } else {
+            println!("{}Keyword: {}", icons::SEARCH, keyword);
+        }
+        println!("{}Location: {}", icons::LOCATION, location);
+        println!();
+        if let Some(budget) = &self.request_budget {
+            budget.reset();
+        }
+        self.cycle_matches.clear();
+
+        let mut retry_queue = retry::RetryQueue::load();
+        let boards = if self.retry_queue_only {
+            if retry_queue.is_empty() {
+                println!("{}The retry queue is empty; nothing to search", icons::EMPTY);
+                return Ok(Vec::new());
+            }
+            retry_queue.boards().cloned().collect()
+        } else {
+            // First, find board tokens (kept across repeated searches, e.g. in watch mode)
+            if self.board_tokens.is_empty() {
+                self.discover_board_tokens().await?;
+                if self.pick_boards && !self.board_tokens.is_empty() {
+                    let picked = picker::pick_boards(self.board_tokens.iter().cloned().collect())?.unwrap_or_default();
+                    if picked.is_empty() {
+                        println!("{}No boards picked; nothing searched", icons::BYE);
+                        self.board_tokens.clear();
+                        return Ok(Vec::new());
+                    }
+                    println!("{}Searching {} of {} discovered board(s) you picked", icons::TARGET, picked.len(), self.board_tokens.len());
+                    self.board_tokens = picked.into_iter().collect();
+                }
+            }
+            self.boards_to_search(&retry_queue)
+        };
+
+        let total_boards = boards.len();
+        match &self.limiter {
+            Some(limiter) => println!(
+                "{}Searching jobs across {} companies, {} at a time{}...",
+                icons::SEARCHING, total_boards,
+                limiter.limit(),
+                if limiter.is_adaptive() { " to start" } else { "" }
+            ),
+            None => println!("{}Searching jobs across {} companies concurrently...", icons::SEARCHING, total_boards),
+        }
+
+        // Create concurrent tasks for all board tokens
+        let mut tasks = Vec::new();
+        let location = location.to_string();
+        let mut keywords = keywords::KeywordSpec::parse(keyword)?;
+        keywords.min_relevance = self.min_relevance;
+        if let Some(normalization) = self.normalization {
+            keywords.normalize(normalization);
+        }
+        let query = SearchQuery {
+            keywords,
+            whole_word: self.whole_word,
+            location: location.clone(),
+            location_regex: self.location_regex.clone(),
+            department: self.department.clone(),
+            max_matches: self.max_matches_per_board,
+        };
+
+        let mut skipped = Vec::new();
+        for board_token in boards {
+            // Claimed in board order, so boards due for a retry still go first
+            if !self.take_request() {
+                skipped.push(board_token);
+                continue;
+            }
+            let api = self.api.clone();
+            let query = query.clone();
+            let limiter = self.limiter.clone();
+
+            let task = tokio::spawn({
+                let board_token = board_token.clone();
+                async move {
+                    let slot = match &limiter {
+                        Some(limiter) => Some(limiter.acquire().await),
+                        None => None,
+                    };
+                    let outcome = api.search_board(&board_token, &query).await;
+                    Self::print_board_outcome(&board_token, &outcome);
+                    if let (Some(limiter), Some(slot)) = (&limiter, slot) {
+                        limiter.finish(slot, outcome.status == BoardStatus::RateLimited);
+                    }
+                    outcome
+                }
+            });
+            
+            tasks.push((board_token, task));
+        }
+
+        // Wait for all tasks to complete and collect results
+        let mut all_jobs = Vec::new();
+        let mut completed = 0;
+        let searched_boards = tasks.len();
+        let mut summary = RunSummary { boards_searched: searched_boards, ..RunSummary::default() };
+        
+        let now = chrono::Utc::now();
+        let mut given_up = Vec::new();
+        let mut renamed = Vec::new();
+        // A --board-query may narrow the listing, so only unfiltered listings can close tracked jobs
+        let mut listings = tracked::Listings::new();
+        let mut unlisted_boards = HashSet::new();
+        for (board_token, task) in tasks {
+            match task.await {
+                // Never listed, so it counts with the boards the budget left out
+                Ok(outcome) if outcome.problem == Some(BoardProblem::OutOfRequests) => {
+                    summary.boards_searched -= 1;
+                    skipped.push(board_token);
+                }
+                Ok(outcome) => {
+                    summary.record(&outcome);
+                    if let Some(new_token) = outcome.renamed_to {
+                        renamed.push((board_token.clone(), new_token));
+                    }
+                    if outcome.status != BoardStatus::Searched {
+                        unlisted_boards.insert(board_token.clone());
+                    } else if self.api.board_query.is_none() {
+                        listings.insert(board_token.clone(), outcome.listed_ids);
+                    }
+                    let error = match outcome.status {
+                        BoardStatus::Searched | BoardStatus::NotFound => None,
+                        BoardStatus::RateLimited => Some("rate limited"),
+                        BoardStatus::Failed => Some("failed"),
+                    };
+                    match error {
+                        None => retry_queue.record_success(&board_token),
+                        Some(error) => {
+                            if let Some(attempts) = retry_queue.record_failure(&board_token, error, now) {
+                                given_up.push((board_token, attempts));
+                            }
+                        }
+                    }
+                    all_jobs.extend(outcome.matches);
+                }
+                Err(e) => {
+                    unlisted_boards.insert(board_token);
+                    eprintln!("\n{}Task join error: {}", icons::WARNING, e);
+                }
+            }
+
+            // Reported once the board is in, so the match count includes it
+            completed += 1;
+            print!(
+                "\rProgress: {}/{} companies completed, {} match(es) so far",
+                completed,
+                searched_boards,
+                locale::current().count(all_jobs.len())
+            );
+            let _ = io::stdout().flush();
+        }
+
+        println!("\n");
+        if let (Some(budget), false) = (&self.request_budget, skipped.is_empty()) {
+            println!(
+                "{}Stopped at --max-requests {}: {} of {} board(s) weren't searched, so these results are incomplete",
+                icons::WARNING, budget.max(), skipped.len(), total_boards
+            );
+            // Skipped boards were never listed, so they can't close tracked jobs either
+            summary.boards_skipped = skipped.len();
+            unlisted_boards.extend(skipped);
+        }
+        for (board_token, attempts) in &given_up {
+            println!("{}Giving up on {} after {} failed attempts in a row", icons::GAVE_UP, board_token, attempts);
+        }
+        if !renamed.is_empty() {
+            renamed.sort();
+            println!("{}{} board(s) moved to a new token; update your boards file to skip the redirect:", icons::CHANGED, renamed.len());
+            for (old, new) in &renamed {
+                println!("   {} -> {}", old, new);
+            }
+        }
+        if let Err(e) = retry_queue.save() {
+            eprintln!("{}Failed to save the retry queue: {}", icons::WARNING, e);
+        }
+        summary.elapsed = started.elapsed();
+        if let Some(report) = self.limiter.as_ref().and_then(|limiter| limiter.report()) {
+            println!("{}Auto concurrency {}", icons::SETTINGS, report);
+        }
+        self.last_run = summary;
+        if let (Some(department), left_out @ 1..) = (&self.department, self.last_run.excluded.department) {
+            println!("{}Left out {} match(es) outside the \"{}\" department", icons::DEPARTMENT, left_out, department);
+        }
+        if !self.extract_labels.is_empty() {
+            for job in &mut all_jobs {
+                let content = job.raw.get("content").and_then(serde_json::Value::as_str).unwrap_or_default();
+                job.extras = extract::extract_fields(content, &self.extract_labels);
+            }
+        }
+        if self.verify_live && !all_jobs.is_empty() {
+            println!("{}Checking that {} match(es) are still live...", icons::SEARCH, all_jobs.len());
+            let report = passes::verify_live(&self.api, &mut all_jobs, &self.post_limiter, self.request_budget.as_deref()).await;
+            self.print_pass_report(&report, "taken down since the board was listed");
+        }
+        if self.resolve_urls && !all_jobs.is_empty() {
+            println!("{}Resolving {} match URL(s)...", icons::WEB, all_jobs.len());
+            let report = passes::resolve_urls(self.api.client(), &mut all_jobs, &self.post_limiter, self.request_budget.as_deref()).await;
+            self.print_pass_report(&report, "moved to a new URL");
+        }
+        for job in &mut all_jobs {
+            job.favorite = self.favorites.contains(&job.board_token.to_lowercase());
+        }
+        sort_results(&mut all_jobs);
+
+        // Look up what the store already knew before marking this run's matches as seen
+        let mut first_run = false;
+        let filters = history_filters(self);
+        let results_path = if self.save_results && paths::persistent() { self.save_run(keyword, &location, searched_boards, &all_jobs) } else { None };
+        if let Some(store) = &mut self.store {
+            first_run = store.search_count().unwrap_or(0) == 0;
+            if let Err(e) = store.annotate_first_seen(&mut all_jobs).and_then(|_| store.annotate_tags(&mut all_jobs)) {
+                eprintln!("{}Failed to read job store: {}", icons::WARNING, e);
+            }
+            let run = store::SearchRun {
+                keyword,
+                location: &location,
+                filters,
+                boards_searched: searched_boards,
+                boards_succeeded: self.last_run.boards_succeeded,
+                jobs_scanned: self.last_run.jobs_scanned,
+                duration: self.last_run.elapsed,
+                results_path: results_path.as_deref(),
+            };
+            if let Err(e) = store.record_search(&run, &all_jobs) {
+                eprintln!("{}Failed to update job store: {}", icons::WARNING, e);
+            }
+        }
+        self.check_tracked_jobs(listings, &unlisted_boards).await;
+
+        self.cycle_matches = all_jobs.clone();
+        if let Some(filter) = self.new_filter {
+            let total = all_jobs.len();
+            if self.store.is_none() {
+                println!("{}Job store unavailable, so every match is shown as new", icons::WARNING);
+            } else if first_run {
+                println!("{}First run: the job store was empty, so every match counts as new", icons::INFO);
+            }
+            all_jobs.retain(|job| filter.keeps(job));
+            self.last_run.excluded.age = total - all_jobs.len();
+            match filter {
+                NewFilter::Unseen => println!(
+                    "{}{} new of {} total",
+                    icons::NEW,
+                    locale::current().count(all_jobs.len()),
+                    locale::current().count(total)
+                ),
+                NewFilter::Since(since) => println!(
+                    "{}{} first seen since {} of {} total",
+                    icons::NEW,
+                    locale::current().count(all_jobs.len()),
+                    locale::current().date(since),
+                    locale::current().count(total)
+                ),
+            }
+            println!();
+        }
+
+        if !self.tags.is_empty() {
+            let before = all_jobs.len();
+            all_jobs.retain(|job| tags::has_all(job, &self.tags));
+            self.last_run.excluded.tag = before - all_jobs.len();
+            println!("{}{} of {} match(es) tagged {}\n", icons::TAGS, all_jobs.len(), before, self.tags.join(", "));
+        }
+
+        if !self.show_hidden {
+            let hidden = hidden::HiddenJobs::load();
+            let before = all_jobs.len();
+            all_jobs.retain(|job| !hidden.contains(&job.url));
+            self.last_run.excluded.hidden = before - all_jobs.len();
+            if before > all_jobs.len() {
+                println!("{}Left out {} job(s) you marked not interested (--show-hidden includes them)\n", icons::HIDDEN, before - all_jobs.len());
+            }
+        }
+
+        if !self.dedupe.is_empty() {
+            let before = all_jobs.len();
+            dedupe_results(&mut all_jobs, &self.dedupe);
+            self.last_run.excluded.duplicate = before - all_jobs.len();
+            if before > all_jobs.len() {
+                println!("{}Collapsed {} duplicate result(s)\n", icons::SIMILAR, before - all_jobs.len());
+            }
+        }
+
+        let applications = match &self.store {
+            Some(store) => store.applications(&store::ApplicationFilter::default()).unwrap_or_default(),
+            None => Vec::new(),
+        };
+        if self.ignore_applied {
+            let before = all_jobs.len();
+            all_jobs.retain(|job| !matches!(
+                store::find_prior_application(&applications, job),
+                Some(store::PriorApplication::Applied(_))
+            ));
+            self.last_run.excluded.applied = before - all_jobs.len();
+            if before > all_jobs.len() {
+                println!("{}Hid {} job(s) you've already applied to\n", icons::HIDDEN, before - all_jobs.len());
+            }
+        }
+
+        // Everything before this point (the job store, saved runs, the hidden
+        // list) needs the real names; everything after is output
+        if let Some(redactor) = &mut self.redactor {
+            redactor.apply(&mut all_jobs);
+            match paths::persistent().then(|| redactor.save_key()) {
+                None => println!("{}Company names redacted; the key isn't kept (persist = false in config.toml)\n", icons::REDACTED),
+                Some(Ok(path)) => println!("{}Company names redacted; the key is in {}\n", icons::REDACTED, path.display()),
+                Some(Err(e)) => eprintln!("{}Company names redacted, but saving the key failed: {}\n", icons::WARNING, e),
+            }
+        }
+        let paged = if self.pager { pager::capture() } else { None };
+        self.display_results(&all_jobs, &applications);
+        if self.show_stats {
+            self.print_exclusions();
+        }
+        if let Some(paged) = paged {
+            paged.finish();
+        }
+        Ok(all_jobs)
+    }
+
+    // --stats: which filter left out the most jobs, to show what to loosen
+    fn print_exclusions(&self) {
+        let excluded = &self.last_run.excluded;
+        let considered = self.last_run.jobs_scanned + excluded.unscanned;
+        if excluded.total() == 0 {
+            println!("{}No jobs were excluded ({} considered)", icons::EXCLUDED, locale::current().count(considered));
+        } else {
+            println!(
+                "{}Excluded {} of {} job(s): {}",
+                icons::EXCLUDED, locale::current().count(excluded.total()),
+                locale::current().count(considered),
+                exclusion_breakdown(excluded)
+            );
+        }
+    }
+
+    // Close tracked jobs that dropped off their boards, listing only the boards this search didn't cover
+    async fn check_tracked_jobs(&mut self, mut listings: tracked::Listings, unlisted_boards: &HashSet<String>) {
+        self.closed_tracked.clear();
+        let Some(store) = &self.store else {
+            return;
+        };
+        let tracked = match store.tracked_jobs() {
+            Ok(tracked) if tracked.is_empty() => return,
+            Ok(tracked) => tracked,
+            Err(e) => {
+                eprintln!("{}Failed to read tracked jobs: {}", icons::WARNING, e);
+                return;
+            }
+        };
+        // Keeps the latest text of postings that changed since they were last archived
+        for job in tracked.iter().filter(|job| job.closed_at.is_none()) {
+            if let Err(e) = store.archive_job(job) {
+                eprintln!("{}Failed to archive {} at {}: {}", icons::WARNING, job.title, job.company, e);
+                break;
+            }
+        }
+        if self.requests_exhausted() {
+            println!("{}Not checking the boards of tracked jobs: --max-requests has run out", icons::SKIPPED);
+        } else {
+            tracked::fetch_missing_listings(self.api.client(), &tracked, &mut listings, unlisted_boards, &self.api.credentials).await;
+        }
+
+        let Some(store) = &self.store else {
+            return;
+        };
+        match tracked::reconcile(store, tracked, &listings) {
+            Ok(outcome) => {
+                tracked::print_outcome(&outcome);
+                if !outcome.closed.is_empty() || !outcome.reopened.is_empty() || outcome.unverified > 0 {
+                    println!();
+                }
+                self.closed_tracked = outcome.closed;
+            }
+            Err(e) => eprintln!("{}Failed to update tracked jobs: {}", icons::WARNING, e),
+        }
+    }
+
+    fn display_results(&self, jobs: &[JobResult], applications: &[store::ApplicationRecord]) {
+        println!("{}SEARCH RESULTS", icons::RESULTS);
+        println!("=================");
+        
+        if jobs.is_empty() {
+            println!("{}No jobs found matching your criteria.", icons::ERROR);
+            return;
+        }
+
+        println!("{}Found {} matching job(s):\n", icons::DONE, locale::current().count(jobs.len()));
+
+        // Only truncate for a terminal; piped output keeps full lines for grepping
+        let width = if self.compact_output && io::stdout().is_terminal() {
+            crossterm::terminal::size().ok().map(|(cols, _)| cols as usize)
+        } else {
+            None
+        };
+
+        let Some(group_by) = self.group_by else {
+            for (i, job) in jobs.iter().enumerate() {
+                self.display_job(i + 1, job, applications, "", width);
+            }
+            return;
+        };
+        // Numbers keep counting across groups so they still name one job each
+        let mut number = 0;
+        for (name, group) in result_groups(jobs, group_by) {
+            let icon = match group_by {
+                cli::GroupBy::Company => icons::COMPANY,
+                cli::GroupBy::Department => icons::DEPARTMENT,
+            };
+            println!("{}{} ({})", icon, name, locale::current().count(group.len()));
+            for job in group {
+                number += 1;
+                self.display_job(number, job, applications, "   ", width);
+            }
+            if self.compact_output {
+                println!();
+            }
+        }
+    }
+
+    fn display_job(&self, number: usize, job: &JobResult, applications: &[store::ApplicationRecord], indent: &str, width: Option<usize>) {
+        if self.compact_output {
+            let width = width.map(|width| width.saturating_sub(indent.len()));
+            println!("{}{}", indent, compact_line(number, job, width));
+            return;
+        }
+
+        println!("{}{}. {}Job Title: {}", indent, number, icons::JOB, job.title);
+        println!("{}   {}Company: {}", indent, icons::COMPANY, job.company);
+        if job.favorite {
+            println!("{}   {}Favorite company", indent, icons::FAVORITE);
+        }
+        if let Some(relevance) = job.relevance {
+            println!("{}   {}Relevance: {:.0}%", indent, icons::RELEVANCE, relevance * 100.0);
+        }
+        if !job.departments.is_empty() {
+            println!("{}   {}Departments: {}", indent, icons::DEPARTMENT, job.departments.join(", "));
+        }
+        if !job.tags.is_empty() {
+            println!("{}   {}Tags: {}", indent, icons::TAGS, job.tags.join(", "));
+        }
+        if let Some(closed_at) = &job.closed_at {
+            println!("{}   {}Closed: no longer listed since {}", indent, icons::CLOSED, locale::current().timestamp_date(closed_at));
+        }
+        println!("{}   {}Date Posted: {}", indent, icons::CALENDAR, locale::current().timestamp_date_time(&job.date_posted));
+        println!("{}   {}URL: {}", indent, icons::LINK, job.url);
+        println!("{}   {}Job ID: {} ({})", indent, icons::ID, job.id, job.board_token);
+        match store::find_prior_application(applications, job) {
+            Some(store::PriorApplication::Applied(record)) => {
+                println!("{}   {}Already applied on {}", indent, icons::DONE, record.applied_date());
+            }
+            Some(store::PriorApplication::PossiblyApplied(record)) => {
+                println!("{}   {}Possibly applied before: \"{}\" on {}", indent, icons::MAYBE, record.title, record.applied_date());
+            }
+            Some(store::PriorApplication::FollowUp(record)) => {
+                println!("{}   {}Opened in the browser on {}; not marked done (#{})", indent, icons::WEB, record.applied_date(), record.id);
+            }
+            None => {}
+        }
+        println!();
+    }
+}
+
+// The results under each company or department name, names sorted without
+// regard to case and jobs kept in the order the search ranked them. Jobs in
+// several departments go under the first one Greenhouse lists. Favorite
+// companies come first, the same as in the flat list.
+fn result_groups(jobs: &[JobResult], group_by: cli::GroupBy) -> Vec<(String, Vec<&JobResult>)> {
+    let mut groups: Vec<(String, Vec<&JobResult>)> = Vec::new();
+    for job in jobs {
+        let name = match group_by {
+            cli::GroupBy::Company => job.company.clone(),
+            cli::GroupBy::Department => job.departments.first().cloned().unwrap_or_else(|| "No department".to_string()),
+        };
+        match groups.iter_mut().find(|(existing, _)| *existing == name) {
+            Some((_, group)) => group.push(job),
+            None => groups.push((name, vec![job])),
+        }
+    }
+    let company = matches!(group_by, cli::GroupBy::Company);
+    groups.sort_by_cached_key(|(name, group)| (!(company && group[0].favorite), name.to_lowercase()));
+    groups
+}
+
+// Workplace, seniority and freshness, bold on a colored background so they don't read as tags
+fn badge_chips(badges: &[badges::Badge]) -> Vec<Span<'static>> {
+    let mut chips = Vec::new();
+    for (i, badge) in badges.iter().enumerate() {
+        if i > 0 {
+            chips.push(Span::raw(" "));
+        }
+        chips.push(Span::styled(
+            format!(" {} ", badge.label),
+            Style::default().bg(badge.color).fg(Color::Black).add_modifier(Modifier::BOLD),
+        ));
+    }
+    chips
+}
+
+// Colored chips for a job's tags, separated by spaces
+fn tag_chips(tags: &[String]) -> Vec<Span<'static>> {
+    let mut chips = Vec::new();
+    for (i, tag) in tags.iter().enumerate() {
+        if i > 0 {
+            chips.push(Span::raw(" "));
+        }
+        chips.push(Span::styled(format!(" {} ", tag), Style::default().bg(tags::chip_color(tag)).fg(Color::Black)));
+    }
+    chips
+}
+
+// `N. [Company] Title — date — url`, cut to `width` characters when given
+// "3. Label (required)", then the board's note, the kind of answer it takes
+// and, with a profile, what it would be answered with
+fn push_question(lines: &mut Vec<String>, number: usize, question: &application::Question, filled: Option<&submit::FilledForm>) {
+    let required = if question.required { "required" } else { "optional" };
+    lines.push(String::new());
+    lines.push(format!("{}. {} ({})", number, question.label, required));
+    for note in question.description.iter().flat_map(|text| extract::plain_text(text)) {
+        lines.push(format!("   {}", note));
+    }
+    let fields = question.describe_fields();
+    if !fields.is_empty() {
+        lines.push(format!("   Answer with {}", fields));
+    }
+    let Some(filled) = filled else {
+        return;
+    };
+    let answers: Vec<String> = filled.answers_for(question).map(submit::describe).collect();
+    if !answers.is_empty() {
+        let source = match filled.source_for(question) {
+            Some(submit::AnswerSource::Override) => "From overrides.toml",
+            _ => "From your profile",
+        };
+        lines.push(format!("   {}{}: {}", icons::CHECK, source, answers.join(", ")));
+    } else if question.required {
+        lines.push(format!("   {}Unanswered (required)", icons::CROSS));
+    }
+}
+
+// Rough age of saved data: "40 minutes", "5 hours", "3 days"
+fn age(elapsed: chrono::Duration) -> String {
+    let (count, unit) = match elapsed {
+        e if e.num_days() > 0 => (e.num_days(), "day"),
+        e if e.num_hours() > 0 => (e.num_hours(), "hour"),
+        e => (e.num_minutes().max(0), "minute"),
+    };
+    format!("{} {}{}", count, unit, if count == 1 { "" } else { "s" })
+}
+
+fn compact_line(number: usize, job: &JobResult, width: Option<usize>) -> String {
+    let date = locale::current().timestamp_date(&job.date_posted);
+    let favorite = if job.favorite { ", favorite" } else { "" };
+    let line = format!("{}. [{}{}] {} — {} — {}", number, job.company, favorite, job.title, date, job.url);
+
+    match width {
+        Some(width) if width > 0 && line.chars().count() > width => {
+            let mut truncated: String = line.chars().take(width - 1).collect();
+            truncated.push('…');
+            truncated
+        }
+        _ => line,
+    }
+}
+
+// Keeps the first job for each key, so the sort order decides which copy stays
+fn dedupe_results(jobs: &mut Vec<JobResult>, keys: &[cli::DedupeKey]) {
+    let mut seen_urls = HashSet::new();
+    let mut seen_ids = HashSet::new();
+    jobs.retain(|job| {
+        let duplicate = keys.iter().any(|key| match key {
+            cli::DedupeKey::Url => seen_urls.contains(&job.url),
+            cli::DedupeKey::Id => seen_ids.contains(&job.id),
+        });
+        seen_urls.insert(job.url.clone());
+        seen_ids.insert(job.id);
+        !duplicate
+    });
 }
 
-impl GreenhouseJobSearcher {
-    fn new() -> Self {
-        let client = reqwest::Client::builder()
-            .timeout(Duration::from_secs(30))
-            .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36")
-            .build()
-            .expect("Failed to create HTTP client");
+fn start_of_local_day(date: chrono::NaiveDate) -> chrono::DateTime<chrono::Utc> {
+    let midnight = date.and_hms_opt(0, 0, 0).expect("midnight is a valid time");
+    midnight
+        .and_local_timezone(chrono::Local)
+        .earliest()
+        .map(|dt| dt.with_timezone(&chrono::Utc))
+        .unwrap_or_else(|| midnight.and_utc())
+}
 
-        Self {
-            client,
-            board_tokens: HashSet::new(),
-        }
+fn application_filter(args: &cli::ApplicationFilterArgs) -> store::ApplicationFilter {
+    store::ApplicationFilter {
+        since: args.since.map(start_of_local_day),
+        until: args.until.and_then(|d| d.succ_opt()).map(start_of_local_day),
+        company: args.company.clone(),
+        dry_runs: args.dry_runs,
     }
+}
 
-    // Method 1: Search Google for greenhouse board tokens (simplified approach)
-    async fn find_board_tokens_via_google(&mut self) -> Result<(), Box<dyn Error>> {
-        println!("🔍 Searching for Greenhouse board tokens...");
-        
-        // Google search query to find greenhouse boards
-        let search_query = "site:boards.greenhouse.io";
-        let google_url = format!("https://www.google.com/search?q={}&num=100", 
-                                urlencoding::encode(search_query));
-
-        match self.client.get(&google_url).send().await {
-            Ok(response) => {
-                let html = response.text().await?;
-                let document = Html::parse_document(&html);
-                let link_selector = Selector::parse("a[href*='boards.greenhouse.io']")
-                    .map_err(|_| "Failed to parse CSS selector")?;
-
-                for element in document.select(&link_selector) {
-                    if let Some(href) = element.value().attr("href") {
-                        if let Some(token) = self.extract_board_token(href) {
-                            self.board_tokens.insert(token);
-                        }
-                    }
-                }
-                
-                println!("📋 Found {} board tokens from Google search", self.board_tokens.len());
-                
-                // Print found tokens for debugging
-                if !self.board_tokens.is_empty() {
-                    println!("🔍 Board tokens from Google: {:?}", 
-                            self.board_tokens.iter().take(10).collect::<Vec<_>>());
-                }
-            }
-            Err(e) => {
-                println!("⚠️  Google search failed: {}. Using fallback method.", e);
-                self.use_known_board_tokens();
-            }
-        }
+// --favorites as lowercase board tokens, so they match however the boards file spells them
+fn favorite_boards(cli: &cli::Cli) -> Result<HashSet<String>, String> {
+    match &cli.favorites {
+        None => Ok(HashSet::new()),
+        Some(path) if path == std::path::Path::new("-") => Err("--favorites needs a file; stdin is only read for --boards -".to_string()),
+        Some(path) => Ok(discovery::from_file(path)?.into_iter().map(|token| token.to_lowercase()).collect()),
+    }
+}
 
-        // If Google search didn't find anything, use fallback
-        if self.board_tokens.is_empty() {
-            println!("⚠️  No tokens found via Google search. Using fallback method.");
-            self.use_known_board_tokens();
+// Searcher configured from the command-line flags, with the job store attached
+fn build_searcher(cli: &cli::Cli) -> Result<Searcher, String> {
+    let mut api = GreenhouseJobSearcher::builder()
+        .token_sources(sources::token_sources(cli)?)
+        .board_query(cli.board_query.clone())
+        .credentials(credentials::board_credentials(&config::Config::load()))
+        .lite(cli.lite);
+    if let Some(limit) = cli.concurrency {
+        api = api.concurrency(limit.get());
+    }
+    // Shared with the searcher, which claims the requests it makes past each board's listing
+    let request_budget = cli.max_requests.map(|max| budget::RequestBudget::new(max.get()));
+    if let Some(budget) = &request_budget {
+        api = api.request_budget(budget.clone());
+    }
+    let mut searcher = Searcher::new(api.build()?);
+    searcher.discover_mode = cli.discover_mode;
+    searcher.new_filter = match cli.since {
+        Some(date) => Some(NewFilter::Since(start_of_local_day(date))),
+        None if cli.new_only => Some(NewFilter::Unseen),
+        None => None,
+    };
+    if cli.pick_boards && cli.boards_from_stdin() {
+        return Err("--pick-boards can't be used with --boards -, which reads board tokens from stdin".to_string());
+    }
+    if cli.pick_boards && !(io::stdin().is_terminal() && io::stdout().is_terminal()) {
+        return Err("--pick-boards needs an interactive terminal".to_string());
+    }
+    searcher.pick_boards = cli.pick_boards;
+    searcher.limiter = match searcher.api.concurrency() {
+        Some(limit) => Some(concurrency::Limiter::fixed(limit)),
+        None if cli.auto_concurrency => Some(concurrency::Limiter::adaptive()),
+        None => None,
+    };
+    searcher.post_limiter = concurrency::Limiter::fixed(cli.post_concurrency.get());
+    searcher.verify_live = cli.verify_live;
+    searcher.resolve_urls = cli.resolve_urls;
+    if cli.redact.contains(&cli::RedactTarget::Companies) {
+        searcher.redactor = Some(redact::Redactor::default());
+    }
+    searcher.compact_output = cli.compact;
+    searcher.group_by = cli.group_by;
+    searcher.favorites = favorite_boards(cli)?;
+    searcher.ignore_applied = cli.ignore_applied;
+    searcher.dedupe = cli.dedupe.clone();
+    searcher.whole_word = cli.whole_word;
+    searcher.normalization = match (cli.normalize_unicode, cli.strip_diacritics) {
+        (_, true) => Some(normalize::Normalization::StripDiacritics),
+        (true, false) => Some(normalize::Normalization::Nfkc),
+        (false, false) => None,
+    };
+    let keywords = keywords::KeywordSpec::parse(cli.title_keyword()).map_err(|e| format!("--keyword: {}", e))?;
+    if !keywords.is_weighted() && cli.min_relevance.is_some() {
+        return Err("--min-relevance only applies to weighted keywords like \"principal:3 manager\"".to_string());
+    }
+    searcher.min_relevance = cli.min_relevance.map_or(0.5, |percent| f64::from(percent) / 100.0);
+    if cli.location_regex {
+        let pattern = regex::RegexBuilder::new(&cli.location)
+            .case_insensitive(true)
+            .build()
+            .map_err(|e| format!("--location \"{}\" is not a valid regex:\n{}", cli.location, e))?;
+        searcher.location_regex = Some(pattern);
+    }
+    searcher.extract_labels = cli.extract.clone();
+    searcher.department = cli.department.clone();
+    // Checked before the job store is opened, since that creates the data directory
+    searcher.save_results = cli.save_results || (!cli.no_save_results && paths::data_dir().is_dir());
+    searcher.keep_runs = cli.keep_runs.get();
+    searcher.retry_queue_only = cli.process_retry_queue;
+    searcher.max_matches_per_board = cli.max_matches_per_board.map(std::num::NonZeroUsize::get);
+    searcher.max_boards = cli.max_boards.map(std::num::NonZeroUsize::get);
+    searcher.request_budget = request_budget;
+    searcher.show_stats = cli.stats;
+    searcher.show_hidden = cli.show_hidden;
+    searcher.tags = cli.tag.iter().filter_map(|tag| tags::normalize(tag)).collect();
+    searcher.store = match store::JobStore::open(&paths::database_path()) {
+        Ok(store) => Some(store),
+        Err(e) => {
+            eprintln!("{}Job store unavailable ({}); results won't be recorded", icons::WARNING, e);
+            None
         }
+    };
+    Ok(searcher)
+}
 
-        println!("📋 Total board tokens to search: {}", self.board_tokens.len());
-        
-        // Print some of the tokens we'll be using
-        if !self.board_tokens.is_empty() {
-            println!("🎯 Sample board tokens: {:?}", 
-                    self.board_tokens.iter().take(10).collect::<Vec<_>>());
+// The options that narrowed a search, as recorded in the search history
+fn history_filters(searcher: &Searcher) -> serde_json::Value {
+    let mut filters = serde_json::Map::new();
+    if searcher.whole_word {
+        filters.insert("whole_word".into(), true.into());
+    }
+    match searcher.normalization {
+        Some(normalize::Normalization::Nfkc) => {
+            filters.insert("normalize_unicode".into(), true.into());
         }
-        
-        Ok(())
+        Some(normalize::Normalization::StripDiacritics) => {
+            filters.insert("normalize_unicode".into(), true.into());
+            filters.insert("strip_diacritics".into(), true.into());
+        }
+        None => {}
+    }
+    if let Some(pattern) = &searcher.location_regex {
+        filters.insert("location_regex".into(), pattern.as_str().into());
+    }
+    if let Some(department) = &searcher.department {
+        filters.insert("department".into(), department.as_str().into());
+    }
+    match searcher.new_filter {
+        Some(NewFilter::Unseen) => {
+            filters.insert("new_only".into(), true.into());
+        }
+        Some(NewFilter::Since(since)) => {
+            filters.insert("since".into(), since.to_rfc3339().into());
+        }
+        None => {}
+    }
+    if searcher.ignore_applied {
+        filters.insert("ignore_applied".into(), true.into());
+    }
+    if searcher.retry_queue_only {
+        filters.insert("retry_queue_only".into(), true.into());
+    }
+    if let Some(query) = &searcher.api.board_query {
+        filters.insert("board_query".into(), query.as_str().into());
+    }
+    if searcher.verify_live {
+        filters.insert("verify_live".into(), true.into());
+    }
+    if searcher.api.lite {
+        filters.insert("lite".into(), true.into());
     }
+    if searcher.show_hidden {
+        filters.insert("show_hidden".into(), true.into());
+    }
+    if !sources::is_default(searcher.api.token_sources(), searcher.discover_mode) {
+        let sources: Vec<&str> = searcher.api.token_sources().iter().map(sources::source_name).collect();
+        filters.insert("discover".into(), sources.into());
+        if searcher.discover_mode == cli::DiscoverMode::Union {
+            filters.insert("discover_mode".into(), "union".into());
+        }
+    }
+    if searcher.min_relevance != 0.5 {
+        filters.insert("min_relevance".into(), searcher.min_relevance.into());
+    }
+    if let Some(max) = searcher.max_matches_per_board {
+        filters.insert("max_matches_per_board".into(), max.into());
+    }
+    if let Some(max) = searcher.max_boards {
+        filters.insert("max_boards".into(), max.into());
+    }
+    if let Some(budget) = &searcher.request_budget {
+        filters.insert("max_requests".into(), budget.max().into());
+    }
+    if !searcher.dedupe.is_empty() {
+        let keys: Vec<&str> = searcher.dedupe.iter().map(|key| match key {
+            cli::DedupeKey::Url => "url",
+            cli::DedupeKey::Id => "id",
+        }).collect();
+        filters.insert("dedupe".into(), keys.into());
+    }
+    if !searcher.extract_labels.is_empty() {
+        filters.insert("extract".into(), searcher.extract_labels.clone().into());
+    }
+    if !searcher.tags.is_empty() {
+        filters.insert("tags".into(), searcher.tags.clone().into());
+    }
+    serde_json::Value::Object(filters)
+}
 
-    // Method 2: Use some known popular board tokens as fallback
-    fn use_known_board_tokens(&mut self) {
-        // More verified board tokens that are likely to work
-        let known_tokens = vec![
-            "stripe", "uber", "airbnb", "shopify", "atlassian", 
-            "mongodb", "snowflake", "databricks", "plaid", "twilio",
-            "coinbase", "square", "dropbox", "slack", "zoom",
-            "figma", "notion", "airtable", "zapier", "hubspot",
-            "asana", "gitlab", "newrelic", "datadog", "sendgrid",
-            // Add some more verified ones
-            "doordash", "instacart", "reddit", "discord", "spotify",
-            "pinterest", "robinhood", "lyft", "github", "palantir",
-        ];
+// The search as the exports describe it
+fn search_parameters(cli: &cli::Cli, searcher: &Searcher) -> export::SearchParameters {
+    export::SearchParameters {
+        keyword: cli.title_keyword().to_string(),
+        location: cli.location.clone(),
+        new_only: cli.new_only || searcher.new_filter.is_some(),
+        since: cli.since,
+    }
+}
 
-        println!("🔄 Adding {} known board tokens as fallback", known_tokens.len());
-        
-        for token in known_tokens {
-            self.board_tokens.insert(token.to_string());
+fn export_results(cli: &cli::Cli, searcher: &Searcher, jobs: &[JobResult]) {
+    if let Some(path) = &cli.feed {
+        match feed::update_feed(path, jobs, cli.feed_limit) {
+            Ok(entries) => println!("{}Updated {} ({} entries)", icons::FEED, path.display(), entries),
+            Err(e) => eprintln!("{}Failed to update feed: {}", icons::WARNING, e),
         }
-        
-        println!("✅ Fallback tokens added: {:?}", 
-                self.board_tokens.iter().take(10).collect::<Vec<_>>());
     }
-
-    // Extract board token from greenhouse URL
-    fn extract_board_token(&self, url: &str) -> Option<String> {
-        if url.contains("boards.greenhouse.io/") {
-            let parts: Vec<&str> = url.split("boards.greenhouse.io/").collect();
-            if parts.len() > 1 {
-                let token_part = parts[1].split('/').next()?;
-                if !token_part.is_empty() && token_part != "embed" {
-                    return Some(token_part.to_string());
-                }
-            }
+    if let Some(path) = &cli.ics_out {
+        match calendar::write_ics(path, jobs) {
+            Ok(events) => println!("{}Wrote {} application deadline(s) to {}", icons::CALENDAR, events, path.display()),
+            Err(e) => eprintln!("{}Failed to write {}: {}", icons::WARNING, path.display(), e),
         }
-        None
     }
 
-    // Static version for concurrent execution
-    async fn search_jobs_for_board_static(client: &reqwest::Client, board_token: &str, keyword: &str, location: &str) 
-        -> Result<Vec<JobResult>, String> {
-        
-        // Use content=true to get department information
-        let api_url = format!("https://boards-api.greenhouse.io/v1/boards/{}/jobs?content=true", board_token);
-        
-        let response = match client.get(&api_url).send().await {
-            Ok(resp) => {
-                if !resp.status().is_success() {
-                    // Print debug info for failed requests occasionally
-                    if resp.status() == 404 && rand::random::<f32>() < 0.2 { // 20% chance to print 404s
-                        println!("\n🔍 Debug: {} returned status {} (board doesn't exist)", board_token, resp.status());
-                    } else if rand::random::<f32>() < 0.1 {
-                        println!("\n🔍 Debug: {} returned status {}", board_token, resp.status());
-                    }
-                    return Ok(vec![]);
-                }
-                resp
-            },
+    if cli.json_out.is_none() && cli.export.is_none() {
+        return;
+    }
+    let export = export::SearchExport::new(search_parameters(cli, searcher), searcher.board_tokens.len(), jobs.to_vec());
+    let annotations = match (&searcher.store, cli.include_annotations) {
+        (Some(store), true) => match export::load_annotations(store, jobs) {
+            Ok(annotations) => Some(annotations),
             Err(e) => {
-                if rand::random::<f32>() < 0.1 { // 10% chance to print network errors
-                    println!("\n🔍 Debug: {} network error: {}", board_token, e);
-                }
-                return Ok(vec![]);
+                eprintln!("{}Failed to read annotations from the job store: {}", icons::WARNING, e);
+                None
             }
-        };
+        },
+        (None, true) => {
+            eprintln!("{}Job store unavailable, so exports won't include annotations", icons::WARNING);
+            None
+        }
+        (_, false) => None,
+    };
 
-        let jobs_response: JobsResponse = match response.json().await {
-            Ok(data) => data,
-            Err(e) => {
-                if rand::random::<f32>() < 0.1 { // 10% chance to print JSON errors
-                    println!("\n🔍 Debug: {} JSON parse error: {}", board_token, e);
+    if let Some(path) = &cli.json_out {
+        match export::write_json(path, &export, cli.bare, annotations.as_deref()) {
+            Ok(()) => println!("{}Exported {} job(s) to {}", icons::SAVED, jobs.len(), path.display()),
+            Err(e) => eprintln!("{}Failed to write {}: {}", icons::WARNING, path.display(), e),
+        }
+    }
+    if let Some(path) = &cli.export {
+        match export::write_export(path, &export, annotations.as_deref()) {
+            Ok(()) => println!("{}Exported {} job(s) to {}", icons::SAVED, jobs.len(), path.display()),
+            Err(e) => eprintln!("{}Failed to write {}: {}", icons::WARNING, path.display(), e),
+        }
+    }
+}
+
+// Runs after every search, including watch cycles with nothing new, so the
+// dashboard's history has a point per run
+fn update_dashboard(cli: &cli::Cli, jobs: &[JobResult]) {
+    let Some(dir) = &cli.dashboard else {
+        return;
+    };
+    match dashboard::update(dir, jobs) {
+        Ok(()) => println!("{}Dashboard updated: {}", icons::RESULTS, dir.join("index.html").display()),
+        Err(e) => eprintln!("{}Failed to update dashboard: {}", icons::WARNING, e),
+    }
+}
+
+fn run_command(cli: &cli::Cli, command: &cli::Command) -> Result<(), Box<dyn Error>> {
+    match command {
+        cli::Command::Db { action } => match action {
+            cli::DbCommand::Path => println!("{}", paths::database_path().display()),
+            cli::DbCommand::Stats => {
+                let store = store::JobStore::open(&paths::database_path())?;
+                store::print_stats(&store)?;
+            }
+        },
+        cli::Command::Paths => paths::print_paths(),
+        cli::Command::State { action } => match action {
+            cli::StateCommand::Export { bundle } => state::export(bundle)?,
+            cli::StateCommand::Import { bundle } => state::import(bundle)?,
+            cli::StateCommand::Wipe { applications, cache, annotations, yes } => state::wipe(*applications, *cache, *annotations, *yes)?,
+        },
+        cli::Command::History { limit, action } => {
+            let store = store::JobStore::open(&paths::database_path())?;
+            match action {
+                None => store::print_history(&store.search_history(*limit)?),
+                Some(cli::HistoryCommand::Show { id, browse }) => show_search(cli, store, *id, *browse)?,
+            }
+        }
+        cli::Command::Hidden { action } => {
+            let mut hidden = hidden::HiddenJobs::load();
+            match action {
+                cli::HiddenCommand::List => hidden::print_hidden(&hidden),
+                cli::HiddenCommand::Unhide { url } => {
+                    if !hidden.unhide(url) {
+                        return Err(format!("{} isn't hidden", url).into());
+                    }
+                    hidden.save()?;
+                    println!("{}{} will show up in searches again", icons::UNHIDDEN, url);
+                }
+                cli::HiddenCommand::UnhideAll => {
+                    let count = hidden.len();
+                    hidden.clear();
+                    hidden.save()?;
+                    println!("{}Unhid {} job(s)", icons::UNHIDDEN, count);
                 }
-                return Ok(vec![]);
             }
-        };
-
-        let mut matching_jobs = Vec::new();
-        let total_jobs = jobs_response.jobs.len();
-        
-        // Always print successful API calls with job counts
-        if total_jobs > 0 {
-            println!("\n✅ {}: {} jobs found", board_token, total_jobs);
         }
-        
-        for job in &jobs_response.jobs {
-            // More flexible keyword matching - split the search term
-            let keyword_lower = keyword.to_lowercase();
-            let keywords: Vec<&str> = keyword_lower.split_whitespace().collect();
-            let job_title_lower = job.title.to_lowercase();
-            
-            // Check if job title contains all keywords (more flexible than exact phrase)
-            let title_matches = keywords.iter().all(|&kw| {
-                job_title_lower.contains(kw) || 
-                // Also check for common variations
-                (kw == "principal" && (job_title_lower.contains("senior") || job_title_lower.contains("staff") || job_title_lower.contains("lead"))) ||
-                (kw == "product" && job_title_lower.contains("product")) ||
-                (kw == "manager" && (job_title_lower.contains("manager") || job_title_lower.contains("management")))
-            });
-            
-            // More flexible location matching
-            let job_location_lower = job.location.name.to_lowercase();
-            let location_matches = 
-                job_location_lower.contains(&location.to_lowercase()) ||
-                job_location_lower.contains("remote") ||
-                job_location_lower.contains("bay area") ||
-                job_location_lower.contains("san francisco") ||
-                job_location_lower.contains("california") ||
-                job_location_lower.contains("ca") ||
-                job_location_lower.contains("fremont") ||
-                job_location_lower.contains("silicon valley") ||
-                job_location_lower.contains("sf") ||
-                // Also include broader remote/hybrid options
-                job_location_lower.contains("anywhere") ||
-                job_location_lower.contains("us") ||
-                job_location_lower.contains("united states");
-            
-            // Print some examples for debugging (first few jobs from each company)
-            if matching_jobs.len() < 3 && rand::random::<f32>() < 0.3 {
-                println!("🔍 Checking: '{}' at '{}' (title_match: {}, location_match: {})", 
-                        job.title, job.location.name, title_matches, location_matches);
+        cli::Command::Stats { format } => {
+            let store = store::JobStore::open(&paths::database_path())?;
+            pipeline::run(&store, *format)?;
+        }
+        cli::Command::Archive { action } => match action {
+            cli::ArchiveCommand::Show { job_id, board } => {
+                let store = store::JobStore::open(&paths::database_path())?;
+                show_archived(&store, *job_id, board.as_deref())?;
             }
-            
-            if title_matches && location_matches {
-                // Try to get company name from departments or use board token
-                let company_name = if let Some(departments) = &job.departments {
-                    if !departments.is_empty() {
-                        departments[0].name.clone()
+        },
+        cli::Command::Queue { action } => queue::run(cli, action)?,
+        cli::Command::Apply { job_url, board, profile, dry_run, yes } => {
+            apply::run(cli, job_url, board.as_deref(), profile.as_deref(), *dry_run, *yes)?
+        }
+        cli::Command::Analyze { job_id, board, against } => analyze_job(cli, *job_id, board.as_deref(), against.as_deref())?,
+        cli::Command::Diff { old, new, json } => diff::run(old, new, *json)?,
+        cli::Command::Profile { action: cli::ProfileCommand::Init { force } } => profile::init(*force)?,
+        cli::Command::Browse { file, .. } => browse_saved(cli, file.as_deref())?,
+        cli::Command::Watch { .. } | cli::Command::CheckTracked | cli::Command::Serve { .. } | cli::Command::Doctor => {
+            unreachable!("watch, check-tracked, serve and doctor run on the async path in main")
+        }
+        cli::Command::Applications { action } => {
+            let store = store::JobStore::open(&paths::database_path())?;
+            match action {
+                cli::ApplicationsCommand::List { filter } => {
+                    store::print_applications(&store.applications(&application_filter(filter))?, &store.application_events()?);
+                }
+                cli::ApplicationsCommand::Export { filter, csv, output } => {
+                    let records = store.applications(&application_filter(filter))?;
+                    let text = if *csv {
+                        export::applications_csv(&records)
                     } else {
-                        // Capitalize board token
-                        board_token.chars().next().unwrap().to_uppercase().collect::<String>() + &board_token[1..]
+                        serde_json::to_string_pretty(&records)? + "\n"
+                    };
+                    match output {
+                        Some(path) => {
+                            std::fs::write(path, text)?;
+                            println!("{}Exported {} application(s) to {}", icons::SAVED, records.len(), path.display());
+                        }
+                        None => print!("{}", text),
                     }
-                } else {
-                    // Capitalize board token
-                    board_token.chars().next().unwrap().to_uppercase().collect::<String>() + &board_token[1..]
-                };
-
-                println!("\n🎉 MATCH FOUND: '{}' at {} ({})", job.title, company_name, job.location.name);
-
-                matching_jobs.push(JobResult {
-                    title: job.title.clone(),
-                    company: company_name,
-                    date_posted: job.updated_at.clone(),
-                    url: job.absolute_url.clone(),
-                });
+                }
+                cli::ApplicationsCommand::FollowUps => {
+                    let records = store.applications(&store::ApplicationFilter::default())?;
+                    let pending: Vec<store::ApplicationRecord> = store::pending_follow_ups(&records).into_iter().cloned().collect();
+                    if pending.is_empty() {
+                        println!("{}No follow-ups: every job opened in the browser is marked done", icons::EMPTY);
+                    } else {
+                        store::print_applications(&pending, &[]);
+                        println!("\nMark one done with `applications complete <id>` once you've sent it");
+                    }
+                }
+                cli::ApplicationsCommand::Complete { id } => match store.complete_follow_up(*id)? {
+                    Some(entry) => println!("{}Marked #{} done; logged as applied by hand (#{})", icons::DONE, id, entry),
+                    None => return Err(format!("#{} isn't a job opened in the browser; `applications follow-ups` lists them", id).into()),
+                },
+                cli::ApplicationsCommand::Update { id, status, note, follow_up, no_follow_up } => {
+                    let remind_on = if *no_follow_up { Some(None) } else { follow_up.map(Some) };
+                    if !store.update_application(*id, *status, note.as_deref(), remind_on)? {
+                        return Err(format!(
+                            "#{} isn't an application that went out; dry runs and jobs only opened in the browser have no status",
+                            id
+                        ).into());
+                    }
+                    let records = store.applications(&store::ApplicationFilter::default())?;
+                    if let Some(record) = records.iter().find(|record| record.id == *id) {
+                        println!("{}#{} {} — {}: {}", icons::DONE, id, record.company, record.title, record.status_line());
+                        match record.remind_on() {
+                            Some(day) => println!("{}Follow up on {}", icons::REMINDER, day.format(locale::current().date_pattern())),
+                            None if *no_follow_up => println!("{}No follow-up reminder", icons::REMINDER),
+                            None => {}
+                        }
+                    }
+                }
             }
         }
+    }
+    Ok(())
+}
 
-        Ok(matching_jobs)
+// `history show`: the run's parameters, then its saved results printed or browsed
+fn show_search(cli: &cli::Cli, store: store::JobStore, id: i64, browse: bool) -> Result<(), Box<dyn Error>> {
+    let entry = store.search_entry(id)?.ok_or_else(|| format!("no search with id {}", id))?;
+    println!("{}", store::history_line(&entry));
+    if entry.filters.as_object().is_some_and(|filters| !filters.is_empty()) {
+        println!("   filters: {}", entry.filters);
     }
+    println!();
 
+    let mut jobs: Vec<JobResult> = match (&entry.results_path, &entry.results_json) {
+        (Some(path), _) => runs::load(&runs::resolve(std::path::Path::new(path)))?.results,
+        (None, Some(results)) => serde_json::from_str(results)?,
+        (None, None) => {
+            println!("{}Results weren't saved for this run; search with --save-results to keep them", icons::INFO);
+            return Ok(());
+        }
+    };
+    // Tags and closures may have changed since the run, so take them from the store rather than the saved copy
+    store.annotate_tags(&mut jobs)?;
+    store.annotate_closed(&mut jobs)?;
+    let wanted: Vec<String> = cli.tag.iter().filter_map(|tag| tags::normalize(tag)).collect();
+    if !wanted.is_empty() {
+        let before = jobs.len();
+        jobs.retain(|job| tags::has_all(job, &wanted));
+        println!("{}{} of {} saved result(s) tagged {}\n", icons::TAGS, jobs.len(), before, wanted.join(", "));
+    }
+    let applications = store.applications(&store::ApplicationFilter::default()).unwrap_or_default();
 
-    // Main search function - now returns jobs for application interface
-    async fn search_jobs(&mut self, keyword: &str, location: &str) -> Result<Vec<JobResult>, Box<dyn Error>> {
-        println!("🚀 Starting job search...");
-        println!("🔍 Keyword: {}", keyword);
-        println!("📍 Location: {}", location);
-        println!();
+    if !browse {
+        let paged = if cli.pager { pager::capture() } else { None };
+        Searcher::new(GreenhouseJobSearcher::builder().build()?).display_results(&jobs, &applications);
+        if let Some(paged) = paged {
+            paged.finish();
+        }
+        return Ok(());
+    }
+    if jobs.is_empty() {
+        println!("{}This run found no jobs to browse.", icons::ERROR);
+        return Ok(());
+    }
+    let mut app_system = applying_system(cli, jobs);
+    let search = export::SearchParameters { keyword: entry.keyword.clone(), location: entry.location.clone(), new_only: false, since: None };
+    app_system.search = Some((search, entry.boards_searched as usize));
+    app_system.search_elapsed = entry.duration_ms.map(|ms| Duration::from_millis(ms as u64));
+    app_system.show_hidden = cli.show_hidden;
+    app_system.attach_store(store);
+    if cli.accessible {
+        app_system.run_accessible()
+    } else {
+        app_system.run()
+    }
+}
 
-        // First, find board tokens
-        self.find_board_tokens_via_google().await?;
+// A job browser over `jobs` that can apply, set up from config.toml and the flags
+fn applying_system(cli: &cli::Cli, jobs: Vec<JobResult>) -> JobApplicationSystem {
+    let config = config::Config::load();
+    // A search's client, so forms are fetched the same way whether or not a search ran first
+    let mut app_system = JobApplicationSystem::new(jobs, GreenhouseJobSearcher::new().client().clone());
+    app_system.show_clock = config.tui.clock;
+    app_system.export_dir = config.tui.export_dir.clone().unwrap_or_else(paths::exports_dir);
+    app_system.board_credentials = credentials::board_credentials(&config);
+    app_system.resumes = resume::Resumes::new(cli.resume.clone(), &config);
+    app_system.dry_run = cli.dry_run;
+    app_system.daily_limit = config.applications.daily_limit;
+    app_system.override_rate_limit = cli.override_rate_limit;
+    app_system.gh_src = config.applications.gh_src.clone();
+    app_system.phone_country = config.applications.phone_country.clone();
+    app_system.debug = cli.debug;
+    app_system.letter_settings = letter_settings(cli, &config);
+    app_system.profile_name = cli.profile_name.clone();
+    app_system
+}
 
-        let total_boards = self.board_tokens.len();
-        println!("🔄 Searching jobs across {} companies concurrently...", total_boards);
+// --letter-format over [cover_letter] in config.toml
+fn letter_settings(cli: &cli::Cli, config: &config::Config) -> config::CoverLetterConfig {
+    let mut settings = config.cover_letter.clone();
+    settings.format = cli.letter_format.unwrap_or(settings.format);
+    settings
+}
 
-        // Create concurrent tasks for all board tokens
-        let mut tasks = Vec::new();
-        let client = self.client.clone();
-        let keyword = keyword.to_string();
-        let location = location.to_string();
+// `browse`: replay a saved result set in the job browser without touching the network
+fn browse_saved(cli: &cli::Cli, file: Option<&std::path::Path>) -> Result<(), Box<dyn Error>> {
+    let path = match file {
+        Some(path) => path.to_path_buf(),
+        None => runs::latest().ok_or_else(|| {
+            format!("no saved runs in {}; searches save them unless --no-save-results is passed", paths::saved_runs_dir().display())
+        })?,
+    };
+    let export = runs::load(&path)?;
+    let mut jobs = export.results;
+    println!(
+        "{}{} job(s) for \"{}\" in {}, saved {} ({} ago)",
+        icons::OPEN_FOLDER, jobs.len(),
+        export.search.keyword,
+        export.search.location,
+        locale::current().timestamp_date_time(&export.exported_at.to_rfc3339()),
+        age(chrono::Utc::now() - export.exported_at)
+    );
+    if jobs.is_empty() {
+        println!("{}This file has no jobs to browse.", icons::ERROR);
+        return Ok(());
+    }
 
-        for board_token in self.board_tokens.iter() {
-            let client = client.clone();
-            let board_token = board_token.clone();
-            let keyword = keyword.clone();
-            let location = location.clone();
-
-            let task = tokio::spawn(async move {
-                // Add small delay to be respectful to the API
-                tokio::time::sleep(Duration::from_millis(rand::random::<u64>() % 200)).await;
-                
-                Self::search_jobs_for_board_static(&client, &board_token, &keyword, &location).await
-            });
-            
-            tasks.push(task);
+    // Tags, closures and applications come from the store, which may be newer than the file
+    let store = match store::JobStore::open(&paths::database_path()) {
+        Ok(store) => {
+            store.annotate_tags(&mut jobs)?;
+            store.annotate_closed(&mut jobs)?;
+            Some(store)
         }
-
-        // Wait for all tasks to complete and collect results
-        let mut all_jobs = Vec::new();
-        let mut completed = 0;
-        
-        for task in tasks {
-            completed += 1;
-            print!("\rProgress: {}/{} companies completed", completed, total_boards);
-            
-            match task.await {
-                Ok(Ok(jobs)) => {
-                    all_jobs.extend(jobs);
-                }
-                Ok(Err(e)) => {
-                    eprintln!("\n⚠️  Error in search task: {}", e);
-                }
-                Err(e) => {
-                    eprintln!("\n⚠️  Task join error: {}", e);
-                }
-            }
+        Err(e) => {
+            eprintln!("{}Job store unavailable, so tags and applications won't show: {}", icons::WARNING, e);
+            None
         }
+    };
+    let mut app_system = JobApplicationSystem::new(jobs, reqwest::Client::new());
+    let tui = config::Config::load().tui;
+    app_system.show_clock = tui.clock;
+    app_system.export_dir = tui.export_dir.unwrap_or_else(paths::exports_dir);
+    app_system.search = Some((export.search, export.board_count));
+    app_system.show_hidden = cli.show_hidden;
+    app_system.offline_since = Some(export.exported_at);
+    if let Some(store) = store {
+        app_system.attach_store(store);
+    }
+    if cli.accessible {
+        app_system.run_accessible()
+    } else {
+        app_system.run()
+    }
+}
 
-        println!("\n");
-        self.display_results(&all_jobs);
-        Ok(all_jobs)
+fn show_archived(store: &store::JobStore, job_id: u64, board: Option<&str>) -> Result<(), Box<dyn Error>> {
+    let snapshots = store.archived_snapshots(job_id, board)?;
+    let Some(latest) = snapshots.first() else {
+        return Err(format!("no archived posting for job {}; postings are archived when you tag or apply to a job", job_id).into());
+    };
+    let boards: BTreeSet<&str> = snapshots.iter().map(|s| s.board_token.as_str()).collect();
+    if boards.len() > 1 {
+        let boards: Vec<&str> = boards.into_iter().collect();
+        return Err(format!("job {} is archived for several boards ({}); pick one with --board", job_id, boards.join(", ")).into());
     }
 
-    fn display_results(&self, jobs: &Vec<JobResult>) {
-        println!("📊 SEARCH RESULTS");
-        println!("=================");
-        
-        if jobs.is_empty() {
-            println!("❌ No jobs found matching your criteria.");
-            return;
-        }
+    println!(
+        "{}{} job {}, archived {} ({} snapshot(s))\n",
+        icons::ARCHIVE, latest.board_token,
+        latest.job_id,
+        locale::current().timestamp_date_time(&latest.archived_at),
+        snapshots.len()
+    );
+    println!("{}", serde_json::to_string_pretty(&latest.raw)?);
+    Ok(())
+}
 
-        println!("✅ Found {} matching job(s):\n", jobs.len());
+// The job store's record of a job given by --job-id and --board
+fn recorded_job(store: &store::JobStore, job_id: u64, board: Option<&str>) -> Result<JobResult, Box<dyn Error>> {
+    let mut jobs = store.jobs_with_id(job_id, board)?;
+    if jobs.len() > 1 {
+        let boards: Vec<&str> = jobs.iter().map(|job| job.board_token.as_str()).collect();
+        return Err(format!("job {} is on several boards ({}); pick one with --board", job_id, boards.join(", ")).into());
+    }
+    jobs.pop().ok_or_else(|| format!("job {} isn't in the job store; it's recorded when a search finds it", job_id).into())
+}
 
-        for (i, job) in jobs.iter().enumerate() {
-            println!("{}. 📋 Job Title: {}", i + 1, job.title);
-            println!("   🏢 Company: {}", job.company);
-            println!("   📅 Date Posted: {}", job.date_posted);
-            println!("   🔗 URL: {}", job.url);
-            println!();
+// `analyze`: the resume applying would send (or --against) compared with a
+// recorded job's description, weighted against the board's other postings
+fn analyze_job(cli: &cli::Cli, job_id: u64, board: Option<&str>, against: Option<&std::path::Path>) -> Result<(), Box<dyn Error>> {
+    let store = store::JobStore::open(&paths::database_path())?;
+    let job = recorded_job(&store, job_id, board)?;
+    let resume_path = match against {
+        Some(path) => path.to_path_buf(),
+        None => {
+            let profile = match profile::Profiles::load()? {
+                Some(profiles) => profiles.get(cli.profile_name.as_deref())?.clone(),
+                None => profile::Profile::default(),
+            };
+            resume::Resumes::new(cli.resume.clone(), &config::Config::load())
+                .profile_for(&job.board_token, &profile)
+                .resume
+                .ok_or("no resume to analyze; pass --against, or set resume in profile.toml")?
         }
+    };
+    let resume = fit::resume_text(&resume_path)?;
+    let description = fit::description(&job.raw)
+        .or_else(|| store.latest_archive(&job).ok().flatten().and_then(|archived| fit::description(&archived.raw)))
+        .ok_or_else(|| format!("no description was recorded for job {}", job_id))?;
+    let descriptions: Vec<String> = store.board_jobs(&job.board_token)?.iter().filter_map(|other| fit::description(&other.raw)).collect();
+    let analysis = fit::analyze(&resume, &description, &fit::Corpus::new(descriptions.iter().map(String::as_str)));
+    let fit = store::ResumeFit::new(&resume_path, &analysis);
+    store.save_resume_fit(&job, &fit)?;
+
+    println!("{}{} at {} ({} job {})", icons::JOB, job.title, job.company, job.board_token, job.id);
+    println!("{}Resume: {}, weighed against {} posting(s) on the board\n", icons::ATTACHMENT, fit.resume, descriptions.len());
+    println!("{}Resume fit: {}%", icons::RELEVANCE, fit.percent());
+    if !analysis.matched.is_empty() {
+        println!("{}In your resume: {}", icons::CHECK, analysis.matched.join(", "));
+    }
+    if !analysis.missing.is_empty() {
+        println!("{}Missing: {}", icons::CROSS, analysis.missing.join(", "));
+    }
+    Ok(())
+}
+
+// companies.toml in the config directory adds or overrides display names for board tokens:
+//
+//   newrelic = "New Relic"
+//   acme-labs = "ACME Labs"
+fn load_company_names() {
+    let path = paths::companies_path();
+    let Ok(text) = std::fs::read_to_string(&path) else {
+        return;
+    };
+    match toml::from_str(&text) {
+        Ok(names) => companies::set_overrides(names),
+        Err(e) => eprintln!("{}Ignoring {}: {}", icons::WARNING, path.display(), e),
     }
 }
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn Error>> {
-    println!("🌱 Greenhouse Job Search & Application Tool");
+    let mut cli = cli::Cli::parse();
+    icons::init(cli.no_emoji);
+    locale::init(cli.locale.as_deref());
+    paths::init(cli.config_dir.as_deref());
+    paths::set_persistent(config::Config::load().storage.persist);
+    load_company_names();
+    // doctor reports these itself
+    let doctor = matches!(cli.command, Some(cli::Command::Doctor));
+    if !matches!(cli.command, Some(cli::Command::Profile { .. })) && !cli.quiet_notify && !doctor {
+        profile::check_at_startup();
+    }
+    if let Some(Err(e)) = cli.resume.as_deref().filter(|_| !doctor).map(profile::check_resume) {
+        return Err(format!("--resume: {}", e).into());
+    }
+    if let (Some(name), Ok(Some(profiles))) = (cli.profile_name.as_deref(), profile::Profiles::load()) {
+        if !profiles.names().contains(&name) {
+            return Err(format!("--profile-name: there's no profile named \"{}\"; profile.toml has {}", name, profiles.names().join(", ")).into());
+        }
+    }
+    if cli.override_rate_limit {
+        cli.override_rate_limit = quota::confirm_override(config::Config::load().applications.daily_limit)?;
+        if !cli.override_rate_limit {
+            eprintln!("{}Keeping the daily limit", icons::INFO);
+        }
+    }
+
+    match &cli.command {
+        Some(cli::Command::Watch { interval }) => return watch::run(&cli, *interval).await,
+        Some(cli::Command::CheckTracked) => return tracked::run(&cli).await,
+        Some(cli::Command::Doctor) => return doctor::run(&cli).await,
+        Some(cli::Command::Serve { port, bind, interval }) => {
+            return serve::run(&cli, std::net::SocketAddr::new(*bind, *port), *interval).await;
+        }
+        Some(command) => return run_command(&cli, command),
+        None => {}
+    }
+
+    if let Some(name) = &cli.set_credential {
+        return credentials::set_interactive(name);
+    }
+
+    if cli.notify_test {
+        notify::send_test(&cli, &config::Config::load().notifications, &reqwest::Client::new()).await;
+        return Ok(());
+    }
+
+    if cli.quiet_notify {
+        std::process::exit(quiet::run(&cli).await);
+    }
+
+    println!("{}Greenhouse Job Search & Application Tool", icons::APP);
     println!("==========================================\n");
 
-    let mut searcher = GreenhouseJobSearcher::new();
-    
-    // Search parameters
-    let keyword = "principal product manager";
-    let location = "94555"; // Fremont, CA area
+    let mut searcher = build_searcher(&cli).unwrap_or_else(|e| {
+        eprintln!("{}{}", icons::ERROR, e);
+        std::process::exit(2);
+    });
+    // --json-out is for scripts, which shouldn't have to quit a pager
+    searcher.pager = cli.pager && cli.json_out.is_none();
     
     // Phase 1: Search for jobs
-    let jobs = searcher.search_jobs(keyword, location).await?;
+    let jobs = searcher.search_jobs(cli.title_keyword(), &cli.location).await?;
+    export_results(&cli, &searcher, &jobs);
+    update_dashboard(&cli, &jobs);
+    if let Some(bucket) = cli.histogram {
+        histogram::print_histogram(&jobs, bucket);
+    }
+    if cli.summary {
+        summary::print_summary(&jobs);
+    }
+    if !cli.notify.is_empty() {
+        let notifications = notify::Notifications::new(&cli, &config::Config::load().notifications);
+        notifications.notify_new_matches(searcher.api.client(), &jobs).await;
+        notifications.notify_closed(searcher.api.client(), &searcher.closed_tracked).await;
+    }
     
     // Phase 1: Interactive job browser
     if !jobs.is_empty() {
-        println!("\n✅ SEARCH COMPLETE");
-        println!("Found {} matching jobs!", jobs.len());
+        println!("\n{}SEARCH COMPLETE", icons::DONE);
+        println!("Found {} matching jobs!", locale::current().count(jobs.len()));
+        if cli.boards_from_stdin() {
+            println!("{}Board tokens came from stdin, so there's no prompt for the interactive browser", icons::INFO);
+            return Ok(());
+        }
         
         print!("Enter interactive job browser? (y/n): ");
         io::stdout().flush().unwrap();
@@ -756,29 +3859,378 @@ async fn main() -> Result<(), Box<dyn Error>> {
         io::stdin().read_line(&mut input)?;
         
         if input.trim().to_lowercase().starts_with('y') {
-            let mut app_system = JobApplicationSystem::new(jobs);
+            let mut app_system = applying_system(&cli, jobs);
+            app_system.client = searcher.api.client().clone();
+            app_system.board_credentials = searcher.api.credentials.clone();
+            app_system.search = Some((search_parameters(&cli, &searcher), searcher.board_tokens.len()));
+            app_system.search_elapsed = Some(searcher.last_run.elapsed);
+            app_system.show_hidden = cli.show_hidden;
+            // Without the store, nothing gets recorded under a placeholder name
+            if searcher.redactor.is_some() {
+                println!("{}Browsing redacted results; applications and tags won't be saved", icons::REDACTED);
+            } else if let Some(store) = searcher.store.take() {
+                app_system.attach_store(store);
+            }
+            let result = if cli.accessible {
+                app_system.run_accessible()
+            } else {
+                app_system.run()
+            };
             
-            match app_system.run() {
-                Ok(_) => println!("\n✅ Job browser session completed!"),
-                Err(e) => println!("❌ Error in job browser: {}", e),
+            match result {
+                Ok(_) => println!("\n{}Job browser session completed!", icons::DONE),
+                Err(e) => println!("{}Error in job browser: {}", icons::ERROR, e),
             }
         } else {
-            println!("👋 Search completed. Use interactive browser next time to apply!");
+            println!("{}Search completed. Use interactive browser next time to apply!", icons::BYE);
         }
     } else {
-        println!("❌ No jobs found. Try different search criteria.");
+        println!("{}No jobs found. Try different search criteria.", icons::ERROR);
     }
     
     Ok(())
 }
 
-// Add these dependencies to Cargo.toml:
-/*
-[dependencies]
-reqwest = { version = "0.11", features = ["json"] }
-tokio = { version = "1.0", features = ["full"] }
-serde = { version = "1.0", features = ["derive"] }
-serde_json = "1.0"
-scraper = "0.18"
-urlencoding = "2.1"
-*/
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{ids, job, listing};
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn quiet_notify_runs_never_overlap() {
+        let path = std::env::temp_dir().join(format!("quiet_notify_{}.lock", std::process::id()));
+        let first = quiet::acquire_lock(&path).unwrap();
+        assert!(first.is_some());
+        // A second invocation finds the lock taken and must not crawl
+        assert!(quiet::acquire_lock(&path).unwrap().is_none());
+        drop(first);
+        assert!(quiet::acquire_lock(&path).unwrap().is_some());
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn age_filter_applies_to_the_pipeline_survivors() {
+        let jobs = vec![
+            listing(1, "Product Manager", "Remote", &[]),
+            listing(2, "Product Manager", "Remote", &[]),
+            listing(3, "Product Manager", "Remote", &[]),
+            listing(4, "Engineer", "Remote", &[]),
+        ];
+        let mut matches = search::filter_jobs("acme", &jobs, &SearchQuery::new("manager", "Remote").unwrap()).matches;
+        let since = chrono::Utc::now() - chrono::Duration::days(7);
+        // Seen long ago, seen yesterday, never seen before
+        matches[0].first_seen = Some(since - chrono::Duration::days(30));
+        matches[1].first_seen = Some(since + chrono::Duration::days(6));
+        matches.retain(|job| NewFilter::Since(since).keeps(job));
+        assert_eq!(ids(&matches), vec![2, 3]);
+        matches.retain(|job| NewFilter::Unseen.keeps(job));
+        assert_eq!(ids(&matches), vec![3]);
+    }
+
+    #[test]
+    fn favorites_come_first_and_each_part_keeps_the_usual_order() {
+        let favorite = |url: &str, date: &str| JobResult { favorite: true, board_token: "globex".to_string(), company: "Globex".to_string(), ..job(url, date) };
+        let mut jobs = vec![
+            job("https://example.com/newest", "2024-06-03T00:00:00Z"),
+            favorite("https://example.com/old-favorite", "2024-06-01T00:00:00Z"),
+            JobResult { relevance: Some(1.0), ..job("https://example.com/relevant", "2024-05-01T00:00:00Z") },
+            favorite("https://example.com/new-favorite", "2024-06-02T00:00:00Z"),
+        ];
+        sort_results(&mut jobs);
+        let urls: Vec<&str> = jobs.iter().map(|job| job.url.trim_start_matches("https://example.com/")).collect();
+        assert_eq!(urls, ["new-favorite", "old-favorite", "relevant", "newest"]);
+
+        // Grouped by company, the favorite's group leads even though Acme sorts first by name
+        let names: Vec<String> = result_groups(&jobs, cli::GroupBy::Company).into_iter().map(|(name, _)| name).collect();
+        assert_eq!(names, ["Globex", "Acme"]);
+        assert!(compact_line(1, &jobs[0], None).starts_with("1. [Globex, favorite] "));
+    }
+
+    #[test]
+    fn dedupe_keeps_the_first_job_per_key() {
+        let with_id = |url: &str, id: u64| JobResult { id, ..job(url, "") };
+        let jobs = vec![
+            with_id("https://example.com/a", 1),
+            // The same posting under a different URL format
+            with_id("https://acme.example.com/careers?gh_jid=1", 1),
+            with_id("https://example.com/a", 2),
+            with_id("https://example.com/b", 3),
+        ];
+        let kept = |keys: &[cli::DedupeKey]| {
+            let mut jobs = jobs.clone();
+            dedupe_results(&mut jobs, keys);
+            jobs.iter().map(|job| job.id).collect::<Vec<_>>()
+        };
+        assert_eq!(kept(&[cli::DedupeKey::Url]), vec![1, 1, 3]);
+        assert_eq!(kept(&[cli::DedupeKey::Id]), vec![1, 2, 3]);
+        assert_eq!(kept(&[cli::DedupeKey::Url, cli::DedupeKey::Id]), vec![1, 3]);
+    }
+
+    #[test]
+    fn max_boards_caps_by_token_after_leaving_out_backing_off_boards() {
+        let mut searcher = Searcher::new(GreenhouseJobSearcher::builder().build().unwrap());
+        searcher.board_tokens = ["delta", "alpha", "echo", "charlie", "bravo"].into_iter().map(String::from).collect();
+        searcher.max_boards = Some(3);
+        let now = chrono::Utc::now();
+        let mut retry_queue = retry::RetryQueue::default();
+        retry_queue.record_failure("alpha", "timed out", now);
+        retry_queue.record_failure("delta", "timed out", now - chrono::Duration::hours(2));
+        // alpha is still backing off, so the cap keeps bravo..delta; delta is due, so it goes first
+        assert_eq!(searcher.boards_to_search(&retry_queue), vec!["delta", "bravo", "charlie"]);
+        searcher.max_boards = None;
+        assert_eq!(searcher.boards_to_search(&retry_queue), vec!["delta", "bravo", "charlie", "echo"]);
+    }
+
+    #[test]
+    fn state_saved_on_exit_is_there_on_reload() {
+        let jobs = vec![job("https://example.com/1", ""), job("https://example.com/2", "")];
+        let path = format!("{}/tests/fixtures/application_forms/standard_fields.json", env!("CARGO_MANIFEST_DIR"));
+        let form: ApplicationForm = serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+        let mut editor = form_editor::FormEditor::new(&form, &submit::FilledForm::default(), None);
+        editor.restore(&BTreeMap::from([("first_name".to_string(), vec!["Jane".to_string()])]));
+
+        let mut app = JobApplicationSystem::new(jobs.clone(), reqwest::Client::new());
+        app.attach_store(store::JobStore::open(&paths::database_path()).unwrap());
+        app.list_state.select(Some(1));
+        app.form_editors.insert(jobs[1].id, editor);
+        app.save_state();
+
+        let mut reloaded = JobApplicationSystem::new(jobs.clone(), reqwest::Client::new());
+        reloaded.restore_session();
+        assert_eq!(reloaded.list_state.selected(), Some(1));
+        let draft = store::JobStore::open(&paths::database_path()).unwrap().draft(&jobs[1]).unwrap().expect("draft saved");
+        assert_eq!(draft.answers["first_name"], vec!["Jane".to_string()]);
+        let _ = std::fs::remove_file(paths::session_state_path());
+        let _ = std::fs::remove_file(paths::database_path());
+    }
+
+    #[test]
+    fn status_prompt_takes_a_leading_status_a_note_and_a_follow_up_day() {
+        let update = parse_status_update("Interview panel on Friday @2026-10-20").unwrap();
+        assert_eq!(update.status, Some(store::ApplicationStatus::Interview));
+        assert_eq!(update.note.as_deref(), Some("panel on Friday"));
+        assert_eq!(update.remind_on, Some(chrono::NaiveDate::from_ymd_opt(2026, 10, 20)));
+
+        // A status word later on is just part of the note
+        let update = parse_status_update("recruiter said rejected candidates hear back @-").unwrap();
+        assert_eq!(update.status, None);
+        assert_eq!(update.note.as_deref(), Some("recruiter said rejected candidates hear back"));
+        assert_eq!(update.remind_on, Some(None));
+
+        assert!(parse_status_update("offer @next-week").is_err());
+        assert!(parse_status_update("   ").is_err());
+    }
+
+    #[test]
+    fn a_submission_that_did_not_get_through_is_queued_as_it_was_sent() {
+        let path = format!("{}/tests/fixtures/application_forms/standard_fields.json", env!("CARGO_MANIFEST_DIR"));
+        let form: ApplicationForm = serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+        let sent = submit::SavedPayload {
+            answers: vec![
+                ("first_name".to_string(), submit::Answer::Text("Jane".to_string())),
+                ("resume".to_string(), submit::Answer::File("/home/jane/resume.pdf".into())),
+            ],
+            form,
+            profile: "work".to_string(),
+        };
+        let database = std::env::temp_dir().join(format!("queue_retry_{}.db", std::process::id()));
+        let store = store::JobStore::open(&database).unwrap();
+        store.queue_retry(&job("https://example.com/1", ""), "network error: timed out", &serde_json::to_string(&sent).unwrap()).unwrap();
+
+        let entry = store.application_queue().unwrap().remove(0);
+        assert_eq!(entry.status, store::QueueStatus::Failed);
+        assert_eq!(entry.reason.as_deref(), Some("network error: timed out"));
+        let saved: submit::SavedPayload = serde_json::from_str(entry.payload_json.as_deref().unwrap()).unwrap();
+        assert_eq!(submit::preview(&saved.filled()), vec!["first_name: Jane", "resume: resume.pdf (file)"]);
+        assert_eq!(saved.profile, "work");
+
+        // Kept through another failure, dropped once it goes through
+        store.set_queue_status(entry.id, store::QueueStatus::Failed, Some("Greenhouse rejected the application (503)")).unwrap();
+        assert!(store.application_queue().unwrap()[0].payload_json.is_some());
+        store.set_queue_status(entry.id, store::QueueStatus::Submitted, None).unwrap();
+        assert!(store.application_queue().unwrap()[0].payload_json.is_none());
+        drop(store);
+        std::fs::remove_file(&database).unwrap();
+    }
+
+    #[test]
+    fn wiping_applications_empties_the_log_and_keeps_it_append_only() {
+        let database = std::env::temp_dir().join(format!("wipe_applications_{}.db", std::process::id()));
+        let mut store = store::JobStore::open(&database).unwrap();
+        let applied = job("https://example.com/1", "");
+        store.record_application(&applied, store::ApplicationMethod::Browser, Some("Referred by Sam"), None, None, None).unwrap();
+        store.set_tags(&applied, &["remote".to_string()]).unwrap();
+
+        store.wipe_tables(&["application_events", "application_reminders", "application_drafts", "application_queue", "applications"]).unwrap();
+        assert_eq!(store.row_count("applications").unwrap(), 0);
+        assert_eq!(store.row_count("job_tags").unwrap(), 1);
+        // The log is append-only again once the wipe is done
+        store.record_application(&applied, store::ApplicationMethod::Browser, None, None, None, None).unwrap();
+        drop(store);
+        let conn = rusqlite::Connection::open(&database).unwrap();
+        assert!(conn.execute("DELETE FROM applications", []).is_err());
+        drop(conn);
+        std::fs::remove_file(&database).unwrap();
+    }
+
+    #[test]
+    fn export_menu_writes_only_the_jobs_the_list_shows() {
+        let mut designer = job("https://example.com/2", "");
+        designer.title = "Product Designer".to_string();
+        let jobs = vec![job("https://example.com/1", ""), designer, job("https://example.com/3", "")];
+        let mut app = JobApplicationSystem::new(jobs, reqwest::Client::new());
+        app.export_dir = std::env::temp_dir().join(format!("export_menu_{}", std::process::id()));
+        let third = app.jobs[2].clone();
+        app.hidden.hide(&third);
+        app.set_filter(Some("product".to_string()));
+
+        app.export_menu = true;
+        app.handle_export_key(KeyCode::Char('c'));
+        assert!(!app.export_menu);
+        let notice = app.notice.take().unwrap();
+        let path = notice.rsplit(" to ").next().unwrap();
+        assert!(path.ends_with(".csv"), "{}", notice);
+        let csv = std::fs::read_to_string(path).unwrap();
+        // The header, then the two jobs the filter and hiding leave, in list order
+        let urls: Vec<&str> = csv.lines().skip(1).map(|line| line.split(',').nth(6).unwrap()).collect();
+        assert_eq!(urls, vec!["https://example.com/1", "https://example.com/2"]);
+
+        // Esc closes the menu without writing anything
+        app.export_menu = true;
+        app.handle_export_key(KeyCode::Esc);
+        assert!(!app.export_menu && app.notice.is_none());
+        std::fs::remove_dir_all(&app.export_dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn verify_live_drops_gone_jobs_and_stops_at_the_budget() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        // Job 2 is gone; every other record is there
+        let server = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}/v1/boards", server.local_addr().unwrap());
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = server.accept().await {
+                let mut request = [0; 4096];
+                let read = socket.read(&mut request).await.unwrap_or(0);
+                let status = if String::from_utf8_lossy(&request[..read]).contains("/jobs/2 ") { "404 Not Found" } else { "200 OK" };
+                let response = format!("HTTP/1.1 {}\r\nContent-Length: 2\r\nConnection: close\r\n\r\n{{}}", status);
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+        let api = GreenhouseJobSearcher::builder().base_url(base_url).build().unwrap();
+        let mut jobs: Vec<JobResult> = (1..=4).map(|id| JobResult { id, ..job(&format!("https://example.com/jobs/{}", id), "") }).collect();
+        let budget = budget::RequestBudget::new(3);
+
+        let report = passes::verify_live(&api, &mut jobs, &concurrency::Limiter::fixed(2), Some(&budget)).await;
+        assert_eq!(report, passes::PassReport { changed: 1, failed: 0, out_of_requests: 1 });
+        // Job 4 was never checked, so it stays
+        assert_eq!(ids(&jobs), vec![1, 3, 4]);
+    }
+
+    #[test]
+    fn grouped_results_are_sorted_by_name_and_keep_the_search_order() {
+        let mut jobs = vec![job("https://example.com/1", ""), job("https://example.com/2", ""), job("https://example.com/3", "")];
+        jobs[0].company = "figma".to_string();
+        jobs[0].departments = vec!["Design".to_string(), "Product".to_string()];
+        jobs[1].departments = vec!["Engineering".to_string()];
+        jobs[2].company = "figma".to_string();
+
+        let urls = |groups: Vec<(String, Vec<&JobResult>)>| -> Vec<(String, Vec<String>)> {
+            groups.into_iter().map(|(name, jobs)| (name, jobs.iter().map(|job| job.url.clone()).collect())).collect()
+        };
+        assert_eq!(urls(result_groups(&jobs, cli::GroupBy::Company)), vec![
+            ("Acme".to_string(), vec!["https://example.com/2".to_string()]),
+            ("figma".to_string(), vec!["https://example.com/1".to_string(), "https://example.com/3".to_string()]),
+        ]);
+        // Jobs in several departments go under the first; those in none get their own group
+        let departments: Vec<String> = result_groups(&jobs, cli::GroupBy::Department).into_iter().map(|(name, _)| name).collect();
+        assert_eq!(departments, vec!["Design", "Engineering", "No department"]);
+    }
+
+    // A browser over two fixture jobs, without the footer clock so the controls line is stable
+    fn tui_fixture() -> JobApplicationSystem {
+        let mut jobs = vec![job("https://example.com/1", "2024-06-01T12:00:00Z"), job("https://example.com/2", "2024-06-02T12:00:00Z")];
+        jobs[0].id = 1;
+        jobs[1].id = 2;
+        jobs[1].title = "Staff Software Engineer".to_string();
+        jobs[1].company = "Globex".to_string();
+        jobs[1].favorite = true;
+        let mut app = JobApplicationSystem::new(jobs, reqwest::Client::new());
+        app.show_clock = false;
+        app
+    }
+
+    // The rows of a 160x40 screen drawn by `render`
+    fn rendered(app: &mut JobApplicationSystem, render: fn(&mut JobApplicationSystem, &mut Frame)) -> Vec<String> {
+        let mut terminal = Terminal::new(ratatui::backend::TestBackend::new(160, 40)).unwrap();
+        terminal.draw(|f| render(app, f)).unwrap();
+        let buffer = terminal.backend().buffer();
+        (0..buffer.area.height)
+            .map(|y| (0..buffer.area.width).map(|x| buffer[(x, y)].symbol()).collect::<String>())
+            .collect()
+    }
+
+    fn has(rows: &[String], text: &str) -> bool {
+        rows.iter().any(|row| row.contains(text))
+    }
+
+    #[test]
+    fn the_job_list_renders_a_title_every_job_and_the_controls() {
+        let mut app = tui_fixture();
+        let rows = rendered(&mut app, JobApplicationSystem::render_job_list);
+        // Inside the margin and the title block's border
+        assert!(rows[2].contains("JOB BROWSER - Interactive Mode"), "{:#?}", rows);
+        assert!(has(&rows, "Jobs"));
+        assert!(has(&rows, "→ ") && has(&rows, "Product Manager"));
+        assert!(has(&rows, "Staff Software Engineer") && has(&rows, "Globex") && has(&rows, "FAVORITE"));
+        assert!(rows[rows.len() - 3].contains("↑/↓: Navigate | {/}: Prev/Next Company | Enter: View Details"), "{:#?}", rows);
+
+        app.filter = Some("globex".to_string());
+        let rows = rendered(&mut app, JobApplicationSystem::render_job_list);
+        assert!(has(&rows, "Jobs (1 of 2 match \"globex\")"));
+        assert!(!has(&rows, "Product Manager"));
+    }
+
+    #[test]
+    fn the_details_view_renders_the_selected_job_and_its_controls() {
+        let mut app = tui_fixture();
+        app.selected_job_index = Some(1);
+        app.current_view = AppView::JobDetails;
+        let rows = rendered(&mut app, JobApplicationSystem::render_job_details);
+        assert!(rows[2].contains("JOB DETAILS"), "{:#?}", rows);
+        assert!(has(&rows, "Title: Staff Software Engineer"));
+        assert!(has(&rows, "Company: Globex"));
+        assert!(has(&rows, "URL: https://example.com/2"));
+        assert!(has(&rows, "Job ID: 2 on board acme"));
+        assert!(has(&rows, "Loading application form..."));
+        assert!(rows[rows.len() - 3].contains("a: Apply | +: Queue | f: Form Questions"), "{:#?}", rows);
+        assert!(rows[rows.len() - 3].contains("b: Back to List | q: Quit"));
+    }
+
+    #[test]
+    fn the_confirm_and_complete_views_render_the_job_and_their_controls() {
+        let mut app = tui_fixture();
+        app.selected_job_index = Some(0);
+        app.current_view = AppView::ConfirmApplication;
+        let rows = rendered(&mut app, JobApplicationSystem::render_confirm_application);
+        assert!(rows[2].contains("CONFIRM APPLICATION") && !rows[2].contains("DRY RUN"), "{:#?}", rows);
+        assert!(has(&rows, "Product Manager") && has(&rows, "Acme") && has(&rows, "https://example.com/1"));
+        assert!(has(&rows, "Submit this application to Greenhouse?"));
+        assert!(rows[rows.len() - 3].contains("y: Yes, Submit | d: Dry Run"), "{:#?}", rows);
+        assert!(rows[rows.len() - 3].contains("n: No, Go Back"));
+
+        app.dry_run = true;
+        let rows = rendered(&mut app, JobApplicationSystem::render_confirm_application);
+        assert!(rows[2].contains("CONFIRM APPLICATION (DRY RUN)"));
+        assert!(rows[rows.len() - 3].contains("y: Yes, Dry Run | d: Dry Run Off"));
+
+        app.dry_run = false;
+        app.current_view = AppView::ApplicationComplete;
+        app.status_message = Some("Logged in the application log".to_string());
+        let rows = rendered(&mut app, JobApplicationSystem::render_application_complete);
+        assert!(rows[2].contains("LOGGED AS APPLIED"), "{:#?}", rows);
+        assert!(has(&rows, "Product Manager") && has(&rows, "Logged in the application log"));
+        assert!(rows[rows.len() - 3].contains("Press any key to continue..."));
+    }
+}
@@ -0,0 +1,126 @@
+//! Matching one board's jobs against a search: the title against the
+//! keywords, the location against the query's and the departments against its
+//! department. [`BoardMatcher`] does the lowercasing once per board rather than
+//! once per job, which is what keeps a listing of tens of thousands of jobs
+//! cheap; the `matching` bench measures it. The location regex and the filter
+//! pipeline around this are in [`filter_jobs`](crate::search::filter_jobs).
+
+use crate::keywords::KeywordSpec;
+
+// Locations accepted whatever the query's location says
+pub const LOOSE_LOCATIONS: &[&str] = &[
+    "remote", "bay area", "san francisco", "california", "ca", "fremont", "silicon valley", "sf",
+    // Also include broader remote/hybrid options
+    "anywhere", "us", "united states",
+];
+
+// Every keyword must appear in the title, either directly or via a common variation.
+// An empty keyword (or --all-titles) skips title matching and accepts every title.
+// Weighted keywords only need --min-relevance of their weight to appear. The
+// title comes folded with `KeywordSpec::fold_title`.
+pub fn folded_title_matches(title: &str, keywords: &KeywordSpec, whole_word: bool) -> bool {
+    if keywords.is_empty() {
+        return true;
+    }
+    if keywords.is_weighted() {
+        // Leeway for weights like 0.1 that don't add up exactly
+        return folded_title_relevance(title, keywords, whole_word) >= keywords.min_relevance - 1e-9;
+    }
+    let has = title_has_term(title, whole_word);
+    keywords.terms().iter().all(|term| has(&term.text))
+}
+
+// Share of the keywords' total weight found in the folded title
+pub fn folded_title_relevance(title: &str, keywords: &KeywordSpec, whole_word: bool) -> f64 {
+    keywords.relevance(title_has_term(title, whole_word))
+}
+
+// Whether a lowercase keyword term is in the folded title, directly or via a common variation
+fn title_has_term(job_title_lower: &str, whole_word: bool) -> impl Fn(&str) -> bool + '_ {
+    move |kw| {
+        let has = |term: &str| {
+            if whole_word {
+                contains_word(job_title_lower, term)
+            } else {
+                job_title_lower.contains(term)
+            }
+        };
+        has(kw) ||
+        (kw == "principal" && (has("senior") || has("staff") || has("lead"))) ||
+        (kw == "manager" && has("management"))
+    }
+}
+
+// What each of a board's jobs is checked against, lowercased once per board
+// rather than once per job. Titles are folded into one buffer in turn, so
+// matching a listing of tens of thousands of jobs allocates only for the ones
+// that match (and for titles and locations that aren't ASCII).
+pub struct BoardMatcher<'a> {
+    keywords: &'a KeywordSpec,
+    whole_word: bool,
+    location: String,
+    department: Option<String>,
+    // The title `title_matches` last looked at, folded
+    title: String,
+}
+
+impl<'a> BoardMatcher<'a> {
+    pub fn new(keywords: &'a KeywordSpec, whole_word: bool, location: &str, department: Option<&str>) -> Self {
+        BoardMatcher {
+            keywords,
+            whole_word,
+            location: location.to_lowercase(),
+            department: department.map(str::to_lowercase),
+            title: String::new(),
+        }
+    }
+
+    pub fn title_matches(&mut self, title: &str) -> bool {
+        if self.keywords.is_empty() {
+            return true;
+        }
+        self.keywords.fold_title_into(title, &mut self.title);
+        folded_title_matches(&self.title, self.keywords, self.whole_word)
+    }
+
+    // For the title `title_matches` last accepted, with weighted keywords
+    pub fn relevance(&self) -> Option<f64> {
+        self.keywords.is_weighted().then(|| folded_title_relevance(&self.title, self.keywords, self.whole_word))
+    }
+
+    // More flexible location matching: the --location or any of the loose ones
+    pub fn location_matches(&self, location: &str) -> bool {
+        if !location.is_ascii() {
+            let lower = location.to_lowercase();
+            return lower.contains(&self.location) || LOOSE_LOCATIONS.iter().any(|loose| lower.contains(loose));
+        }
+        contains_ignore_ascii_case(location, &self.location) ||
+            LOOSE_LOCATIONS.iter().any(|loose| contains_ignore_ascii_case(location, loose))
+    }
+
+    // A job matches when any of its departments contains the query, ignoring case
+    pub fn department_matches(&self, departments: &[String]) -> bool {
+        let Some(query) = &self.department else {
+            return true;
+        };
+        departments.iter().any(|department| department.to_lowercase().contains(query.as_str()))
+    }
+}
+
+// `text` contains the lowercase `needle`, comparing ASCII letters without case
+fn contains_ignore_ascii_case(text: &str, needle: &str) -> bool {
+    needle.is_empty() ||
+        text.as_bytes().windows(needle.len()).any(|window| window.eq_ignore_ascii_case(needle.as_bytes()))
+}
+
+// True when `word` occurs in `text` with no alphanumeric character on either side
+fn contains_word(text: &str, word: &str) -> bool {
+    if word.is_empty() {
+        return false;
+    }
+    text.match_indices(word).any(|(start, _)| {
+        let before = text[..start].chars().next_back();
+        let after = text[start + word.len()..].chars().next();
+        !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+    })
+}
@@ -0,0 +1,63 @@
+//! Unicode folding: keywords and titles are both folded before matching, so
+//! typographic variants compare equal. NFKC turns compatibility forms into
+//! their plain ones (the "ﬁ" ligature, full-width letters, non-breaking
+//! spaces); dashes and quotes become their ASCII forms and periods are dropped,
+//! so "Sr." matches "Sr" and "U.S." matches "US". Stripping diacritics takes
+//! accents off too ("Développeur" matches "developpeur"), along with the few
+//! Latin letters that don't decompose, like "ß" and "ø".
+
+use icu_normalizer::{ComposingNormalizerBorrowed, DecomposingNormalizerBorrowed};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Normalization {
+    Nfkc,
+    StripDiacritics,
+}
+
+/// Lowercased as well, since matching is case-insensitive anyway
+pub fn fold(text: &str, normalization: Normalization) -> String {
+    let composed = ComposingNormalizerBorrowed::new_nfkc().normalize(text);
+    let text = match normalization {
+        Normalization::Nfkc => composed.into_owned(),
+        Normalization::StripDiacritics => strip_diacritics(&composed),
+    };
+    let mut folded = String::with_capacity(text.len());
+    for c in text.chars().flat_map(char::to_lowercase) {
+        match c {
+            '.' => {}
+            '\u{2010}'..='\u{2015}' | '\u{2212}' => folded.push('-'),
+            '\u{2018}' | '\u{2019}' | '\u{201B}' | '\u{2032}' => folded.push('\''),
+            '\u{201C}' | '\u{201D}' | '\u{201F}' | '\u{2033}' => folded.push('"'),
+            c => folded.push(c),
+        }
+    }
+    folded
+}
+
+fn strip_diacritics(text: &str) -> String {
+    let decomposed = DecomposingNormalizerBorrowed::new_nfd().normalize(text);
+    let mut stripped = String::with_capacity(decomposed.len());
+    for c in decomposed.chars().filter(|&c| !is_combining_mark(c)) {
+        match c {
+            'ß' => stripped.push_str("ss"),
+            'æ' => stripped.push_str("ae"),
+            'Æ' => stripped.push_str("AE"),
+            'œ' => stripped.push_str("oe"),
+            'Œ' => stripped.push_str("OE"),
+            'ø' => stripped.push('o'),
+            'Ø' => stripped.push('O'),
+            'ł' => stripped.push('l'),
+            'Ł' => stripped.push('L'),
+            'đ' | 'ð' => stripped.push('d'),
+            'Đ' | 'Ð' => stripped.push('D'),
+            'ı' => stripped.push('i'),
+            c => stripped.push(c),
+        }
+    }
+    stripped
+}
+
+// The combining diacritical mark blocks, which is where NFD puts accents
+fn is_combining_mark(c: char) -> bool {
+    matches!(c, '\u{0300}'..='\u{036F}' | '\u{1AB0}'..='\u{1AFF}' | '\u{1DC0}'..='\u{1DFF}' | '\u{20D0}'..='\u{20FF}' | '\u{FE20}'..='\u{FE2F}')
+}
@@ -0,0 +1,205 @@
+use crate::cli::{Cli, NotifyTarget};
+use crate::config::NotificationConfig;
+use crate::store::Reminder;
+use crate::{icons, locale, JobResult};
+use async_trait::async_trait;
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+mod desktop;
+mod discord;
+mod email;
+mod slack;
+mod webhook;
+
+// Notifications for new matches, closed jobs and follow-up reminders. Every backend implements `Notifier`; delivery
+// is best effort, so an unreachable notification daemon or webhook is
+// reported as a warning and never fails the run.
+
+const WEBHOOK_ATTEMPTS: u32 = 3;
+// Upper bound on a server-requested rate limit pause
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    // Called after every search, even with no new jobs, so backends that hold
+    // notifications back (desktop quiet hours) can flush them and email can
+    // check its login on the first search
+    async fn send(&self, client: &reqwest::Client, jobs: &[&JobResult]) -> Result<(), String>;
+
+    // Tracked jobs that have just disappeared from their boards; never called with none
+    async fn send_closed(&self, client: &reqwest::Client, jobs: &[&JobResult]) -> Result<(), String>;
+
+    // Follow-up dates set with `applications update` that have come; never called with none
+    async fn send_reminders(&self, client: &reqwest::Client, reminders: &[Reminder]) -> Result<(), String>;
+}
+
+// The notifiers for the selected --notify targets. Built once per run (or
+// watch session) so backends can keep state between searches.
+pub struct Notifications {
+    notifiers: Vec<Box<dyn Notifier>>,
+}
+
+impl Notifications {
+    // Targets that are missing required settings are skipped with a warning
+    pub fn new(cli: &Cli, config: &NotificationConfig) -> Self {
+        let mut notifiers: Vec<Box<dyn Notifier>> = Vec::new();
+        for target in &cli.notify {
+            match target {
+                NotifyTarget::Desktop => notifiers.push(Box::new(desktop::DesktopNotifier::new(config.clone()))),
+                NotifyTarget::Slack => {
+                    match flag_or_config(&cli.slack_webhook_url, &config.slack_webhook_url) {
+                        Some(url) => notifiers.push(Box::new(slack::SlackNotifier::new(url))),
+                        None => eprintln!(
+                            "{}--notify slack needs --slack-webhook-url, SLACK_WEBHOOK_URL or slack_webhook_url in config.toml", icons::WARNING
+                        ),
+                    }
+                }
+                NotifyTarget::Discord => {
+                    match flag_or_config(&cli.discord_webhook_url, &config.discord_webhook_url) {
+                        Some(url) => notifiers.push(Box::new(discord::DiscordNotifier::new(url))),
+                        None => eprintln!(
+                            "{}--notify discord needs --discord-webhook-url, DISCORD_WEBHOOK_URL or discord_webhook_url in config.toml", icons::WARNING
+                        ),
+                    }
+                }
+                NotifyTarget::Webhook => match flag_or_config(&cli.webhook_url, &config.webhook_url) {
+                    Some(url) => {
+                        let secret = flag_or_config(&cli.webhook_secret, &config.webhook_secret);
+                        let run = webhook::RunInfo {
+                            keyword: cli.title_keyword().to_string(),
+                            location: cli.location.clone(),
+                            tool_version: env!("CARGO_PKG_VERSION"),
+                        };
+                        notifiers.push(Box::new(webhook::WebhookNotifier::new(url, secret, cli.webhook_attempts, run)));
+                    }
+                    None => eprintln!("{}--notify webhook needs --webhook-url, WEBHOOK_URL or webhook_url in config.toml", icons::WARNING),
+                },
+                NotifyTarget::Email => match email::EmailNotifier::new(&config.email) {
+                    Ok(notifier) => notifiers.push(Box::new(notifier)),
+                    Err(e) => eprintln!("{}--notify email is not set up ({}); see [notifications.email] in config.toml", icons::WARNING, e),
+                },
+            }
+        }
+        Notifications { notifiers }
+    }
+
+    pub async fn notify_new_matches(&self, client: &reqwest::Client, jobs: &[JobResult]) {
+        let new_jobs: Vec<&JobResult> = jobs.iter().filter(|job| job.first_seen.is_none()).collect();
+        for notifier in &self.notifiers {
+            if let Err(e) = notifier.send(client, &new_jobs).await {
+                eprintln!("{}{} notification failed: {}", icons::WARNING, notifier.name(), e);
+            }
+        }
+    }
+
+    pub async fn notify_closed(&self, client: &reqwest::Client, jobs: &[JobResult]) {
+        if jobs.is_empty() {
+            return;
+        }
+        let closed: Vec<&JobResult> = jobs.iter().collect();
+        for notifier in &self.notifiers {
+            if let Err(e) = notifier.send_closed(client, &closed).await {
+                eprintln!("{}{} notification failed: {}", icons::WARNING, notifier.name(), e);
+            }
+        }
+    }
+
+    pub async fn notify_reminders(&self, client: &reqwest::Client, reminders: &[Reminder]) {
+        if reminders.is_empty() {
+            return;
+        }
+        for notifier in &self.notifiers {
+            if let Err(e) = notifier.send_reminders(client, reminders).await {
+                eprintln!("{}{} notification failed: {}", icons::WARNING, notifier.name(), e);
+            }
+        }
+    }
+}
+
+// "Follow up with Figma today: Staff Product Manager (interview)"
+pub fn reminder_text(reminder: &Reminder) -> String {
+    let today = chrono::Local::now().date_naive();
+    let when = if reminder.remind_on >= today {
+        "today".to_string()
+    } else {
+        format!("(due {})", reminder.remind_on.format(locale::current().date_pattern()))
+    };
+    format!("Follow up with {} {}: {} ({})", reminder.company, when, reminder.title, reminder.status.as_str())
+}
+
+// --notify-test: send one sample match to every selected target, ignoring quiet hours
+pub async fn send_test(cli: &Cli, config: &NotificationConfig, client: &reqwest::Client) {
+    let sample = JobResult {
+        id: 0,
+        board_token: "example".to_string(),
+        title: "Staff Product Manager (test notification)".to_string(),
+        company: "Example Co".to_string(),
+        location: "Remote".to_string(),
+        date_posted: chrono::Utc::now().to_rfc3339(),
+        url: "https://boards.greenhouse.io/example".to_string(),
+        raw: serde_json::Value::Null,
+        first_seen: None,
+        extras: BTreeMap::new(),
+        departments: vec!["Product".to_string()],
+        tags: Vec::new(),
+        closed_at: None,
+        relevance: None,
+        favorite: false,
+    };
+    let config = NotificationConfig { quiet_hours: None, ..config.clone() };
+    for notifier in Notifications::new(cli, &config).notifiers {
+        match notifier.send(client, &[&sample]).await {
+            Ok(()) => println!("{}Sent a test notification via {}", icons::APPLICATIONS, notifier.name()),
+            Err(e) => eprintln!("{}{} test notification failed: {}", icons::WARNING, notifier.name(), e),
+        }
+    }
+}
+
+// The flag (or its environment variable) wins over config.toml
+fn flag_or_config(flag: &Option<String>, configured: &Option<String>) -> Option<String> {
+    flag.clone().or_else(|| configured.clone())
+}
+
+// POST a JSON payload, retrying failures with a growing pause. A 429 waits
+// for as long as the server asks, via the Retry-After header or the
+// `retry_after` seconds Discord puts in the body.
+async fn post_webhook(client: &reqwest::Client, url: &str, payload: &serde_json::Value) -> Result<(), String> {
+    let mut last_error = String::new();
+    for attempt in 1..=WEBHOOK_ATTEMPTS {
+        let mut pause = Duration::from_secs(2u64.pow(attempt - 1));
+        match client.post(url).json(payload).send().await {
+            Ok(response) if response.status().is_success() => return Ok(()),
+            Ok(response) if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS => {
+                last_error = "rate limited".to_string();
+                if let Some(retry_after) = retry_after(response).await {
+                    pause = retry_after.min(MAX_RETRY_AFTER);
+                }
+            }
+            Ok(response) => last_error = format!("HTTP {}", response.status()),
+            Err(e) => last_error = e.to_string(),
+        }
+        if attempt < WEBHOOK_ATTEMPTS {
+            tokio::time::sleep(pause).await;
+        }
+    }
+    Err(format!("{} (after {} attempts)", last_error, WEBHOOK_ATTEMPTS))
+}
+
+async fn retry_after(response: reqwest::Response) -> Option<Duration> {
+    let header = response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<f64>().ok());
+    let seconds = match header {
+        Some(seconds) => seconds,
+        None => {
+            let body: serde_json::Value = response.json().await.ok()?;
+            body.get("retry_after")?.as_f64()?
+        }
+    };
+    Duration::try_from_secs_f64(seconds).ok()
+}
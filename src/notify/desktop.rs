@@ -0,0 +1,134 @@
+use super::{reminder_text, Notifier};
+use crate::config::NotificationConfig;
+use crate::store::Reminder;
+use crate::{icons, paths, JobResult};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+// Desktop notifications through notify-rust. During quiet hours matches are
+// queued on disk and sent in one batch once the window is over; closed
+// tracked jobs aren't queued, as the job store keeps them marked anyway, and
+// neither are reminders, which watch mode prints as well.
+
+// Jobs named in the notification body; the rest are summarized as a count
+const MAX_LISTED: usize = 3;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PendingMatch {
+    title: String,
+    company: String,
+}
+
+pub struct DesktopNotifier {
+    config: NotificationConfig,
+}
+
+impl DesktopNotifier {
+    pub fn new(config: NotificationConfig) -> Self {
+        DesktopNotifier { config }
+    }
+}
+
+#[async_trait]
+impl Notifier for DesktopNotifier {
+    fn name(&self) -> &'static str {
+        "Desktop"
+    }
+
+    async fn send(&self, _client: &reqwest::Client, jobs: &[&JobResult]) -> Result<(), String> {
+        let mut pending = load_pending();
+        pending.extend(
+            jobs.iter()
+                .map(|job| PendingMatch { title: job.title.clone(), company: job.company.clone() }),
+        );
+        if pending.is_empty() {
+            return Ok(());
+        }
+
+        if self.config.in_quiet_hours(chrono::Local::now().time()) {
+            println!("{}Quiet hours: holding {} new match(es) for a later notification", icons::QUIET, pending.len());
+            save_pending(&pending);
+            return Ok(());
+        }
+
+        // Held on disk until shown, so a failed notification is tried again next run
+        let shown = show(&summary(&pending, "new"));
+        save_pending(if shown.is_ok() { &[] } else { &pending });
+        shown
+    }
+
+    async fn send_closed(&self, _client: &reqwest::Client, jobs: &[&JobResult]) -> Result<(), String> {
+        if self.config.in_quiet_hours(chrono::Local::now().time()) {
+            println!("{}Quiet hours: not showing {} closed tracked job(s)", icons::QUIET, jobs.len());
+            return Ok(());
+        }
+        let closed: Vec<PendingMatch> = jobs
+            .iter()
+            .map(|job| PendingMatch { title: job.title.clone(), company: job.company.clone() })
+            .collect();
+        show(&summary(&closed, "tracked closed"))
+    }
+
+    async fn send_reminders(&self, _client: &reqwest::Client, reminders: &[Reminder]) -> Result<(), String> {
+        if self.config.in_quiet_hours(chrono::Local::now().time()) {
+            println!("{}Quiet hours: not showing {} follow-up reminder(s)", icons::QUIET, reminders.len());
+            return Ok(());
+        }
+        let mut lines: Vec<String> = reminders.iter().take(MAX_LISTED).map(reminder_text).collect();
+        if reminders.len() > MAX_LISTED {
+            lines.push(format!("… and {} more", reminders.len() - MAX_LISTED));
+        }
+        show(&lines.join("\n"))
+    }
+}
+
+fn show(body: &str) -> Result<(), String> {
+    notify_rust::Notification::new()
+        .appname("greenhouse-job-search")
+        .summary("Greenhouse Job Search")
+        .body(body)
+        .show()
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+// "3 new: Staff PM @ Figma, PM @ Stripe, … and 2 more"
+fn summary(matches: &[PendingMatch], what: &str) -> String {
+    let listed: Vec<String> = matches
+        .iter()
+        .take(MAX_LISTED)
+        .map(|m| format!("{} @ {}", m.title, m.company))
+        .collect();
+    let mut text = format!("{} {}: {}", matches.len(), what, listed.join(", "));
+    if matches.len() > MAX_LISTED {
+        text.push_str(&format!(", … and {} more", matches.len() - MAX_LISTED));
+    }
+    text
+}
+
+fn load_pending() -> Vec<PendingMatch> {
+    std::fs::read_to_string(paths::pending_notifications_path())
+        .ok()
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default()
+}
+
+fn save_pending(pending: &[PendingMatch]) {
+    if !paths::persistent() {
+        return;
+    }
+    let path = paths::pending_notifications_path();
+    let result = if pending.is_empty() {
+        match std::fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    } else {
+        path.parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|_| std::fs::write(&path, serde_json::to_string_pretty(pending)?))
+    };
+    if let Err(e) = result {
+        eprintln!("{}Failed to update {}: {}", icons::WARNING, path.display(), e);
+    }
+}
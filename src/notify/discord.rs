@@ -0,0 +1,85 @@
+use super::{post_webhook, reminder_text, Notifier};
+use crate::store::Reminder;
+use crate::{locale, JobResult};
+use async_trait::async_trait;
+use serde_json::json;
+
+// Discord webhook: one embed per new match, closed job or follow-up reminder, packed ten to a message
+
+// Discord allows at most 10 embeds per message
+const EMBEDS_PER_MESSAGE: usize = 10;
+const MAX_TITLE_CHARS: usize = 256;
+
+pub struct DiscordNotifier {
+    url: String,
+}
+
+impl DiscordNotifier {
+    pub fn new(url: String) -> Self {
+        DiscordNotifier { url }
+    }
+}
+
+#[async_trait]
+impl Notifier for DiscordNotifier {
+    fn name(&self) -> &'static str {
+        "Discord"
+    }
+
+    async fn send(&self, client: &reqwest::Client, jobs: &[&JobResult]) -> Result<(), String> {
+        self.post_jobs(client, jobs, &format!("🌱 {} new matching job(s)", jobs.len())).await
+    }
+
+    async fn send_closed(&self, client: &reqwest::Client, jobs: &[&JobResult]) -> Result<(), String> {
+        self.post_jobs(client, jobs, &format!("🚫 {} tracked job(s) closed", jobs.len())).await
+    }
+
+    async fn send_reminders(&self, client: &reqwest::Client, reminders: &[Reminder]) -> Result<(), String> {
+        for (part, chunk) in reminders.chunks(EMBEDS_PER_MESSAGE).enumerate() {
+            let content = if part == 0 { format!("🔔 {} follow-up reminder(s)", reminders.len()) } else { String::new() };
+            let embeds: Vec<serde_json::Value> = chunk
+                .iter()
+                .map(|reminder| {
+                    let title: String = reminder_text(reminder).chars().take(MAX_TITLE_CHARS).collect();
+                    json!({ "title": title, "url": reminder.url })
+                })
+                .collect();
+            post_webhook(client, &self.url, &json!({ "content": content, "embeds": embeds })).await?;
+        }
+        Ok(())
+    }
+}
+
+impl DiscordNotifier {
+    async fn post_jobs(&self, client: &reqwest::Client, jobs: &[&JobResult], headline: &str) -> Result<(), String> {
+        let chunks: Vec<&[&JobResult]> = jobs.chunks(EMBEDS_PER_MESSAGE).collect();
+        for (part, chunk) in chunks.iter().enumerate() {
+            // Only the first message carries the headline so a batch reads as one post
+            let content = if part == 0 { headline.to_string() } else { String::new() };
+            let payload = json!({
+                "content": content,
+                "embeds": chunk.iter().map(|job| embed(job)).collect::<Vec<_>>(),
+            });
+            post_webhook(client, &self.url, &payload).await?;
+        }
+        Ok(())
+    }
+}
+
+fn embed(job: &JobResult) -> serde_json::Value {
+    let title: String = job.title.chars().take(MAX_TITLE_CHARS).collect();
+    json!({
+        "title": title,
+        "url": job.url,
+        "fields": [
+            { "name": "Company", "value": field_value(&job.company), "inline": true },
+            { "name": "Location", "value": field_value(&job.location), "inline": true },
+            { "name": "Posted", "value": locale::current().timestamp_date(&job.date_posted), "inline": true },
+        ],
+    })
+}
+
+// Discord rejects embed fields with an empty value
+fn field_value(text: &str) -> &str {
+    if text.trim().is_empty() { "—" } else { text }
+}
@@ -0,0 +1,149 @@
+use super::{reminder_text, Notifier};
+use crate::config::EmailConfig;
+use crate::store::Reminder;
+use crate::{locale, report, JobResult};
+use async_trait::async_trait;
+use lettre::message::{Mailbox, MultiPart};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+// SMTP email: one HTML message (with a plain-text alternative) per search
+// that found new matches, one when tracked jobs close, and a plain-text one
+// for follow-up reminders. Credentials come from SMTP_USERNAME and
+// SMTP_PASSWORD; the username defaults to the sender address.
+
+const SMTP_TIMEOUT: Duration = Duration::from_secs(30);
+// Implicit TLS; any other port negotiates STARTTLS
+const SMTPS_PORT: u16 = 465;
+
+pub struct EmailNotifier {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+    to: Vec<Mailbox>,
+    host: String,
+    // Set once the server has accepted a login, so a bad password shows up on
+    // the first watch cycle rather than the first one with matches
+    verified: AtomicBool,
+}
+
+impl EmailNotifier {
+    pub fn new(config: &EmailConfig) -> Result<Self, String> {
+        let host = config.smtp_host.clone().ok_or("smtp_host is not set")?;
+        let from: Mailbox = config
+            .from
+            .as_deref()
+            .ok_or("from is not set")?
+            .parse()
+            .map_err(|e| format!("invalid from address: {}", e))?;
+        let to = config
+            .to
+            .iter()
+            .map(|address| address.parse::<Mailbox>().map_err(|e| format!("invalid to address \"{}\": {}", address, e)))
+            .collect::<Result<Vec<_>, _>>()?;
+        if to.is_empty() {
+            return Err("no to addresses are set".to_string());
+        }
+
+        let port = config.smtp_port.unwrap_or(587);
+        let builder = if port == SMTPS_PORT {
+            AsyncSmtpTransport::<Tokio1Executor>::relay(&host)
+        } else {
+            AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&host)
+        }
+        .map_err(|e| format!("TLS setup for {} failed: {}", host, e))?
+        .port(port)
+        .timeout(Some(SMTP_TIMEOUT));
+
+        let password = std::env::var("SMTP_PASSWORD").ok();
+        let builder = match password {
+            Some(password) => {
+                let username = std::env::var("SMTP_USERNAME").unwrap_or_else(|_| from.email.to_string());
+                builder.credentials(Credentials::new(username, password))
+            }
+            None => builder,
+        };
+
+        Ok(EmailNotifier { transport: builder.build(), from, to, host, verified: AtomicBool::new(false) })
+    }
+
+    fn describe(&self, error: lettre::transport::smtp::Error) -> String {
+        let status = error.status().map(|code| code.to_string());
+        match status.as_deref() {
+            Some("535") | Some("534") | Some("530") => format!(
+                "{} rejected the login ({}); check SMTP_USERNAME and SMTP_PASSWORD",
+                self.host, error
+            ),
+            _ if error.is_timeout() => format!("{} did not respond within {}s", self.host, SMTP_TIMEOUT.as_secs()),
+            _ => format!("{}: {}", self.host, error),
+        }
+    }
+}
+
+#[async_trait]
+impl Notifier for EmailNotifier {
+    fn name(&self) -> &'static str {
+        "Email"
+    }
+
+    async fn send(&self, _client: &reqwest::Client, jobs: &[&JobResult]) -> Result<(), String> {
+        if !self.verified.load(Ordering::SeqCst) {
+            match self.transport.test_connection().await {
+                Ok(true) => self.verified.store(true, Ordering::SeqCst),
+                Ok(false) => return Err(format!("could not connect to {}", self.host)),
+                Err(e) => return Err(self.describe(e)),
+            }
+        }
+        if jobs.is_empty() {
+            return Ok(());
+        }
+        self.send_jobs(&format!("{} new matching job(s)", locale::current().count(jobs.len())), jobs).await
+    }
+
+    async fn send_closed(&self, _client: &reqwest::Client, jobs: &[&JobResult]) -> Result<(), String> {
+        self.send_jobs(&format!("{} tracked job(s) closed", locale::current().count(jobs.len())), jobs).await
+    }
+
+    async fn send_reminders(&self, _client: &reqwest::Client, reminders: &[Reminder]) -> Result<(), String> {
+        let lines: Vec<String> = reminders.iter().map(|reminder| format!("{}\n{}\n", reminder_text(reminder), reminder.url)).collect();
+        // A single reminder is its own subject: "Follow up with Figma today: ..."
+        let subject = match reminders {
+            [reminder] => reminder_text(reminder),
+            _ => format!("{} follow-up reminders", locale::current().count(reminders.len())),
+        };
+        let mut builder = Message::builder().from(self.from.clone()).subject(subject);
+        for recipient in &self.to {
+            builder = builder.to(recipient.clone());
+        }
+        let message = builder.body(lines.join("\n")).map_err(|e| e.to_string())?;
+        self.transport.send(message).await.map(|_| ()).map_err(|e| self.describe(e))
+    }
+}
+
+impl EmailNotifier {
+    async fn send_jobs(&self, subject: &str, jobs: &[&JobResult]) -> Result<(), String> {
+        let mut builder = Message::builder().from(self.from.clone()).subject(subject);
+        for recipient in &self.to {
+            builder = builder.to(recipient.clone());
+        }
+        let message = builder
+            .multipart(MultiPart::alternative_plain_html(plain_text(jobs), report::render_html(subject, jobs)))
+            .map_err(|e| e.to_string())?;
+
+        self.transport.send(message).await.map(|_| ()).map_err(|e| self.describe(e))
+    }
+}
+
+fn plain_text(jobs: &[&JobResult]) -> String {
+    let locale = locale::current();
+    jobs.iter()
+        .map(|job| {
+            format!(
+                "{} — {} ({}, posted {})\n{}\n",
+                job.title, job.company, job.location, locale.timestamp_date(&job.date_posted), job.url
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -0,0 +1,98 @@
+use super::{post_webhook, reminder_text, Notifier};
+use crate::store::Reminder;
+use crate::{locale, JobResult};
+use async_trait::async_trait;
+use serde_json::json;
+
+// Slack incoming webhook: one message of blocks per batch of new matches, closed jobs or follow-up reminders
+
+// Slack rejects messages with more than 50 blocks; leave room for the header
+const JOBS_PER_MESSAGE: usize = 45;
+
+pub struct SlackNotifier {
+    url: String,
+}
+
+impl SlackNotifier {
+    pub fn new(url: String) -> Self {
+        SlackNotifier { url }
+    }
+}
+
+#[async_trait]
+impl Notifier for SlackNotifier {
+    fn name(&self) -> &'static str {
+        "Slack"
+    }
+
+    async fn send(&self, client: &reqwest::Client, jobs: &[&JobResult]) -> Result<(), String> {
+        self.post_jobs(client, jobs, &format!("🌱 {} new matching job(s)", jobs.len())).await
+    }
+
+    async fn send_closed(&self, client: &reqwest::Client, jobs: &[&JobResult]) -> Result<(), String> {
+        self.post_jobs(client, jobs, &format!("🚫 {} tracked job(s) closed", jobs.len())).await
+    }
+
+    async fn send_reminders(&self, client: &reqwest::Client, reminders: &[Reminder]) -> Result<(), String> {
+        for chunk in reminders.chunks(JOBS_PER_MESSAGE) {
+            let heading = format!("🔔 {} follow-up reminder(s)", reminders.len());
+            let mut blocks = vec![json!({
+                "type": "header",
+                "text": { "type": "plain_text", "text": heading },
+            })];
+            blocks.extend(chunk.iter().map(|reminder| {
+                json!({
+                    "type": "section",
+                    "text": { "type": "mrkdwn", "text": format!("<{}|{}>", reminder.url, escape(&reminder_text(reminder))) },
+                })
+            }));
+            post_webhook(client, &self.url, &json!({ "text": heading, "blocks": blocks })).await?;
+        }
+        Ok(())
+    }
+}
+
+impl SlackNotifier {
+    async fn post_jobs(&self, client: &reqwest::Client, jobs: &[&JobResult], headline: &str) -> Result<(), String> {
+        let chunks: Vec<&[&JobResult]> = jobs.chunks(JOBS_PER_MESSAGE).collect();
+        for (part, chunk) in chunks.iter().enumerate() {
+            post_webhook(client, &self.url, &message(chunk, headline, part + 1, chunks.len())).await?;
+        }
+        Ok(())
+    }
+}
+
+fn message(jobs: &[&JobResult], headline: &str, part: usize, parts: usize) -> serde_json::Value {
+    let mut heading = headline.to_string();
+    if parts > 1 {
+        heading.push_str(&format!(" ({}/{})", part, parts));
+    }
+
+    let mut blocks = vec![json!({
+        "type": "header",
+        "text": { "type": "plain_text", "text": heading },
+    })];
+    blocks.extend(jobs.iter().map(|job| {
+        json!({
+            "type": "section",
+            "text": {
+                "type": "mrkdwn",
+                "text": format!(
+                    "*<{}|{}>*\n{} · {} · posted {}",
+                    job.url,
+                    escape(&job.title),
+                    escape(&job.company),
+                    escape(&job.location),
+                    locale::current().timestamp_date(&job.date_posted)
+                ),
+            },
+        })
+    }));
+
+    json!({ "text": heading, "blocks": blocks })
+}
+
+// Slack mrkdwn treats these three as control characters
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
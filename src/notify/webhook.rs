@@ -0,0 +1,120 @@
+use super::Notifier;
+use crate::store::Reminder;
+use crate::{icons, JobResult};
+use async_trait::async_trait;
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use sha2::Sha256;
+use std::time::Duration;
+
+// Generic webhook: POSTs a versioned JSON document to any endpoint, for
+// feeding matches into the user's own automation. With a shared secret the
+// body is signed with HMAC-SHA256 so the receiver can check where it came from.
+//
+//   X-Signature-256: sha256=<hex HMAC of the raw request body>
+//
+// Server errors (5xx), rate limiting and network failures are retried with a
+// doubling pause; any other non-success status gives up straight away.
+
+// Bump when fields are renamed or removed; new fields keep the version
+pub const SCHEMA_VERSION: u32 = 1;
+pub const SIGNATURE_HEADER: &str = "X-Signature-256";
+
+// The search the matches came from
+#[derive(Debug, Clone, Serialize)]
+pub struct RunInfo {
+    pub keyword: String,
+    pub location: String,
+    pub tool_version: &'static str,
+}
+
+#[derive(Serialize)]
+struct Payload<'a> {
+    schema_version: u32,
+    // "new_matches", "closed_jobs" or "follow_up_reminders"
+    event: &'static str,
+    sent_at: chrono::DateTime<chrono::Utc>,
+    run: &'a RunInfo,
+    jobs: &'a [&'a JobResult],
+    // Only in "follow_up_reminders", whose `jobs` is empty
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    reminders: &'a [Reminder],
+}
+
+pub struct WebhookNotifier {
+    url: String,
+    secret: Option<String>,
+    attempts: u32,
+    run: RunInfo,
+}
+
+impl WebhookNotifier {
+    pub fn new(url: String, secret: Option<String>, attempts: u32, run: RunInfo) -> Self {
+        WebhookNotifier { url, secret, attempts, run }
+    }
+
+    async fn post(&self, client: &reqwest::Client, event: &'static str, jobs: &[&JobResult], reminders: &[Reminder]) -> Result<(), String> {
+        if jobs.is_empty() && reminders.is_empty() {
+            return Ok(());
+        }
+        let payload = Payload { schema_version: SCHEMA_VERSION, event, sent_at: chrono::Utc::now(), run: &self.run, jobs, reminders };
+        let body = serde_json::to_vec(&payload).map_err(|e| e.to_string())?;
+
+        let mut last_error = String::new();
+        for attempt in 1..=self.attempts {
+            let mut request = client
+                .post(&self.url)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .body(body.clone());
+            if let Some(secret) = &self.secret {
+                request = request.header(SIGNATURE_HEADER, signature(secret, &body));
+            }
+            match request.send().await {
+                Ok(response) => {
+                    let status = response.status();
+                    println!("{}Webhook answered {} (attempt {} of {})", icons::WEBHOOK, status, attempt, self.attempts);
+                    if status.is_success() {
+                        return Ok(());
+                    }
+                    if !status.is_server_error() && status != reqwest::StatusCode::TOO_MANY_REQUESTS {
+                        return Err(format!("HTTP {}", status));
+                    }
+                    last_error = format!("HTTP {}", status);
+                }
+                Err(e) => last_error = e.to_string(),
+            }
+            if attempt < self.attempts {
+                tokio::time::sleep(Duration::from_secs(2u64.pow(attempt - 1))).await;
+            }
+        }
+        Err(format!("{} (after {} attempts)", last_error, self.attempts))
+    }
+}
+
+// `sha256=` followed by the lowercase hex HMAC-SHA256 of `body`
+pub fn signature(secret: &str, body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(body);
+    let digest = mac.finalize().into_bytes();
+    let hex: String = digest.iter().map(|byte| format!("{:02x}", byte)).collect();
+    format!("sha256={}", hex)
+}
+
+#[async_trait]
+impl Notifier for WebhookNotifier {
+    fn name(&self) -> &'static str {
+        "Webhook"
+    }
+
+    async fn send(&self, client: &reqwest::Client, jobs: &[&JobResult]) -> Result<(), String> {
+        self.post(client, "new_matches", jobs, &[]).await
+    }
+
+    async fn send_closed(&self, client: &reqwest::Client, jobs: &[&JobResult]) -> Result<(), String> {
+        self.post(client, "closed_jobs", jobs, &[]).await
+    }
+
+    async fn send_reminders(&self, client: &reqwest::Client, reminders: &[Reminder]) -> Result<(), String> {
+        self.post(client, "follow_up_reminders", &[], reminders).await
+    }
+}
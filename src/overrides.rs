@@ -0,0 +1,148 @@
+use crate::paths;
+use regex::Regex;
+use std::collections::BTreeMap;
+
+// overrides.toml: canned answers to the questions particular boards always
+// ask, one table per board token, keyed by a pattern for the question label:
+//
+//   [figma]
+//   "how did you hear" = "Referral from a current employee"
+//   "/^desired (salary|compensation)/" = "Open to discussing"
+//
+// A plain pattern matches any label containing it, ignoring case; one between
+// slashes is a regular expression, also case-insensitive. When more than one
+// matches a label the longest pattern wins, being the most specific, and an
+// override is used before the profile's answers.
+
+#[derive(Debug, Default)]
+pub struct Overrides {
+    // By lowercase board token, longest pattern first
+    boards: BTreeMap<String, Vec<Rule>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Rule {
+    // As written in the file, for reporting
+    pub pattern: String,
+    matcher: Matcher,
+    pub answer: String,
+}
+
+#[derive(Debug, Clone)]
+enum Matcher {
+    // Lowercased
+    Contains(String),
+    Regex(Regex),
+}
+
+impl Overrides {
+    // No file means no overrides
+    pub fn load() -> Result<Overrides, String> {
+        let path = paths::overrides_path();
+        let text = match std::fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Overrides::default()),
+            Err(e) => return Err(format!("{}: {}", path.display(), e)),
+        };
+        Overrides::parse(&text).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    pub fn parse(text: &str) -> Result<Overrides, String> {
+        let document = text.parse::<toml::Table>().map_err(|e| e.to_string())?;
+        let mut boards = BTreeMap::new();
+        for (board, entries) in document {
+            let toml::Value::Table(entries) = entries else {
+                return Err(format!("`{}` should be a table of question patterns and answers", board));
+            };
+            let mut rules = Vec::new();
+            for (pattern, answer) in entries {
+                let toml::Value::String(answer) = answer else {
+                    return Err(format!("the answer to \"{}\" under [{}] should be a string", pattern, board));
+                };
+                rules.push(Rule::new(&pattern, answer).map_err(|e| format!("[{}] \"{}\": {}", board, pattern, e))?);
+            }
+            rules.sort_by_key(|rule| std::cmp::Reverse(rule.pattern.len()));
+            boards.insert(board.to_lowercase(), rules);
+        }
+        Ok(Overrides { boards })
+    }
+
+    // How many boards have overrides, and how many patterns they have between them
+    pub fn counts(&self) -> (usize, usize) {
+        (self.boards.len(), self.boards.values().map(Vec::len).sum())
+    }
+
+    // Longest pattern first, so the first match is the one to use
+    pub fn for_board(&self, board_token: &str) -> &[Rule] {
+        self.boards.get(&board_token.to_lowercase()).map_or(&[], Vec::as_slice)
+    }
+}
+
+impl Rule {
+    fn new(pattern: &str, answer: String) -> Result<Rule, String> {
+        let matcher = match pattern.strip_prefix('/').and_then(|rest| rest.strip_suffix('/')) {
+            // The error draws the pattern over several lines and ends with what's wrong
+            Some(expression) => Matcher::Regex(regex::RegexBuilder::new(expression).case_insensitive(true).build().map_err(|e| {
+                let error = e.to_string();
+                error.lines().last().unwrap_or_default().trim_start_matches("error: ").to_string()
+            })?),
+            None => Matcher::Contains(pattern.to_lowercase()),
+        };
+        Ok(Rule { pattern: pattern.to_string(), matcher, answer })
+    }
+
+    pub fn matches(&self, label: &str) -> bool {
+        match &self.matcher {
+            Matcher::Contains(text) => label.to_lowercase().contains(text.as_str()),
+            Matcher::Regex(regex) => regex.is_match(label),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::ApplicationForm;
+    use crate::profile::Profile;
+    use crate::submit::{self, AnswerSource};
+
+    #[test]
+    fn overrides_answer_matching_questions_before_the_profile() {
+        let overrides = Overrides::parse(
+            r#"
+            [Fabrikam]
+            "why do you want" = "Your developer tools."
+            "/^are you .*authorized/" = "No"
+            "desired salary" = "Open to discussing"
+
+            [contoso]
+            "why do you want" = "Something else"
+            "#,
+        )
+        .unwrap();
+        let rules = overrides.for_board("fabrikam");
+        assert_eq!(rules.len(), 3);
+        assert!(rules.iter().any(|rule| rule.matches("Why do you WANT to work at Fabrikam?")));
+        assert!(!rules.iter().any(|rule| rule.matches("Whom do you want to work with?")));
+        assert!(overrides.for_board("northwind").is_empty());
+
+        let path = format!("{}/tests/fixtures/application_forms/custom_questions_eeoc.json", env!("CARGO_MANIFEST_DIR"));
+        let form: ApplicationForm = serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+        let mut profile = Profile { first_name: "Jane".to_string(), ..Profile::default() };
+        profile.work_authorization.authorized = Some(true);
+        let filled = submit::fill(&form, &profile, None, rules);
+
+        let answer = |field: &str| filled.answers.iter().find(|(name, _)| name == field).map(|(_, answer)| submit::describe(answer));
+        assert_eq!(answer("question_31604874").as_deref(), Some("Your developer tools."));
+        assert_eq!(answer("question_31604873").as_deref(), Some("No"));
+        assert_eq!(filled.sources["question_31604873"], AnswerSource::Override);
+        assert_eq!(filled.sources["first_name"], AnswerSource::Profile);
+        assert_eq!(filled.unused_overrides, vec!["desired salary".to_string()]);
+    }
+
+    #[test]
+    fn a_bad_pattern_names_its_board() {
+        let error = Overrides::parse("[figma]\n\"/(unclosed/\" = \"x\"").unwrap_err();
+        assert_eq!(error, "[figma] \"/(unclosed/\": unclosed group");
+    }
+}
@@ -0,0 +1,91 @@
+use crate::icons;
+use std::io::{ErrorKind, IsTerminal, Read, Write};
+use std::process::{Command, Stdio};
+
+// --pager: results taller than the terminal go through $PAGER (`less` when it
+// isn't set) instead of scrolling past. The output is collected first, so its
+// height is known before anything is shown and quitting the pager early
+// doesn't break the rest of the run. Unless LESS is already set, less gets
+// `-FRX`, as git does. Nothing is paged when stdout isn't a terminal.
+
+pub struct Capture {
+    buffer: gag::BufferRedirect,
+}
+
+// None when paging is off; the output then goes to stdout as usual
+pub fn capture() -> Option<Capture> {
+    if !std::io::stdout().is_terminal() {
+        return None;
+    }
+    // Whatever was printed before has to come out ahead of the captured part
+    let _ = std::io::stdout().flush();
+    match gag::BufferRedirect::stdout() {
+        Ok(buffer) => Some(Capture { buffer }),
+        Err(e) => {
+            eprintln!("{}Couldn't page the results: {}", icons::WARNING, e);
+            None
+        }
+    }
+}
+
+impl Capture {
+    // Puts stdout back and shows what was printed, through the pager if it doesn't fit
+    pub fn finish(mut self) {
+        let _ = std::io::stdout().flush();
+        let mut output = String::new();
+        let read = self.buffer.read_to_string(&mut output);
+        drop(self.buffer);
+        if let Err(e) = read {
+            eprintln!("{}Couldn't page the results: {}", icons::WARNING, e);
+            return;
+        }
+        if !fits_on_screen(&output) && page(&output) {
+            return;
+        }
+        print!("{}", output);
+        let _ = std::io::stdout().flush();
+    }
+}
+
+// Counts lines as the terminal wraps them
+fn fits_on_screen(output: &str) -> bool {
+    let Ok((columns, rows)) = crossterm::terminal::size() else {
+        return true;
+    };
+    let columns = usize::from(columns.max(1));
+    let height: usize = output.lines().map(|line| line.chars().count().div_ceil(columns).max(1)).sum();
+    // Leave a row for the prompt that follows
+    height < usize::from(rows)
+}
+
+// False when the pager couldn't be started, so the caller prints instead
+fn page(output: &str) -> bool {
+    let command = std::env::var("PAGER").unwrap_or_default();
+    let command = if command.trim().is_empty() { "less" } else { command.trim() };
+    let mut words = command.split_whitespace();
+    let Some(program) = words.next() else {
+        return false;
+    };
+    let mut pager = Command::new(program);
+    pager.args(words).stdin(Stdio::piped());
+    if std::env::var_os("LESS").is_none() {
+        pager.env("LESS", "FRX");
+    }
+    let mut child = match pager.spawn() {
+        Ok(child) => child,
+        Err(e) => {
+            eprintln!("{}Couldn't start the pager \"{}\": {}", icons::WARNING, command, e);
+            return false;
+        }
+    };
+    if let Some(mut stdin) = child.stdin.take() {
+        // Quitting before the end closes the pipe, which isn't an error
+        if let Err(e) = stdin.write_all(output.as_bytes()) {
+            if e.kind() != ErrorKind::BrokenPipe {
+                eprintln!("{}Writing to the pager failed: {}", icons::WARNING, e);
+            }
+        }
+    }
+    let _ = child.wait();
+    true
+}
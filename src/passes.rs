@@ -0,0 +1,128 @@
+use crate::budget::RequestBudget;
+use crate::concurrency::Limiter;
+use crate::JobResult;
+use greenhouse_job_search::GreenhouseJobSearcher;
+use std::future::Future;
+use std::sync::Arc;
+
+// Optional passes over a search's matches once every board is in:
+// --verify-live asks the Job Board API for each match again and drops the ones
+// that are gone, and --resolve-urls follows each match's URL to where it ends
+// up (company career sites often wrap the Greenhouse page). Each is one request
+// per match, so they run --post-concurrency at a time, apart from
+// --concurrency, and every request counts toward --max-requests. Matches left
+// when the budget runs out, or whose request fails, are kept as they are.
+
+#[derive(Debug, Default, PartialEq)]
+pub struct PassReport {
+    // Dropped by --verify-live, or given a new URL by --resolve-urls
+    pub changed: usize,
+    pub failed: usize,
+    pub out_of_requests: usize,
+}
+
+// `check` on every job, --post-concurrency at a time; None for the jobs the
+// budget left out
+async fn each_job<T, F, Fut>(jobs: &[JobResult], limiter: &Arc<Limiter>, budget: Option<&RequestBudget>, check: F) -> Vec<Option<T>>
+where
+    F: Fn(JobResult) -> Fut,
+    Fut: Future<Output = T> + Send + 'static,
+    T: Send + 'static,
+{
+    let mut tasks = Vec::new();
+    for job in jobs {
+        if !budget.is_none_or(RequestBudget::take) {
+            tasks.push(None);
+            continue;
+        }
+        let limiter = limiter.clone();
+        let checked = check(job.clone());
+        tasks.push(Some(tokio::spawn(async move {
+            let slot = limiter.acquire().await;
+            let outcome = checked.await;
+            limiter.finish(slot, false);
+            outcome
+        })));
+    }
+    let mut outcomes = Vec::new();
+    for task in tasks {
+        outcomes.push(match task {
+            Some(task) => task.await.ok(),
+            None => None,
+        });
+    }
+    outcomes
+}
+
+// Whether the job is still on its board: its own record answers 404 once it's gone
+async fn is_live(api: GreenhouseJobSearcher, job: JobResult) -> Result<bool, String> {
+    let url = format!("{}/{}/jobs/{}", api.base_url(), job.board_token, job.id);
+    let mut request = api.client().get(&url);
+    if let Some(secret) = api.credentials.get(&job.board_token) {
+        request = request.bearer_auth(secret);
+    }
+    let response = request.send().await.map_err(|e| format!("network error: {}", e))?;
+    match response.status() {
+        status if status.is_success() => Ok(true),
+        reqwest::StatusCode::NOT_FOUND => Ok(false),
+        status => Err(format!("returned status {}", status)),
+    }
+}
+
+pub async fn verify_live(
+    api: &GreenhouseJobSearcher,
+    jobs: &mut Vec<JobResult>,
+    limiter: &Arc<Limiter>,
+    budget: Option<&RequestBudget>,
+) -> PassReport {
+    let outcomes = each_job(jobs, limiter, budget, |job| is_live(api.clone(), job)).await;
+    let mut report = PassReport::default();
+    let mut live = outcomes.into_iter();
+    jobs.retain(|_| match live.next().flatten() {
+        Some(Ok(false)) => {
+            report.changed += 1;
+            false
+        }
+        Some(Ok(true)) => true,
+        Some(Err(_)) => {
+            report.failed += 1;
+            true
+        }
+        None => {
+            report.out_of_requests += 1;
+            true
+        }
+    });
+    report
+}
+
+// Where the job's URL ends up after redirects
+async fn final_url(client: reqwest::Client, job: JobResult) -> Result<String, String> {
+    let response = client.get(&job.url).send().await.map_err(|e| format!("network error: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("returned status {}", response.status()));
+    }
+    Ok(response.url().to_string())
+}
+
+pub async fn resolve_urls(
+    client: &reqwest::Client,
+    jobs: &mut [JobResult],
+    limiter: &Arc<Limiter>,
+    budget: Option<&RequestBudget>,
+) -> PassReport {
+    let outcomes = each_job(jobs, limiter, budget, |job| final_url(client.clone(), job)).await;
+    let mut report = PassReport::default();
+    for (job, outcome) in jobs.iter_mut().zip(outcomes) {
+        match outcome {
+            Some(Ok(url)) if url != job.url => {
+                job.url = url;
+                report.changed += 1;
+            }
+            Some(Ok(_)) => {}
+            Some(Err(_)) => report.failed += 1,
+            None => report.out_of_requests += 1,
+        }
+    }
+    report
+}
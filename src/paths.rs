@@ -0,0 +1,225 @@
+use crate::icons;
+use directories::ProjectDirs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+
+// Config, data and cache live in the platform's usual places (XDG dirs on
+// Linux, Application Support on macOS, %APPDATA% on Windows) unless
+// --config-dir / GREENHOUSE_JOB_SEARCH_DIR puts everything in one folder.
+// Installs that already have ~/.greenhouse-job-search keep using it.
+
+static DIRS: OnceLock<Dirs> = OnceLock::new();
+// `persist = false` under [storage] in config.toml: nothing is written to these
+// directories, and the job store only lives in memory for the run
+static PERSIST: AtomicBool = AtomicBool::new(true);
+
+#[derive(Debug)]
+struct Dirs {
+    config: PathBuf,
+    data: PathBuf,
+    cache: PathBuf,
+    source: Source,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Source {
+    Override,
+    Legacy,
+    Platform,
+}
+
+impl Dirs {
+    fn single(dir: PathBuf, source: Source) -> Self {
+        Self { config: dir.clone(), data: dir.clone(), cache: dir, source }
+    }
+
+    fn resolve(config_dir: Option<&Path>) -> Self {
+        if let Some(dir) = config_dir {
+            return Self::single(dir.to_path_buf(), Source::Override);
+        }
+        let legacy = legacy_dir();
+        if legacy.is_dir() {
+            return Self::single(legacy, Source::Legacy);
+        }
+        match ProjectDirs::from("", "", "greenhouse-job-search") {
+            Some(project) => Self {
+                config: project.config_dir().to_path_buf(),
+                data: project.data_dir().to_path_buf(),
+                cache: project.cache_dir().to_path_buf(),
+                source: Source::Platform,
+            },
+            // No home directory to anchor to, so stay next to the working directory
+            None => Self::single(PathBuf::from(".greenhouse-job-search"), Source::Override),
+        }
+    }
+}
+
+// Before the platform directories everything lived under one hidden directory in $HOME
+fn legacy_dir() -> PathBuf {
+    let home = std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("."));
+    home.join(".greenhouse-job-search")
+}
+
+pub fn init(config_dir: Option<&Path>) {
+    let _ = DIRS.set(Dirs::resolve(config_dir));
+}
+
+pub fn set_persistent(persist: bool) {
+    PERSIST.store(persist, Ordering::Relaxed);
+}
+
+pub fn persistent() -> bool {
+    PERSIST.load(Ordering::Relaxed)
+}
+
+fn dirs() -> &'static Dirs {
+    DIRS.get_or_init(default_dirs)
+}
+
+#[cfg(not(test))]
+fn default_dirs() -> Dirs {
+    Dirs::resolve(None)
+}
+
+// Tests never read or write the real directories
+#[cfg(test)]
+fn default_dirs() -> Dirs {
+    let dir = std::env::temp_dir().join(format!("greenhouse-job-search-test-{}", std::process::id()));
+    Dirs::single(dir, Source::Override)
+}
+
+fn source() -> Source {
+    dirs().source
+}
+
+pub fn config_dir() -> &'static Path {
+    &dirs().config
+}
+
+pub fn data_dir() -> &'static Path {
+    &dirs().data
+}
+
+// Disposable copies of things that can be fetched again
+pub fn cache_dir() -> &'static Path {
+    &dirs().cache
+}
+
+// `dir/name`, creating `dir` first (owner-only on Unix) if it doesn't exist
+// yet and state is being kept
+fn file_in(dir: &Path, name: &str) -> PathBuf {
+    if persistent() && !dir.is_dir() {
+        let mut builder = std::fs::DirBuilder::new();
+        builder.recursive(true);
+        #[cfg(unix)]
+        std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+        // Callers report the real error when they fail to use the file
+        let _ = builder.create(dir);
+    }
+    dir.join(name)
+}
+
+pub fn database_path() -> PathBuf {
+    file_in(data_dir(), "jobs.db")
+}
+
+pub fn config_path() -> PathBuf {
+    file_in(config_dir(), "config.toml")
+}
+
+pub fn profile_path() -> PathBuf {
+    file_in(config_dir(), "profile.toml")
+}
+
+pub fn cover_letter_path() -> PathBuf {
+    file_in(config_dir(), "cover_letter.txt")
+}
+
+pub fn companies_path() -> PathBuf {
+    file_in(config_dir(), "companies.toml")
+}
+
+pub fn overrides_path() -> PathBuf {
+    file_in(config_dir(), "overrides.toml")
+}
+
+pub fn pending_notifications_path() -> PathBuf {
+    file_in(data_dir(), "pending_notifications.json")
+}
+
+pub fn hidden_jobs_path() -> PathBuf {
+    file_in(data_dir(), "hidden_jobs.json")
+}
+
+// --redact: which placeholder stands for which company, never exported
+pub fn redaction_key_path() -> PathBuf {
+    file_in(data_dir(), "redaction_key.json")
+}
+
+// --quiet-notify: held while a run is going, and where its output goes
+pub fn quiet_notify_lock_path() -> PathBuf {
+    file_in(data_dir(), "quiet_notify.lock")
+}
+
+pub fn quiet_notify_log_path() -> PathBuf {
+    file_in(data_dir(), "quiet_notify.log")
+}
+
+pub fn retry_queue_path() -> PathBuf {
+    file_in(data_dir(), "retry_queue.json")
+}
+
+// Not created here; saving a run creates it
+pub fn saved_runs_dir() -> PathBuf {
+    data_dir().join("runs")
+}
+
+// --dry-run: what each application would have sent; created by the first one
+pub fn dry_runs_dir() -> PathBuf {
+    data_dir().join("dry-runs")
+}
+
+// `e` in the job browser, unless [tui] export_dir says otherwise; created by the first export
+pub fn exports_dir() -> PathBuf {
+    data_dir().join("exports")
+}
+
+// School, degree and discipline lists per board; created by the first one fetched
+pub fn education_cache_dir() -> PathBuf {
+    cache_dir().join("education")
+}
+
+pub fn session_state_path() -> PathBuf {
+    file_in(data_dir(), "session.json")
+}
+
+pub fn watch_state_path() -> PathBuf {
+    file_in(data_dir(), "watch_state.json")
+}
+
+// `paths`: where everything is, without creating anything
+pub fn print_paths() {
+    match source() {
+        Source::Override => println!("{}Everything is kept in one folder (--config-dir / GREENHOUSE_JOB_SEARCH_DIR)", icons::FOLDER),
+        Source::Legacy => println!("{}Using the existing ~/.greenhouse-job-search folder for everything", icons::FOLDER),
+        Source::Platform => println!("{}Using the platform's standard directories", icons::FOLDER),
+    }
+    println!();
+    let status = |path: &Path| if path.exists() { "" } else { "  (not created yet)" };
+    let config = config_dir().join("config.toml");
+    let database = data_dir().join("jobs.db");
+    let entries = [
+        ("Config", config_dir()),
+        ("Data", data_dir()),
+        ("Cache", cache_dir()),
+        ("Settings", config.as_path()),
+        ("Job store", database.as_path()),
+    ];
+    for (label, path) in entries {
+        println!("{:<10} {}{}", label, path.display(), status(path));
+    }
+}
@@ -0,0 +1,272 @@
+use std::fmt::Write as _;
+
+// Cover letters as PDF, written out by hand: US Letter pages set in the
+// standard Helvetica fonts, which every reader has, so nothing is embedded.
+// Text goes through WinAnsiEncoding, which covers Latin-1 plus curly quotes,
+// dashes, bullets and the euro sign; anything else comes out as "?". Nothing
+// in the file depends on when or where it was written, so the same letter
+// always makes the same bytes and its hash identifies it.
+
+const PAGE_WIDTH: f32 = 612.0;
+const PAGE_HEIGHT: f32 = 792.0;
+const MARGIN: f32 = 72.0;
+const BODY_SIZE: f32 = 11.0;
+const BODY_LEADING: f32 = 15.0;
+const NAME_SIZE: f32 = 16.0;
+const CONTACT_SIZE: f32 = 9.5;
+const FOOTER_SIZE: f32 = 9.0;
+
+const REGULAR: &str = "F1";
+const BOLD: &str = "F2";
+
+// Helvetica's advance widths for ' ' through '~', in thousandths of the font size
+const ASCII_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278, // ' ' to '/'
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556, // '0' to '?'
+    1015, 667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722, 778, // '@' to 'O'
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469, 556, // 'P' to '_'
+    333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556, 556, // '`' to 'o'
+    556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584, // 'p' to '~'
+];
+
+struct Line {
+    font: &'static str,
+    size: f32,
+    text: String,
+    // Space above the line's baseline, from the one before
+    advance: f32,
+}
+
+// The letter under a header: the name in bold, then the contact details on one
+// line, wrapped to the margins and broken into as many pages as it takes. Pages
+// are numbered at the foot once there's more than one.
+pub fn letter(name: &str, contact: &[String], body: &str) -> Vec<u8> {
+    let width = PAGE_WIDTH - 2.0 * MARGIN;
+    let mut lines = Vec::new();
+    for text in wrap(name.trim(), NAME_SIZE, width) {
+        lines.push(Line { font: BOLD, size: NAME_SIZE, text, advance: NAME_SIZE * 1.25 });
+    }
+    for text in wrap(&contact.join(" \u{b7} "), CONTACT_SIZE, width) {
+        lines.push(Line { font: REGULAR, size: CONTACT_SIZE, text, advance: CONTACT_SIZE * 1.4 });
+    }
+    let mut gap = if lines.is_empty() { 0.0 } else { BODY_LEADING * 1.5 };
+    for paragraph in body.trim_end().lines() {
+        let wrapped = wrap(&paragraph.replace('\t', "    "), BODY_SIZE, width);
+        if wrapped.is_empty() {
+            gap += BODY_LEADING;
+        }
+        for text in wrapped {
+            lines.push(Line { font: REGULAR, size: BODY_SIZE, text, advance: BODY_LEADING + gap });
+            gap = 0.0;
+        }
+    }
+
+    let mut pages: Vec<Vec<(f32, &Line)>> = vec![Vec::new()];
+    let mut y = PAGE_HEIGHT - MARGIN;
+    for line in &lines {
+        let page = pages.last_mut().expect("there's always a page");
+        // The first line of a page starts at the top, whatever space it had before it
+        let advance = if page.is_empty() { line.size } else { line.advance };
+        if !page.is_empty() && y - advance < MARGIN {
+            pages.push(Vec::new());
+            y = PAGE_HEIGHT - MARGIN - line.size;
+        } else {
+            y -= advance;
+        }
+        pages.last_mut().expect("there's always a page").push((y, line));
+    }
+
+    let contents: Vec<String> = pages
+        .iter()
+        .enumerate()
+        .map(|(i, page)| {
+            let mut stream = String::new();
+            for (y, line) in page {
+                text_op(&mut stream, line.font, line.size, MARGIN, *y, &line.text);
+            }
+            if pages.len() > 1 {
+                let footer = format!("Page {} of {}", i + 1, pages.len());
+                let x = (PAGE_WIDTH - text_width(&footer, FOOTER_SIZE)) / 2.0;
+                text_op(&mut stream, REGULAR, FOOTER_SIZE, x, MARGIN / 2.0, &footer);
+            }
+            stream
+        })
+        .collect();
+    document(&contents)
+}
+
+// Objects 1 and 2 are the catalog and the page tree, 3 and 4 the fonts, then
+// each page and its content stream
+fn document(contents: &[String]) -> Vec<u8> {
+    let page_ids: Vec<usize> = (0..contents.len()).map(|i| 5 + 2 * i).collect();
+    let kids: Vec<String> = page_ids.iter().map(|id| format!("{} 0 R", id)).collect();
+    let mut objects = vec![
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        format!("<< /Type /Pages /Kids [{}] /Count {} >>", kids.join(" "), contents.len()),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>".to_string(),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>".to_string(),
+    ];
+    for (content, page_id) in contents.iter().zip(&page_ids) {
+        objects.push(format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Resources << /Font << /{} 3 0 R /{} 4 0 R >> >> /Contents {} 0 R >>",
+            PAGE_WIDTH, PAGE_HEIGHT, REGULAR, BOLD, page_id + 1
+        ));
+        objects.push(format!("<< /Length {} >>\nstream\n{}endstream", content.len(), content));
+    }
+
+    // The binary comment marks the file as binary for tools that check
+    let mut out = b"%PDF-1.4\n%\xe2\xe3\xcf\xd3\n".to_vec();
+    let mut offsets = Vec::new();
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(out.len());
+        out.extend_from_slice(format!("{} 0 obj\n{}\nendobj\n", i + 1, object).as_bytes());
+    }
+    let xref = out.len();
+    let mut trailer = format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+    for offset in offsets {
+        let _ = writeln!(trailer, "{:010} 00000 n ", offset);
+    }
+    let _ = write!(trailer, "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n", objects.len() + 1, xref);
+    out.extend_from_slice(trailer.as_bytes());
+    out
+}
+
+fn text_op(stream: &mut String, font: &str, size: f32, x: f32, y: f32, text: &str) {
+    let _ = writeln!(stream, "BT /{} {} Tf {:.2} {:.2} Td ({}) Tj ET", font, size, x, y, escape(text));
+}
+
+// A PDF string literal's contents: WinAnsi bytes, anything outside ASCII as an octal escape
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for byte in text.chars().map(win_ansi) {
+        match byte {
+            b'\\' | b'(' | b')' => {
+                out.push('\\');
+                out.push(byte as char);
+            }
+            0x20..=0x7e => out.push(byte as char),
+            _ => {
+                let _ = write!(out, "\\{:03o}", byte);
+            }
+        }
+    }
+    out
+}
+
+// The WinAnsiEncoding byte for `c`, or "?" without one
+fn win_ansi(c: char) -> u8 {
+    match c {
+        ' '..='~' | '\u{a0}'..='\u{ff}' => c as u8,
+        '\u{20ac}' => 0x80,
+        '\u{201a}' => 0x82,
+        '\u{192}' => 0x83,
+        '\u{201e}' => 0x84,
+        '\u{2026}' => 0x85,
+        '\u{2020}' => 0x86,
+        '\u{2021}' => 0x87,
+        '\u{2c6}' => 0x88,
+        '\u{2030}' => 0x89,
+        '\u{160}' => 0x8a,
+        '\u{2039}' => 0x8b,
+        '\u{152}' => 0x8c,
+        '\u{17d}' => 0x8e,
+        '\u{2018}' => 0x91,
+        '\u{2019}' => 0x92,
+        '\u{201c}' => 0x93,
+        '\u{201d}' => 0x94,
+        '\u{2022}' => 0x95,
+        '\u{2013}' => 0x96,
+        '\u{2014}' => 0x97,
+        '\u{2dc}' => 0x98,
+        '\u{2122}' => 0x99,
+        '\u{161}' => 0x9a,
+        '\u{203a}' => 0x9b,
+        '\u{153}' => 0x9c,
+        '\u{17e}' => 0x9e,
+        '\u{178}' => 0x9f,
+        _ => b'?',
+    }
+}
+
+// Close enough for wrapping: exact for ASCII, a typical letter's width otherwise
+fn char_width(c: char) -> f32 {
+    let units = match win_ansi(c) {
+        byte @ 0x20..=0x7e => ASCII_WIDTHS[(byte - 0x20) as usize],
+        0x85 | 0x89 | 0x97 | 0x99 => 1000,
+        0x91 | 0x92 => 222,
+        0x95 => 350,
+        _ => 556,
+    };
+    f32::from(units) / 1000.0
+}
+
+fn text_width(text: &str, size: f32) -> f32 {
+    text.chars().map(char_width).sum::<f32>() * size
+}
+
+// `text` broken at spaces into lines no wider than `width`; a word too long
+// for a line of its own is broken wherever it has to be. Empty text makes no lines.
+fn wrap(text: &str, size: f32, width: f32) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split(' ').filter(|word| !word.is_empty()) {
+        let candidate = if line.is_empty() { word.to_string() } else { format!("{} {}", line, word) };
+        if text_width(&candidate, size) <= width {
+            line = candidate;
+            continue;
+        }
+        if !line.is_empty() {
+            lines.push(std::mem::take(&mut line));
+        }
+        for c in word.chars() {
+            if !line.is_empty() && text_width(&format!("{}{}", line, c), size) > width {
+                lines.push(std::mem::take(&mut line));
+            }
+            line.push(c);
+        }
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn count(haystack: &[u8], needle: &[u8]) -> usize {
+        haystack.windows(needle.len()).filter(|window| *window == needle).count()
+    }
+
+    #[test]
+    fn long_letters_run_onto_numbered_pages_and_render_the_same_every_time() {
+        let body = "I'd like to apply for the role. ".repeat(20) + "\n\n";
+        let body = body.repeat(12);
+        let contact = vec!["jane@example.com".to_string(), "+14155550100".to_string()];
+        let pdf = letter("Jane Doe", &contact, &body);
+        assert!(pdf.starts_with(b"%PDF-1.4"));
+        assert!(pdf.ends_with(b"%%EOF\n"));
+        let pages = count(&pdf, b"/Type /Page ");
+        assert!(pages >= 2, "{} page(s)", pages);
+        assert_eq!(count(&pdf, format!("(Page {} of {})", pages, pages).as_bytes()), 1);
+        assert_eq!(pdf, letter("Jane Doe", &contact, &body));
+        assert_ne!(pdf, letter("Jane Doe", &contact, &body.replace("role", "job")));
+
+        // A short letter is one unnumbered page
+        let short = letter("Jane Doe", &contact, "Dear Figma team,\n\nThanks.");
+        assert_eq!(count(&short, b"/Type /Page "), 1);
+        assert_eq!(count(&short, b"(Page "), 0);
+    }
+
+    #[test]
+    fn text_is_encoded_as_win_ansi_and_wrapped_to_the_margins() {
+        assert_eq!(escape("Café (Zürich) — “naïve” \\ 日本"), "Caf\\351 \\(Z\\374rich\\) \\227 \\223na\\357ve\\224 \\\\ ??");
+        let width = PAGE_WIDTH - 2.0 * MARGIN;
+        let lines = wrap(&"word ".repeat(200), BODY_SIZE, width);
+        assert!(lines.len() > 1);
+        assert!(lines.iter().all(|line| text_width(line, BODY_SIZE) <= width));
+        assert_eq!(wrap(&"x".repeat(300), BODY_SIZE, width).concat(), "x".repeat(300));
+        assert!(wrap("   ", BODY_SIZE, width).is_empty());
+    }
+}
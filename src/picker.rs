@@ -0,0 +1,132 @@
+use crate::{companies, icons};
+use crossterm::{
+    event::{self, Event, KeyCode},
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    ExecutableCommand,
+};
+use ratatui::{
+    backend::CrosstermBackend,
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    Frame, Terminal,
+};
+use std::error::Error;
+use std::io;
+
+// --pick-boards: a checklist of the discovered board tokens, shown before the
+// first search so a noisy discovery can be trimmed by hand. Every board
+// starts checked.
+
+struct BoardPicker {
+    boards: Vec<String>,
+    checked: Vec<bool>,
+    list_state: ListState,
+}
+
+impl BoardPicker {
+    fn new(mut boards: Vec<String>) -> Self {
+        boards.sort();
+        let checked = vec![true; boards.len()];
+        let mut list_state = ListState::default();
+        list_state.select((!boards.is_empty()).then_some(0));
+        BoardPicker { boards, checked, list_state }
+    }
+
+    fn move_selection(&mut self, down: bool) {
+        let Some(current) = self.list_state.selected() else {
+            return;
+        };
+        let next = if down { (current + 1) % self.boards.len() } else { current.checked_sub(1).unwrap_or(self.boards.len() - 1) };
+        self.list_state.select(Some(next));
+    }
+
+    fn toggle_current(&mut self) {
+        if let Some(current) = self.list_state.selected() {
+            self.checked[current] = !self.checked[current];
+        }
+    }
+
+    // Check everything, or clear everything when it's all checked already
+    fn toggle_all(&mut self) {
+        let all = self.checked.iter().all(|&checked| checked);
+        self.checked.iter_mut().for_each(|checked| *checked = !all);
+    }
+
+    fn picked(&self) -> Vec<String> {
+        self.boards.iter().zip(&self.checked).filter(|(_, &checked)| checked).map(|(board, _)| board.clone()).collect()
+    }
+
+    fn render(&mut self, f: &mut Frame) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([Constraint::Length(3), Constraint::Min(0), Constraint::Length(3)])
+            .split(f.area());
+
+        let picked = self.checked.iter().filter(|&&checked| checked).count();
+        let title = Paragraph::new(format!("{}PICK BOARDS - {} of {} selected", icons::TARGET, picked, self.boards.len()))
+            .block(Block::default().borders(Borders::ALL))
+            .style(Style::default().fg(Color::Cyan));
+        f.render_widget(title, chunks[0]);
+
+        let items: Vec<ListItem> = self
+            .boards
+            .iter()
+            .zip(&self.checked)
+            .map(|(board, &checked)| {
+                let mark = if checked { "[x] " } else { "[ ] " };
+                ListItem::new(Line::from(vec![
+                    Span::styled(mark, Style::default().fg(if checked { Color::Green } else { Color::Gray })),
+                    Span::raw(board.as_str()),
+                    Span::styled(
+                        companies::mapped_name(board).map(|name| format!("  {}", name)).unwrap_or_default(),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                ]))
+            })
+            .collect();
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title("Boards"))
+            .highlight_style(Style::default().bg(Color::LightBlue).fg(Color::Black).add_modifier(Modifier::BOLD))
+            .highlight_symbol("→ ");
+        f.render_stateful_widget(list, chunks[1], &mut self.list_state);
+
+        let controls = Paragraph::new(format!("{}↑/↓: Navigate | Space: Check/Uncheck | a: All/None | Enter: Search Checked | q: Cancel", icons::KEYS))
+            .block(Block::default().borders(Borders::ALL))
+            .style(Style::default().fg(Color::Gray));
+        f.render_widget(controls, chunks[2]);
+    }
+
+    // The checked boards on Enter, or None when cancelled
+    fn run_app(&mut self, terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<Option<Vec<String>>, Box<dyn Error>> {
+        loop {
+            terminal.draw(|f| self.render(f))?;
+            if let Event::Key(key) = event::read()? {
+                match key.code {
+                    KeyCode::Up => self.move_selection(false),
+                    KeyCode::Down => self.move_selection(true),
+                    KeyCode::Char(' ') => self.toggle_current(),
+                    KeyCode::Char('a') => self.toggle_all(),
+                    KeyCode::Enter => return Ok(Some(self.picked())),
+                    KeyCode::Char('q') | KeyCode::Esc => return Ok(None),
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+pub fn pick_boards(boards: Vec<String>) -> Result<Option<Vec<String>>, Box<dyn Error>> {
+    let mut picker = BoardPicker::new(boards);
+    enable_raw_mode()?;
+    io::stdout().execute(EnterAlternateScreen)?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(io::stdout()))?;
+
+    let result = picker.run_app(&mut terminal);
+
+    disable_raw_mode()?;
+    io::stdout().execute(LeaveAlternateScreen)?;
+    result
+}
@@ -0,0 +1,223 @@
+use crate::cli::{HistogramBucket, StatsFormat};
+use crate::histogram::bucket_start;
+use crate::store::{ApplicationEvent, ApplicationFilter, ApplicationRecord, ApplicationStatus, JobStore};
+use crate::{icons, locale, JobResult};
+use chrono::{DateTime, Duration, Local, NaiveDate, Utc};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+use std::error::Error;
+
+// `stats`: how the job hunt itself is going, from the application log and the
+// job store. Weeks are bucketed in the local timezone and start on Monday, so
+// an application sent late on Sunday evening counts towards that week.
+
+#[derive(Debug, Serialize)]
+struct PipelineStats {
+    applications: usize,
+    // Every week from the first application to the current week, gaps included
+    applications_per_week: Vec<WeekCount>,
+    // Most applications first
+    applications_by_company: Vec<CompanyCount>,
+    // Applications that went out, by their latest status, in pipeline order
+    applications_by_status: Vec<StatusCount>,
+    tagged_jobs: usize,
+    tagged_and_applied: usize,
+    // `tagged_and_applied / tagged_jobs`, absent with nothing tagged
+    tagged_to_applied_ratio: Option<f64>,
+    // Only applications to jobs the store saw before applying count
+    median_days_first_seen_to_applied: Option<f64>,
+    open_tracked_jobs: BTreeMap<&'static str, usize>,
+}
+
+#[derive(Debug, Serialize)]
+struct WeekCount {
+    week_start: NaiveDate,
+    applications: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct StatusCount {
+    status: ApplicationStatus,
+    applications: usize,
+    // From applying to the status first being recorded; for `applied`, days waited so far
+    median_days: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+struct CompanyCount {
+    company: String,
+    applications: usize,
+}
+
+pub fn run(store: &JobStore, format: StatsFormat) -> Result<(), Box<dyn Error>> {
+    let applications = store.applications(&ApplicationFilter::default())?;
+    let mut tracked = store.tracked_jobs()?;
+    store.annotate_tags(&mut tracked)?;
+    let events = store.application_events()?;
+    let stats = collect(&applications, &events, &tracked, Local::now());
+
+    match format {
+        StatsFormat::Json => println!("{}", serde_json::to_string_pretty(&stats)?),
+        StatsFormat::Text => print_text(&stats),
+    }
+    Ok(())
+}
+
+fn collect(applications: &[ApplicationRecord], events: &[ApplicationEvent], tracked: &[JobResult], now: DateTime<Local>) -> PipelineStats {
+    let today = now.date_naive();
+    let applied: HashSet<(&str, u64)> =
+        applications.iter().map(|record| (record.board_token.as_str(), record.job_id)).collect();
+    let is_applied = |job: &JobResult| applied.contains(&(job.board_token.as_str(), job.id));
+
+    let mut weeks: BTreeMap<NaiveDate, usize> = BTreeMap::new();
+    for record in applications {
+        if let Some(applied_at) = parse(&record.applied_at) {
+            let day = applied_at.with_timezone(&Local).date_naive();
+            *weeks.entry(bucket_start(day, HistogramBucket::Week)).or_default() += 1;
+        }
+    }
+    let mut applications_per_week = Vec::new();
+    if let Some(&first) = weeks.keys().next() {
+        let mut week = first;
+        while week <= bucket_start(today, HistogramBucket::Week) {
+            applications_per_week.push(WeekCount { week_start: week, applications: weeks.get(&week).copied().unwrap_or(0) });
+            week += Duration::weeks(1);
+        }
+    }
+
+    let mut by_company: BTreeMap<&str, usize> = BTreeMap::new();
+    for record in applications {
+        *by_company.entry(record.company.as_str()).or_default() += 1;
+    }
+    let mut applications_by_company: Vec<CompanyCount> = by_company
+        .into_iter()
+        .map(|(company, applications)| CompanyCount { company: company.to_string(), applications })
+        .collect();
+    applications_by_company.sort_by_key(|entry| std::cmp::Reverse(entry.applications));
+
+    let mut by_status: BTreeMap<ApplicationStatus, (usize, Vec<f64>)> = BTreeMap::new();
+    for record in applications.iter().filter(|record| record.went_out()) {
+        let status = record.current_status();
+        let reached = match status {
+            ApplicationStatus::Applied => Some(now.with_timezone(&Utc)),
+            _ => events
+                .iter()
+                .find(|event| event.application_id == record.id && event.status.as_deref() == Some(status.as_str()))
+                .and_then(|event| parse(&event.at)),
+        };
+        let entry = by_status.entry(status).or_default();
+        entry.0 += 1;
+        entry.1.extend(reached.zip(parse(&record.applied_at)).map(|(reached, applied)| (reached - applied).num_seconds().max(0) as f64 / 86_400.0));
+    }
+    let applications_by_status: Vec<StatusCount> = by_status
+        .into_iter()
+        .map(|(status, (applications, mut days))| {
+            days.sort_by(f64::total_cmp);
+            StatusCount { status, applications, median_days: median(&days) }
+        })
+        .collect();
+
+    let tagged: Vec<&JobResult> = tracked.iter().filter(|job| !job.tags.is_empty()).collect();
+    let tagged_and_applied = tagged.iter().filter(|job| is_applied(job)).count();
+
+    let mut days: Vec<f64> = applications
+        .iter()
+        .filter_map(|record| {
+            let job = tracked.iter().find(|job| job.board_token == record.board_token && job.id == record.job_id)?;
+            let waited = parse(&record.applied_at)? - job.first_seen?;
+            Some(waited.num_seconds().max(0) as f64 / 86_400.0)
+        })
+        .collect();
+    days.sort_by(f64::total_cmp);
+
+    let mut open_tracked_jobs = BTreeMap::new();
+    for job in tracked.iter().filter(|job| job.closed_at.is_none()) {
+        let status = if is_applied(job) { "applied" } else { "tagged" };
+        *open_tracked_jobs.entry(status).or_default() += 1;
+    }
+
+    PipelineStats {
+        applications: applications.len(),
+        applications_per_week,
+        applications_by_company,
+        applications_by_status,
+        tagged_jobs: tagged.len(),
+        tagged_and_applied,
+        tagged_to_applied_ratio: (!tagged.is_empty()).then(|| tagged_and_applied as f64 / tagged.len() as f64),
+        median_days_first_seen_to_applied: median(&days),
+        open_tracked_jobs,
+    }
+}
+
+fn parse(raw: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(raw).ok().map(|dt| dt.with_timezone(&Utc))
+}
+
+// Of an already sorted list
+fn median(sorted: &[f64]) -> Option<f64> {
+    let mid = sorted.len() / 2;
+    match sorted.len() {
+        0 => None,
+        n if n % 2 == 0 => Some((sorted[mid - 1] + sorted[mid]) / 2.0),
+        _ => Some(sorted[mid]),
+    }
+}
+
+fn print_text(stats: &PipelineStats) {
+    let locale = locale::current();
+    if stats.applications == 0 && stats.tagged_jobs == 0 {
+        println!("{}Nothing to report yet: no applications recorded and no jobs tagged.", icons::EMPTY);
+        return;
+    }
+
+    println!("{}Applications: {}", icons::APPLICATIONS, locale.count(stats.applications));
+    if !stats.applications_per_week.is_empty() {
+        println!("\n{}Applications per week:", icons::CALENDAR);
+        println!("   {:<12} {:>5}", "Week of", "Count");
+        for week in &stats.applications_per_week {
+            println!("   {:<12} {:>5}", week.week_start.format(locale.date_pattern()), locale.count(week.applications));
+        }
+    }
+    if !stats.applications_by_company.is_empty() {
+        println!("\n{}Applications by company:", icons::COMPANY);
+        for entry in &stats.applications_by_company {
+            println!("   {:>5}  {}", locale.count(entry.applications), entry.company);
+        }
+    }
+
+    if !stats.applications_by_status.is_empty() {
+        println!("\n{}Applications by status:", icons::STATUS);
+        for entry in &stats.applications_by_status {
+            let elapsed = match (entry.status, entry.median_days) {
+                (_, None) => String::new(),
+                (ApplicationStatus::Applied, Some(days)) => format!("  waiting a median {:.1} day(s) so far", days),
+                (_, Some(days)) => format!("  a median {:.1} day(s) after applying", days),
+            };
+            println!("   {:>5}  {:<10}{}", locale.count(entry.applications), entry.status.as_str(), elapsed);
+        }
+    }
+
+    println!("\n{}Tagged → applied:", icons::TAGS);
+    match stats.tagged_to_applied_ratio {
+        Some(ratio) => println!(
+            "   {} of {} tagged job(s) applied to ({:.0}%)",
+            locale.count(stats.tagged_and_applied), locale.count(stats.tagged_jobs), ratio * 100.0
+        ),
+        None => println!("   No jobs tagged yet"),
+    }
+
+    println!("\n{}First seen → applied:", icons::TIMER);
+    match stats.median_days_first_seen_to_applied {
+        Some(days) => println!("   Median {:.1} day(s)", days),
+        None => println!("   No applications to jobs the store had seen first"),
+    }
+
+    println!("\n{}Open tracked jobs:", icons::TITLE);
+    if stats.open_tracked_jobs.is_empty() {
+        println!("   None");
+    }
+    for (status, count) in &stats.open_tracked_jobs {
+        let label = if *status == "tagged" { "tagged, not applied yet" } else { status };
+        println!("   {:>5}  {}", locale.count(count), label);
+    }
+}
@@ -0,0 +1,399 @@
+use crate::{config, contact, icons, paths};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+
+// The applicant's details, from profile.toml in the config directory. Every
+// application form is prefilled from it: the standard contact and resume
+// fields by name, links and work-authorization questions by their labels,
+// and anything else from `[answers]`, keyed by question label or field name.
+//
+//   first_name = "Jane"
+//   last_name = "Doe"
+//   email = "jane@example.com"
+//   resume = "/home/jane/resume.pdf"
+//   linkedin = "https://www.linkedin.com/in/janedoe"
+//
+//   [work_authorization]
+//   authorized = true
+//   requires_sponsorship = false
+//
+//   [answers]
+//   "How did you hear about us?" = "Greenhouse job board"
+//
+// Voluntary self-identification (EEOC) questions take their answers from
+// `[demographics]`, keyed the same way; the ones it doesn't list are answered
+// with the form's "decline to self-identify" choice.
+//
+//   [demographics]
+//   "VeteranStatus" = "I am not a protected veteran"
+//
+// Named profiles under `[profiles.<name>]` list only what differs from the
+// main profile, and everything else is taken from it; tables like `[answers]`
+// are merged key by key. `--profile-name` or the confirm screen picks one.
+//
+//   [profiles.mgmt]
+//   resume = "/home/jane/resume-management.pdf"
+//   website = "https://janedoe.com/leadership"
+
+// Greenhouse takes larger files, but a resume over this is almost certainly the wrong file
+pub const MAX_RESUME_BYTES: u64 = 10 * 1024 * 1024;
+pub const RESUME_EXTENSIONS: &[&str] = &["pdf", "docx"];
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Profile {
+    pub first_name: String,
+    pub last_name: String,
+    pub email: String,
+    pub phone: Option<String>,
+    // Where you're based, for "Location (City)" questions
+    pub location: Option<String>,
+    pub linkedin: Option<String>,
+    pub github: Option<String>,
+    pub website: Option<String>,
+    pub resume: Option<PathBuf>,
+    #[serde(default)]
+    pub work_authorization: WorkAuthorization,
+    // Answers to the boards' own questions, keyed by question label or field name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub answers: BTreeMap<String, String>,
+    // Answers to self-identification questions, keyed by question label or field name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub demographics: BTreeMap<String, String>,
+    // {{custom_paragraph}} in the cover letter, keyed by board token or company name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub cover_letter_paragraphs: BTreeMap<String, String>,
+}
+
+// The main profile is called this wherever profiles are named
+pub const MAIN_PROFILE: &str = "default";
+
+// profile.toml with every named profile merged over the main one. Each named
+// profile is parsed and checked on its own, so a broken one is only unusable
+// itself.
+#[derive(Debug, Clone)]
+pub struct Profiles {
+    pub main: Profile,
+    pub named: BTreeMap<String, Result<Profile, String>>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WorkAuthorization {
+    // "Are you legally authorized to work in ...?"
+    pub authorized: Option<bool>,
+    // "Will you now or in the future require sponsorship ...?"
+    pub requires_sponsorship: Option<bool>,
+}
+
+impl Profiles {
+    // None when there's no profile.toml yet
+    pub fn load() -> Result<Option<Profiles>, String> {
+        let path = paths::profile_path();
+        let text = match std::fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(format!("{}: {}", path.display(), e)),
+        };
+        Profiles::parse(&text).map(Some).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    pub fn parse(text: &str) -> Result<Profiles, String> {
+        let mut table: toml::Table = toml::from_str(text).map_err(|e| e.to_string())?;
+        let named = match table.remove("profiles") {
+            Some(toml::Value::Table(named)) => named,
+            Some(_) => return Err("`profiles` should be a table of named profiles".to_string()),
+            None => toml::Table::new(),
+        };
+        let main: Profile = table.clone().try_into().map_err(|e: toml::de::Error| e.to_string())?;
+        let named = named
+            .into_iter()
+            .map(|(name, overrides)| {
+                let profile = match overrides {
+                    _ if name == MAIN_PROFILE => Err(format!("\"{}\" is the main profile's name; pick another", MAIN_PROFILE)),
+                    toml::Value::Table(overrides) => {
+                        let mut merged = table.clone();
+                        merge(&mut merged, overrides);
+                        merged.try_into().map_err(|e: toml::de::Error| e.to_string().trim().replace('\n', " "))
+                    }
+                    _ => Err("should be a table of fields".to_string()),
+                };
+                (name, profile)
+            })
+            .collect();
+        Ok(Profiles { main, named })
+    }
+
+    // The main profile first, including named profiles that can't be used
+    pub fn names(&self) -> Vec<&str> {
+        std::iter::once(MAIN_PROFILE).chain(self.named.keys().map(String::as_str)).collect()
+    }
+
+    // The main profile for None or "default"
+    pub fn get(&self, name: Option<&str>) -> Result<&Profile, String> {
+        match name {
+            None | Some(MAIN_PROFILE) => Ok(&self.main),
+            Some(name) => match self.named.get(name) {
+                Some(Ok(profile)) => Ok(profile),
+                Some(Err(e)) => Err(format!("profile \"{}\" in profile.toml: {}", name, e)),
+                None => Err(format!("there's no profile named \"{}\"; profile.toml has {}", name, self.names().join(", "))),
+            },
+        }
+    }
+}
+
+// Values in `overrides` replace those in `base`, except tables, which are merged
+fn merge(base: &mut toml::Table, overrides: toml::Table) {
+    for (key, value) in overrides {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base)), toml::Value::Table(overrides)) => merge(base, overrides),
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+impl Profile {
+    // Everything wrong with the profile, one message per field
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        for (field, value) in [("first_name", &self.first_name), ("last_name", &self.last_name)] {
+            if value.trim().is_empty() {
+                problems.push(format!("{} is empty", field));
+            }
+        }
+        if let Err(e) = check_email(&self.email) {
+            problems.push(format!("email: {}", e));
+        }
+        if let Some(Err(e)) = self.phone.as_deref().map(check_phone) {
+            problems.push(format!("phone: {}", e));
+        }
+        if let Some(Err(e)) = self.resume.as_deref().map(check_resume) {
+            problems.push(format!("resume: {}", e));
+        }
+        problems
+    }
+
+    // The contact fields as forms take them (see contact.rs); any that can't
+    // be fixed without guessing are left as written, for `warnings` to report
+    pub fn normalized(&self, phone_country: Option<&str>) -> Profile {
+        let mut profile = self.clone();
+        if let Ok(email) = contact::email(&self.email) {
+            profile.email = email;
+        }
+        let normalize = |value: &mut Option<String>, normalize: &dyn Fn(&str) -> Result<String, contact::Invalid>| {
+            if let Some(Ok(normalized)) = value.as_deref().map(normalize) {
+                *value = Some(normalized);
+            }
+        };
+        normalize(&mut profile.phone, &|phone| contact::phone(phone, phone_country));
+        normalize(&mut profile.linkedin, &contact::linkedin);
+        normalize(&mut profile.github, &contact::github);
+        normalize(&mut profile.website, &contact::website);
+        profile
+    }
+
+    // Contact fields a form would likely reject, each with the fix when there's
+    // an obvious one. Fields `problems` already reports aren't repeated.
+    pub fn warnings(&self, phone_country: Option<&str>) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if check_email(&self.email).is_ok() {
+            if let Err(invalid) = contact::email(&self.email) {
+                warnings.push(format!("email: {}", invalid));
+            }
+        }
+        if let Some(phone) = self.phone.as_deref().filter(|phone| check_phone(phone).is_ok()) {
+            if let Err(invalid) = contact::phone(phone, phone_country) {
+                warnings.push(format!("phone: {}", invalid));
+            }
+        }
+        let mut check_link = |field: &str, value: &Option<String>, normalize: fn(&str) -> Result<String, contact::Invalid>| {
+            if let Some(Err(invalid)) = value.as_deref().map(normalize) {
+                warnings.push(format!("{}: {}", field, invalid));
+            }
+        };
+        check_link("linkedin", &self.linkedin, contact::linkedin);
+        check_link("github", &self.github, contact::github);
+        check_link("website", &self.website, contact::website);
+        warnings
+    }
+}
+
+// At startup: a broken profile is reported, not fatal, since most commands
+// don't need it. Each profile is reported separately; only the ones with
+// problems are held back.
+pub fn check_at_startup() {
+    let profiles = match Profiles::load() {
+        Ok(Some(profiles)) => profiles,
+        Ok(None) => return,
+        Err(e) => {
+            eprintln!("{}Ignoring the applicant profile: {}", icons::WARNING, e);
+            return;
+        }
+    };
+    let report = |name: &str, problems: Vec<String>| {
+        if problems.is_empty() {
+            return;
+        }
+        let which = if name == MAIN_PROFILE { String::new() } else { format!(" (profile \"{}\")", name) };
+        eprintln!(
+            "{}{}{} has problems; applications can't be submitted with it until they're fixed:",
+            icons::WARNING,
+            paths::profile_path().display(),
+            which
+        );
+        for problem in problems {
+            eprintln!("   • {}", problem);
+        }
+    };
+    report(MAIN_PROFILE, profiles.main.problems());
+    for (name, profile) in &profiles.named {
+        report(name, profile.as_ref().map_or_else(|e| vec![e.clone()], Profile::problems));
+    }
+}
+
+pub fn check_email(email: &str) -> Result<(), String> {
+    let email = email.trim();
+    let Some((local, domain)) = email.split_once('@') else {
+        return Err(format!("\"{}\" has no @", email));
+    };
+    if local.is_empty() || domain.contains('@') || !domain.contains('.') || domain.starts_with('.') || domain.ends_with('.') {
+        return Err(format!("\"{}\" doesn't look like an email address", email));
+    }
+    if email.chars().any(char::is_whitespace) {
+        return Err(format!("\"{}\" contains spaces", email));
+    }
+    Ok(())
+}
+
+// Digits with the usual separators, 7 to 15 digits long (the E.164 maximum)
+pub fn check_phone(phone: &str) -> Result<(), String> {
+    if let Some(bad) = phone.chars().find(|c| !(c.is_ascii_digit() || " +-().".contains(*c))) {
+        return Err(format!("\"{}\" contains '{}'; use digits, spaces, + - ( ) and .", phone, bad));
+    }
+    let digits = phone.chars().filter(char::is_ascii_digit).count();
+    if !(7..=15).contains(&digits) {
+        return Err(format!("\"{}\" has {} digits; phone numbers have 7 to 15", phone, digits));
+    }
+    Ok(())
+}
+
+pub fn check_resume(path: &Path) -> Result<(), String> {
+    let extension = path.extension().and_then(|ext| ext.to_str()).map(str::to_lowercase).unwrap_or_default();
+    if !RESUME_EXTENSIONS.contains(&extension.as_str()) {
+        return Err(format!("{} should be a {} file", path.display(), RESUME_EXTENSIONS.join(" or ")));
+    }
+    let metadata = std::fs::metadata(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    if !metadata.is_file() {
+        return Err(format!("{} isn't a file", path.display()));
+    }
+    if metadata.len() > MAX_RESUME_BYTES {
+        return Err(format!("{} is {} MB; the limit is {} MB", path.display(), metadata.len() / (1024 * 1024), MAX_RESUME_BYTES / (1024 * 1024)));
+    }
+    Ok(())
+}
+
+// `profile init`: ask for each field, checking the ones that can be checked as
+// they're entered. Contact fields are saved as forms take them, and a likely
+// fix for a wrong one is offered as the default for the next try.
+pub fn init(force: bool) -> Result<(), Box<dyn Error>> {
+    let path = paths::profile_path();
+    if path.exists() && !force {
+        return Err(format!("{} already exists; pass --force to replace it", path.display()).into());
+    }
+    println!("{}Creating {}", icons::FORM, path.display());
+    println!("   Press Enter to skip optional fields.\n");
+
+    let phone_country = config::Config::load().applications.phone_country;
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    // `check` gives back the value to save, or what's wrong with it
+    type Check<'a> = &'a dyn Fn(&str) -> Result<String, contact::Invalid>;
+    let mut ask = |prompt: &str, required: bool, check: Check| -> io::Result<Option<String>> {
+        let mut suggestion = None;
+        loop {
+            print!("{}{}: ", prompt, if required { "" } else { " (optional)" });
+            io::stdout().flush()?;
+            let Some(line) = lines.next() else {
+                return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "input ended before the profile was complete"));
+            };
+            let answer = line?.trim().to_string();
+            let answer = match (answer.is_empty(), suggestion.take()) {
+                (false, _) => answer,
+                (true, Some(suggested)) => suggested,
+                (true, None) if required => {
+                    println!("   This one is required.");
+                    continue;
+                }
+                (true, None) => return Ok(None),
+            };
+            match check(&answer) {
+                Ok(value) => {
+                    if value != answer {
+                        println!("   Saved as {}", value);
+                    }
+                    return Ok(Some(value));
+                }
+                Err(invalid) => {
+                    println!("   {}", invalid.message);
+                    if let Some(suggested) = invalid.suggestion {
+                        println!("   Did you mean {}? Press Enter to use it, or type another", suggested);
+                        suggestion = Some(suggested);
+                    }
+                }
+            }
+        }
+    };
+    let any = |answer: &str| Ok(answer.to_string());
+    let email = |answer: &str| {
+        check_email(answer)?;
+        contact::email(answer)
+    };
+    let phone = |answer: &str| {
+        check_phone(answer)?;
+        contact::phone(answer, phone_country.as_deref())
+    };
+    let resume = |answer: &str| {
+        check_resume(Path::new(answer))?;
+        Ok(answer.to_string())
+    };
+    let yes_no = |answer: &str| match answer.to_lowercase().as_str() {
+        "y" | "yes" | "n" | "no" => Ok(answer.to_string()),
+        _ => Err(contact::Invalid::from("answer yes or no".to_string())),
+    };
+    let as_bool = |answer: Option<String>| answer.map(|answer| answer.to_lowercase().starts_with('y'));
+
+    let profile = Profile {
+        first_name: ask("First name", true, &any)?.unwrap_or_default(),
+        last_name: ask("Last name", true, &any)?.unwrap_or_default(),
+        email: ask("Email", true, &email)?.unwrap_or_default(),
+        phone: ask("Phone, with its country code, e.g. +1 415 555 0100", false, &phone)?,
+        location: ask("Location, e.g. San Francisco, CA", false, &any)?,
+        linkedin: ask("LinkedIn URL", false, &contact::linkedin)?,
+        github: ask("GitHub URL", false, &contact::github)?,
+        website: ask("Website or portfolio URL", false, &contact::website)?,
+        resume: ask("Resume path (.pdf or .docx)", false, &resume)?.map(PathBuf::from),
+        work_authorization: WorkAuthorization {
+            authorized: as_bool(ask("Are you authorized to work where you're applying? (yes/no)", false, &yes_no)?),
+            requires_sponsorship: as_bool(ask("Will you need visa sponsorship? (yes/no)", false, &yes_no)?),
+        },
+        answers: BTreeMap::new(),
+        demographics: BTreeMap::new(),
+        cover_letter_paragraphs: BTreeMap::new(),
+    };
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let text = format!(
+        "{}\n# Answers to other questions, keyed by question label or field name:\n# [answers]\n# \"How did you hear about us?\" = \"Greenhouse job board\"\n\n# Self-identification questions not listed here are declined:\n# [demographics]\n# \"Gender\" = \"Decline To Self Identify\"\n",
+        toml::to_string(&profile)?
+    );
+    let tmp = path.with_extension("toml.tmp");
+    std::fs::write(&tmp, text)?;
+    std::fs::rename(&tmp, &path)?;
+    println!("\n{}Saved {}", icons::DONE, path.display());
+    Ok(())
+}
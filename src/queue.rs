@@ -0,0 +1,390 @@
+use crate::store::{self, PriorApplication, QueueEntry, QueueStatus};
+use crate::{applying_system, cli, icons, locale, paths, recorded_job, resume, submit, JobApplicationSystem, JobResult};
+use std::error::Error;
+use std::io::{self, BufRead, Write};
+
+// The application queue: jobs saved with `queue add` or `+` in the job browser,
+// applied to later by `queue process` one at a time. Each job's filled-in form
+// is shown and nothing goes out without a yes for it. Every status change is
+// written to the job store as it happens, and `submitting` goes in just before
+// the POST, so a run stopped by Ctrl-C or a dropped connection leaves the
+// queue as it was and the next run starts from the first job not finished.
+
+// Jobs to confirm one by one before `Y` can confirm the rest of the run
+const YES_TO_ALL_AFTER: usize = 3;
+
+pub fn run(cli: &cli::Cli, action: &cli::QueueCommand) -> Result<(), Box<dyn Error>> {
+    let store = store::JobStore::open(&paths::database_path())?;
+    match action {
+        cli::QueueCommand::Add { job_id, board } => {
+            let job = recorded_job(&store, *job_id, board.as_deref())?;
+            if store.queue_job(&job)? {
+                println!("{}Queued {}; `queue process` applies to the queue", icons::QUEUE, label(&job));
+            } else {
+                println!("{}{} is already queued", icons::INFO, label(&job));
+            }
+        }
+        cli::QueueCommand::List => print_queue(&store.application_queue()?),
+        cli::QueueCommand::Process => {
+            let entries: Vec<QueueEntry> = store.application_queue()?.into_iter().filter(|entry| !entry.status.finished()).collect();
+            if entries.is_empty() {
+                println!("{}Nothing is waiting in the queue", icons::EMPTY);
+                return Ok(());
+            }
+            let mut app_system = applying_system(cli, entries.iter().map(|entry| entry.job.clone()).collect());
+            app_system.attach_store(store);
+            let stdin = io::stdin();
+            app_system.process_queue(&entries, &mut stdin.lock().lines())?;
+        }
+        cli::QueueCommand::Remove { id } => {
+            if !store.remove_from_queue(*id)? {
+                return Err(format!("#{} isn't in the queue; `queue list` shows it", id).into());
+            }
+            println!("{}Took #{} out of the queue", icons::REMOVED, id);
+        }
+        cli::QueueCommand::Clear => {
+            let cleared = store.clear_finished_queue()?;
+            println!("{}Cleared {} submitted or skipped job(s) from the queue", icons::QUEUE, cleared);
+        }
+    }
+    Ok(())
+}
+
+fn label(job: &JobResult) -> String {
+    format!("{} at {} ({} job {})", job.title, job.company, job.board_token, job.id)
+}
+
+fn print_queue(entries: &[QueueEntry]) {
+    if entries.is_empty() {
+        println!("{}The queue is empty; add jobs with `queue add --job-id <id>` or + in the job browser", icons::EMPTY);
+        return;
+    }
+
+    println!("{}Application queue ({}):\n", icons::QUEUE, locale::current().count(entries.len()));
+    for entry in entries {
+        let mut attempts = if entry.attempts > 0 { format!(", {} attempt(s)", entry.attempts) } else { String::new() };
+        if entry.payload_json.is_some() {
+            attempts.push_str(", sent again as it was");
+        }
+        println!(
+            "#{} [{}{}] {}, queued {}",
+            entry.id,
+            entry.status.as_str(),
+            attempts,
+            label(&entry.job),
+            locale::current().timestamp_date(&entry.queued_at)
+        );
+        if let Some(reason) = &entry.reason {
+            println!("    {}", reason);
+        }
+    }
+}
+
+// How each job in a run went, for the summary at the end
+#[derive(Default)]
+struct Outcomes {
+    submitted: Vec<String>,
+    failed: Vec<(String, String)>,
+    skipped: Vec<(String, String)>,
+    dry_run: Vec<String>,
+}
+
+enum Answer {
+    Yes,
+    YesToAll,
+    No,
+    Quit,
+}
+
+impl JobApplicationSystem {
+    // `entries` are in the same order as `self.jobs`
+    pub fn process_queue<B: BufRead>(&mut self, entries: &[QueueEntry], lines: &mut io::Lines<B>) -> Result<(), Box<dyn Error>> {
+        println!("{}{} job(s) waiting in the queue", icons::QUEUE, entries.len());
+        let mut outcomes = Outcomes::default();
+        let mut confirmed = 0;
+        let mut yes_to_all = false;
+        let mut stopped = None;
+
+        for (index, entry) in entries.iter().enumerate() {
+            let job = self.jobs[index].clone();
+            println!("\n{}[{}/{}] {}", icons::JOB, index + 1, entries.len(), label(&job));
+            // A run stopped mid-submission leaves `submitting`; the log says whether it went out
+            let interrupted = entry.status == QueueStatus::Submitting;
+            if interrupted && store::submitted_since(&self.applications, &job, &entry.updated_at) {
+                println!("{}Already submitted by the run that was interrupted", icons::DONE);
+                self.set_queue_status(entry, QueueStatus::Submitted, None)?;
+                outcomes.submitted.push(label(&job));
+                continue;
+            }
+            if let Some(PriorApplication::Applied(record)) = self.prior_application(&job) {
+                let reason = format!("already applied on {}", record.applied_date());
+                println!("{}Skipped: {}", icons::SKIPPED, reason);
+                self.set_queue_status(entry, QueueStatus::Skipped, Some(&reason))?;
+                outcomes.skipped.push((label(&job), reason));
+                continue;
+            }
+            if let Some(reason) = self.rate_limited().filter(|_| !self.dry_run) {
+                stopped = Some(format!("{}{}", icons::PAUSED, reason));
+                break;
+            }
+
+            self.selected_job_index = Some(index);
+            // What didn't get through last time goes again as it was, without the form
+            let saved = entry.payload_json.as_deref().and_then(|json| serde_json::from_str::<submit::SavedPayload>(json).ok());
+            if let Some(saved) = &saved {
+                println!(
+                    "{}Sending the answers from the last attempt again ({})",
+                    icons::RETRY,
+                    entry.reason.as_deref().unwrap_or("it didn't get through")
+                );
+                for line in submit::preview(&saved.filled()) {
+                    println!("  {}", line);
+                }
+            } else {
+                self.confirm_application();
+                println!("{}Loading the application form...", icons::SEARCHING);
+                self.load_application_form();
+
+                if let Some(unavailable) = self.api_unavailable(&job) {
+                    print!("The API can't take this application: {}. Type o to open it in your browser, anything else to skip it: ", unavailable);
+                    io::stdout().flush()?;
+                    let reason = match read_line(lines)? {
+                        Some(answer) if answer.trim() == "o" => {
+                            self.apply_in_browser();
+                            match &self.browser_result {
+                                Some(Ok(_)) => format!("opened in the browser to apply by hand: {}", unavailable),
+                                _ => format!("the API can't take it ({}) and the browser didn't open", unavailable),
+                            }
+                        }
+                        Some(_) => format!("the API can't take it: {}", unavailable),
+                        None => {
+                            stopped = Some(format!("{}Stopped at the end of input", icons::STOP));
+                            break;
+                        }
+                    };
+                    println!("{}Skipped: {}", icons::SKIPPED, reason);
+                    self.set_queue_status(entry, QueueStatus::Skipped, Some(&reason))?;
+                    outcomes.skipped.push((label(&job), reason));
+                    continue;
+                }
+
+                for line in self.submission_preview(&job) {
+                    println!("  {}", line);
+                }
+                let not_ready = match self.filled_form(&job) {
+                    Err(e) => Some(e),
+                    Ok((_, filled)) if !filled.problems.is_empty() => Some("some required questions aren't answered".to_string()),
+                    Ok(_) => None,
+                };
+                if let Some(reason) = not_ready {
+                    println!("{}Not submitted: {}", icons::ERROR, reason);
+                    self.set_queue_status(entry, QueueStatus::Failed, Some(&reason))?;
+                    outcomes.failed.push((label(&job), reason));
+                    continue;
+                }
+            }
+
+            // One that may already have gone out always needs its own yes
+            let answer = if yes_to_all && !interrupted {
+                Answer::Yes
+            } else {
+                if interrupted {
+                    println!(
+                        "{}A run was stopped while submitting this one, and the application log doesn't have it; \
+                        check your email before sending it again",
+                        icons::WARNING
+                    );
+                }
+                let offer_all = confirmed >= YES_TO_ALL_AFTER && !interrupted;
+                ask(lines, offer_all)?
+            };
+            match answer {
+                Answer::Yes => {}
+                Answer::YesToAll => yes_to_all = true,
+                Answer::No => {
+                    println!("{}Skipped", icons::SKIPPED);
+                    self.set_queue_status(entry, QueueStatus::Skipped, Some("declined"))?;
+                    outcomes.skipped.push((label(&job), "declined".to_string()));
+                    continue;
+                }
+                Answer::Quit => {
+                    stopped = Some(format!("{}Stopped", icons::STOP));
+                    break;
+                }
+            }
+            confirmed += 1;
+
+            // A dry run sends nothing, so the job stays queued
+            if self.dry_run {
+                match &saved {
+                    Some(saved) => {
+                        let authenticated = self.board_credentials.contains_key(&job.board_token);
+                        let report = tokio::task::block_in_place(|| {
+                            tokio::runtime::Handle::current().block_on(submit::dry_run(&job, &saved.filled(), authenticated))
+                        });
+                        for line in report.lines() {
+                            println!("  {}", line);
+                        }
+                    }
+                    None => {
+                        self.dry_run_application();
+                        if let Some(status) = &self.status_message {
+                            println!("{}{}", icons::DRY_RUN, status);
+                        }
+                    }
+                }
+                outcomes.dry_run.push(label(&job));
+                continue;
+            }
+            self.set_queue_status(entry, QueueStatus::Submitting, None)?;
+            println!("{}Submitting...", icons::APPLICATIONS);
+            match &saved {
+                Some(saved) => self.resend(index, saved),
+                None => self.apply_to_job(),
+            }
+            match &self.submission {
+                Some(Ok(())) => {
+                    println!("{}Submitted. {}", icons::DONE, self.status_message.as_deref().unwrap_or_default());
+                    self.set_queue_status(entry, QueueStatus::Submitted, None)?;
+                    outcomes.submitted.push(label(&job));
+                }
+                // Retrying can't help once the board has turned the API away
+                Some(Err(e)) if e.api_refused => {
+                    let reason = format!("Greenhouse turned it away ({}); apply in the browser", e.message);
+                    println!("{}Not submitted: {}", icons::ERROR, reason);
+                    self.set_queue_status(entry, QueueStatus::Skipped, Some(&reason))?;
+                    outcomes.skipped.push((label(&job), reason));
+                }
+                Some(Err(e)) => {
+                    println!("{}Not submitted: {}", icons::ERROR, e.message);
+                    self.set_queue_status(entry, QueueStatus::Failed, Some(&e.message))?;
+                    // Answers Greenhouse objected to need fixing, so the next run fills the form again
+                    if saved.is_some() && !e.transient {
+                        self.store.as_ref().ok_or("the job store isn't available")?.drop_queue_payload(entry.id)?;
+                    }
+                    outcomes.failed.push((label(&job), e.message.clone()));
+                }
+                None => {
+                    self.set_queue_status(entry, QueueStatus::Failed, Some("nothing was sent"))?;
+                    outcomes.failed.push((label(&job), "nothing was sent".to_string()));
+                }
+            }
+        }
+
+        let waiting = match &self.store {
+            Some(store) => store.application_queue()?.iter().filter(|entry| !entry.status.finished()).count(),
+            None => 0,
+        };
+        print_summary(&outcomes, stopped.as_deref(), waiting);
+        Ok(())
+    }
+
+    // A saved payload sent again as it was, attachments read from disk again;
+    // `self.submission` has the outcome, as after `apply_to_job`
+    fn resend(&mut self, index: usize, saved: &submit::SavedPayload) {
+        self.status_message = None;
+        let job = self.jobs[index].clone();
+        let resume = saved.answers.iter().find_map(|(name, answer)| match answer {
+            submit::Answer::File(path) if name == "resume" => Some(resume::Resume::open(path)),
+            _ => None,
+        });
+        let resume = match resume.transpose() {
+            Ok(resume) => resume,
+            Err(e) => {
+                self.submission = Some(Err(submit::SubmitError::with_fields(
+                    format!("the resume can't be sent: {}", e),
+                    vec![submit::FieldError { field: "resume".to_string(), message: e }],
+                )));
+                return;
+            }
+        };
+        let api_key = self.board_credentials.get(&job.board_token).map(String::as_str);
+        let outcome = tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(submit::submit(&self.client, &job, &saved.form, &saved.answers, api_key))
+        });
+        if outcome.is_ok() {
+            self.job_profiles.insert(job.id, saved.profile.clone());
+            let cover_letter = saved.filled().sent_letter();
+            self.record_application(index, store::ApplicationMethod::Api, resume.as_ref(), cover_letter.as_ref());
+        }
+        self.submission = Some(outcome);
+    }
+
+    // Written before moving on, so an interrupted run never loses an outcome
+    fn set_queue_status(&self, entry: &QueueEntry, status: QueueStatus, reason: Option<&str>) -> Result<(), Box<dyn Error>> {
+        let store = self.store.as_ref().ok_or("the job store isn't available")?;
+        store.set_queue_status(entry.id, status, reason)?;
+        Ok(())
+    }
+}
+
+fn ask<B: BufRead>(lines: &mut io::Lines<B>, offer_all: bool) -> io::Result<Answer> {
+    loop {
+        if offer_all {
+            print!("Submit this application? y: yes | n: skip it | Y: yes to this and every one left | q: stop here: ");
+        } else {
+            print!("Submit this application? y: yes | n: skip it | q: stop here: ");
+        }
+        io::stdout().flush()?;
+        // Case matters: Y is yes to all
+        let Some(answer) = read_line(lines)? else {
+            return Ok(Answer::Quit);
+        };
+        match answer.trim() {
+            "Y" if offer_all => return Ok(Answer::YesToAll),
+            "y" | "yes" => return Ok(Answer::Yes),
+            "n" | "no" => return Ok(Answer::No),
+            "q" | "quit" => return Ok(Answer::Quit),
+            "Y" => println!("Y is offered once you've confirmed {} jobs one by one.", YES_TO_ALL_AFTER),
+            _ => {}
+        }
+    }
+}
+
+// None at the end of input
+fn read_line<B: BufRead>(lines: &mut io::Lines<B>) -> io::Result<Option<String>> {
+    lines.next().transpose()
+}
+
+fn print_summary(outcomes: &Outcomes, stopped: Option<&str>, waiting: usize) {
+    println!(
+        "\n{}Queue run: {} submitted, {} failed, {} skipped",
+        icons::RESULTS,
+        outcomes.submitted.len(),
+        outcomes.failed.len(),
+        outcomes.skipped.len()
+    );
+    if !outcomes.submitted.is_empty() {
+        println!("\nSubmitted:");
+        for job in &outcomes.submitted {
+            println!("  {}{}", icons::CHECK, job);
+        }
+    }
+    if !outcomes.failed.is_empty() {
+        println!("\nFailed, queued again for the next run:");
+        for (job, reason) in &outcomes.failed {
+            println!("  {}{}: {}", icons::CROSS, job, reason);
+        }
+    }
+    if !outcomes.skipped.is_empty() {
+        println!("\nSkipped:");
+        for (job, reason) in &outcomes.skipped {
+            println!("  {}{}: {}", icons::SKIPPED, job, reason);
+        }
+    }
+    if !outcomes.dry_run.is_empty() {
+        println!("\nDry run, nothing sent and still queued:");
+        for job in &outcomes.dry_run {
+            println!("  {}{}", icons::DRY_RUN, job);
+        }
+    }
+    println!();
+    if let Some(stopped) = stopped {
+        println!("{}", stopped);
+    }
+    if waiting > 0 {
+        println!("{}{} job(s) still waiting; `queue process` picks up from the first of them", icons::QUEUE, waiting);
+    } else {
+        println!("{}Nothing left in the queue", icons::DONE);
+    }
+}
@@ -0,0 +1,108 @@
+use crate::cli::Cli;
+use crate::{build_searcher, config::Config, export_results, icons, notify, paths, update_dashboard, NewFilter};
+use std::fs::{File, OpenOptions, TryLockError};
+use std::io::Write;
+use std::path::Path;
+
+// --quiet-notify: one search for a crontab entry. Only unseen matches count,
+// they go out through the --notify targets, and everything the search would
+// print goes to a log file instead of stdout. Warnings and errors still reach
+// stderr. A lock file keeps a slow run from overlapping with the next one.
+//
+// Exit codes: 0 the search ran, 1 it failed, 2 invalid options,
+// 3 skipped because an earlier run still holds the lock.
+
+pub const EXIT_OK: i32 = 0;
+pub const EXIT_FAILED: i32 = 1;
+pub const EXIT_INVALID: i32 = 2;
+pub const EXIT_LOCKED: i32 = 3;
+
+// The log starts over (keeping one old copy) once it grows past this
+const MAX_LOG_BYTES: u64 = 1024 * 1024;
+
+// Held until dropped; the OS releases it if the process dies, so it never goes stale
+pub fn acquire_lock(path: &Path) -> std::io::Result<Option<File>> {
+    let file = OpenOptions::new().create(true).truncate(false).write(true).open(path)?;
+    match file.try_lock() {
+        Ok(()) => Ok(Some(file)),
+        Err(TryLockError::WouldBlock) => Ok(None),
+        Err(TryLockError::Error(e)) => Err(e),
+    }
+}
+
+fn open_log(path: &Path) -> std::io::Result<File> {
+    if std::fs::metadata(path).is_ok_and(|meta| meta.len() > MAX_LOG_BYTES) {
+        std::fs::rename(path, path.with_extension("log.1"))?;
+    }
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+pub async fn run(cli: &Cli) -> i32 {
+    if !paths::persistent() {
+        eprintln!("{}--quiet-notify keeps a lock and a log in the data directory, so it can't run with persist = false in config.toml", icons::ERROR);
+        return EXIT_FAILED;
+    }
+    let log_path = paths::quiet_notify_log_path();
+    let started = chrono::Local::now().format("%Y-%m-%d %H:%M:%S");
+
+    let _lock = match acquire_lock(&paths::quiet_notify_lock_path()) {
+        Ok(Some(lock)) => lock,
+        Ok(None) => {
+            // Append without rotating, since the running search is writing to the log
+            if let Ok(mut log) = OpenOptions::new().create(true).append(true).open(&log_path) {
+                let _ = writeln!(log, "\n{}{}: skipped, the previous run is still going", icons::SKIPPED, started);
+            }
+            return EXIT_LOCKED;
+        }
+        Err(e) => {
+            eprintln!("{}Failed to lock {}: {}", icons::ERROR, paths::quiet_notify_lock_path().display(), e);
+            return EXIT_FAILED;
+        }
+    };
+
+    let log = match open_log(&log_path) {
+        Ok(log) => log,
+        Err(e) => {
+            eprintln!("{}Failed to open the log {}: {}", icons::ERROR, log_path.display(), e);
+            return EXIT_FAILED;
+        }
+    };
+    let redirect = match gag::Redirect::stdout(log) {
+        Ok(redirect) => redirect,
+        Err(e) => {
+            eprintln!("{}Failed to send output to {}: {}", icons::ERROR, log_path.display(), e);
+            return EXIT_FAILED;
+        }
+    };
+    println!("\n{}{}: quiet notify run", icons::CLOCK, started);
+
+    let mut searcher = match build_searcher(cli) {
+        Ok(searcher) => searcher,
+        Err(e) => {
+            eprintln!("{}{}", icons::ERROR, e);
+            return EXIT_INVALID;
+        }
+    };
+    searcher.new_filter = Some(NewFilter::Unseen);
+
+    let code = match searcher.search_jobs(cli.title_keyword(), &cli.location).await {
+        Ok(new_jobs) => {
+            if !new_jobs.is_empty() {
+                export_results(cli, &searcher, &new_jobs);
+            }
+            update_dashboard(cli, &searcher.cycle_matches);
+            let notifications = notify::Notifications::new(cli, &Config::load().notifications);
+            notifications.notify_new_matches(searcher.api.client(), &new_jobs).await;
+            notifications.notify_closed(searcher.api.client(), &searcher.closed_tracked).await;
+            println!("{}{} new match(es)", icons::RISING, new_jobs.len());
+            EXIT_OK
+        }
+        Err(e) => {
+            eprintln!("{}Search failed: {} (details in {})", icons::ERROR, e, log_path.display());
+            EXIT_FAILED
+        }
+    };
+    let _ = std::io::stdout().flush();
+    drop(redirect);
+    code
+}
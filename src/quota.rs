@@ -0,0 +1,206 @@
+use crate::icons;
+use crate::store::{ApplicationMethod, ApplicationRecord};
+use chrono::{DateTime, Duration, Utc};
+use std::io::{self, Write};
+
+// Automated submissions (the ones sent through the Job Board API) are capped
+// per rolling 24 hours, counted from the application log. The window is
+// measured between instants, not calendar days: an application at 23:50 still
+// counts at 00:10, and a timezone or daylight-saving change neither frees nor
+// uses up a slot. Manual log entries and dry runs don't count. The cap is
+// `daily_limit` under [applications] in config.toml.
+
+pub const DEFAULT_DAILY_LIMIT: usize = 20;
+
+fn window() -> Duration {
+    Duration::hours(24)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Quota {
+    pub limit: usize,
+    // Automated submissions in the 24 hours before now
+    pub used: usize,
+    // When the oldest submission that keeps the cap reached drops out of the window
+    pub next_slot: Option<DateTime<Utc>>,
+}
+
+impl Quota {
+    pub fn remaining(&self) -> usize {
+        self.limit.saturating_sub(self.used)
+    }
+
+    pub fn reached(&self) -> bool {
+        self.used >= self.limit
+    }
+
+    pub fn describe(&self) -> String {
+        if !self.reached() {
+            return format!("{} of {} automated submission(s) left in the last 24 hours", self.remaining(), self.limit);
+        }
+        match self.next_slot {
+            Some(at) => format!(
+                "Daily limit of {} automated submission(s) reached; the next one can go out at {}",
+                self.limit,
+                at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M")
+            ),
+            // A limit of 0 turns automated submissions off
+            None => format!("Daily limit of {} automated submission(s) reached", self.limit),
+        }
+    }
+}
+
+// Timestamps are compared as instants, whatever offset they were written with.
+// Ones in the future (the clock was set back) still count, so changing the
+// clock can't free slots; unreadable ones are skipped.
+pub fn quota(limit: usize, applications: &[ApplicationRecord], now: DateTime<Utc>) -> Quota {
+    let mut recent: Vec<DateTime<Utc>> = applications
+        .iter()
+        .filter(|record| record.method == ApplicationMethod::Api.as_str())
+        .filter_map(|record| DateTime::parse_from_rfc3339(&record.applied_at).ok())
+        .map(|at| at.with_timezone(&Utc))
+        .filter(|at| now - *at < window())
+        .collect();
+    recent.sort();
+    let used = recent.len();
+    let next_slot = if used >= limit && limit > 0 {
+        // Enough of the oldest have to age out to bring the count back under the limit
+        Some(recent[used - limit] + window())
+    } else {
+        None
+    };
+    Quota { limit, used, next_slot }
+}
+
+// --override-rate-limit only takes effect once the user types the phrase
+pub fn confirm_override(limit: usize) -> io::Result<bool> {
+    eprintln!("{}--override-rate-limit lifts the cap of {} automated submission(s) per 24 hours.", icons::STOP, limit);
+    eprintln!("    Mass-submitting applications hurts your chances: recruiters notice, and Greenhouse");
+    eprintln!("    may block your address. Only go on if every application is one you'd send by hand.");
+    eprint!("Type \"override\" to lift the cap for this session, anything else to keep it: ");
+    io::stderr().flush()?;
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+    Ok(answer.trim() == "override")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{FixedOffset, TimeZone};
+
+    fn record(applied_at: &str, method: ApplicationMethod) -> ApplicationRecord {
+        ApplicationRecord {
+            id: 0,
+            applied_at: applied_at.to_string(),
+            board_token: "acme".to_string(),
+            job_id: 1,
+            title: "Engineer".to_string(),
+            company: "Acme".to_string(),
+            url: "https://boards.greenhouse.io/acme/jobs/1".to_string(),
+            method: method.as_str().to_string(),
+            notes: None,
+            resume: None,
+            resume_sha256: None,
+            cover_letter: None,
+            cover_letter_file: None,
+            cover_letter_sha256: None,
+            profile: None,
+            status: None,
+            status_at: None,
+            remind_on: None,
+        }
+    }
+
+    fn submitted(applied_at: &str) -> ApplicationRecord {
+        record(applied_at, ApplicationMethod::Api)
+    }
+
+    fn utc(text: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(text).expect("valid timestamp").with_timezone(&Utc)
+    }
+
+    #[test]
+    fn yesterday_evening_still_counts_after_midnight() {
+        let log = [submitted("2026-03-09T23:30:00+00:00"), submitted("2026-03-09T23:50:00+00:00")];
+        let quota = quota(2, &log, utc("2026-03-10T00:10:00+00:00"));
+        assert_eq!(quota.used, 2);
+        assert!(quota.reached());
+        // The earlier one frees the first slot, a day after it went out
+        assert_eq!(quota.next_slot, Some(utc("2026-03-10T23:30:00+00:00")));
+    }
+
+    #[test]
+    fn submissions_age_out_exactly_a_day_later() {
+        let log = [submitted("2026-03-09T12:00:00+00:00"), submitted("2026-03-10T09:00:00+00:00")];
+        assert_eq!(quota(5, &log, utc("2026-03-10T11:59:59+00:00")).used, 2);
+        assert_eq!(quota(5, &log, utc("2026-03-10T12:00:00+00:00")).used, 1);
+    }
+
+    #[test]
+    fn a_new_calendar_day_does_not_reset_the_count() {
+        let log: Vec<_> = (0..3).map(|minute| submitted(&format!("2026-03-09T23:5{}:00+00:00", minute))).collect();
+        let quota = quota(3, &log, utc("2026-03-10T00:00:01+00:00"));
+        assert_eq!(quota.remaining(), 0);
+        assert_eq!(quota.next_slot, Some(utc("2026-03-10T23:50:00+00:00")));
+    }
+
+    #[test]
+    fn offsets_are_compared_as_instants() {
+        // 18:00 in San Francisco is 02:00 UTC the next day and 07:30 in Kolkata
+        let log = [
+            submitted("2026-03-09T18:00:00-08:00"),
+            submitted("2026-03-10T07:30:00+05:30"),
+            submitted("2026-03-10T02:00:00Z"),
+        ];
+        assert_eq!(quota(10, &log, utc("2026-03-11T01:59:00+00:00")).used, 3);
+        assert_eq!(quota(10, &log, utc("2026-03-11T02:00:00+00:00")).used, 0);
+    }
+
+    #[test]
+    fn moving_to_another_timezone_frees_no_slots() {
+        // Applied from New York, then flew to Tokyo, where it's already tomorrow
+        let log = [submitted("2026-03-09T21:00:00-05:00")];
+        let tokyo = FixedOffset::east_opt(9 * 3600).expect("valid offset");
+        let now = tokyo.with_ymd_and_hms(2026, 3, 10, 12, 0, 0).single().expect("valid time").with_timezone(&Utc);
+        let quota = quota(1, &log, now);
+        assert!(quota.reached());
+        assert_eq!(quota.next_slot, Some(utc("2026-03-11T02:00:00+00:00")));
+    }
+
+    #[test]
+    fn daylight_saving_change_keeps_a_24_hour_window() {
+        // US clocks went forward at 02:00 on 2026-03-08, so this local day had 23 hours
+        let log = [submitted("2026-03-07T12:00:00-05:00")];
+        assert_eq!(quota(5, &log, utc("2026-03-08T12:00:00-04:00")).used, 1);
+        assert_eq!(quota(5, &log, utc("2026-03-08T13:00:00-04:00")).used, 0);
+    }
+
+    #[test]
+    fn only_api_submissions_count() {
+        let log = [
+            submitted("2026-03-10T08:00:00Z"),
+            record("2026-03-10T08:05:00Z", ApplicationMethod::Manual),
+            record("2026-03-10T08:10:00Z", ApplicationMethod::DryRun),
+            submitted("not a timestamp"),
+        ];
+        let quota = quota(2, &log, utc("2026-03-10T09:00:00Z"));
+        assert_eq!((quota.used, quota.remaining()), (1, 1));
+        assert_eq!(quota.next_slot, None);
+    }
+
+    #[test]
+    fn future_timestamps_still_count_after_the_clock_goes_back() {
+        let log = [submitted("2026-03-10T10:00:00Z")];
+        assert!(quota(1, &log, utc("2026-03-10T09:00:00Z")).reached());
+    }
+
+    #[test]
+    fn over_the_limit_waits_for_enough_to_age_out() {
+        // The limit was lowered after four went out
+        let log: Vec<_> = (1..=4).map(|hour| submitted(&format!("2026-03-10T0{}:00:00Z", hour))).collect();
+        let quota = quota(2, &log, utc("2026-03-10T06:00:00Z"));
+        assert_eq!(quota.remaining(), 0);
+        assert_eq!(quota.next_slot, Some(utc("2026-03-11T03:00:00Z")));
+    }
+}
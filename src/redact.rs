@@ -0,0 +1,91 @@
+use crate::{paths, JobResult};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+// --redact companies: swaps company names for "Company A", "Company B", ... in
+// everything a run prints, exports or sends, so results can be shared without
+// giving away the target list. Board tokens, job URLs and ids would name the
+// company too, so they become placeholders as well; titles, locations and
+// dates are kept. The same company keeps its label for the whole run (every
+// cycle of `watch` and `serve`). The job store and saved runs keep the real
+// names, and the key is written only to the data directory.
+
+#[derive(Debug, Default)]
+pub struct Redactor {
+    // Real board token -> its label; labels go out in the order companies first appear
+    labels: BTreeMap<String, Label>,
+    // Real job URL -> its placeholder
+    urls: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct Label {
+    label: String,
+    company: String,
+    board_token: String,
+}
+
+#[derive(Serialize)]
+struct Key<'a> {
+    written_at: chrono::DateTime<chrono::Utc>,
+    companies: Vec<&'a Label>,
+    // Placeholder URL -> the real one
+    jobs: BTreeMap<&'a str, &'a str>,
+}
+
+// "A".."Z", then "AA", "AB", ...
+fn letters(mut index: usize) -> String {
+    let mut letters = Vec::new();
+    loop {
+        letters.push(b'A' + (index % 26) as u8);
+        if index < 26 {
+            break;
+        }
+        index = index / 26 - 1;
+    }
+    letters.reverse();
+    String::from_utf8(letters).expect("ASCII letters")
+}
+
+impl Redactor {
+    // Redacts the jobs in place, labelling new companies in the order they appear
+    pub fn apply(&mut self, jobs: &mut [JobResult]) {
+        for job in jobs {
+            let next = self.labels.len();
+            let label = self.labels.entry(job.board_token.clone()).or_insert_with(|| Label {
+                label: format!("Company {}", letters(next)),
+                company: job.company.clone(),
+                board_token: job.board_token.clone(),
+            });
+            let slug = label.label.to_lowercase().replace(' ', "-");
+            let number = self.urls.len() + 1;
+            let placeholder = self
+                .urls
+                .entry(job.url.clone())
+                .or_insert_with(|| format!("https://redacted.invalid/{}/jobs/{}", slug, number));
+            job.id = placeholder.rsplit('/').next().and_then(|n| n.parse().ok()).unwrap_or_default();
+            job.url = placeholder.clone();
+            job.company = label.label.clone();
+            job.board_token = slug;
+            // The description and Greenhouse record name the company
+            job.raw = serde_json::Value::Null;
+        }
+    }
+
+    // Writes the label -> company key for de-anonymizing locally
+    pub fn save_key(&self) -> std::io::Result<PathBuf> {
+        let path = paths::redaction_key_path();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut companies: Vec<&Label> = self.labels.values().collect();
+        companies.sort_by_key(|label| (label.label.len(), label.label.clone()));
+        let jobs = self.urls.iter().map(|(real, placeholder)| (placeholder.as_str(), real.as_str())).collect();
+        let key = Key { written_at: chrono::Utc::now(), companies, jobs };
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_string_pretty(&key)?)?;
+        std::fs::rename(&tmp, &path)?;
+        Ok(path)
+    }
+}
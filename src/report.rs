@@ -0,0 +1,58 @@
+use crate::{locale, JobResult};
+
+// Self-contained HTML rendering of a list of jobs, with inline styles so it
+// displays the same in a browser or an email client.
+
+pub const BODY_STYLE: &str = "font-family: -apple-system, Segoe UI, Helvetica, Arial, sans-serif; color: #222;";
+const CELL_STYLE: &str = "padding: 4px 12px 4px 0;";
+
+pub fn render_html(title: &str, jobs: &[&JobResult]) -> String {
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str(&format!("<title>{}</title>\n</head>\n", escape(title)));
+    html.push_str(&format!("<body style=\"{}\">\n", BODY_STYLE));
+    html.push_str(&format!("<h2>{}</h2>\n", escape(title)));
+    html.push_str(&job_table(jobs));
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+// Title (linked), company, location and posting date, one row per job
+pub fn job_table(jobs: &[&JobResult]) -> String {
+    if jobs.is_empty() {
+        return "<p>No matching jobs.</p>\n".to_string();
+    }
+
+    let locale = locale::current();
+    let mut html = String::new();
+    html.push_str("<table style=\"border-collapse: collapse;\">\n");
+    html.push_str("<tr style=\"text-align: left; border-bottom: 1px solid #ccc;\">");
+    for heading in ["Title", "Company", "Location", "Posted"] {
+        html.push_str(&format!("<th style=\"{}\">{}</th>", CELL_STYLE, heading));
+    }
+    html.push_str("</tr>\n");
+    for job in jobs {
+        html.push_str(&format!(
+            "<tr><td style=\"{style}\"><a href=\"{}\">{}</a></td>\
+             <td style=\"{style}\">{}</td>\
+             <td style=\"{style}\">{}</td>\
+             <td style=\"{style}\">{}</td></tr>\n",
+            escape(&job.url),
+            escape(&job.title),
+            escape(&job.company),
+            escape(&job.location),
+            escape(&locale.timestamp_date(&job.date_posted)),
+            style = CELL_STYLE,
+        ));
+    }
+    html.push_str("</table>\n");
+    html
+}
+
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
@@ -0,0 +1,66 @@
+use crate::config::Config;
+use crate::profile::{self, Profile};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+// Which resume goes with an application: --resume when given, then the board's
+// entry under [resumes] in config.toml, then the profile's resume.
+//
+//   [resumes]
+//   stripe = "/home/jane/resume-fintech.pdf"
+//   plaid = "/home/jane/resume-fintech.pdf"
+//
+// The file is checked and hashed before anything is sent, and its SHA-256 goes
+// into the application log, so you can tell which version went to which company.
+
+#[derive(Debug, Clone, Default)]
+pub struct Resumes {
+    // --resume, for every board
+    pub override_path: Option<PathBuf>,
+    // Board token -> resume for that board
+    pub by_board: HashMap<String, PathBuf>,
+}
+
+// A resume that passed the checks, as it was when it was hashed
+#[derive(Debug, Clone)]
+pub struct Resume {
+    pub path: PathBuf,
+    pub sha256: String,
+}
+
+impl Resumes {
+    pub fn new(override_path: Option<PathBuf>, config: &Config) -> Self {
+        Resumes { override_path, by_board: config.resumes.clone() }
+    }
+
+    // The profile as sent to this board, with the resume it gets
+    pub fn profile_for(&self, board_token: &str, profile: &Profile) -> Profile {
+        let resume = self.override_path.clone().or_else(|| self.by_board.get(board_token).cloned());
+        Profile { resume: resume.or_else(|| profile.resume.clone()), ..profile.clone() }
+    }
+}
+
+impl Resume {
+    // Checks the type and size, then hashes the file a chunk at a time
+    pub fn open(path: &Path) -> Result<Resume, String> {
+        profile::check_resume(path)?;
+        let mut file = std::fs::File::open(path).map_err(|e| format!("can't read {}: {}", path.display(), e))?;
+        let mut hasher = Sha256::new();
+        let mut buffer = vec![0; 64 * 1024];
+        loop {
+            let read = file.read(&mut buffer).map_err(|e| format!("can't read {}: {}", path.display(), e))?;
+            if read == 0 {
+                break;
+            }
+            hasher.update(&buffer[..read]);
+        }
+        let sha256 = hasher.finalize().iter().map(|byte| format!("{:02x}", byte)).collect();
+        Ok(Resume { path: path.to_path_buf(), sha256 })
+    }
+
+    pub fn file_name(&self) -> String {
+        self.path.file_name().unwrap_or(self.path.as_os_str()).to_string_lossy().into_owned()
+    }
+}
@@ -0,0 +1,111 @@
+use crate::paths;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+// Boards that failed or were rate limited, carried over to later runs. Queued
+// boards are searched first next time; a board that keeps failing waits
+// exponentially longer between attempts and is dropped after MAX_ATTEMPTS,
+// so one dead board can't slow every run down forever.
+
+const BASE_BACKOFF_MINUTES: i64 = 30;
+const MAX_BACKOFF_HOURS: i64 = 24;
+const MAX_ATTEMPTS: u32 = 8;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct RetryQueue {
+    #[serde(default)]
+    boards: BTreeMap<String, RetryEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RetryEntry {
+    attempts: u32,
+    first_failed_at: DateTime<Utc>,
+    last_failed_at: DateTime<Utc>,
+    next_attempt_at: DateTime<Utc>,
+    // "rate limited" or "failed"
+    last_error: String,
+}
+
+// Where a board stands in the queue at the start of a run
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Standing {
+    NotQueued,
+    Due,
+    BackingOff,
+}
+
+impl RetryQueue {
+    pub fn load() -> Self {
+        std::fs::read_to_string(paths::retry_queue_path())
+            .ok()
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_default()
+    }
+
+    // Write to a temporary file and rename so a crash never leaves half a file
+    pub fn save(&self) -> std::io::Result<()> {
+        if !paths::persistent() {
+            return Ok(());
+        }
+        let path = paths::retry_queue_path();
+        if self.boards.is_empty() {
+            return match std::fs::remove_file(&path) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+                _ => Ok(()),
+            };
+        }
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_string_pretty(self)?)?;
+        std::fs::rename(tmp, path)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.boards.is_empty()
+    }
+
+    pub fn boards(&self) -> impl Iterator<Item = &String> {
+        self.boards.keys()
+    }
+
+    pub fn standing(&self, board: &str, now: DateTime<Utc>) -> Standing {
+        match self.boards.get(board) {
+            None => Standing::NotQueued,
+            Some(entry) if entry.next_attempt_at <= now => Standing::Due,
+            Some(_) => Standing::BackingOff,
+        }
+    }
+
+    pub fn record_success(&mut self, board: &str) {
+        self.boards.remove(board);
+    }
+
+    // Queue or re-queue a board; returns the attempt count if it has now been given up on
+    pub fn record_failure(&mut self, board: &str, error: &str, now: DateTime<Utc>) -> Option<u32> {
+        let entry = self.boards.entry(board.to_string()).or_insert_with(|| RetryEntry {
+            attempts: 0,
+            first_failed_at: now,
+            last_failed_at: now,
+            next_attempt_at: now,
+            last_error: String::new(),
+        });
+        entry.attempts += 1;
+        entry.last_failed_at = now;
+        entry.last_error = error.to_string();
+        entry.next_attempt_at = now + backoff(entry.attempts);
+
+        if entry.attempts >= MAX_ATTEMPTS {
+            let attempts = entry.attempts;
+            self.boards.remove(board);
+            return Some(attempts);
+        }
+        None
+    }
+}
+
+// 30m, 1h, 2h, ... capped at a day
+fn backoff(attempts: u32) -> Duration {
+    let minutes = BASE_BACKOFF_MINUTES.saturating_mul(1 << attempts.saturating_sub(1).min(16));
+    Duration::minutes(minutes.min(MAX_BACKOFF_HOURS * 60))
+}
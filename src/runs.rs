@@ -0,0 +1,76 @@
+use crate::export::{SearchExport, EXPORT_SCHEMA_VERSION};
+use crate::paths;
+use std::path::{Path, PathBuf};
+
+// Every search's full result set is saved as one file under runs/ in the data
+// directory, in the same versioned envelope as --json-out, and the search
+// history points at it by its name within runs/, so the data directory can
+// move (or come from a `state import`) without the history losing its runs.
+// Only the newest --keep-runs files are kept.
+
+// Default for --keep-runs
+pub const DEFAULT_KEEP: usize = 50;
+
+// `runs/<UTC timestamp>.json`, written to a temporary file and renamed into
+// place; returns the name within runs/
+pub fn save(export: &SearchExport) -> std::io::Result<PathBuf> {
+    let dir = paths::saved_runs_dir();
+    std::fs::create_dir_all(&dir)?;
+    let name = export.exported_at.format("%Y%m%dT%H%M%S%.3fZ").to_string();
+    let path = dir.join(format!("{}.json", name));
+    let tmp = dir.join(format!("{}.json.tmp", name));
+    std::fs::write(&tmp, serde_json::to_vec(export)?)?;
+    std::fs::rename(&tmp, &path)?;
+    Ok(PathBuf::from(format!("{}.json", name)))
+}
+
+// Where a saved run recorded in the history is. Entries from before runs were
+// recorded by name hold a full path, which `join` keeps as it is.
+pub fn resolve(recorded: &Path) -> PathBuf {
+    paths::saved_runs_dir().join(recorded)
+}
+
+// Saved run files, oldest first (timestamped names sort by time)
+fn saved_files() -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(paths::saved_runs_dir()) else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    files.sort();
+    files
+}
+
+pub fn latest() -> Option<PathBuf> {
+    saved_files().pop()
+}
+
+// Delete all but the newest `keep` saved runs; returns how many went
+pub fn prune(keep: usize) -> std::io::Result<usize> {
+    let files = saved_files();
+    let excess = files.len().saturating_sub(keep);
+    for path in &files[..excess] {
+        std::fs::remove_file(path)?;
+    }
+    Ok(excess)
+}
+
+// Saved runs from this or any earlier version; newer schemas are refused
+// rather than half read
+pub fn load(path: &Path) -> Result<SearchExport, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => format!("{} no longer exists; only the newest saved runs are kept", path.display()),
+        _ => format!("{}: {}", path.display(), e),
+    })?;
+    let value: serde_json::Value = serde_json::from_str(&text).map_err(|e| format!("{}: not valid JSON ({})", path.display(), e))?;
+    let version = value.get("schema_version").and_then(serde_json::Value::as_u64).unwrap_or(0);
+    if version > u64::from(EXPORT_SCHEMA_VERSION) {
+        return Err(format!(
+            "{} was saved by a newer version of the tool (schema {}, this one reads up to {})",
+            path.display(), version, EXPORT_SCHEMA_VERSION
+        ));
+    }
+    serde_json::from_value(value).map_err(|e| format!("{}: unexpected layout ({})", path.display(), e))
+}
@@ -0,0 +1,1258 @@
+//! Fetching Greenhouse job boards and matching their jobs against a query.
+//!
+//! Each board is one request to the public Job Board API:
+//!
+//! ```text
+//! GET https://boards-api.greenhouse.io/v1/boards/{token}/jobs?content=true
+//! ```
+//!
+//! which lists every open job at once. [`filter_jobs`] runs a listing through
+//! the title, location and department filters; [`GreenhouseJobSearcher`] does
+//! the fetching, one board or many at a time. Nothing here prints: how a board
+//! went comes back in its [`BoardOutcome`].
+
+use crate::budget::RequestBudget;
+use crate::discovery::{self, TokenSource};
+use crate::matching::BoardMatcher;
+use crate::{companies, keywords};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// The Job Board API's boards, in the US region
+pub const DEFAULT_BASE_URL: &str = "https://boards-api.greenhouse.io/v1/boards";
+
+/// Renamed boards redirect to their new token; more hops than this give up on the board
+pub const MAX_BOARD_REDIRECTS: usize = 3;
+
+/// In lite mode, a board with more candidates than this is listed again in full
+/// rather than fetched one job at a time
+pub const LITE_JOB_FETCHES: usize = 10;
+
+/// A job as the Job Board API lists it.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Job {
+    pub id: u64,
+    pub title: String,
+    pub updated_at: String,
+    pub location: JobLocation,
+    pub absolute_url: String,
+    pub departments: Option<Vec<Department>>,
+    /// Everything else in the record (content, metadata, ...) so it can be stored verbatim
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct JobLocation {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Department {
+    pub id: u64,
+    pub name: String,
+}
+
+/// A board's listing, the body of its jobs endpoint.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct JobsResponse {
+    pub jobs: Vec<Job>,
+}
+
+// An error document in place of a job list, e.g. `{"status": 429, "error": "Too many requests"}`
+// or `{"errors": [{"message": "..."}]}`
+#[derive(Debug)]
+struct ApiError {
+    message: String,
+    rate_limited: bool,
+}
+
+impl ApiError {
+    fn from_body(body: &str) -> Option<Self> {
+        let value: serde_json::Value = serde_json::from_str(body).ok()?;
+        let object = value.as_object()?;
+        if object.contains_key("jobs") {
+            return None;
+        }
+        // A string, or an object or list of objects with a message
+        fn message_of(value: &serde_json::Value) -> Option<String> {
+            match value {
+                serde_json::Value::String(text) => Some(text.clone()),
+                serde_json::Value::Object(object) => object.get("message").and_then(message_of),
+                serde_json::Value::Array(items) => {
+                    let messages: Vec<String> = items.iter().filter_map(message_of).collect();
+                    (!messages.is_empty()).then(|| messages.join("; "))
+                }
+                _ => None,
+            }
+        }
+        let message = ["error", "errors", "message"].iter().find_map(|key| object.get(*key).and_then(message_of))?;
+        let status = object.get("status").and_then(|status| {
+            status.as_u64().or_else(|| status.as_str().and_then(|s| s.parse().ok()))
+        });
+        let lower = message.to_lowercase();
+        let rate_limited = status == Some(429)
+            || ["rate limit", "rate-limit", "too many requests", "throttl"].iter().any(|hint| lower.contains(hint));
+        Some(ApiError { message, rate_limited })
+    }
+}
+
+/// A job that matched, with what the search and its caller know about it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobResult {
+    pub id: u64,
+    pub board_token: String,
+    pub title: String,
+    pub company: String,
+    pub location: String,
+    pub date_posted: String,
+    pub url: String,
+    /// Full Greenhouse job record as returned by the API
+    #[serde(skip)]
+    pub raw: serde_json::Value,
+    /// When the caller's job store first recorded this job; `None` means new in this run
+    pub first_seen: Option<chrono::DateTime<chrono::Utc>>,
+    /// Labeled sections pulled from the description by the caller
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub extras: BTreeMap<String, String>,
+    /// Every department the job is listed under, in the order Greenhouse gives them
+    #[serde(default)]
+    pub departments: Vec<String>,
+    /// The user's own tags, from the caller's job store
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// When a tracked job was found missing from its board
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub closed_at: Option<String>,
+    /// Share of the weighted keyword terms in the title, for ranking
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub relevance: Option<f64>,
+    /// From a board the caller marked as a favorite, so [`sort_results`] lists it ahead of the rest
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub favorite: bool,
+}
+
+/// Which of a board's jobs match.
+#[derive(Debug, Clone)]
+pub struct SearchQuery {
+    pub keywords: keywords::KeywordSpec,
+    /// Match keywords only as whole words rather than substrings
+    pub whole_word: bool,
+    /// Matched loosely, along with remote and Bay Area locations, unless `location_regex` takes over
+    pub location: String,
+    /// Tried against the job's location and each of its offices' names and locations
+    pub location_regex: Option<regex::Regex>,
+    /// Keep only jobs with a department containing this text, ignoring case
+    pub department: Option<String>,
+    /// Stop scanning a board once it has produced this many matches
+    pub max_matches: Option<usize>,
+}
+
+impl SearchQuery {
+    /// A query for the `keyword` spec (see [`keywords::KeywordSpec::parse`]) near `location`,
+    /// with the other filters off.
+    pub fn new(keyword: &str, location: &str) -> Result<Self, String> {
+        Ok(SearchQuery {
+            keywords: keywords::KeywordSpec::parse(keyword)?,
+            whole_word: false,
+            location: location.to_string(),
+            location_regex: None,
+            department: None,
+            max_matches: None,
+        })
+    }
+}
+
+/// How fetching a single board went.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum BoardStatus {
+    Searched,
+    NotFound,
+    RateLimited,
+    Failed,
+}
+
+/// Why a board didn't give a listing, or how it nearly didn't.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BoardProblem {
+    /// A redirect that couldn't be followed to another board
+    Redirect(String),
+    Status(reqwest::StatusCode),
+    Network(String),
+    /// Greenhouse answered with an error document instead of a job list
+    Api(String),
+    /// A body that's neither a job list nor an error document
+    Parse(String),
+    /// The request budget ran out before the listing was reached, partway
+    /// through the board's redirects
+    OutOfRequests,
+}
+
+impl std::fmt::Display for BoardProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            BoardProblem::Redirect(problem) => f.write_str(problem),
+            BoardProblem::Status(status) => write!(f, "returned status {}", status),
+            BoardProblem::Network(e) => write!(f, "network error: {}", e),
+            BoardProblem::Api(message) => write!(f, "Greenhouse returned an error instead of jobs: {}", message),
+            BoardProblem::Parse(e) => write!(f, "JSON parse error: {}", e),
+            BoardProblem::OutOfRequests => f.write_str("the request budget ran out before the board's redirect was followed"),
+        }
+    }
+}
+
+/// How a board went, alongside its matches.
+#[derive(Debug, Clone)]
+pub struct BoardOutcome {
+    pub status: BoardStatus,
+    pub jobs_scanned: usize,
+    pub matches: Vec<JobResult>,
+    /// Every job id on the board, matching or not, for checking tracked jobs
+    pub listed_ids: HashSet<u64>,
+    pub excluded: Exclusions,
+    /// The token the board redirected to, when it was renamed
+    pub renamed_to: Option<String>,
+    pub problem: Option<BoardProblem>,
+}
+
+impl BoardOutcome {
+    pub fn without_jobs(status: BoardStatus) -> Self {
+        Self {
+            status,
+            jobs_scanned: 0,
+            matches: Vec::new(),
+            listed_ids: HashSet::new(),
+            excluded: Exclusions::default(),
+            renamed_to: None,
+            problem: None,
+        }
+    }
+
+    fn failed(status: BoardStatus, problem: BoardProblem) -> Self {
+        Self { problem: Some(problem), ..Self::without_jobs(status) }
+    }
+}
+
+/// Jobs left out of a run, each counted under the first filter it failed.
+/// [`filter_jobs`] counts the first four; the rest are for callers' own filters.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Exclusions {
+    pub title: usize,
+    pub location: usize,
+    /// Never looked at because [`SearchQuery::max_matches`] cut the board short
+    pub unscanned: usize,
+    pub department: usize,
+    pub age: usize,
+    pub tag: usize,
+    pub hidden: usize,
+    pub applied: usize,
+    pub duplicate: usize,
+}
+
+impl Exclusions {
+    pub fn add(&mut self, other: &Exclusions) {
+        self.title += other.title;
+        self.location += other.location;
+        self.unscanned += other.unscanned;
+        self.department += other.department;
+        self.age += other.age;
+        self.tag += other.tag;
+        self.hidden += other.hidden;
+        self.applied += other.applied;
+        self.duplicate += other.duplicate;
+    }
+
+    pub fn total(&self) -> usize {
+        self.title + self.location + self.unscanned + self.department + self.age + self.tag + self.hidden + self.applied
+            + self.duplicate
+    }
+
+    /// The counts that aren't zero with what left the jobs out ("by title"), largest first
+    pub fn reasons(&self) -> Vec<(usize, &'static str)> {
+        let mut reasons = vec![
+            (self.title, "by title"),
+            (self.location, "by location"),
+            (self.department, "by department"),
+            (self.age, "by age"),
+            (self.tag, "by tag"),
+            (self.hidden, "as hidden"),
+            (self.applied, "as already applied"),
+            (self.duplicate, "as duplicates (--dedupe)"),
+            (self.unscanned, "unscanned (--max-matches-per-board)"),
+        ];
+        reasons.retain(|(count, _)| *count > 0);
+        reasons.sort_by_key(|(count, _)| std::cmp::Reverse(*count));
+        reasons
+    }
+}
+
+/// Fetches boards from the Job Board API and matches their jobs.
+///
+/// Redirects aren't left to the HTTP client: a renamed board answers 301/302
+/// with its new token, which is followed (at most [`MAX_BOARD_REDIRECTS`]
+/// times) and reported in [`BoardOutcome::renamed_to`].
+///
+/// [`new`](Self::new) has the defaults; [`builder`](Self::builder) sets up
+/// anything else, including which server the boards come from:
+///
+/// ```
+/// use greenhouse_job_search::search::{GreenhouseJobSearcher, Region};
+/// use std::time::Duration;
+///
+/// let searcher = GreenhouseJobSearcher::builder()
+///     .region(Region::Eu)
+///     .concurrency(8)
+///     .politeness_delay(Duration::ZERO)
+///     .build()?;
+/// assert_eq!(searcher.base_url(), "https://boards-api.eu.greenhouse.io/v1/boards");
+///
+/// let mock = GreenhouseJobSearcher::builder().base_url("http://127.0.0.1:8080/v1/boards").build()?;
+/// assert_eq!(mock.jobs_url("acme", false), "http://127.0.0.1:8080/v1/boards/acme/jobs");
+/// # Ok::<(), String>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct GreenhouseJobSearcher {
+    // Board fetches, which handle redirects themselves
+    board_client: reqwest::Client,
+    // Everything else: discovery and the pages around a job
+    client: reqwest::Client,
+    base_url: String,
+    concurrency: Option<usize>,
+    politeness_delay: Duration,
+    token_sources: Vec<TokenSource>,
+    request_budget: Option<Arc<RequestBudget>>,
+    /// Extra query parameters appended to every board's jobs URL
+    pub board_query: Option<String>,
+    /// Bearer tokens for authenticated boards, keyed by board token
+    pub credentials: HashMap<String, String>,
+    /// List boards without `content=true`, which leaves out descriptions,
+    /// departments and offices, and fetch those only for the jobs that pass
+    /// the title and location filters. Much less to download on broad
+    /// searches; see [`lite_candidates`] for what it can miss.
+    pub lite: bool,
+}
+
+impl Default for GreenhouseJobSearcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GreenhouseJobSearcher {
+    /// A searcher with the defaults of [`GreenhouseJobSearcherBuilder`]
+    pub fn new() -> Self {
+        Self::builder().build().expect("the default settings are valid")
+    }
+
+    pub fn builder() -> GreenhouseJobSearcherBuilder {
+        GreenhouseJobSearcherBuilder::default()
+    }
+
+    /// The client for anything that isn't a board fetch; it follows redirects
+    /// unless it was supplied to the builder
+    pub fn client(&self) -> &reqwest::Client {
+        &self.client
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// How many boards [`search`](Self::search) fetches at once; `None` is all of them
+    pub fn concurrency(&self) -> Option<usize> {
+        self.concurrency
+    }
+
+    pub fn token_sources(&self) -> &[TokenSource] {
+        &self.token_sources
+    }
+
+    /// A board's jobs endpoint, see [`board_jobs_url`]
+    pub fn jobs_url(&self, board_token: &str, content: bool) -> String {
+        board_jobs_url(&self.base_url, board_token, self.board_query.as_deref(), content)
+    }
+
+    /// The tokens from one source
+    pub async fn discover(&self, source: &TokenSource) -> Result<HashSet<String>, String> {
+        match source {
+            TokenSource::File(path) => discovery::from_file(path),
+            TokenSource::Urls(urls) => {
+                let mut found = HashSet::new();
+                for url in urls {
+                    found.extend(discovery::from_url(&self.client, url).await?);
+                }
+                Ok(found)
+            }
+            TokenSource::Google => discovery::from_google(&self.client).await,
+            TokenSource::Builtin => Ok(discovery::builtin()),
+        }
+    }
+
+    /// The tokens from the first of the searcher's sources that finds any.
+    /// Fails only if none did and at least one of them failed.
+    pub async fn discover_tokens(&self) -> Result<HashSet<String>, String> {
+        let mut failures = Vec::new();
+        for source in &self.token_sources {
+            match self.discover(source).await {
+                Ok(tokens) if !tokens.is_empty() => return Ok(tokens),
+                Ok(_) => {}
+                Err(e) => failures.push(e),
+            }
+        }
+        if failures.is_empty() {
+            Ok(HashSet::new())
+        } else {
+            Err(failures.join("; "))
+        }
+    }
+
+    /// Every board, at most [`concurrency`](Self::concurrency) at once, matches
+    /// from all of them sorted by [`sort_results`]. Boards that fail are left
+    /// out; [`search_board`](Self::search_board) says why. With a request
+    /// budget, each board's listing is claimed in the order given, and the
+    /// boards past the end of the budget aren't searched.
+    pub async fn search(&self, board_tokens: &[&str], query: &SearchQuery) -> Vec<JobResult> {
+        let slots = self.concurrency.map(|limit| Arc::new(tokio::sync::Semaphore::new(limit)));
+        let mut tasks = tokio::task::JoinSet::new();
+        for &board_token in board_tokens {
+            if !self.take_request() {
+                break;
+            }
+            let (searcher, board_token, query) = (self.clone(), board_token.to_string(), query.clone());
+            let slots = slots.clone();
+            tasks.spawn(async move {
+                let _slot = match slots {
+                    Some(slots) => Some(slots.acquire_owned().await.expect("the semaphore is never closed")),
+                    None => None,
+                };
+                searcher.search_board(&board_token, &query).await
+            });
+        }
+        let mut jobs = Vec::new();
+        while let Some(outcome) = tasks.join_next().await {
+            if let Ok(outcome) = outcome {
+                jobs.extend(outcome.matches);
+            }
+        }
+        sort_results(&mut jobs);
+        jobs
+    }
+
+    /// One board's listing through [`filter_jobs`], after a random wait of up
+    /// to the politeness delay. The listing's own request is the caller's to
+    /// claim from the request budget, as [`search`](Self::search) does; every
+    /// request after it is claimed here.
+    pub async fn search_board(&self, board_token: &str, query: &SearchQuery) -> BoardOutcome {
+        let delay_ms = self.politeness_delay.as_millis() as u64;
+        if delay_ms > 0 {
+            tokio::time::sleep(Duration::from_millis(rand::random::<u64>() % delay_ms)).await;
+        }
+        let mut visited = vec![board_token.to_string()];
+        let response = loop {
+            let token = visited.last().expect("starts with the board's own token");
+            let api_url = self.jobs_url(token, !self.lite);
+            let response = self.request(board_token, &api_url).send().await;
+            let Some(resp) = response.as_ref().ok().filter(|resp| resp.status().is_redirection()) else {
+                break response;
+            };
+            let location = resp.headers().get(reqwest::header::LOCATION).and_then(|value| value.to_str().ok());
+            let problem = match location.and_then(|location| self.redirect_target(resp.url(), location)) {
+                None => format!("redirects to {}, which isn't a board", location.unwrap_or("nowhere")),
+                Some(next) if visited.contains(&next) => format!("redirects in a loop through {}", visited.join(" -> ")),
+                Some(_) if visited.len() > MAX_BOARD_REDIRECTS => {
+                    format!("redirects more than {} times ({})", MAX_BOARD_REDIRECTS, visited.join(" -> "))
+                }
+                Some(_) if !self.take_request() => {
+                    return BoardOutcome::failed(BoardStatus::Failed, BoardProblem::OutOfRequests);
+                }
+                Some(next) => {
+                    visited.push(next);
+                    continue;
+                }
+            };
+            return BoardOutcome::failed(BoardStatus::Failed, BoardProblem::Redirect(problem));
+        };
+        let renamed_to = (visited.len() > 1).then(|| visited.last().cloned()).flatten();
+        let board_token = visited.last().expect("starts with the board's own token").as_str();
+
+        let response = match response {
+            Ok(resp) if !resp.status().is_success() => {
+                let status = match resp.status().as_u16() {
+                    404 => BoardStatus::NotFound,
+                    429 => BoardStatus::RateLimited,
+                    _ => BoardStatus::Failed,
+                };
+                return BoardOutcome::failed(status, BoardProblem::Status(resp.status()));
+            }
+            Ok(resp) => resp,
+            Err(e) => return BoardOutcome::failed(BoardStatus::Failed, BoardProblem::Network(e.to_string())),
+        };
+        let body = match response.text().await {
+            Ok(body) => body,
+            Err(e) => return BoardOutcome::failed(BoardStatus::Failed, BoardProblem::Network(e.to_string())),
+        };
+        let jobs_response: JobsResponse = match serde_json::from_str(&body) {
+            Ok(data) => data,
+            // Greenhouse sometimes answers 200 with an error document instead of a job list
+            Err(e) => {
+                return match ApiError::from_body(&body) {
+                    Some(error) => {
+                        let status = if error.rate_limited { BoardStatus::RateLimited } else { BoardStatus::Failed };
+                        BoardOutcome::failed(status, BoardProblem::Api(error.message))
+                    }
+                    None => BoardOutcome::failed(BoardStatus::Failed, BoardProblem::Parse(e.to_string())),
+                };
+            }
+        };
+        let listing = jobs_response.jobs;
+        if !self.lite {
+            return BoardOutcome { renamed_to, ..filter_jobs(board_token, &listing, query) };
+        }
+
+        let (candidates, excluded) = lite_candidates(&listing, query);
+        // Out of requests, the candidates keep their listing entries
+        if candidates.len() > LITE_JOB_FETCHES && self.take_request() {
+            let api_url = self.jobs_url(board_token, true);
+            if let Ok(full) = self.fetch::<JobsResponse>(&visited[0], &api_url).await {
+                return BoardOutcome { renamed_to, ..filter_jobs(board_token, &full.jobs, query) };
+            }
+        }
+        let upgraded = self.full_records(&visited[0], board_token, &candidates).await;
+        BoardOutcome { renamed_to, ..upgraded_outcome(board_token, &listing, &upgraded, excluded, query) }
+    }
+
+    // The token a board's redirect points to; Location may be relative to the request
+    fn redirect_target(&self, from: &reqwest::Url, location: &str) -> Option<String> {
+        let target = from.join(location).ok()?;
+        match target.as_str().strip_prefix(&format!("{}/", self.base_url)) {
+            Some(rest) => rest.split(['/', '?']).next().filter(|token| !token.is_empty()).map(String::from),
+            None => discovery::extract_board_token(target.as_str()),
+        }
+    }
+
+    // A GET with the board's credential, if it has one. Credentials are keyed
+    // by the token searched for, so a renamed board's new token is fetched with
+    // its old token's credential.
+    fn request(&self, searched_token: &str, url: &str) -> reqwest::RequestBuilder {
+        let request = self.board_client.get(url);
+        match self.credentials.get(searched_token) {
+            Some(secret) => request.bearer_auth(secret),
+            None => request,
+        }
+    }
+
+    async fn fetch<T: serde::de::DeserializeOwned>(&self, searched_token: &str, url: &str) -> Result<T, String> {
+        let response = self.request(searched_token, url).send().await.map_err(|e| format!("network error: {}", e))?;
+        if !response.status().is_success() {
+            return Err(format!("returned status {}", response.status()));
+        }
+        response.json().await.map_err(|e| format!("unexpected response: {}", e))
+    }
+
+    // Each lite candidate's full record, fetched all at once; a job whose
+    // record can't be had, or can't be asked for within the request budget,
+    // keeps its listing entry
+    async fn full_records(&self, searched_token: &str, board_token: &str, candidates: &[&Job]) -> Vec<Job> {
+        let mut tasks = tokio::task::JoinSet::new();
+        for (i, job) in candidates.iter().enumerate() {
+            if !self.take_request() {
+                break;
+            }
+            let (searcher, searched_token) = (self.clone(), searched_token.to_string());
+            let url = format!("{}/{}/jobs/{}", self.base_url, board_token, job.id);
+            tasks.spawn(async move { (i, searcher.fetch::<Job>(&searched_token, &url).await) });
+        }
+        let mut records: Vec<Job> = candidates.iter().map(|&job| job.clone()).collect();
+        while let Some(fetched) = tasks.join_next().await {
+            if let Ok((i, Ok(job))) = fetched {
+                records[i] = job;
+            }
+        }
+        records
+    }
+
+    // Claims a request from the budget, if there is one
+    fn take_request(&self) -> bool {
+        self.request_budget.as_ref().is_none_or(|budget| budget.take())
+    }
+}
+
+/// Where a company's boards are hosted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Region {
+    Us,
+    Eu,
+}
+
+impl Region {
+    pub fn base_url(self) -> &'static str {
+        match self {
+            Region::Us => DEFAULT_BASE_URL,
+            Region::Eu => "https://boards-api.eu.greenhouse.io/v1/boards",
+        }
+    }
+}
+
+/// Settings for a [`GreenhouseJobSearcher`], checked by [`build`](Self::build).
+/// Unset, a searcher waits 30 seconds for a response, fetches every board at
+/// once after a random wait of up to 200 ms, uses the US region and discovers
+/// boards through [`TokenSource::defaults`].
+#[derive(Debug, Default)]
+pub struct GreenhouseJobSearcherBuilder {
+    timeout: Option<Duration>,
+    user_agent: Option<String>,
+    client: Option<reqwest::Client>,
+    concurrency: Option<usize>,
+    politeness_delay: Option<Duration>,
+    base_url: Option<String>,
+    region: Option<Region>,
+    token_sources: Option<Vec<TokenSource>>,
+    request_budget: Option<Arc<RequestBudget>>,
+    board_query: Option<String>,
+    credentials: HashMap<String, String>,
+    lite: bool,
+}
+
+impl GreenhouseJobSearcherBuilder {
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Use this client for every request instead of building one. It should
+    /// not follow redirects, or renamed boards aren't reported as renamed;
+    /// the timeout and user agent are its own.
+    pub fn client(mut self, client: reqwest::Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Fetch at most this many boards at once in [`GreenhouseJobSearcher::search`]
+    pub fn concurrency(mut self, limit: usize) -> Self {
+        self.concurrency = Some(limit);
+        self
+    }
+
+    /// The longest random wait before each board's fetch; zero doesn't wait
+    pub fn politeness_delay(mut self, delay: Duration) -> Self {
+        self.politeness_delay = Some(delay);
+        self
+    }
+
+    /// The boards endpoint, e.g. a mock server's; instead of a region
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    pub fn region(mut self, region: Region) -> Self {
+        self.region = Some(region);
+        self
+    }
+
+    pub fn token_sources(mut self, sources: Vec<TokenSource>) -> Self {
+        self.token_sources = Some(sources);
+        self
+    }
+
+    /// Claim a turn from `budget` for every request: each board's listing in
+    /// [`GreenhouseJobSearcher::search`], and the follow-up requests
+    /// [`GreenhouseJobSearcher::search_board`] makes itself
+    pub fn request_budget(mut self, budget: Arc<RequestBudget>) -> Self {
+        self.request_budget = Some(budget);
+        self
+    }
+
+    /// See [`GreenhouseJobSearcher::board_query`]
+    pub fn board_query(mut self, query: Option<String>) -> Self {
+        self.board_query = query;
+        self
+    }
+
+    /// See [`GreenhouseJobSearcher::credentials`]
+    pub fn credentials(mut self, credentials: HashMap<String, String>) -> Self {
+        self.credentials = credentials;
+        self
+    }
+
+    /// See [`GreenhouseJobSearcher::lite`]
+    pub fn lite(mut self, lite: bool) -> Self {
+        self.lite = lite;
+        self
+    }
+
+    pub fn build(self) -> Result<GreenhouseJobSearcher, String> {
+        if self.timeout.is_some_and(|timeout| timeout.is_zero()) {
+            return Err("the timeout must be longer than zero".to_string());
+        }
+        if self.user_agent.as_deref().is_some_and(|agent| agent.trim().is_empty()) {
+            return Err("the user agent can't be empty".to_string());
+        }
+        if self.concurrency == Some(0) {
+            return Err("the concurrency limit must be at least 1".to_string());
+        }
+        if self.client.is_some() && (self.timeout.is_some() || self.user_agent.is_some()) {
+            return Err("a supplied client keeps its own timeout and user agent; set them on the client".to_string());
+        }
+        let base_url = match (self.base_url, self.region) {
+            (Some(_), Some(_)) => return Err("set either a base URL or a region, not both".to_string()),
+            (Some(url), None) => {
+                let parsed = reqwest::Url::parse(&url).map_err(|e| format!("base URL \"{}\" is not a URL: {}", url, e))?;
+                if !matches!(parsed.scheme(), "http" | "https") {
+                    return Err(format!("base URL \"{}\" must be http or https", url));
+                }
+                url.trim_end_matches('/').to_string()
+            }
+            (None, region) => region.unwrap_or(Region::Us).base_url().to_string(),
+        };
+        let token_sources = self.token_sources.unwrap_or_else(TokenSource::defaults);
+        for source in &token_sources {
+            if matches!(source, TokenSource::Urls(urls) if urls.is_empty()) {
+                return Err("a URL token source needs at least one URL".to_string());
+            }
+        }
+        let (board_client, client) = match self.client {
+            Some(client) => (client.clone(), client),
+            None => {
+                let builder = || {
+                    reqwest::Client::builder()
+                        .timeout(self.timeout.unwrap_or(Duration::from_secs(30)))
+                        .user_agent(self.user_agent.as_deref().unwrap_or(
+                            "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36",
+                        ))
+                };
+                let board_client = builder().redirect(reqwest::redirect::Policy::none()).build();
+                let client = builder().build();
+                match (board_client, client) {
+                    (Ok(board_client), Ok(client)) => (board_client, client),
+                    (Err(e), _) | (_, Err(e)) => return Err(format!("failed to create the HTTP client: {}", e)),
+                }
+            }
+        };
+        Ok(GreenhouseJobSearcher {
+            board_client,
+            client,
+            base_url,
+            concurrency: self.concurrency,
+            politeness_delay: self.politeness_delay.unwrap_or(Duration::from_millis(200)),
+            token_sources,
+            request_budget: self.request_budget,
+            board_query: self.board_query,
+            credentials: self.credentials,
+            lite: self.lite,
+        })
+    }
+}
+
+/// Per-board jobs endpoint under `base_url` (e.g. [`DEFAULT_BASE_URL`]). With
+/// `content`, `content=true` is requested for descriptions and department
+/// information, unless `board_query` sets `content` itself.
+pub fn board_jobs_url(base_url: &str, board_token: &str, board_query: Option<&str>, content: bool) -> String {
+    let base = format!("{}/{}/jobs", base_url, board_token);
+    match board_query {
+        Some(query) if !content || query.split('&').any(|pair| pair.split('=').next() == Some("content")) => {
+            format!("{}?{}", base, query)
+        }
+        Some(query) => format!("{}?content=true&{}", base, query),
+        None if content => format!("{}?content=true", base),
+        None => base,
+    }
+}
+
+/// The jobs in a listing fetched without content that pass the title and
+/// location filters, the ones worth fetching in full, and how many each filter
+/// left out. The department filter and the match cap wait for the full
+/// records. A location regex is tried against the location alone, since a
+/// listing without content has no offices, so a job that only an office's name
+/// would have matched is missed.
+pub fn lite_candidates<'a>(listing: &'a [Job], query: &SearchQuery) -> (Vec<&'a Job>, Exclusions) {
+    let mut matcher = BoardMatcher::new(&query.keywords, query.whole_word, &query.location, query.department.as_deref());
+    let mut excluded = Exclusions::default();
+    let mut candidates = Vec::new();
+    for job in listing {
+        if !matcher.title_matches(&job.title) {
+            excluded.title += 1;
+            continue;
+        }
+        let location_matches = match &query.location_regex {
+            Some(pattern) => location_regex_matches(job, pattern),
+            None => matcher.location_matches(&job.location.name),
+        };
+        if location_matches {
+            candidates.push(job);
+        } else {
+            excluded.location += 1;
+        }
+    }
+    (candidates, excluded)
+}
+
+// The candidates' full records through every filter, counted as if the whole
+// listing had been: `listing` is every job the board listed and `excluded`
+// what `lite_candidates` left out of it
+fn upgraded_outcome(board_token: &str, listing: &[Job], upgraded: &[Job], excluded: Exclusions, query: &SearchQuery) -> BoardOutcome {
+    let mut outcome = filter_jobs(board_token, upgraded, query);
+    outcome.listed_ids = listing.iter().map(|job| job.id).collect();
+    outcome.jobs_scanned += excluded.title + excluded.location;
+    outcome.excluded.add(&excluded);
+    outcome
+}
+
+/// The filter pipeline for one board's listing: title, then location, then
+/// department, until [`SearchQuery::max_matches`] is reached. Each job left out
+/// is counted under the first filter it fails.
+///
+/// ```
+/// use greenhouse_job_search::search::{filter_jobs, JobsResponse, SearchQuery};
+///
+/// let listing: JobsResponse = serde_json::from_str(r#"{"jobs": [
+///     {"id": 1, "title": "Senior Product Manager", "updated_at": "2024-06-01T12:00:00Z",
+///      "location": {"name": "Seattle, WA"}, "absolute_url": "https://boards.greenhouse.io/acme/jobs/1"},
+///     {"id": 2, "title": "Product Designer", "updated_at": "2024-06-02T12:00:00Z",
+///      "location": {"name": "Seattle, WA"}, "absolute_url": "https://boards.greenhouse.io/acme/jobs/2"},
+///     {"id": 3, "title": "Product Manager", "updated_at": "2024-06-03T12:00:00Z",
+///      "location": {"name": "Berlin, Germany"}, "absolute_url": "https://boards.greenhouse.io/acme/jobs/3"}
+/// ]}"#)?;
+///
+/// let outcome = filter_jobs("acme", &listing.jobs, &SearchQuery::new("product manager", "Seattle")?);
+/// assert_eq!(outcome.matches.len(), 1);
+/// assert_eq!(outcome.matches[0].title, "Senior Product Manager");
+/// assert_eq!((outcome.excluded.title, outcome.excluded.location), (1, 1));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn filter_jobs(board_token: &str, jobs: &[Job], query: &SearchQuery) -> BoardOutcome {
+    let mut outcome = BoardOutcome {
+        listed_ids: jobs.iter().map(|job| job.id).collect(),
+        ..BoardOutcome::without_jobs(BoardStatus::Searched)
+    };
+    let mut matcher = BoardMatcher::new(&query.keywords, query.whole_word, &query.location, query.department.as_deref());
+    for job in jobs {
+        // The board API returns every job in one response, so the cap saves matching work, not requests
+        if query.max_matches.is_some_and(|max| outcome.matches.len() >= max) {
+            outcome.excluded.unscanned = jobs.len() - outcome.jobs_scanned;
+            break;
+        }
+        outcome.jobs_scanned += 1;
+        if !matcher.title_matches(&job.title) {
+            outcome.excluded.title += 1;
+            continue;
+        }
+        let location_matches = match &query.location_regex {
+            Some(pattern) => location_regex_matches(job, pattern),
+            None => matcher.location_matches(&job.location.name),
+        };
+        if !location_matches {
+            outcome.excluded.location += 1;
+            continue;
+        }
+        let result = JobResult { relevance: matcher.relevance(), ..listed_job(board_token, job) };
+        if !matcher.department_matches(&result.departments) {
+            outcome.excluded.department += 1;
+            continue;
+        }
+        outcome.matches.push(result);
+    }
+    outcome
+}
+
+/// A job as its board lists it, before any search has looked at it
+pub fn listed_job(board_token: &str, job: &Job) -> JobResult {
+    // A mapped name wins, then the first department, then the capitalized board token
+    let company_name = match companies::mapped_name(board_token) {
+        Some(name) => name.to_string(),
+        None => match job.departments.as_ref().and_then(|departments| departments.first()) {
+            Some(department) => department.name.clone(),
+            None => companies::display_company_name(board_token),
+        },
+    };
+    JobResult {
+        id: job.id,
+        board_token: board_token.to_string(),
+        title: job.title.clone(),
+        company: company_name,
+        location: job.location.name.clone(),
+        date_posted: job.updated_at.clone(),
+        url: job.absolute_url.clone(),
+        raw: serde_json::to_value(job).unwrap_or_default(),
+        first_seen: None,
+        extras: BTreeMap::new(),
+        departments: job.departments.iter().flatten().map(|d| d.name.clone()).collect(),
+        tags: Vec::new(),
+        closed_at: None,
+        relevance: None,
+        favorite: false,
+    }
+}
+
+/// Favorite boards' jobs first, then within each part the most relevant first
+/// when the keywords are weighted, then newest first. Boards answer in a
+/// different order from run to run, so ties on the timestamp are broken by
+/// URL to keep the order identical across runs.
+pub fn sort_results(jobs: &mut [JobResult]) {
+    jobs.sort_by(|a, b| {
+        let relevance = |job: &JobResult| job.relevance.unwrap_or(0.0);
+        b.favorite.cmp(&a.favorite)
+            .then_with(|| relevance(b).total_cmp(&relevance(a)))
+            .then_with(|| posted_at(b).cmp(&posted_at(a)))
+            .then_with(|| a.url.cmp(&b.url))
+    });
+}
+
+/// When the job was last updated on its board, if Greenhouse's timestamp parses
+pub fn posted_at(job: &JobResult) -> Option<chrono::DateTime<chrono::Utc>> {
+    chrono::DateTime::parse_from_rfc3339(&job.date_posted)
+        .ok()
+        .map(|dt| dt.with_timezone(&chrono::Utc))
+}
+
+// The pattern is tried against the job's location and each of its offices'
+// names and locations
+fn location_regex_matches(job: &Job, pattern: &regex::Regex) -> bool {
+    if pattern.is_match(&job.location.name) {
+        return true;
+    }
+    let offices = job.extra.get("offices").and_then(serde_json::Value::as_array);
+    offices.into_iter().flatten().any(|office| {
+        ["name", "location"]
+            .iter()
+            .filter_map(|field| office.get(field).and_then(serde_json::Value::as_str))
+            .any(|text| pattern.is_match(text))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{ids, job, listing};
+    use crate::normalize;
+
+    fn keywords(raw: &str) -> keywords::KeywordSpec {
+        keywords::KeywordSpec::parse(raw).unwrap()
+    }
+
+    fn title_matches(title: &str, keywords: &keywords::KeywordSpec, whole_word: bool) -> bool {
+        crate::matching::folded_title_matches(&keywords.fold_title(title), keywords, whole_word)
+    }
+
+    #[test]
+    fn whole_word_matching_respects_word_boundaries() {
+        assert!(title_matches("Senior Product Manager", &keywords("manager"), true));
+        assert!(title_matches("Product Manager, Payments", &keywords("manager"), true));
+        assert!(!title_matches("Director of Mismanagement", &keywords("manager"), true));
+        assert!(!title_matches("Mismanager", &keywords("manager"), true));
+    }
+
+    #[test]
+    fn substring_matching_remains_the_default() {
+        assert!(title_matches("Product Manager", &keywords("manager"), false));
+        assert!(title_matches("Mismanager", &keywords("manager"), false));
+        assert!(title_matches("Director of Mismanagement", &keywords("manager"), false));
+    }
+
+    #[test]
+    fn empty_keyword_matches_every_title() {
+        for keyword in ["", "   "] {
+            for whole_word in [false, true] {
+                assert!(title_matches("Senior Product Manager", &keywords(keyword), whole_word));
+                assert!(title_matches("Staff Software Engineer", &keywords(keyword), whole_word));
+                assert!(title_matches("", &keywords(keyword), whole_word));
+            }
+        }
+    }
+
+    fn normalized_keywords(raw: &str, normalization: normalize::Normalization) -> keywords::KeywordSpec {
+        let mut spec = keywords(raw);
+        spec.normalize(normalization);
+        spec
+    }
+
+    #[test]
+    fn accented_titles_match_only_when_normalized() {
+        use normalize::Normalization::{Nfkc, StripDiacritics};
+        // Decomposed "e" + U+0301 in the title, precomposed "é" in the keyword
+        let decomposed = "De\u{301}veloppeur Backend";
+        assert!(!title_matches(decomposed, &keywords("développeur"), false));
+        assert!(title_matches(decomposed, &normalized_keywords("développeur", Nfkc), false));
+
+        // Without stripping, accents still have to match
+        assert!(!title_matches("Développeur Senior", &normalized_keywords("developpeur", Nfkc), false));
+        for (title, keyword) in [
+            ("Développeur Senior", "developpeur"),
+            ("Ingeniero de Datos Señor", "senor"),
+            ("Softwareentwickler (m/w/d) – Zürich", "zurich"),
+            ("Ingénieur Logiciel", "INGENIEUR"),
+            ("Straßenbau Projektleiter", "strassenbau"),
+            ("Senior Engineer", "sénior"),
+        ] {
+            assert!(title_matches(title, &normalized_keywords(keyword, StripDiacritics), false), "{} / {}", title, keyword);
+        }
+        assert!(title_matches("Développeur Full-Stack", &normalized_keywords("developpeur:2 full-stack:1", StripDiacritics), true));
+        assert!(!title_matches("Développeur Senior", &normalized_keywords("designer", StripDiacritics), false));
+    }
+
+    #[test]
+    fn normalization_folds_typographic_variants() {
+        use normalize::Normalization::Nfkc;
+        assert!(!title_matches("Sr Software Engineer", &keywords("sr."), false));
+        assert!(title_matches("Sr Software Engineer", &normalized_keywords("sr.", Nfkc), true));
+        assert!(title_matches("Sr. Software Engineer", &normalized_keywords("sr", Nfkc), true));
+        // The "ﬁ" ligature, an en dash and full-width letters
+        assert!(title_matches("Oﬃce Manager", &normalized_keywords("office", Nfkc), false));
+        assert!(title_matches("Front\u{2013}End Engineer", &normalized_keywords("front-end", Nfkc), false));
+        assert!(title_matches("ＳＲＥ Lead", &normalized_keywords("sre", Nfkc), true));
+    }
+
+    fn query(keyword: &str, location: &str) -> SearchQuery {
+        SearchQuery {
+            keywords: keywords(keyword),
+            whole_word: false,
+            location: location.to_string(),
+            location_regex: None,
+            department: None,
+            max_matches: None,
+        }
+    }
+
+    #[test]
+    fn filter_pipeline_composes_title_location_and_department() {
+        let jobs = vec![
+            listing(1, "Product Manager", "Remote", &["Product"]),
+            listing(2, "Product Manager", "Berlin, Germany", &["Product"]),
+            listing(3, "Software Engineer", "Remote", &["Engineering"]),
+            listing(4, "Product Manager, Platform", "San Francisco", &["Engineering"]),
+            listing(5, "Senior Product Manager", "Seattle, WA", &["Product Management"]),
+        ];
+
+        let outcome = filter_jobs("acme", &jobs, &query("product manager", "Seattle"));
+        assert_eq!(ids(&outcome.matches), vec![1, 4, 5]);
+
+        let outcome = filter_jobs("acme", &jobs, &SearchQuery { department: Some("product".to_string()), ..query("product manager", "Seattle") });
+        assert_eq!(ids(&outcome.matches), vec![1, 5]);
+        assert_eq!(outcome.jobs_scanned, 5);
+        assert_eq!((outcome.excluded.title, outcome.excluded.location, outcome.excluded.department), (1, 1, 1));
+        assert_eq!(outcome.listed_ids.len(), 5);
+    }
+
+    #[test]
+    fn filter_pipeline_counts_each_job_under_its_first_failing_filter() {
+        // Fails every filter, so only the title filter counts it
+        let jobs = vec![listing(1, "Recruiter", "Berlin, Germany", &["People"])];
+        let outcome = filter_jobs("acme", &jobs, &SearchQuery { department: Some("product".to_string()), ..query("manager", "Seattle") });
+        assert!(outcome.matches.is_empty());
+        assert_eq!(outcome.excluded.total(), 1);
+        assert_eq!(outcome.excluded.title, 1);
+    }
+
+    #[test]
+    fn filter_pipeline_caps_matches_after_every_filter() {
+        let jobs = vec![
+            listing(1, "Product Manager", "Remote", &["Engineering"]),
+            listing(2, "Product Manager", "Remote", &["Product"]),
+            listing(3, "Product Manager", "Remote", &["Product"]),
+            listing(4, "Product Manager", "Remote", &["Product"]),
+        ];
+        let query = SearchQuery { department: Some("product".to_string()), max_matches: Some(2), ..query("manager", "Remote") };
+        let outcome = filter_jobs("acme", &jobs, &query);
+        // A job the department filter drops doesn't use up the cap
+        assert_eq!(ids(&outcome.matches), vec![2, 3]);
+        assert_eq!((outcome.jobs_scanned, outcome.excluded.department, outcome.excluded.unscanned), (3, 1, 1));
+        // Every job is listed, scanned or not
+        assert_eq!(outcome.listed_ids.len(), 4);
+    }
+
+    #[test]
+    fn filter_pipeline_location_regex_replaces_loose_matching() {
+        let mut in_office = listing(3, "Product Manager", "Hybrid", &[]);
+        in_office.extra.insert("offices".to_string(), serde_json::json!([{ "name": "London", "location": "London, UK" }]));
+        let jobs = vec![
+            listing(1, "Product Manager", "Remote", &[]),
+            listing(2, "Product Manager", "London, UK", &[]),
+            in_office,
+        ];
+        let query = SearchQuery { location_regex: Some(regex::Regex::new("^London").unwrap()), ..query("manager", "Remote") };
+        let outcome = filter_jobs("acme", &jobs, &query);
+        assert_eq!(ids(&outcome.matches), vec![2, 3]);
+        assert_eq!(outcome.excluded.location, 1);
+    }
+
+    #[test]
+    fn filter_pipeline_weighted_keywords_combine_with_location() {
+        let jobs = vec![
+            listing(1, "Principal Product Manager", "Remote", &[]),
+            listing(2, "Product Designer", "Remote", &[]),
+            listing(3, "Principal Engineer", "Berlin, Germany", &[]),
+            listing(4, "Staff Product Manager", "New York, NY", &[]),
+        ];
+        let outcome = filter_jobs("acme", &jobs, &query("principal:3 product:1 manager:2", "New York"));
+        assert_eq!(ids(&outcome.matches), vec![1, 4]);
+        assert_eq!((outcome.excluded.title, outcome.excluded.location), (1, 1));
+        assert!(outcome.matches.iter().all(|job| job.relevance == Some(1.0)));
+    }
+
+    #[test]
+    fn filter_pipeline_ignores_case_in_ascii_and_other_text_alike() {
+        let jobs = vec![
+            listing(1, "PRODUCT MANAGER", "ZÜRICH, SCHWEIZ", &["Produkt"]),
+            listing(2, "Développeur Produit", "Zürich", &["PRODUKT"]),
+            listing(3, "Produktmanager", "Berlin", &["Produkt"]),
+            listing(4, "Product Designer", "Zürich", &["Design"]),
+        ];
+        let mut query = SearchQuery { department: Some("produkt".to_string()), ..query("produ:2 manager:1", "zürich") };
+        query.keywords.min_relevance = 0.5;
+        let outcome = filter_jobs("acme", &jobs, &query);
+        assert_eq!(ids(&outcome.matches), vec![1, 2]);
+        assert_eq!((outcome.excluded.location, outcome.excluded.department), (1, 1));
+        // Each job's relevance is of its own title, not the one matched before it
+        let relevance: Vec<Option<f64>> = outcome.matches.iter().map(|job| job.relevance).collect();
+        assert_eq!(relevance, vec![Some(1.0), Some(2.0 / 3.0)]);
+    }
+
+    #[test]
+    fn sort_results_breaks_date_ties_by_url() {
+        let tied = "2024-06-01T12:00:00-04:00";
+        let mut forward = vec![
+            job("https://example.com/c", tied),
+            job("https://example.com/a", tied),
+            job("https://example.com/newest", "2024-06-02T09:00:00-04:00"),
+            job("https://example.com/b", tied),
+            job("https://example.com/unparseable", "not a date"),
+        ];
+        let mut reversed: Vec<JobResult> = forward.iter().rev().cloned().collect();
+
+        sort_results(&mut forward);
+        sort_results(&mut reversed);
+
+        let urls = |jobs: &[JobResult]| jobs.iter().map(|j| j.url.clone()).collect::<Vec<_>>();
+        assert_eq!(urls(&forward), urls(&reversed));
+        assert_eq!(urls(&forward), vec![
+            "https://example.com/newest",
+            "https://example.com/a",
+            "https://example.com/b",
+            "https://example.com/c",
+            "https://example.com/unparseable",
+        ]);
+    }
+
+    #[test]
+    fn sort_results_compares_instants_across_offsets() {
+        // 09:00 in New York is later than 12:00 in London on the same day
+        let mut jobs = vec![
+            job("https://example.com/london", "2024-06-01T12:00:00+01:00"),
+            job("https://example.com/new-york", "2024-06-01T09:00:00-04:00"),
+        ];
+        sort_results(&mut jobs);
+        assert_eq!(jobs[0].url, "https://example.com/new-york");
+    }
+
+    #[test]
+    fn a_lite_search_counts_jobs_as_the_full_listing_would() {
+        let full = vec![
+            listing(1, "Product Manager", "Remote", &["Product"]),
+            listing(2, "Product Manager", "Berlin, Germany", &["Product"]),
+            listing(3, "Software Engineer", "Remote", &["Engineering"]),
+            listing(4, "Product Manager, Platform", "San Francisco", &["Engineering"]),
+        ];
+        // Listed without content, so without departments
+        let lite: Vec<Job> = full.iter().map(|job| Job { departments: None, ..job.clone() }).collect();
+        let query = SearchQuery { department: Some("product".to_string()), ..query("product manager", "Seattle") };
+
+        let (candidates, excluded) = lite_candidates(&lite, &query);
+        assert_eq!(candidates.iter().map(|job| job.id).collect::<Vec<_>>(), vec![1, 4]);
+        assert_eq!((excluded.title, excluded.location, excluded.department), (1, 1, 0));
+
+        let upgraded: Vec<Job> = full.iter().filter(|job| [1, 4].contains(&job.id)).cloned().collect();
+        let outcome = upgraded_outcome("acme", &lite, &upgraded, excluded, &query);
+        let expected = filter_jobs("acme", &full, &query);
+        assert_eq!(ids(&outcome.matches), ids(&expected.matches));
+        assert_eq!(outcome.jobs_scanned, expected.jobs_scanned);
+        assert_eq!(outcome.excluded.reasons(), expected.excluded.reasons());
+        assert_eq!(outcome.listed_ids, expected.listed_ids);
+
+        let url = |query, content| board_jobs_url(DEFAULT_BASE_URL, "acme", query, content);
+        assert_eq!(url(None, false), "https://boards-api.greenhouse.io/v1/boards/acme/jobs");
+        assert_eq!(url(Some("for=x"), false), "https://boards-api.greenhouse.io/v1/boards/acme/jobs?for=x");
+    }
+
+    #[test]
+    fn the_builder_checks_its_settings_and_redirects_follow_its_base_url() {
+        let err = |builder: GreenhouseJobSearcherBuilder| builder.build().unwrap_err();
+        assert!(err(GreenhouseJobSearcher::builder().concurrency(0)).contains("at least 1"));
+        assert!(err(GreenhouseJobSearcher::builder().timeout(Duration::ZERO)).contains("timeout"));
+        assert!(err(GreenhouseJobSearcher::builder().user_agent(" ")).contains("user agent"));
+        assert!(err(GreenhouseJobSearcher::builder().base_url("ftp://example.com")).contains("http"));
+        assert!(err(GreenhouseJobSearcher::builder().base_url("http://x").region(Region::Eu)).contains("not both"));
+        assert!(err(GreenhouseJobSearcher::builder().client(reqwest::Client::new()).user_agent("me")).contains("client"));
+        assert!(err(GreenhouseJobSearcher::builder().token_sources(vec![TokenSource::Urls(Vec::new())])).contains("URL"));
+
+        let searcher = GreenhouseJobSearcher::builder().build().unwrap();
+        assert_eq!(searcher.token_sources(), TokenSource::defaults());
+        let from = reqwest::Url::parse("https://boards-api.greenhouse.io/v1/boards/oldco/jobs?content=true").unwrap();
+        let target = |location: &str| searcher.redirect_target(&from, location);
+        assert_eq!(target("https://boards-api.greenhouse.io/v1/boards/newco/jobs?content=true").as_deref(), Some("newco"));
+        // Relative to the request
+        assert_eq!(target("/v1/boards/newco/jobs?content=true").as_deref(), Some("newco"));
+        assert_eq!(target("https://job-boards.greenhouse.io/newco").as_deref(), Some("newco"));
+        assert_eq!(target("https://www.example.com/careers"), None);
+        assert_eq!(discovery::extract_board_token("https://boards.greenhouse.io/embed/job_board?for=x"), None);
+
+        let mock = GreenhouseJobSearcher::builder().base_url("http://127.0.0.1:9/v1/boards/").build().unwrap();
+        assert_eq!(mock.jobs_url("oldco", true), "http://127.0.0.1:9/v1/boards/oldco/jobs?content=true");
+        let from = reqwest::Url::parse(&mock.jobs_url("oldco", true)).unwrap();
+        assert_eq!(mock.redirect_target(&from, "/v1/boards/newco/jobs").as_deref(), Some("newco"));
+    }
+
+    #[tokio::test]
+    async fn lite_follow_ups_stop_at_the_request_budget() {
+        let budget = RequestBudget::new(1);
+        // Nothing listens on the discard port, so the one fetch the budget allows fails fast
+        let searcher = GreenhouseJobSearcher::builder()
+            .base_url("http://127.0.0.1:9/v1/boards")
+            .request_budget(budget.clone())
+            .build()
+            .unwrap();
+        let listed: Vec<Job> = (1..=3).map(|id| Job { departments: None, ..listing(id, "Product Manager", "Remote", &[]) }).collect();
+        let candidates: Vec<&Job> = listed.iter().collect();
+        let records = searcher.full_records("acme", "acme", &candidates).await;
+        assert_eq!(records.iter().map(|job| job.id).collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert!(records.iter().all(|job| job.departments.is_none()));
+        assert!(!budget.take());
+    }
+
+    #[tokio::test]
+    async fn redirects_stop_at_the_request_budget() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        // Every board on this server has moved to another token
+        let server = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}/v1/boards", server.local_addr().unwrap());
+        let requests = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let served = requests.clone();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = server.accept().await {
+                served.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                let _ = socket.read(&mut [0; 4096]).await;
+                let moved = "HTTP/1.1 301 Moved Permanently\r\nLocation: /v1/boards/newco/jobs\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+                let _ = socket.write_all(moved.as_bytes()).await;
+            }
+        });
+        let budget = RequestBudget::new(1);
+        let searcher = GreenhouseJobSearcher::builder()
+            .base_url(base_url)
+            .politeness_delay(Duration::ZERO)
+            .request_budget(budget.clone())
+            .build()
+            .unwrap();
+
+        // The listing's own request is the caller's to claim
+        assert!(budget.take());
+        let outcome = searcher.search_board("oldco", &query("product manager", "Remote")).await;
+        assert_eq!(outcome.problem, Some(BoardProblem::OutOfRequests));
+        assert_eq!(requests.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,160 @@
+use crate::cli::Cli;
+use crate::{build_searcher, config::Config, export_results, icons, notify, update_dashboard, Exclusions, JobResult};
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::net::SocketAddr;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+// `serve`: run the search once, or every --interval, and answer read-only
+// JSON requests about the most recent completed cycle. Each cycle builds a
+// new snapshot and swaps it in whole, so a request sees one cycle or the
+// next and never a half-updated set.
+
+#[derive(Debug, Serialize)]
+struct Snapshot {
+    results: Vec<JobResult>,
+    stats: CycleStats,
+}
+
+#[derive(Debug, Serialize)]
+struct CycleStats {
+    cycle: u64,
+    completed_at: chrono::DateTime<chrono::Utc>,
+    elapsed_ms: u64,
+    boards_searched: usize,
+    boards_succeeded: usize,
+    boards_not_found: usize,
+    boards_rate_limited: usize,
+    boards_failed: usize,
+    boards_skipped: usize,
+    jobs_scanned: usize,
+    matches: usize,
+    // Matches the job store hadn't seen before this cycle
+    new_matches: usize,
+    excluded: Exclusions,
+}
+
+// None until the first search completes
+type Latest = Arc<RwLock<Option<Arc<Snapshot>>>>;
+
+#[derive(Deserialize)]
+struct ResultsQuery {
+    #[serde(default)]
+    new: bool,
+}
+
+fn latest(state: &Latest) -> Option<Arc<Snapshot>> {
+    state.read().unwrap_or_else(|poisoned| poisoned.into_inner()).clone()
+}
+
+fn not_ready() -> Response {
+    let body = serde_json::json!({ "error": "the first search hasn't completed yet" });
+    (StatusCode::SERVICE_UNAVAILABLE, Json(body)).into_response()
+}
+
+async fn results(State(state): State<Latest>, Query(query): Query<ResultsQuery>) -> Response {
+    let Some(snapshot) = latest(&state) else {
+        return not_ready();
+    };
+    let jobs: Vec<&JobResult> = snapshot.results.iter().filter(|job| !query.new || job.first_seen.is_none()).collect();
+    Json(jobs).into_response()
+}
+
+async fn stats(State(state): State<Latest>) -> Response {
+    match latest(&state) {
+        Some(snapshot) => Json(&snapshot.stats).into_response(),
+        None => not_ready(),
+    }
+}
+
+async fn healthz(State(state): State<Latest>) -> Response {
+    let snapshot = latest(&state);
+    Json(serde_json::json!({
+        "status": "ok",
+        "cycles": snapshot.as_ref().map_or(0, |s| s.stats.cycle),
+        "last_completed_at": snapshot.as_ref().map(|s| s.stats.completed_at),
+    }))
+    .into_response()
+}
+
+pub async fn run(cli: &Cli, addr: SocketAddr, interval: Option<Duration>) -> Result<(), Box<dyn Error>> {
+    println!("{}Greenhouse Job Search - Serve Mode", icons::WEB);
+    println!("=====================================\n");
+
+    let mut searcher = build_searcher(cli).unwrap_or_else(|e| {
+        eprintln!("{}{}", icons::ERROR, e);
+        std::process::exit(2);
+    });
+    let state: Latest = Arc::new(RwLock::new(None));
+    let app = Router::new()
+        .route("/results", get(results))
+        .route("/stats", get(stats))
+        .route("/healthz", get(healthz))
+        .with_state(state.clone());
+    let listener = tokio::net::TcpListener::bind(addr).await.map_err(|e| format!("can't listen on {}: {}", addr, e))?;
+    println!("{}Serving http://{}/results, /results?new=true, /stats and /healthz (Ctrl-C to stop)\n", icons::SERVING, addr);
+    tokio::spawn(async move {
+        if let Err(e) = axum::serve(listener, app).await {
+            eprintln!("{}Server stopped: {}", icons::WARNING, e);
+        }
+    });
+
+    let notifications = notify::Notifications::new(cli, &Config::load().notifications);
+    let cycles = async {
+        let mut cycle = 0;
+        loop {
+            match searcher.search_jobs(cli.title_keyword(), &cli.location).await {
+                Ok(jobs) => {
+                    cycle += 1;
+                    // Every match is served, but only the ones the job store hadn't seen are notified
+                    let new_jobs: Vec<JobResult> = jobs.iter().filter(|job| job.first_seen.is_none()).cloned().collect();
+                    let run = &searcher.last_run;
+                    let stats = CycleStats {
+                        cycle,
+                        completed_at: chrono::Utc::now(),
+                        elapsed_ms: run.elapsed.as_millis() as u64,
+                        boards_searched: run.boards_searched,
+                        boards_succeeded: run.boards_succeeded,
+                        boards_not_found: run.boards_not_found,
+                        boards_rate_limited: run.boards_rate_limited,
+                        boards_failed: run.boards_failed,
+                        boards_skipped: run.boards_skipped,
+                        jobs_scanned: run.jobs_scanned,
+                        matches: jobs.len(),
+                        new_matches: new_jobs.len(),
+                        excluded: run.excluded.clone(),
+                    };
+                    *state.write().unwrap_or_else(|poisoned| poisoned.into_inner()) =
+                        Some(Arc::new(Snapshot { results: jobs.clone(), stats }));
+
+                    export_results(cli, &searcher, &jobs);
+                    update_dashboard(cli, &jobs);
+                    notifications.notify_new_matches(searcher.api.client(), &new_jobs).await;
+                    notifications.notify_closed(searcher.api.client(), &searcher.closed_tracked).await;
+                    println!("{}Serving {} match(es) from cycle {}, {} new", icons::BROADCAST, jobs.len(), cycle, new_jobs.len());
+                }
+                Err(e) => eprintln!("{}Search failed; still serving the previous results: {}", icons::WARNING, e),
+            }
+
+            let Some(interval) = interval else {
+                println!("{}Not searching again (pass --interval to repeat)", icons::SLEEP);
+                return std::future::pending::<()>().await;
+            };
+            let next = chrono::Local::now() + chrono::Duration::from_std(interval).unwrap_or_default();
+            println!("{}Next search at {}\n", icons::SLEEP, next.format("%H:%M:%S"));
+            tokio::time::sleep(interval).await;
+        }
+    };
+
+    tokio::select! {
+        _ = cycles => {}
+        _ = tokio::signal::ctrl_c() => println!("\n{}Stopped serving", icons::BYE),
+    }
+    Ok(())
+}
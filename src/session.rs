@@ -0,0 +1,34 @@
+use crate::paths;
+use serde::{Deserialize, Serialize};
+
+// Browser state carried between TUI sessions so reopening a result set lands
+// where the previous session left off.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SessionState {
+    #[serde(default)]
+    pub last_selected_url: Option<String>,
+    #[serde(default)]
+    pub scroll_offset: usize,
+}
+
+impl SessionState {
+    pub fn load() -> Self {
+        std::fs::read_to_string(paths::session_state_path())
+            .ok()
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        if !paths::persistent() {
+            return Ok(());
+        }
+        let path = paths::session_state_path();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_string_pretty(self)?)?;
+        std::fs::rename(tmp, path)
+    }
+}
@@ -0,0 +1,58 @@
+use crate::cli::{Cli, DiscoverMode, DiscoverySource};
+use greenhouse_job_search::discovery::TokenSource;
+
+// Where board tokens come from. Sources run in the order given; in `first`
+// mode the chain stops at the first source that yields any tokens, in
+// `union` mode every source runs and their tokens are merged. Without
+// --discover the chain is the files and pages the user named, then Google,
+// then the built-in list, in `first` mode. Each source itself is in
+// `discovery`.
+
+pub fn token_sources(cli: &Cli) -> Result<Vec<TokenSource>, String> {
+    let mut chosen = Vec::new();
+    if cli.discover.is_empty() {
+        if cli.boards.is_some() {
+            chosen.push(DiscoverySource::BoardsFile);
+        }
+        if !cli.discover_url.is_empty() {
+            chosen.push(DiscoverySource::DiscoverFromUrl);
+        }
+        chosen.extend([DiscoverySource::Google, DiscoverySource::Builtin]);
+    } else {
+        for source in &cli.discover {
+            if !chosen.contains(source) {
+                chosen.push(*source);
+            }
+        }
+    }
+    chosen
+        .into_iter()
+        .map(|source| match source {
+            DiscoverySource::BoardsFile => match &cli.boards {
+                Some(path) => Ok(TokenSource::File(path.clone())),
+                None => Err("--discover boards-file needs --boards PATH".to_string()),
+            },
+            DiscoverySource::DiscoverFromUrl if cli.discover_url.is_empty() => {
+                Err("--discover discover-from-url needs at least one --discover-url URL".to_string())
+            }
+            DiscoverySource::DiscoverFromUrl => Ok(TokenSource::Urls(cli.discover_url.clone())),
+            DiscoverySource::Google => Ok(TokenSource::Google),
+            DiscoverySource::Builtin => Ok(TokenSource::Builtin),
+        })
+        .collect()
+}
+
+// Google then the built-in list; the search history only records other chains
+pub fn is_default(sources: &[TokenSource], mode: DiscoverMode) -> bool {
+    sources == TokenSource::defaults() && mode == DiscoverMode::First
+}
+
+// The --discover name of a source
+pub fn source_name(source: &TokenSource) -> &'static str {
+    match source {
+        TokenSource::File(_) => "boards-file",
+        TokenSource::Urls(_) => "discover-from-url",
+        TokenSource::Google => "google",
+        TokenSource::Builtin => "builtin",
+    }
+}
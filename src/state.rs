@@ -0,0 +1,464 @@
+use crate::{icons, paths, store};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::io::{BufRead, Read, Write};
+use std::path::{Path, PathBuf};
+
+// Moving the local state between machines. A bundle is a .tar.gz holding
+// manifest.json plus each persistence file under its plain file name, and the
+// saved runs under runs/ so the search history can still show them. The
+// manifest records the job store's schema version: bundles from a newer
+// schema are refused, older ones are migrated forward before anything is
+// replaced.
+//
+// `state wipe` deletes it instead: every file under the config, data and cache
+// directories, or with --applications, --cache or --annotations only those
+// parts. Files are overwritten with zeros before they're unlinked and emptied
+// tables are vacuumed out of the job store. On SSDs and copy-on-write
+// filesystems old blocks can outlive the overwrite; disk encryption is what
+// covers those.
+
+// Bumped when the bundle layout itself changes
+const BUNDLE_FORMAT: u32 = 1;
+const MANIFEST: &str = "manifest.json";
+const JOB_STORE: &str = "jobs.db";
+
+// Children before the applications they reference
+const APPLICATION_TABLES: &[&str] =
+    &["application_events", "application_reminders", "application_drafts", "application_queue", "applications"];
+const ANNOTATION_TABLES: &[&str] = &["job_tags"];
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Manifest {
+    format: u32,
+    created_at: chrono::DateTime<chrono::Utc>,
+    tool_version: String,
+    // File name -> schema version, for files that have one
+    files: BTreeMap<String, Option<usize>>,
+}
+
+// Bundle contents other than the manifest, by file name
+type BundleFiles = BTreeMap<String, Vec<u8>>;
+
+const SAVED_RUNS: &str = "runs/";
+
+// Every file that makes up the local state, keyed by its name in a bundle
+fn state_files() -> Vec<(String, PathBuf)> {
+    let mut files: Vec<(String, PathBuf)> = [
+        (JOB_STORE, paths::database_path()),
+        ("config.toml", paths::config_path()),
+        ("profile.toml", paths::profile_path()),
+        ("overrides.toml", paths::overrides_path()),
+        ("cover_letter.txt", paths::cover_letter_path()),
+        ("companies.toml", paths::companies_path()),
+        ("session.json", paths::session_state_path()),
+        ("watch_state.json", paths::watch_state_path()),
+        ("retry_queue.json", paths::retry_queue_path()),
+        ("hidden_jobs.json", paths::hidden_jobs_path()),
+        ("pending_notifications.json", paths::pending_notifications_path()),
+    ]
+    .into_iter()
+    .map(|(name, path)| (name.to_string(), path))
+    .collect();
+    if let Ok(entries) = std::fs::read_dir(paths::saved_runs_dir()) {
+        let mut runs: Vec<(String, PathBuf)> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter_map(|path| Some((format!("{}{}", SAVED_RUNS, saved_run_name(&path)?), path)))
+            .collect();
+        runs.sort();
+        files.extend(runs);
+    }
+    files
+}
+
+// A saved run's file name, for files that look like one
+fn saved_run_name(path: &Path) -> Option<&str> {
+    let name = path.file_name()?.to_str()?;
+    (name.ends_with(".json") && !name.starts_with('.')).then_some(name)
+}
+
+// Where a file in a bundle goes. Saved runs go under runs/ by their own name;
+// anything with more path to it than that is refused.
+fn import_target(name: &str) -> Option<PathBuf> {
+    if let Some(run) = name.strip_prefix(SAVED_RUNS) {
+        let path = Path::new(run);
+        return (path.components().count() == 1 && saved_run_name(path) == Some(run)).then(|| paths::saved_runs_dir().join(run));
+    }
+    state_files().into_iter().find(|(file, _)| file == name).map(|(_, path)| path)
+}
+
+pub fn export(bundle: &Path) -> Result<(), Box<dyn Error>> {
+    let mut manifest = Manifest {
+        format: BUNDLE_FORMAT,
+        created_at: chrono::Utc::now(),
+        tool_version: env!("CARGO_PKG_VERSION").to_string(),
+        files: BTreeMap::new(),
+    };
+    let mut contents: Vec<(String, Vec<u8>)> = Vec::new();
+
+    for (name, path) in state_files() {
+        if !path.exists() {
+            continue;
+        }
+        if name == JOB_STORE {
+            // Copy through SQLite so a search running alongside can't tear the file
+            let snapshot = std::env::temp_dir().join(format!("greenhouse-job-search-export-{}.db", std::process::id()));
+            let _ = std::fs::remove_file(&snapshot);
+            let result = store::JobStore::open_file(&path)
+                .and_then(|store| store.snapshot_to(&snapshot))
+                .map_err(|e| format!("{}: {}", path.display(), e))
+                .and_then(|_| std::fs::read(&snapshot).map_err(|e| e.to_string()));
+            let _ = std::fs::remove_file(&snapshot);
+            manifest.files.insert(name.clone(), Some(store::SCHEMA_VERSION));
+            contents.push((name, result?));
+        } else {
+            manifest.files.insert(name.clone(), None);
+            contents.push((name, std::fs::read(&path).map_err(|e| format!("{}: {}", path.display(), e))?));
+        }
+    }
+
+    let tmp = bundle.with_extension("tmp");
+    let file = std::fs::File::create(&tmp).map_err(|e| format!("{}: {}", tmp.display(), e))?;
+    let mut archive = tar::Builder::new(GzEncoder::new(file, flate2::Compression::default()));
+    append(&mut archive, MANIFEST, &serde_json::to_vec_pretty(&manifest)?)?;
+    for (name, data) in &contents {
+        append(&mut archive, name, data)?;
+    }
+    archive.into_inner()?.finish()?;
+    std::fs::rename(&tmp, bundle).map_err(|e| format!("{}: {}", bundle.display(), e))?;
+
+    println!("{}Exported {} file(s) to {}", icons::ARCHIVE, contents.len(), bundle.display());
+    for (name, _) in &contents {
+        println!("   {}", name);
+    }
+    Ok(())
+}
+
+fn append<W: std::io::Write>(archive: &mut tar::Builder<W>, name: &str, data: &[u8]) -> std::io::Result<()> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(data.len() as u64);
+    header.set_mode(0o600);
+    header.set_mtime(chrono::Utc::now().timestamp().max(0) as u64);
+    header.set_cksum();
+    archive.append_data(&mut header, name, data)
+}
+
+pub fn import(bundle: &Path) -> Result<(), Box<dyn Error>> {
+    if !paths::persistent() {
+        return Err("state isn't kept (persist = false under [storage] in config.toml), so there's nothing to import into".into());
+    }
+    let (manifest, mut files) = read_bundle(bundle)?;
+    if manifest.format > BUNDLE_FORMAT {
+        return Err(format!(
+            "{} was written by a newer version ({}); upgrade before importing it",
+            bundle.display(),
+            manifest.tool_version
+        ).into());
+    }
+
+    // Only files the manifest vouches for are restored; anything else in the archive is ignored
+    let targets: BTreeMap<String, PathBuf> = files
+        .keys()
+        .filter(|name| manifest.files.contains_key(*name))
+        .filter_map(|name| Some((name.clone(), import_target(name)?)))
+        .collect();
+    files.retain(|name, _| targets.contains_key(name));
+    if files.is_empty() {
+        return Err(format!("{} contains no state files", bundle.display()).into());
+    }
+
+    // Stage every file next to its destination, so the final renames stay on one filesystem
+    let mut staged: Vec<(PathBuf, PathBuf)> = Vec::new();
+    let result = stage(&files, &targets, &mut staged).and_then(|_| replace(&staged));
+    for (tmp, _) in &staged {
+        let _ = std::fs::remove_file(tmp);
+    }
+    result?;
+
+    println!(
+        "{}Imported {} file(s) from {} (exported {})",
+        icons::IMPORT, files.len(),
+        bundle.display(),
+        manifest.created_at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M")
+    );
+    for name in files.keys() {
+        println!("   {}", name);
+    }
+    Ok(())
+}
+
+fn read_bundle(bundle: &Path) -> Result<(Manifest, BundleFiles), Box<dyn Error>> {
+    let file = std::fs::File::open(bundle).map_err(|e| format!("{}: {}", bundle.display(), e))?;
+    let mut archive = tar::Archive::new(GzDecoder::new(file));
+    let mut manifest = None;
+    let mut files = BTreeMap::new();
+    for entry in archive.entries().map_err(|e| format!("{}: {}", bundle.display(), e))? {
+        let mut entry = entry.map_err(|e| format!("{}: not a state bundle ({})", bundle.display(), e))?;
+        // Entries are looked up by bare name and never extracted by path
+        let name = entry.path()?.to_string_lossy().into_owned();
+        let mut data = Vec::new();
+        entry.read_to_end(&mut data)?;
+        if name == MANIFEST {
+            manifest = Some(
+                serde_json::from_slice::<Manifest>(&data)
+                    .map_err(|e| format!("{}: unreadable manifest ({})", bundle.display(), e))?,
+            );
+        } else {
+            files.insert(name, data);
+        }
+    }
+    let manifest = manifest.ok_or_else(|| format!("{}: no manifest; not a state bundle", bundle.display()))?;
+    Ok((manifest, files))
+}
+
+fn stage(
+    files: &BundleFiles,
+    targets: &BTreeMap<String, PathBuf>,
+    staged: &mut Vec<(PathBuf, PathBuf)>,
+) -> Result<(), Box<dyn Error>> {
+    for (name, data) in files {
+        let target = &targets[name];
+        if let Some(dir) = target.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+        }
+        let tmp = target.with_extension("import");
+        std::fs::write(&tmp, data).map_err(|e| format!("{}: {}", tmp.display(), e))?;
+        staged.push((tmp.clone(), target.clone()));
+
+        if name == JOB_STORE {
+            let version = store::file_schema_version(&tmp).map_err(|e| format!("{} in the bundle: {}", name, e))?;
+            if version > store::SCHEMA_VERSION {
+                return Err(format!(
+                    "the bundle's job store uses schema {}, newer than this version supports ({}); upgrade first",
+                    version,
+                    store::SCHEMA_VERSION
+                ).into());
+            }
+            // Opening runs any migrations the bundle is missing
+            store::JobStore::open(&tmp).map_err(|e| format!("couldn't migrate the bundle's job store: {}", e))?;
+        } else if name.ends_with(".json") {
+            serde_json::from_slice::<serde_json::Value>(data).map_err(|e| format!("{} in the bundle: {}", name, e))?;
+        }
+    }
+    Ok(())
+}
+
+// Move each staged file into place, keeping the old copy until every rename
+// has succeeded so a failure part way through can put everything back
+fn replace(staged: &[(PathBuf, PathBuf)]) -> Result<(), Box<dyn Error>> {
+    let mut done: Vec<(&Path, Option<PathBuf>)> = Vec::new();
+    let mut failure = None;
+    for (tmp, target) in staged {
+        let backup = target.with_extension("before-import");
+        let had_file = target.exists();
+        if had_file {
+            if let Err(e) = std::fs::rename(target, &backup) {
+                failure = Some(format!("{}: {}", target.display(), e));
+                break;
+            }
+        }
+        let backup = had_file.then_some(backup);
+        if let Err(e) = std::fs::rename(tmp, target) {
+            if let Some(backup) = &backup {
+                let _ = std::fs::rename(backup, target);
+            }
+            failure = Some(format!("{}: {}", target.display(), e));
+            break;
+        }
+        done.push((target, backup));
+    }
+
+    match failure {
+        None => {
+            for (_, backup) in done {
+                if let Some(backup) = backup {
+                    let _ = std::fs::remove_file(backup);
+                }
+            }
+            Ok(())
+        }
+        Some(e) => {
+            for (target, backup) in done.into_iter().rev() {
+                match backup {
+                    Some(backup) => {
+                        let _ = std::fs::rename(backup, target);
+                    }
+                    None => {
+                        let _ = std::fs::remove_file(target);
+                    }
+                }
+            }
+            Err(format!("import failed and was rolled back: {}", e).into())
+        }
+    }
+}
+
+// What a wipe deletes, listed in full before anything goes
+#[derive(Debug, Default)]
+struct WipePlan {
+    files: Vec<PathBuf>,
+    // Table -> rows, emptied in the job store
+    tables: Vec<(&'static str, i64)>,
+    // Removed once their files are gone, deepest first
+    dirs: Vec<PathBuf>,
+}
+
+impl WipePlan {
+    fn is_empty(&self) -> bool {
+        self.files.is_empty() && self.tables.iter().all(|(_, rows)| *rows == 0) && self.dirs.is_empty()
+    }
+}
+
+pub fn wipe(applications: bool, cache: bool, annotations: bool, yes: bool) -> Result<(), Box<dyn Error>> {
+    let everything = !(applications || cache || annotations);
+    let mut plan = WipePlan::default();
+    if everything {
+        let mut roots = vec![paths::config_dir(), paths::data_dir(), paths::cache_dir()];
+        roots.sort();
+        roots.dedup();
+        // A platform's cache directory can sit inside its data directory
+        let roots: Vec<&Path> = roots.iter().filter(|root| !roots.iter().any(|other| other != *root && root.starts_with(other))).copied().collect();
+        for root in roots {
+            collect(root, &mut plan)?;
+        }
+    } else {
+        let database = paths::database_path();
+        let store = if database.is_file() { Some(store::JobStore::open_file(&database)?) } else { None };
+        let mut tables = Vec::new();
+        if applications {
+            tables.extend(APPLICATION_TABLES);
+            collect(&paths::dry_runs_dir(), &mut plan)?;
+        }
+        if annotations {
+            tables.extend(ANNOTATION_TABLES);
+            plan.files.extend(Some(paths::hidden_jobs_path()).filter(|path| path.is_file()));
+        }
+        if cache {
+            // When the cache shares a directory with everything else, only what's known to be cache goes
+            let shared = paths::cache_dir() == paths::data_dir() || paths::cache_dir() == paths::config_dir();
+            let dir = if shared { paths::education_cache_dir() } else { paths::cache_dir().to_path_buf() };
+            collect(&dir, &mut plan)?;
+        }
+        if let Some(store) = &store {
+            for table in tables {
+                plan.tables.push((table, store.row_count(table)?));
+            }
+        }
+    }
+
+    if plan.is_empty() {
+        println!("{}Nothing to delete", icons::INFO);
+        return Ok(());
+    }
+    println!("{}This permanently deletes:", icons::STOP);
+    for path in &plan.files {
+        println!("   {}", path.display());
+    }
+    for (table, rows) in plan.tables.iter().filter(|(_, rows)| *rows > 0) {
+        println!("   {} row(s) of {} in the job store", rows, table);
+    }
+    for dir in &plan.dirs {
+        println!("   {}{}", dir.display(), std::path::MAIN_SEPARATOR);
+    }
+    if !yes && !crate::apply::confirm("Delete all of this?", &mut std::io::stdin().lock().lines())? {
+        println!("{}Nothing deleted", icons::SKIPPED);
+        return Ok(());
+    }
+
+    let mut failures = Vec::new();
+    if !plan.tables.is_empty() {
+        let tables: Vec<&str> = plan.tables.iter().map(|(table, _)| *table).collect();
+        if let Err(e) = store::JobStore::open_file(&paths::database_path()).and_then(|mut store| store.wipe_tables(&tables)) {
+            failures.push(format!("the job store: {}", e));
+        }
+    }
+    for path in &plan.files {
+        if let Err(e) = shred(path) {
+            failures.push(format!("{}: {}", path.display(), e));
+        }
+    }
+    for dir in &plan.dirs {
+        if let Err(e) = std::fs::remove_dir(dir) {
+            failures.push(format!("{}: {}", dir.display(), e));
+        }
+    }
+    if !failures.is_empty() {
+        return Err(format!("some of it couldn't be deleted:\n   {}", failures.join("\n   ")).into());
+    }
+    println!("{}Deleted", icons::DONE);
+    Ok(())
+}
+
+// Every file under `dir`, and `dir` and its subdirectories after them
+fn collect(dir: &Path, plan: &mut WipePlan) -> std::io::Result<()> {
+    if !dir.is_dir() {
+        return Ok(());
+    }
+    let mut entries: Vec<PathBuf> = std::fs::read_dir(dir)?.map(|entry| entry.map(|entry| entry.path())).collect::<Result<_, _>>()?;
+    entries.sort();
+    for path in entries {
+        // Symlinks are removed, not followed
+        if path.is_dir() && !path.is_symlink() {
+            collect(&path, plan)?;
+        } else {
+            plan.files.push(path);
+        }
+    }
+    plan.dirs.push(dir.to_path_buf());
+    Ok(())
+}
+
+// Zeros over the contents, synced, then unlinked
+fn shred(path: &Path) -> std::io::Result<()> {
+    let metadata = std::fs::symlink_metadata(path)?;
+    if metadata.is_file() {
+        let mut file = std::fs::OpenOptions::new().write(true).open(path)?;
+        let zeros = [0u8; 64 * 1024];
+        let mut left = metadata.len();
+        while left > 0 {
+            let chunk = left.min(zeros.len() as u64) as usize;
+            file.write_all(&zeros[..chunk])?;
+            left -= chunk as u64;
+        }
+        file.sync_all()?;
+    }
+    std::fs::remove_file(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_wipe_lists_every_file_and_leaves_none() {
+        let root = std::env::temp_dir().join(format!("state_wipe_{}", std::process::id()));
+        std::fs::create_dir_all(root.join("runs")).unwrap();
+        std::fs::write(root.join("jobs.db"), b"applications").unwrap();
+        std::fs::write(root.join("runs").join("results.json"), vec![b'x'; 100_000]).unwrap();
+
+        let mut plan = WipePlan::default();
+        collect(&root, &mut plan).unwrap();
+        assert_eq!(plan.files, vec![root.join("jobs.db"), root.join("runs").join("results.json")]);
+        assert_eq!(plan.dirs, vec![root.join("runs"), root.clone()]);
+
+        for path in &plan.files {
+            shred(path).unwrap();
+        }
+        for dir in &plan.dirs {
+            std::fs::remove_dir(dir).unwrap();
+        }
+        assert!(!root.exists());
+    }
+
+    #[test]
+    fn bundled_runs_go_under_runs_by_name_only() {
+        assert_eq!(import_target("runs/20240601T120000.000Z.json"), Some(paths::saved_runs_dir().join("20240601T120000.000Z.json")));
+        assert_eq!(import_target("profile.toml"), Some(paths::profile_path()));
+        for name in ["runs/../jobs.db", "runs/nested/run.json", "runs/..json", "runs/notes.txt", "../profile.toml"] {
+            assert_eq!(import_target(name), None, "{}", name);
+        }
+    }
+}
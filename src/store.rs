@@ -0,0 +1,1382 @@
+use crate::cover_letter::SentLetter;
+use crate::resume::Resume;
+use crate::{fit, icons, locale, paths, JobResult};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+// Durable record of every matched job across runs, plus the searches that
+// produced them. Schema changes are appended to MIGRATIONS and tracked with
+// SQLite's `user_version` pragma, so existing databases upgrade in place.
+
+const MIGRATIONS: &[&str] = &[
+    // 1: jobs, searches and the matches linking them
+    "CREATE TABLE jobs (
+        board_token TEXT NOT NULL,
+        job_id INTEGER NOT NULL,
+        title TEXT NOT NULL,
+        company TEXT NOT NULL,
+        location TEXT NOT NULL,
+        url TEXT NOT NULL,
+        first_seen TEXT NOT NULL,
+        last_seen TEXT NOT NULL,
+        raw_json TEXT NOT NULL,
+        PRIMARY KEY (board_token, job_id)
+    );
+    CREATE TABLE searches (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        searched_at TEXT NOT NULL,
+        keyword TEXT NOT NULL,
+        location TEXT NOT NULL,
+        boards_searched INTEGER NOT NULL,
+        match_count INTEGER NOT NULL
+    );
+    CREATE TABLE search_matches (
+        search_id INTEGER NOT NULL REFERENCES searches(id),
+        board_token TEXT NOT NULL,
+        job_id INTEGER NOT NULL,
+        PRIMARY KEY (search_id, board_token, job_id)
+    );",
+    // 2: append-only log of applications
+    "CREATE TABLE applications (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        applied_at TEXT NOT NULL,
+        board_token TEXT NOT NULL,
+        job_id INTEGER NOT NULL,
+        title TEXT NOT NULL,
+        company TEXT NOT NULL,
+        url TEXT NOT NULL,
+        method TEXT NOT NULL,
+        notes TEXT
+    );
+    CREATE TRIGGER applications_no_update BEFORE UPDATE ON applications
+    BEGIN SELECT RAISE(ABORT, 'the application log is append-only'); END;
+    CREATE TRIGGER applications_no_delete BEFORE DELETE ON applications
+    BEGIN SELECT RAISE(ABORT, 'the application log is append-only'); END;",
+    // 3: what each search asked for and how it went; older rows keep NULLs
+    "ALTER TABLE searches ADD COLUMN filters TEXT NOT NULL DEFAULT '{}';
+    ALTER TABLE searches ADD COLUMN boards_succeeded INTEGER;
+    ALTER TABLE searches ADD COLUMN jobs_scanned INTEGER;
+    ALTER TABLE searches ADD COLUMN duration_ms INTEGER;
+    ALTER TABLE searches ADD COLUMN results_json TEXT;",
+    // 4: free-form tags on jobs, kept independently of whether the job is still listed
+    "CREATE TABLE job_tags (
+        board_token TEXT NOT NULL,
+        job_id INTEGER NOT NULL,
+        tag TEXT NOT NULL,
+        tagged_at TEXT NOT NULL,
+        PRIMARY KEY (board_token, job_id, tag)
+    );",
+    // 5: when a tracked job was found missing from its board; cleared if it's listed again
+    "ALTER TABLE jobs ADD COLUMN closed_at TEXT;",
+    // 6: snapshots of the full Greenhouse record of tagged and applied-to jobs
+    "CREATE TABLE job_archive (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        board_token TEXT NOT NULL,
+        job_id INTEGER NOT NULL,
+        archived_at TEXT NOT NULL,
+        raw_json TEXT NOT NULL
+    );
+    CREATE INDEX job_archive_job ON job_archive (board_token, job_id);",
+    // 7: the file a run's results were saved to; results_json is only read for older runs
+    "ALTER TABLE searches ADD COLUMN results_path TEXT;",
+    // 8: the resume file sent with an application and its SHA-256
+    "ALTER TABLE applications ADD COLUMN resume TEXT;
+    ALTER TABLE applications ADD COLUMN resume_sha256 TEXT;",
+    // 9: the cover letter text as it was sent
+    "ALTER TABLE applications ADD COLUMN cover_letter TEXT;",
+    // 10: partly filled application forms, one per job, deleted once submitted
+    "CREATE TABLE application_drafts (
+        board_token TEXT NOT NULL,
+        job_id INTEGER NOT NULL,
+        saved_at TEXT NOT NULL,
+        answers_json TEXT NOT NULL,
+        PRIMARY KEY (board_token, job_id)
+    );",
+    // 11: which applicant profile an application went out with
+    "ALTER TABLE applications ADD COLUMN profile TEXT;",
+    // 12: the latest resume fit analysis of each job
+    "CREATE TABLE resume_fits (
+        board_token TEXT NOT NULL,
+        job_id INTEGER NOT NULL,
+        analyzed_at TEXT NOT NULL,
+        resume TEXT NOT NULL,
+        score REAL NOT NULL,
+        missing_json TEXT NOT NULL,
+        PRIMARY KEY (board_token, job_id)
+    );",
+    // 13: jobs waiting to be applied to by `queue process`, in the order they were queued
+    "CREATE TABLE application_queue (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        board_token TEXT NOT NULL,
+        job_id INTEGER NOT NULL,
+        queued_at TEXT NOT NULL,
+        status TEXT NOT NULL,
+        reason TEXT,
+        attempts INTEGER NOT NULL DEFAULT 0,
+        updated_at TEXT NOT NULL,
+        UNIQUE (board_token, job_id)
+    );",
+    // 14: what happened after applying, as an append-only history, and the day to follow up on each application
+    "CREATE TABLE application_events (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        application_id INTEGER NOT NULL REFERENCES applications(id),
+        at TEXT NOT NULL,
+        status TEXT,
+        note TEXT
+    );
+    CREATE INDEX application_events_application ON application_events (application_id);
+    CREATE TRIGGER application_events_no_update BEFORE UPDATE ON application_events
+    BEGIN SELECT RAISE(ABORT, 'application history is append-only'); END;
+    CREATE TRIGGER application_events_no_delete BEFORE DELETE ON application_events
+    BEGIN SELECT RAISE(ABORT, 'application history is append-only'); END;
+    CREATE TABLE application_reminders (
+        application_id INTEGER PRIMARY KEY REFERENCES applications(id),
+        remind_on TEXT NOT NULL,
+        set_at TEXT NOT NULL,
+        notified_at TEXT
+    );",
+    // 15: the file a cover letter was attached as and its SHA-256; NULL when it was pasted
+    "ALTER TABLE applications ADD COLUMN cover_letter_file TEXT;
+    ALTER TABLE applications ADD COLUMN cover_letter_sha256 TEXT;",
+    // 16: a queued job's last submission as it was sent, while it can be sent again unchanged
+    "ALTER TABLE application_queue ADD COLUMN payload_json TEXT;",
+];
+
+// Archived snapshots beyond this many bytes of JSON are evicted, oldest untagged first
+const MAX_ARCHIVE_BYTES: i64 = 50 * 1024 * 1024;
+
+pub struct JobStore {
+    conn: Connection,
+    path: PathBuf,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ApplicationMethod {
+    Manual,
+    // Submitted through the Job Board API
+    Api,
+    // Went through every step but the POST; never an application
+    DryRun,
+    // Opened in the browser to apply by hand, because the API couldn't take it;
+    // a follow-up until a manual entry for the same job marks it done
+    Browser,
+}
+
+impl ApplicationMethod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApplicationMethod::Manual => "manual",
+            ApplicationMethod::Api => "api",
+            ApplicationMethod::DryRun => "dry-run",
+            ApplicationMethod::Browser => "browser",
+        }
+    }
+}
+
+// Where a queued job stands. Everything but `Submitted` and `Skipped` is
+// picked up again by the next `queue process`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QueueStatus {
+    Pending,
+    // Written just before the POST, so an interrupted run can tell it might have gone out
+    Submitting,
+    Submitted,
+    // Didn't go out; retried next time
+    Failed,
+    // Declined, or handed to the browser; stays out of later runs
+    Skipped,
+}
+
+impl QueueStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QueueStatus::Pending => "pending",
+            QueueStatus::Submitting => "submitting",
+            QueueStatus::Submitted => "submitted",
+            QueueStatus::Failed => "failed",
+            QueueStatus::Skipped => "skipped",
+        }
+    }
+
+    fn parse(text: &str) -> QueueStatus {
+        match text {
+            "submitting" => QueueStatus::Submitting,
+            "submitted" => QueueStatus::Submitted,
+            "failed" => QueueStatus::Failed,
+            "skipped" => QueueStatus::Skipped,
+            _ => QueueStatus::Pending,
+        }
+    }
+
+    pub fn finished(&self) -> bool {
+        matches!(self, QueueStatus::Submitted | QueueStatus::Skipped)
+    }
+}
+
+// What happened after applying, as recorded by `applications update`. An
+// application without any recorded status is still `Applied`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum ApplicationStatus {
+    #[value(skip)]
+    Applied,
+    /// A recruiter or phone screen
+    Screening,
+    Interview,
+    Offer,
+    Rejected,
+}
+
+impl ApplicationStatus {
+    pub const ALL: [ApplicationStatus; 5] = [
+        ApplicationStatus::Applied,
+        ApplicationStatus::Screening,
+        ApplicationStatus::Interview,
+        ApplicationStatus::Offer,
+        ApplicationStatus::Rejected,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApplicationStatus::Applied => "applied",
+            ApplicationStatus::Screening => "screening",
+            ApplicationStatus::Interview => "interview",
+            ApplicationStatus::Offer => "offer",
+            ApplicationStatus::Rejected => "rejected",
+        }
+    }
+
+    pub fn parse(text: &str) -> Option<ApplicationStatus> {
+        ApplicationStatus::ALL.into_iter().find(|status| status.as_str() == text)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ApplicationEvent {
+    pub id: i64,
+    pub application_id: i64,
+    pub at: String,
+    // None for an entry that only adds a note
+    pub status: Option<String>,
+    pub note: Option<String>,
+}
+
+// A follow-up date that has come, with what it's about
+#[derive(Debug, Clone, Serialize)]
+pub struct Reminder {
+    pub application_id: i64,
+    pub remind_on: chrono::NaiveDate,
+    pub company: String,
+    pub title: String,
+    pub url: String,
+    pub status: ApplicationStatus,
+}
+
+#[derive(Debug, Clone)]
+pub struct QueueEntry {
+    pub id: i64,
+    pub queued_at: String,
+    pub status: QueueStatus,
+    pub reason: Option<String>,
+    pub attempts: i64,
+    pub updated_at: String,
+    // submit::SavedPayload as JSON, after a submission that didn't get through
+    pub payload_json: Option<String>,
+    // As last recorded in `jobs`
+    pub job: JobResult,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ApplicationRecord {
+    pub id: i64,
+    pub applied_at: String,
+    pub board_token: String,
+    pub job_id: u64,
+    pub title: String,
+    pub company: String,
+    pub url: String,
+    pub method: String,
+    pub notes: Option<String>,
+    // File name and SHA-256 of the resume sent, for submitted applications
+    pub resume: Option<String>,
+    pub resume_sha256: Option<String>,
+    pub cover_letter: Option<String>,
+    // File name and SHA-256 of the letter when it was attached
+    pub cover_letter_file: Option<String>,
+    pub cover_letter_sha256: Option<String>,
+    // The applicant profile it went out with; NULL for manual entries and older rows
+    pub profile: Option<String>,
+    // The latest status `applications update` recorded, and when; None until the first
+    pub status: Option<String>,
+    pub status_at: Option<String>,
+    // Day to follow up on (YYYY-MM-DD), if one is set
+    pub remind_on: Option<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct ApplicationFilter {
+    pub since: Option<chrono::DateTime<chrono::Utc>>,
+    pub until: Option<chrono::DateTime<chrono::Utc>>,
+    pub company: Option<String>,
+    // Dry runs are left out unless asked for, so they never count as applying
+    pub dry_runs: bool,
+}
+
+// The schema version a database has once every migration has run
+pub const SCHEMA_VERSION: usize = MIGRATIONS.len();
+
+// A database file's schema version, read without migrating it
+pub fn file_schema_version(path: &Path) -> rusqlite::Result<usize> {
+    let conn = Connection::open_with_flags(path, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    conn.pragma_query_value(None, "user_version", |row| row.get(0))
+}
+
+// One run's parameters and outcome, written to the search history
+pub struct SearchRun<'a> {
+    pub keyword: &'a str,
+    pub location: &'a str,
+    pub filters: serde_json::Value,
+    pub boards_searched: usize,
+    pub boards_succeeded: usize,
+    pub jobs_scanned: usize,
+    pub duration: std::time::Duration,
+    // Where the full result set was saved, relative to the saved runs directory,
+    // so the run can be shown again later
+    pub results_path: Option<&'a std::path::Path>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SearchHistoryEntry {
+    pub id: i64,
+    pub searched_at: String,
+    pub keyword: String,
+    pub location: String,
+    pub filters: serde_json::Value,
+    pub boards_searched: i64,
+    pub boards_succeeded: Option<i64>,
+    pub jobs_scanned: Option<i64>,
+    pub match_count: i64,
+    pub duration_ms: Option<i64>,
+    // Saved results of runs from before they went to their own file
+    #[serde(skip)]
+    pub results_json: Option<String>,
+    pub results_path: Option<String>,
+}
+
+const HISTORY_COLUMNS: &str = "id, searched_at, keyword, location, filters, boards_searched, boards_succeeded,
+    jobs_scanned, match_count, duration_ms, results_json, results_path";
+
+fn history_entry(row: &rusqlite::Row) -> rusqlite::Result<SearchHistoryEntry> {
+    let filters: String = row.get(4)?;
+    Ok(SearchHistoryEntry {
+        id: row.get(0)?,
+        searched_at: row.get(1)?,
+        keyword: row.get(2)?,
+        location: row.get(3)?,
+        filters: serde_json::from_str(&filters).unwrap_or_default(),
+        boards_searched: row.get(5)?,
+        boards_succeeded: row.get(6)?,
+        jobs_scanned: row.get(7)?,
+        match_count: row.get(8)?,
+        duration_ms: row.get(9)?,
+        results_json: row.get(10)?,
+        results_path: row.get(11)?,
+    })
+}
+
+const STORED_JOB_COLUMNS: &str = "board_token, job_id, title, company, location, url, first_seen, raw_json, closed_at";
+
+// A job as last recorded, without the parts only a search fills in
+fn stored_job(row: &rusqlite::Row) -> rusqlite::Result<JobResult> {
+    let first_seen: String = row.get(6)?;
+    let raw: serde_json::Value = serde_json::from_str(&row.get::<_, String>(7)?).unwrap_or_default();
+    let date_posted = raw.get("updated_at").and_then(serde_json::Value::as_str).unwrap_or(&first_seen).to_string();
+    Ok(JobResult {
+        id: row.get::<_, i64>(1)? as u64,
+        board_token: row.get(0)?,
+        title: row.get(2)?,
+        company: row.get(3)?,
+        location: row.get(4)?,
+        date_posted,
+        url: row.get(5)?,
+        first_seen: chrono::DateTime::parse_from_rfc3339(&first_seen).ok().map(|dt| dt.with_timezone(&chrono::Utc)),
+        raw,
+        extras: BTreeMap::new(),
+        departments: Vec::new(),
+        tags: Vec::new(),
+        closed_at: row.get(8)?,
+        relevance: None,
+        favorite: false,
+    })
+}
+
+// One archived copy of a job's Greenhouse record
+#[derive(Debug, Clone)]
+pub struct ArchivedJob {
+    pub board_token: String,
+    pub job_id: u64,
+    pub archived_at: String,
+    pub raw: serde_json::Value,
+}
+
+fn archived_job(row: &rusqlite::Row) -> rusqlite::Result<ArchivedJob> {
+    let raw: String = row.get(3)?;
+    Ok(ArchivedJob {
+        board_token: row.get(0)?,
+        job_id: row.get::<_, i64>(1)? as u64,
+        archived_at: row.get(2)?,
+        raw: serde_json::from_str(&raw).unwrap_or_default(),
+    })
+}
+
+// A partly filled application form: field name -> the text, the chosen
+// options' labels, "Yes"/"No" or the file path entered for it
+#[derive(Debug, Clone)]
+pub struct ApplicationDraft {
+    pub saved_at: String,
+    pub answers: BTreeMap<String, Vec<String>>,
+}
+
+// How well a resume covered a job's description when it was last analyzed
+#[derive(Debug, Clone)]
+pub struct ResumeFit {
+    pub analyzed_at: String,
+    // File name of the resume analyzed
+    pub resume: String,
+    pub score: f64,
+    // The description's prominent terms the resume lacks, heaviest first
+    pub missing: Vec<String>,
+}
+
+impl ResumeFit {
+    pub fn new(resume: &Path, analysis: &fit::Analysis) -> Self {
+        ResumeFit {
+            analyzed_at: chrono::Utc::now().to_rfc3339(),
+            resume: resume.file_name().unwrap_or(resume.as_os_str()).to_string_lossy().into_owned(),
+            score: analysis.score,
+            missing: analysis.missing.clone(),
+        }
+    }
+
+    pub fn percent(&self) -> u32 {
+        (self.score * 100.0).round() as u32
+    }
+}
+
+pub struct StoreStats {
+    pub job_count: i64,
+    pub company_count: i64,
+    pub search_count: i64,
+    pub first_search: Option<String>,
+    pub last_search: Option<String>,
+    pub new_last_7_days: i64,
+    pub top_companies: Vec<(String, i64)>,
+}
+
+impl JobStore {
+    // In memory, empty, when state isn't kept (`paths::persistent`)
+    pub fn open(path: &Path) -> rusqlite::Result<Self> {
+        if !paths::persistent() {
+            let mut store = Self { conn: Connection::open_in_memory()?, path: path.to_path_buf() };
+            store.migrate()?;
+            return Ok(store);
+        }
+        Self::open_file(path)
+    }
+
+    // The database on disk whatever the setting, for `state wipe`
+    pub fn open_file(path: &Path) -> rusqlite::Result<Self> {
+        if let Some(parent) = path.parent() {
+            // Surfaced as an open error below if the directory really can't be created
+            let _ = std::fs::create_dir_all(parent);
+        }
+        let conn = Connection::open(path)?;
+        let mut store = Self { conn, path: path.to_path_buf() };
+        store.migrate()?;
+        Ok(store)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn migrate(&mut self) -> rusqlite::Result<()> {
+        let version: usize = self.conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+        for (i, sql) in MIGRATIONS.iter().enumerate().skip(version) {
+            let tx = self.conn.transaction()?;
+            tx.execute_batch(sql)?;
+            tx.pragma_update(None, "user_version", i + 1)?;
+            tx.commit()?;
+        }
+        Ok(())
+    }
+
+    // A consistent copy of the whole database, safe to take while it's in use
+    pub fn snapshot_to(&self, path: &Path) -> rusqlite::Result<()> {
+        self.conn.execute("VACUUM INTO ?1", params![path.to_string_lossy()])?;
+        Ok(())
+    }
+
+    pub fn row_count(&self, table: &str) -> rusqlite::Result<i64> {
+        self.conn.query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |row| row.get(0))
+    }
+
+    // `state wipe`: deletes every row of `tables`, lifting their append-only
+    // triggers for the deletes, then vacuums so no deleted row is left behind
+    // in the file's free pages
+    pub fn wipe_tables(&mut self, tables: &[&str]) -> rusqlite::Result<()> {
+        self.conn.pragma_update(None, "secure_delete", true)?;
+        let tx = self.conn.transaction()?;
+        let mut triggers = Vec::new();
+        for table in tables {
+            let mut stmt = tx.prepare("SELECT name, sql FROM sqlite_master WHERE type = 'trigger' AND tbl_name = ?1")?;
+            let rows = stmt.query_map(params![table], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?;
+            triggers.extend(rows.collect::<rusqlite::Result<Vec<_>>>()?);
+        }
+        for (name, _) in &triggers {
+            tx.execute_batch(&format!("DROP TRIGGER {}", name))?;
+        }
+        for table in tables {
+            tx.execute(&format!("DELETE FROM {}", table), [])?;
+        }
+        for (_, sql) in &triggers {
+            tx.execute_batch(sql)?;
+        }
+        tx.commit()?;
+        self.conn.execute_batch("VACUUM")
+    }
+
+    pub fn search_count(&self) -> rusqlite::Result<i64> {
+        self.conn.query_row("SELECT COUNT(*) FROM searches", [], |row| row.get(0))
+    }
+
+    // Fill in when each job was first recorded; jobs never seen before keep `None`
+    pub fn annotate_first_seen(&self, jobs: &mut [JobResult]) -> rusqlite::Result<()> {
+        let mut stmt = self.conn.prepare_cached(
+            "SELECT first_seen FROM jobs WHERE board_token = ?1 AND job_id = ?2",
+        )?;
+        for job in jobs.iter_mut() {
+            let first_seen: Option<String> = stmt
+                .query_row(params![job.board_token, job.id as i64], |row| row.get(0))
+                .optional()?;
+            job.first_seen = first_seen
+                .and_then(|ts| chrono::DateTime::parse_from_rfc3339(&ts).ok())
+                .map(|dt| dt.with_timezone(&chrono::Utc));
+        }
+        Ok(())
+    }
+
+    // Fill in each job's tags, sorted by name
+    pub fn annotate_tags(&self, jobs: &mut [JobResult]) -> rusqlite::Result<()> {
+        for job in jobs.iter_mut() {
+            job.tags = self.tags_for(job)?;
+        }
+        Ok(())
+    }
+
+    pub fn tags_for(&self, job: &JobResult) -> rusqlite::Result<Vec<String>> {
+        let mut stmt = self.conn.prepare_cached(
+            "SELECT tag FROM job_tags WHERE board_token = ?1 AND job_id = ?2 ORDER BY tag",
+        )?;
+        let rows = stmt.query_map(params![job.board_token, job.id as i64], |row| row.get(0))?;
+        rows.collect()
+    }
+
+    // Replace a job's tags with `tags`; an empty list removes them all
+    pub fn set_tags(&self, job: &JobResult, tags: &[String]) -> rusqlite::Result<()> {
+        let now = chrono::Utc::now().to_rfc3339();
+        let tx = self.conn.unchecked_transaction()?;
+        tx.execute(
+            "DELETE FROM job_tags WHERE board_token = ?1 AND job_id = ?2",
+            params![job.board_token, job.id as i64],
+        )?;
+        for tag in tags {
+            tx.execute(
+                "INSERT OR IGNORE INTO job_tags (board_token, job_id, tag, tagged_at) VALUES (?1, ?2, ?3, ?4)",
+                params![job.board_token, job.id as i64, tag, now],
+            )?;
+        }
+        tx.commit()
+    }
+
+    // Every tag in use, for autocompletion
+    pub fn all_tags(&self) -> rusqlite::Result<Vec<String>> {
+        let mut stmt = self.conn.prepare("SELECT DISTINCT tag FROM job_tags ORDER BY tag")?;
+        let rows = stmt.query_map([], |row| row.get(0))?;
+        rows.collect()
+    }
+
+    // Jobs worth noticing when they close: tagged or applied to, closed ones included
+    pub fn tracked_jobs(&self) -> rusqlite::Result<Vec<JobResult>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM jobs j
+             WHERE EXISTS (SELECT 1 FROM job_tags t WHERE t.board_token = j.board_token AND t.job_id = j.job_id)
+                OR EXISTS (SELECT 1 FROM applications a WHERE a.board_token = j.board_token AND a.job_id = j.job_id)
+             ORDER BY board_token, job_id",
+            STORED_JOB_COLUMNS
+        ))?;
+        let rows = stmt.query_map([], stored_job)?;
+        rows.collect()
+    }
+
+    // Every recorded job with this id; `board_token` narrows an id shared by several boards
+    pub fn jobs_with_id(&self, job_id: u64, board_token: Option<&str>) -> rusqlite::Result<Vec<JobResult>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM jobs WHERE job_id = ?1 AND (?2 IS NULL OR board_token = ?2) ORDER BY board_token",
+            STORED_JOB_COLUMNS
+        ))?;
+        let rows = stmt.query_map(params![job_id as i64, board_token], stored_job)?;
+        rows.collect()
+    }
+
+    pub fn board_jobs(&self, board_token: &str) -> rusqlite::Result<Vec<JobResult>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM jobs WHERE board_token = ?1 ORDER BY job_id",
+            STORED_JOB_COLUMNS
+        ))?;
+        let rows = stmt.query_map(params![board_token], stored_job)?;
+        rows.collect()
+    }
+
+    // Mark a job as gone from its board at `closed_at`, or as listed again with `None`
+    pub fn set_closed(&self, job: &JobResult, closed_at: Option<&str>) -> rusqlite::Result<()> {
+        self.conn.execute(
+            "UPDATE jobs SET closed_at = ?3 WHERE board_token = ?1 AND job_id = ?2",
+            params![job.board_token, job.id as i64, closed_at],
+        )?;
+        Ok(())
+    }
+
+    // Fill in when each job was found closed; jobs still listed keep `None`
+    pub fn annotate_closed(&self, jobs: &mut [JobResult]) -> rusqlite::Result<()> {
+        let mut stmt = self.conn.prepare_cached(
+            "SELECT closed_at FROM jobs WHERE board_token = ?1 AND job_id = ?2",
+        )?;
+        for job in jobs.iter_mut() {
+            job.closed_at = stmt
+                .query_row(params![job.board_token, job.id as i64], |row| row.get(0))
+                .optional()?
+                .flatten();
+        }
+        Ok(())
+    }
+
+    // Snapshot the job's full record, falling back to the copy in `jobs` when the
+    // result has none (e.g. saved results). Identical snapshots are stored once.
+    // Returns whether a new snapshot was written.
+    pub fn archive_job(&self, job: &JobResult) -> rusqlite::Result<bool> {
+        let raw_json = if job.raw.is_null() {
+            let stored: Option<String> = self.conn.query_row(
+                "SELECT raw_json FROM jobs WHERE board_token = ?1 AND job_id = ?2",
+                params![job.board_token, job.id as i64],
+                |row| row.get(0),
+            ).optional()?;
+            match stored {
+                Some(raw) => raw,
+                None => return Ok(false),
+            }
+        } else {
+            job.raw.to_string()
+        };
+        if matches!(raw_json.as_str(), "null" | "{}") {
+            return Ok(false);
+        }
+
+        let exists: bool = self.conn.query_row(
+            "SELECT EXISTS (SELECT 1 FROM job_archive WHERE board_token = ?1 AND job_id = ?2 AND raw_json = ?3)",
+            params![job.board_token, job.id as i64, raw_json],
+            |row| row.get(0),
+        )?;
+        if exists {
+            return Ok(false);
+        }
+        self.conn.execute(
+            "INSERT INTO job_archive (board_token, job_id, archived_at, raw_json) VALUES (?1, ?2, ?3, ?4)",
+            params![job.board_token, job.id as i64, chrono::Utc::now().to_rfc3339(), raw_json],
+        )?;
+        self.evict_archive(MAX_ARCHIVE_BYTES)?;
+        Ok(true)
+    }
+
+    // Drop the oldest snapshots of untagged jobs until the archive fits in `max_bytes`.
+    // Snapshots of tagged jobs are never evicted.
+    fn evict_archive(&self, max_bytes: i64) -> rusqlite::Result<()> {
+        let mut total: i64 =
+            self.conn.query_row("SELECT COALESCE(SUM(LENGTH(raw_json)), 0) FROM job_archive", [], |row| row.get(0))?;
+        if total <= max_bytes {
+            return Ok(());
+        }
+
+        let mut stmt = self.conn.prepare(
+            "SELECT id, LENGTH(raw_json) FROM job_archive a
+             WHERE NOT EXISTS (SELECT 1 FROM job_tags t WHERE t.board_token = a.board_token AND t.job_id = a.job_id)
+             ORDER BY archived_at, id",
+        )?;
+        let candidates: Vec<(i64, i64)> =
+            stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?.collect::<rusqlite::Result<_>>()?;
+        for (id, size) in candidates {
+            if total <= max_bytes {
+                break;
+            }
+            self.conn.execute("DELETE FROM job_archive WHERE id = ?1", params![id])?;
+            total -= size;
+        }
+        Ok(())
+    }
+
+    // Newest snapshot first; `board_token` narrows a job id shared by several boards
+    pub fn archived_snapshots(&self, job_id: u64, board_token: Option<&str>) -> rusqlite::Result<Vec<ArchivedJob>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT board_token, job_id, archived_at, raw_json FROM job_archive
+             WHERE job_id = ?1 AND (?2 IS NULL OR board_token = ?2)
+             ORDER BY archived_at DESC, id DESC",
+        )?;
+        let rows = stmt.query_map(params![job_id as i64, board_token], archived_job)?;
+        rows.collect()
+    }
+
+    pub fn latest_archive(&self, job: &JobResult) -> rusqlite::Result<Option<ArchivedJob>> {
+        Ok(self.archived_snapshots(job.id, Some(&job.board_token))?.into_iter().next())
+    }
+
+    // Record one completed search and upsert every matched job
+    pub fn record_search(&mut self, run: &SearchRun, jobs: &[JobResult]) -> rusqlite::Result<i64> {
+        let now = chrono::Utc::now().to_rfc3339();
+        let tx = self.conn.transaction()?;
+
+        tx.execute(
+            "INSERT INTO searches (searched_at, keyword, location, boards_searched, match_count, filters,
+                boards_succeeded, jobs_scanned, duration_ms, results_path)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![
+                now,
+                run.keyword,
+                run.location,
+                run.boards_searched as i64,
+                jobs.len() as i64,
+                run.filters.to_string(),
+                run.boards_succeeded as i64,
+                run.jobs_scanned as i64,
+                run.duration.as_millis() as i64,
+                run.results_path.map(|path| path.to_string_lossy().into_owned()),
+            ],
+        )?;
+        let search_id = tx.last_insert_rowid();
+
+        for job in jobs {
+            tx.execute(
+                "INSERT INTO jobs (board_token, job_id, title, company, location, url, first_seen, last_seen, raw_json)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?7, ?8)
+                 ON CONFLICT (board_token, job_id) DO UPDATE SET
+                    title = excluded.title,
+                    company = excluded.company,
+                    location = excluded.location,
+                    url = excluded.url,
+                    last_seen = excluded.last_seen,
+                    raw_json = excluded.raw_json,
+                    closed_at = NULL",
+                params![job.board_token, job.id as i64, job.title, job.company, job.location, job.url, now, job.raw.to_string()],
+            )?;
+            tx.execute(
+                "INSERT OR IGNORE INTO search_matches (search_id, board_token, job_id) VALUES (?1, ?2, ?3)",
+                params![search_id, job.board_token, job.id as i64],
+            )?;
+        }
+
+        tx.commit()?;
+        Ok(search_id)
+    }
+
+    // Append one entry to the application log; existing entries are never modified
+    pub fn record_application(&self, job: &JobResult, method: ApplicationMethod, notes: Option<&str>,
+        resume: Option<&Resume>, cover_letter: Option<&SentLetter>, profile: Option<&str>) -> rusqlite::Result<i64> {
+        self.conn.execute(
+            "INSERT INTO applications
+                (applied_at, board_token, job_id, title, company, url, method, notes, resume, resume_sha256,
+                 cover_letter, cover_letter_file, cover_letter_sha256, profile)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
+            params![
+                chrono::Utc::now().to_rfc3339(), job.board_token, job.id as i64, job.title,
+                job.company, job.url, method.as_str(), notes,
+                resume.map(Resume::file_name), resume.map(|resume| &resume.sha256),
+                cover_letter.map(|letter| &letter.text), cover_letter.and_then(|letter| letter.file_name.as_ref()),
+                cover_letter.and_then(|letter| letter.sha256.as_ref()), profile,
+            ],
+        )?;
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    // Marks a browser follow-up done with a manual entry for the same job; None
+    // when `id` isn't a browser entry
+    pub fn complete_follow_up(&self, id: i64) -> rusqlite::Result<Option<i64>> {
+        let inserted = self.conn.execute(
+            "INSERT INTO applications (applied_at, board_token, job_id, title, company, url, method, notes)
+             SELECT ?1, board_token, job_id, title, company, url, ?2, ?3 FROM applications WHERE id = ?4 AND method = ?5",
+            params![
+                chrono::Utc::now().to_rfc3339(), ApplicationMethod::Manual.as_str(),
+                format!("Applied by hand after #{} opened it in the browser", id), id, ApplicationMethod::Browser.as_str(),
+            ],
+        )?;
+        Ok((inserted > 0).then(|| self.conn.last_insert_rowid()))
+    }
+
+    // Adds a status and/or note to an application's history and sets or clears
+    // (`Some(None)`) its follow-up date. False when `id` isn't an application
+    // that went out; dry runs and jobs only opened in the browser have no status.
+    pub fn update_application(&self, id: i64, status: Option<ApplicationStatus>, note: Option<&str>,
+        remind_on: Option<Option<chrono::NaiveDate>>) -> rusqlite::Result<bool> {
+        let tx = self.conn.unchecked_transaction()?;
+        let went_out: bool = tx.query_row(
+            "SELECT EXISTS (SELECT 1 FROM applications WHERE id = ?1 AND method IN (?2, ?3))",
+            params![id, ApplicationMethod::Api.as_str(), ApplicationMethod::Manual.as_str()],
+            |row| row.get(0),
+        )?;
+        if !went_out {
+            return Ok(false);
+        }
+        let now = chrono::Utc::now().to_rfc3339();
+        if status.is_some() || note.is_some() {
+            tx.execute(
+                "INSERT INTO application_events (application_id, at, status, note) VALUES (?1, ?2, ?3, ?4)",
+                params![id, now, status.map(|status| status.as_str()), note],
+            )?;
+        }
+        match remind_on {
+            Some(Some(day)) => {
+                tx.execute(
+                    "INSERT OR REPLACE INTO application_reminders (application_id, remind_on, set_at, notified_at)
+                     VALUES (?1, ?2, ?3, NULL)",
+                    params![id, day.to_string(), now],
+                )?;
+            }
+            Some(None) => {
+                tx.execute("DELETE FROM application_reminders WHERE application_id = ?1", params![id])?;
+            }
+            None => {}
+        }
+        tx.commit()?;
+        Ok(true)
+    }
+
+    // Every application's history, oldest first
+    pub fn application_events(&self) -> rusqlite::Result<Vec<ApplicationEvent>> {
+        let mut stmt = self.conn.prepare("SELECT id, application_id, at, status, note FROM application_events ORDER BY id")?;
+        let rows = stmt.query_map([], |row| {
+            Ok(ApplicationEvent {
+                id: row.get(0)?,
+                application_id: row.get(1)?,
+                at: row.get(2)?,
+                status: row.get(3)?,
+                note: row.get(4)?,
+            })
+        })?;
+        rows.collect()
+    }
+
+    // Follow-up dates up to `today` that haven't been notified yet. Rejected
+    // applications are left out; there's nobody left to follow up with.
+    pub fn due_reminders(&self, today: chrono::NaiveDate) -> rusqlite::Result<Vec<Reminder>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT r.application_id, r.remind_on, a.company, a.title, a.url,
+                    (SELECT status FROM application_events
+                     WHERE application_id = a.id AND status IS NOT NULL ORDER BY id DESC LIMIT 1)
+             FROM application_reminders r JOIN applications a ON a.id = r.application_id
+             WHERE r.remind_on <= ?1 AND r.notified_at IS NULL
+             ORDER BY r.remind_on, r.application_id",
+        )?;
+        let rows = stmt.query_map(params![today.to_string()], |row| {
+            let remind_on: String = row.get(1)?;
+            let status: Option<String> = row.get(5)?;
+            Ok(Reminder {
+                application_id: row.get(0)?,
+                remind_on: remind_on.parse().unwrap_or(today),
+                company: row.get(2)?,
+                title: row.get(3)?,
+                url: row.get(4)?,
+                status: status.as_deref().and_then(ApplicationStatus::parse).unwrap_or(ApplicationStatus::Applied),
+            })
+        })?;
+        let reminders: Vec<Reminder> = rows.collect::<rusqlite::Result<_>>()?;
+        Ok(reminders.into_iter().filter(|reminder| reminder.status != ApplicationStatus::Rejected).collect())
+    }
+
+    // So a reminder goes out once; setting a new date arms it again
+    pub fn mark_reminded(&self, reminders: &[Reminder]) -> rusqlite::Result<()> {
+        let now = chrono::Utc::now().to_rfc3339();
+        for reminder in reminders {
+            self.conn.execute(
+                "UPDATE application_reminders SET notified_at = ?1 WHERE application_id = ?2",
+                params![now, reminder.application_id],
+            )?;
+        }
+        Ok(())
+    }
+
+    // Replaces the job's draft, if it has one
+    pub fn save_draft(&self, job: &JobResult, answers: &BTreeMap<String, Vec<String>>) -> rusqlite::Result<()> {
+        let answers = serde_json::to_string(answers).expect("answers serialize");
+        self.conn.execute(
+            "INSERT OR REPLACE INTO application_drafts (board_token, job_id, saved_at, answers_json) VALUES (?1, ?2, ?3, ?4)",
+            params![job.board_token, job.id as i64, chrono::Utc::now().to_rfc3339(), answers],
+        )?;
+        Ok(())
+    }
+
+    pub fn draft(&self, job: &JobResult) -> rusqlite::Result<Option<ApplicationDraft>> {
+        self.conn
+            .query_row(
+                "SELECT saved_at, answers_json FROM application_drafts WHERE board_token = ?1 AND job_id = ?2",
+                params![job.board_token, job.id as i64],
+                |row| {
+                    let answers: String = row.get(1)?;
+                    Ok(ApplicationDraft { saved_at: row.get(0)?, answers: serde_json::from_str(&answers).unwrap_or_default() })
+                },
+            )
+            .optional()
+    }
+
+    pub fn delete_draft(&self, job: &JobResult) -> rusqlite::Result<()> {
+        self.conn.execute(
+            "DELETE FROM application_drafts WHERE board_token = ?1 AND job_id = ?2",
+            params![job.board_token, job.id as i64],
+        )?;
+        Ok(())
+    }
+
+    // Replaces the job's earlier analysis, if it has one
+    pub fn save_resume_fit(&self, job: &JobResult, fit: &ResumeFit) -> rusqlite::Result<()> {
+        let missing = serde_json::to_string(&fit.missing).expect("terms serialize");
+        self.conn.execute(
+            "INSERT OR REPLACE INTO resume_fits (board_token, job_id, analyzed_at, resume, score, missing_json)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![job.board_token, job.id as i64, fit.analyzed_at, fit.resume, fit.score, missing],
+        )?;
+        Ok(())
+    }
+
+    // The stored analyses of these jobs, by job id
+    pub fn resume_fits(&self, jobs: &[JobResult]) -> rusqlite::Result<HashMap<u64, ResumeFit>> {
+        let mut stmt = self.conn.prepare_cached(
+            "SELECT analyzed_at, resume, score, missing_json FROM resume_fits WHERE board_token = ?1 AND job_id = ?2",
+        )?;
+        let mut fits = HashMap::new();
+        for job in jobs {
+            let fit = stmt
+                .query_row(params![job.board_token, job.id as i64], |row| {
+                    let missing: String = row.get(3)?;
+                    Ok(ResumeFit {
+                        analyzed_at: row.get(0)?,
+                        resume: row.get(1)?,
+                        score: row.get(2)?,
+                        missing: serde_json::from_str(&missing).unwrap_or_default(),
+                    })
+                })
+                .optional()?;
+            if let Some(fit) = fit {
+                fits.insert(job.id, fit);
+            }
+        }
+        Ok(fits)
+    }
+
+    // Adds the job to the end of the queue; one that was skipped goes back in as
+    // pending. Returns false when it's already waiting or was submitted.
+    pub fn queue_job(&self, job: &JobResult) -> rusqlite::Result<bool> {
+        let now = chrono::Utc::now().to_rfc3339();
+        // The queue lists jobs from `jobs`, which a saved result set may not have reached
+        self.conn.execute(
+            "INSERT OR IGNORE INTO jobs (board_token, job_id, title, company, location, url, first_seen, last_seen, raw_json)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?7, ?8)",
+            params![job.board_token, job.id as i64, job.title, job.company, job.location, job.url, now, job.raw.to_string()],
+        )?;
+        let changed = self.conn.execute(
+            "INSERT INTO application_queue (board_token, job_id, queued_at, status, updated_at) VALUES (?1, ?2, ?3, 'pending', ?3)
+             ON CONFLICT (board_token, job_id) DO UPDATE SET status = 'pending', reason = NULL, updated_at = ?3
+             WHERE status = 'skipped'",
+            params![job.board_token, job.id as i64, now],
+        )?;
+        Ok(changed > 0)
+    }
+
+    // The whole queue in order, for jobs the job store still has
+    pub fn application_queue(&self) -> rusqlite::Result<Vec<QueueEntry>> {
+        let columns: Vec<String> = STORED_JOB_COLUMNS.split(", ").map(|column| format!("j.{}", column)).collect();
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {}, q.id, q.queued_at, q.status, q.reason, q.attempts, q.updated_at, q.payload_json
+             FROM application_queue q JOIN jobs j ON j.board_token = q.board_token AND j.job_id = q.job_id
+             ORDER BY q.id",
+            columns.join(", ")
+        ))?;
+        let rows = stmt.query_map([], |row| {
+            let status: String = row.get(11)?;
+            Ok(QueueEntry {
+                job: stored_job(row)?,
+                id: row.get(9)?,
+                queued_at: row.get(10)?,
+                status: QueueStatus::parse(&status),
+                reason: row.get(12)?,
+                attempts: row.get(13)?,
+                updated_at: row.get(14)?,
+                payload_json: row.get(15)?,
+            })
+        })?;
+        rows.collect()
+    }
+
+    // Moving to `Submitting` counts an attempt; a finished entry's saved payload is dropped
+    pub fn set_queue_status(&self, id: i64, status: QueueStatus, reason: Option<&str>) -> rusqlite::Result<()> {
+        self.conn.execute(
+            "UPDATE application_queue SET status = ?2, reason = ?3, updated_at = ?4,
+                attempts = attempts + (CASE WHEN ?2 = 'submitting' THEN 1 ELSE 0 END),
+                payload_json = (CASE WHEN ?2 IN ('submitted', 'skipped') THEN NULL ELSE payload_json END)
+             WHERE id = ?1",
+            params![id, status.as_str(), reason, chrono::Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    // A submission that didn't get through: the job is queued, if it wasn't,
+    // as failed with the payload to send again
+    pub fn queue_retry(&self, job: &JobResult, reason: &str, payload_json: &str) -> rusqlite::Result<()> {
+        self.queue_job(job)?;
+        self.conn.execute(
+            "UPDATE application_queue SET status = 'failed', reason = ?3, payload_json = ?4, updated_at = ?5
+             WHERE board_token = ?1 AND job_id = ?2",
+            params![job.board_token, job.id as i64, reason, payload_json, chrono::Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    // After the saved payload was rejected, so the next run fills the form again
+    pub fn drop_queue_payload(&self, id: i64) -> rusqlite::Result<()> {
+        self.conn.execute("UPDATE application_queue SET payload_json = NULL WHERE id = ?1", params![id])?;
+        Ok(())
+    }
+
+    pub fn remove_from_queue(&self, id: i64) -> rusqlite::Result<bool> {
+        Ok(self.conn.execute("DELETE FROM application_queue WHERE id = ?1", params![id])? > 0)
+    }
+
+    // Drops submitted and skipped entries; returns how many
+    pub fn clear_finished_queue(&self) -> rusqlite::Result<usize> {
+        self.conn.execute("DELETE FROM application_queue WHERE status IN ('submitted', 'skipped')", [])
+    }
+
+    pub fn applications(&self, filter: &ApplicationFilter) -> rusqlite::Result<Vec<ApplicationRecord>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT applications.id, applied_at, board_token, job_id, title, company, url, method, notes, resume, resume_sha256, cover_letter, profile,
+                    latest.status, latest.at, (SELECT remind_on FROM application_reminders WHERE application_id = applications.id),
+                    cover_letter_file, cover_letter_sha256
+             FROM applications
+             LEFT JOIN application_events latest ON latest.id = (
+                 SELECT id FROM application_events
+                 WHERE application_id = applications.id AND status IS NOT NULL
+                 ORDER BY id DESC LIMIT 1
+             )
+             WHERE (?1 IS NULL OR applied_at >= ?1)
+               AND (?2 IS NULL OR applied_at < ?2)
+               AND (?3 IS NULL OR company LIKE '%' || ?3 || '%')
+               AND (?4 OR method != ?5)
+             ORDER BY applied_at, applications.id",
+        )?;
+        let rows = stmt.query_map(
+            params![
+                filter.since.map(|dt| dt.to_rfc3339()),
+                filter.until.map(|dt| dt.to_rfc3339()),
+                filter.company,
+                filter.dry_runs,
+                ApplicationMethod::DryRun.as_str(),
+            ],
+            |row| {
+                Ok(ApplicationRecord {
+                    id: row.get(0)?,
+                    applied_at: row.get(1)?,
+                    board_token: row.get(2)?,
+                    job_id: row.get::<_, i64>(3)? as u64,
+                    title: row.get(4)?,
+                    company: row.get(5)?,
+                    url: row.get(6)?,
+                    method: row.get(7)?,
+                    notes: row.get(8)?,
+                    resume: row.get(9)?,
+                    resume_sha256: row.get(10)?,
+                    cover_letter: row.get(11)?,
+                    profile: row.get(12)?,
+                    status: row.get(13)?,
+                    status_at: row.get(14)?,
+                    remind_on: row.get(15)?,
+                    cover_letter_file: row.get(16)?,
+                    cover_letter_sha256: row.get(17)?,
+                })
+            },
+        )?;
+        rows.collect()
+    }
+
+    // Most recent runs first
+    pub fn search_history(&self, limit: usize) -> rusqlite::Result<Vec<SearchHistoryEntry>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM searches ORDER BY id DESC LIMIT ?1",
+            HISTORY_COLUMNS
+        ))?;
+        let rows = stmt.query_map(params![limit as i64], history_entry)?;
+        rows.collect()
+    }
+
+    pub fn search_entry(&self, id: i64) -> rusqlite::Result<Option<SearchHistoryEntry>> {
+        self.conn
+            .query_row(&format!("SELECT {} FROM searches WHERE id = ?1", HISTORY_COLUMNS), params![id], history_entry)
+            .optional()
+    }
+
+    pub fn stats(&self) -> rusqlite::Result<StoreStats> {
+        let count = |sql: &str| self.conn.query_row(sql, [], |row| row.get::<_, i64>(0));
+        let week_ago = (chrono::Utc::now() - chrono::Duration::days(7)).to_rfc3339();
+
+        let mut stmt = self.conn.prepare(
+            "SELECT company, COUNT(*) FROM jobs GROUP BY company ORDER BY COUNT(*) DESC, company LIMIT 10",
+        )?;
+        let top_companies = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(StoreStats {
+            job_count: count("SELECT COUNT(*) FROM jobs")?,
+            company_count: count("SELECT COUNT(DISTINCT company) FROM jobs")?,
+            search_count: count("SELECT COUNT(*) FROM searches")?,
+            first_search: self.conn.query_row("SELECT MIN(searched_at) FROM searches", [], |row| row.get(0))?,
+            last_search: self.conn.query_row("SELECT MAX(searched_at) FROM searches", [], |row| row.get(0))?,
+            new_last_7_days: self.conn.query_row(
+                "SELECT COUNT(*) FROM jobs WHERE first_seen >= ?1", [week_ago], |row| row.get(0),
+            )?,
+            top_companies,
+        })
+    }
+}
+
+pub fn print_stats(store: &JobStore) -> rusqlite::Result<()> {
+    let stats = store.stats()?;
+
+    println!("{}Job store: {}", icons::STORE, store.path().display());
+    let locale = locale::current();
+    println!("   Jobs seen: {} across {} companies", locale.count(stats.job_count), locale.count(stats.company_count));
+    println!("   New in the last 7 days: {}", locale.count(stats.new_last_7_days));
+    println!("   Searches recorded: {}", locale.count(stats.search_count));
+    if let (Some(first), Some(last)) = (&stats.first_search, &stats.last_search) {
+        println!("   First search: {}", locale.timestamp_date_time(first));
+        println!("   Last search: {}", locale.timestamp_date_time(last));
+    }
+
+    if !stats.top_companies.is_empty() {
+        println!("\n{}Top companies by jobs seen:", icons::COMPANY);
+        for (company, count) in &stats.top_companies {
+            println!("   {:>5}  {}", locale.count(count), company);
+        }
+    }
+
+    Ok(())
+}
+
+pub enum PriorApplication<'a> {
+    // Same job id on the same board, or the same URL
+    Applied(&'a ApplicationRecord),
+    // Same company and a near-identical title, e.g. a role reposted under a new id
+    PossiblyApplied(&'a ApplicationRecord),
+    // Opened in the browser to apply by hand, and not marked done yet
+    FollowUp(&'a ApplicationRecord),
+}
+
+pub fn find_prior_application<'a>(records: &'a [ApplicationRecord], job: &JobResult) -> Option<PriorApplication<'a>> {
+    let (follow_ups, applied): (Vec<&ApplicationRecord>, Vec<&ApplicationRecord>) =
+        records.iter().partition(|r| r.is_follow_up());
+    if let Some(record) = applied.iter().find(|r| r.is_for(job)) {
+        return Some(PriorApplication::Applied(record));
+    }
+    if let Some(record) = follow_ups.iter().rev().find(|r| r.is_for(job)) {
+        return Some(PriorApplication::FollowUp(record));
+    }
+
+    let job_words = title_words(&job.title);
+    applied
+        .into_iter()
+        .filter(|r| r.company.eq_ignore_ascii_case(&job.company))
+        .find(|r| word_overlap(&job_words, &title_words(&r.title)) >= 0.8)
+        .map(PriorApplication::PossiblyApplied)
+}
+
+// Jobs opened in the browser that no later submission or manual entry covers,
+// the latest opening of each
+pub fn pending_follow_ups(records: &[ApplicationRecord]) -> Vec<&ApplicationRecord> {
+    let done: HashSet<(&str, u64)> = records
+        .iter()
+        .filter(|r| !r.is_follow_up())
+        .map(|r| (r.board_token.as_str(), r.job_id))
+        .collect();
+    let mut pending: Vec<&ApplicationRecord> = Vec::new();
+    for record in records.iter().filter(|r| r.is_follow_up()) {
+        if done.contains(&(record.board_token.as_str(), record.job_id)) {
+            continue;
+        }
+        pending.retain(|r| !(r.board_token == record.board_token && r.job_id == record.job_id));
+        pending.push(record);
+    }
+    pending
+}
+
+pub fn title_words(title: &str) -> HashSet<String> {
+    title
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+// Jaccard similarity of two word sets
+pub fn word_overlap(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+impl ApplicationRecord {
+    pub fn applied_date(&self) -> String {
+        locale::current().timestamp_date(&self.applied_at)
+    }
+
+    fn is_follow_up(&self) -> bool {
+        self.method == ApplicationMethod::Browser.as_str()
+    }
+
+    // Whether it reached the company, and so can have a status
+    pub fn went_out(&self) -> bool {
+        self.method == ApplicationMethod::Api.as_str() || self.method == ApplicationMethod::Manual.as_str()
+    }
+
+    pub fn current_status(&self) -> ApplicationStatus {
+        self.status.as_deref().and_then(ApplicationStatus::parse).unwrap_or(ApplicationStatus::Applied)
+    }
+
+    pub fn remind_on(&self) -> Option<chrono::NaiveDate> {
+        self.remind_on.as_deref().and_then(|day| day.parse().ok())
+    }
+
+    // "interview since 12 Oct", or "applied" before any update
+    pub fn status_line(&self) -> String {
+        match &self.status_at {
+            Some(at) => format!("{} since {}", self.current_status().as_str(), locale::current().timestamp_date(at)),
+            None => self.current_status().as_str().to_string(),
+        }
+    }
+
+    fn is_for(&self, job: &JobResult) -> bool {
+        (self.board_token == job.board_token && self.job_id == job.id) || self.url == job.url
+    }
+}
+
+// Whether the log has a submission of this job from `since` on, for a queue
+// entry an interrupted run left mid-submission
+pub fn submitted_since(records: &[ApplicationRecord], job: &JobResult, since: &str) -> bool {
+    let Ok(since) = chrono::DateTime::parse_from_rfc3339(since) else {
+        return false;
+    };
+    records.iter().any(|record| {
+        record.is_for(job)
+            && record.method == ApplicationMethod::Api.as_str()
+            && chrono::DateTime::parse_from_rfc3339(&record.applied_at).is_ok_and(|at| at >= since)
+    })
+}
+
+pub fn print_history(entries: &[SearchHistoryEntry]) {
+    if entries.is_empty() {
+        println!("{}No searches recorded.", icons::EMPTY);
+        return;
+    }
+
+    println!("{}Recent searches (newest first):\n", icons::HISTORY);
+    for entry in entries {
+        println!("{}", history_line(entry));
+    }
+}
+
+// One run on a single line; 💾 marks runs whose results were saved
+pub fn history_line(entry: &SearchHistoryEntry) -> String {
+    let locale = locale::current();
+    let boards = match entry.boards_succeeded {
+        Some(succeeded) => format!("{}/{} boards", locale.count(succeeded), locale.count(entry.boards_searched)),
+        None => format!("{} boards", locale.count(entry.boards_searched)),
+    };
+    let scanned = entry.jobs_scanned.map(|n| format!(", {} jobs scanned", locale.count(n))).unwrap_or_default();
+    let duration = entry.duration_ms.map(|ms| format!(", {:.1}s", ms as f64 / 1000.0)).unwrap_or_default();
+    let saved = if entry.results_path.is_some() || entry.results_json.is_some() { format!("  {}", icons::SAVED) } else { String::new() };
+    format!(
+        "#{} {}  \"{}\" in {} — {} match(es); {}{}{}{}",
+        entry.id,
+        locale.timestamp_date_time(&entry.searched_at),
+        entry.keyword,
+        entry.location,
+        locale.count(entry.match_count),
+        boards,
+        scanned,
+        duration,
+        saved
+    )
+}
+
+// `events` is the history shown under each application; pass what `application_events` returned
+pub fn print_applications(records: &[ApplicationRecord], events: &[ApplicationEvent]) {
+    if records.is_empty() {
+        println!("{}No applications recorded.", icons::EMPTY);
+        return;
+    }
+
+    println!("{}Applications ({}):\n", icons::APPLICATIONS, locale::current().count(records.len()));
+    for record in records {
+        let applied_at = locale::current().timestamp_date_time(&record.applied_at);
+        if record.method == ApplicationMethod::DryRun.as_str() {
+            println!("#{} {}  {} — {} [dry run, NOT submitted]", record.id, applied_at, record.company, record.title);
+        } else if record.is_follow_up() {
+            println!("#{} {}  {} — {} [opened in the browser to apply by hand]", record.id, applied_at, record.company, record.title);
+        } else {
+            println!("#{} {}  {} — {} [{}]", record.id, applied_at, record.company, record.title, record.method);
+        }
+        println!("   {}{}", icons::LINK, record.url);
+        if let Some(notes) = &record.notes {
+            println!("   {}{}", icons::FORM, notes);
+        }
+        if let Some(profile) = &record.profile {
+            println!("   {}Profile: {}", icons::PROFILE, profile);
+        }
+        if let (Some(resume), Some(sha256)) = (&record.resume, &record.resume_sha256) {
+            println!("   {}{} (sha256 {})", icons::ATTACHMENT, resume, &sha256[..sha256.len().min(12)]);
+        }
+        if let Some(letter) = &record.cover_letter {
+            let opening = letter.lines().find(|line| !line.trim().is_empty()).unwrap_or("").trim();
+            match (&record.cover_letter_file, &record.cover_letter_sha256) {
+                (Some(file), Some(sha256)) => {
+                    println!("   {}Cover letter: {} ({}, sha256 {})", icons::LETTER, opening, file, &sha256[..sha256.len().min(12)])
+                }
+                _ => println!("   {}Cover letter: {}", icons::LETTER, opening),
+            }
+        }
+        if record.went_out() {
+            println!("   {}Status: {}", icons::STATUS, record.status_line());
+        }
+        for event in events.iter().filter(|event| event.application_id == record.id) {
+            let what = match (&event.status, &event.note) {
+                (Some(status), Some(note)) => format!("{} — {}", status, note),
+                (Some(status), None) => status.clone(),
+                (None, Some(note)) => note.clone(),
+                (None, None) => continue,
+            };
+            println!("      {}  {}", locale::current().timestamp_date(&event.at), what);
+        }
+        if let Some(day) = record.remind_on() {
+            println!("   {}Follow up on {}", icons::REMINDER, day.format(locale::current().date_pattern()));
+        }
+    }
+}
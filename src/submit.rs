@@ -0,0 +1,616 @@
+use crate::application::{ApplicationForm, FieldValue, Question, QuestionField};
+use crate::cover_letter::{Letter, SentLetter};
+use crate::overrides::Rule;
+use crate::profile::Profile;
+use crate::{paths, JobResult};
+use reqwest::multipart::{Form, Part};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+// Submitting an application through the Job Board API:
+//
+//   POST https://boards-api.greenhouse.io/v1/boards/{token}/jobs/{id}
+//
+// as multipart/form-data with one part per form field, authenticated with the
+// board's Job Board API key as the HTTP Basic user name. Answers come from the
+// board's overrides.toml entries, then the applicant profile, and are keyed by
+// the field names of the job's parsed form, so the form is always fetched first. Nothing is sent while a
+// required question is unanswered.
+//
+// A dry run builds the same multipart body and stops short of sending it,
+// reporting each part instead. A submission that got no answer, or a 5xx or
+// 429 one, is kept in the application queue as it was sent (`SavedPayload`)
+// for `queue process` to send again.
+
+const API_URL: &str = "https://boards-api.greenhouse.io/v1/boards";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Answer {
+    Text(String),
+    // A select option: the label is shown, the value is sent
+    Choice { label: String, value: String },
+    File(PathBuf),
+    // The rendered cover letter, sent as a file
+    Document(Letter),
+}
+
+#[derive(Debug, Default)]
+pub struct FilledForm {
+    // Form field name -> answer; multi-selects repeat the name once per choice
+    pub answers: Vec<(String, Answer)>,
+    // Required questions without an answer, and answers that don't fit their field
+    pub problems: Vec<FieldError>,
+    // Field name -> where its answer came from, for the fields that have one
+    pub sources: BTreeMap<String, AnswerSource>,
+    // Patterns in overrides.toml for this board that no question on the form matched
+    pub unused_overrides: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AnswerSource {
+    Override,
+    Profile,
+    // Typed or chosen in the form
+    Manual,
+}
+
+impl AnswerSource {
+    pub fn describe(self) -> &'static str {
+        match self {
+            AnswerSource::Override => "from overrides.toml",
+            AnswerSource::Profile => "from your profile",
+            AnswerSource::Manual => "entered by you",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct SubmitError {
+    pub message: String,
+    // What the error says about particular fields, when it can be tied to them
+    pub fields: Vec<FieldError>,
+    // Greenhouse turned the request itself away (a 4xx other than a validation
+    // error or rate limiting), so only applying in the browser is left
+    pub api_refused: bool,
+    // The request got no answer, or a 5xx or 429 one; sending the same thing
+    // later may well work
+    pub transient: bool,
+}
+
+impl SubmitError {
+    pub fn general(message: impl Into<String>) -> Self {
+        SubmitError { message: message.into(), fields: Vec::new(), api_refused: false, transient: false }
+    }
+
+    pub fn with_fields(message: impl Into<String>, fields: Vec<FieldError>) -> Self {
+        SubmitError { fields, ..SubmitError::general(message) }
+    }
+}
+
+// A submission as it was sent, kept in the queue until it goes through or the
+// job is dropped from it. Attachments are kept as their paths and read from
+// disk again; the form is kept to tie a later rejection to its fields.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedPayload {
+    pub answers: Vec<(String, Answer)>,
+    pub form: ApplicationForm,
+    // The profile it went out as, for the application log
+    pub profile: String,
+}
+
+impl SavedPayload {
+    pub fn filled(&self) -> FilledForm {
+        FilledForm { answers: self.answers.clone(), ..FilledForm::default() }
+    }
+}
+
+// One part of the multipart body, as a dry run reports it
+#[derive(Debug, Clone, Serialize)]
+pub struct PartSummary {
+    pub field: String,
+    pub preview: String,
+    // Attachments only
+    pub file_name: Option<String>,
+    pub bytes: Option<u64>,
+    pub content_type: Option<String>,
+}
+
+// Everything a submission would have sent, without sending it
+#[derive(Debug, Clone, Serialize)]
+pub struct DryRun {
+    pub board_token: String,
+    pub job_id: u64,
+    pub title: String,
+    pub company: String,
+    pub run_at: String,
+    pub url: String,
+    // Whether the board's Job Board API key would have gone with it
+    pub authenticated: bool,
+    pub parts: Vec<PartSummary>,
+    // Why a real submission would have stopped before sending anything
+    pub problems: Vec<FieldError>,
+}
+
+// Answers for every field the board's overrides, the profile and the cover
+// letter cover. Overrides only answer the job's own questions, never the
+// self-identification ones or file uploads.
+pub fn fill(form: &ApplicationForm, profile: &Profile, cover_letter: Option<&Letter>, overrides: &[Rule]) -> FilledForm {
+    let mut filled = FilledForm::default();
+    let mut used = vec![false; overrides.len()];
+    for question in &form.questions {
+        let rule = overrides.iter().position(|rule| rule.matches(&question.label));
+        let answers_before = filled.answers.len();
+        fill_question(&mut filled, question, "add an answer under [answers] in profile.toml", |field| {
+            answer_field(question, field, profile, cover_letter, rule.map(|i| &overrides[i]).filter(|_| overridable(field)))
+        });
+        for field in &question.fields {
+            let overridden = rule.is_some() && overridable(field);
+            if let Some(i) = rule.filter(|_| overridden) {
+                used[i] = true;
+            }
+            if filled.answers[answers_before..].iter().any(|(name, _)| name == &field.name) {
+                let source = if overridden { AnswerSource::Override } else { AnswerSource::Profile };
+                filled.sources.insert(field.name.clone(), source);
+            }
+        }
+    }
+    filled.unused_overrides =
+        overrides.iter().zip(used).filter(|(_, used)| !used).map(|(rule, _)| rule.pattern.clone()).collect();
+    for question in form.compliance.iter().flat_map(|section| &section.questions) {
+        let hint = if question.fields.iter().any(QuestionField::is_select) {
+            "add an answer under [demographics] in profile.toml"
+        } else {
+            "answer it in the form"
+        };
+        let answers_before = filled.answers.len();
+        fill_question(&mut filled, question, hint, |field| demographic_answer(question, field, profile));
+        for (name, _) in &filled.answers[answers_before..] {
+            filled.sources.insert(name.clone(), AnswerSource::Profile);
+        }
+    }
+    filled
+}
+
+// Fields an override can answer: anything typed or chosen
+fn overridable(field: &QuestionField) -> bool {
+    !matches!(field.field_type.as_str(), "input_file" | "input_hidden")
+}
+
+// `hint` says how to answer a required question the profile leaves blank
+fn fill_question(filled: &mut FilledForm, question: &Question, hint: &str,
+    answer: impl Fn(&QuestionField) -> Option<Result<Vec<Answer>, String>>) {
+    let (answers_before, problems_before) = (filled.answers.len(), filled.problems.len());
+    for field in &question.fields {
+        match answer(field) {
+            Some(Ok(answers)) => filled.answers.extend(answers.into_iter().map(|answer| (field.name.clone(), answer))),
+            Some(Err(message)) => filled.problems.push(FieldError { field: field.name.clone(), message }),
+            None => {}
+        }
+    }
+    let answered = filled.answers.len() > answers_before || filled.problems.len() > problems_before;
+    if question.required && !answered {
+        filled.problems.push(FieldError {
+            field: question.fields.first().map(|field| field.name.clone()).unwrap_or_default(),
+            message: format!("\"{}\" is required; {}", question.label, hint),
+        });
+    }
+}
+
+// Self-identification questions: the profile's [demographics] answer, then its
+// [answers] one, then the choice that declines. Free-text fields are left for
+// the applicant to fill in; nothing is ever put in them on their behalf.
+fn demographic_answer(question: &Question, field: &QuestionField, profile: &Profile) -> Option<Result<Vec<Answer>, String>> {
+    if !field.is_select() {
+        return None;
+    }
+    match keyed_answer(&profile.demographics, question, field).or_else(|| keyed_answer(&profile.answers, question, field)) {
+        Some(raw) => Some(choose(question, field, raw)),
+        None => field.decline_option().map(|option| Ok(vec![option_answer(option)])),
+    }
+}
+
+fn answer_field(question: &Question, field: &QuestionField, profile: &Profile, cover_letter: Option<&Letter>, rule: Option<&Rule>)
+    -> Option<Result<Vec<Answer>, String>> {
+    if let Some(rule) = rule {
+        return raw_answer(question, field, &rule.answer);
+    }
+    let text = |value: &str| Some(Ok(vec![Answer::Text(value.to_string())]));
+    match field.name.as_str() {
+        "first_name" => return text(&profile.first_name),
+        "last_name" => return text(&profile.last_name),
+        "email" => return text(&profile.email),
+        "phone" => return profile.phone.as_deref().and_then(text),
+        "location" => return profile.location.as_deref().and_then(text),
+        "resume" => return profile.resume.clone().map(|path| Ok(vec![Answer::File(path)])),
+        // The upload covers it
+        "resume_text" if profile.resume.is_some() => return None,
+        // Pasted when the form has a text box for it, attached otherwise
+        "cover_letter_text" => return cover_letter.and_then(|letter| text(&letter.text)),
+        "cover_letter" if question.fields.iter().any(|field| field.name == "cover_letter_text") => return None,
+        "cover_letter" => return cover_letter.map(|letter| Ok(vec![Answer::Document(letter.clone())])),
+        _ => {}
+    }
+
+    let raw = match keyed_answer(&profile.answers, question, field) {
+        Some(answer) => answer.to_string(),
+        None => profile_answer(profile, &question.label)?,
+    };
+    raw_answer(question, field, &raw)
+}
+
+// `raw` as the kind of answer the field takes
+fn raw_answer(question: &Question, field: &QuestionField, raw: &str) -> Option<Result<Vec<Answer>, String>> {
+    match field.field_type.as_str() {
+        "input_hidden" => None,
+        "input_file" => Some(Ok(vec![Answer::File(PathBuf::from(raw))])),
+        _ if field.is_select() => Some(choose(question, field, raw)),
+        _ => Some(Ok(vec![Answer::Text(raw.to_string())])),
+    }
+}
+
+// The select options named in `raw`, comma-separated for multi-selects
+fn choose(question: &Question, field: &QuestionField, raw: &str) -> Result<Vec<Answer>, String> {
+    let wanted: Vec<&str> = if field.field_type.ends_with("multi_select") {
+        raw.split(',').map(str::trim).filter(|choice| !choice.is_empty()).collect()
+    } else {
+        vec![raw.trim()]
+    };
+    wanted
+        .into_iter()
+        .map(|choice| {
+            let option = field
+                .values
+                .iter()
+                .find(|option| option.label.eq_ignore_ascii_case(choice))
+                // "Yes" from the profile picks "Yes, I am authorized" and the like
+                .or_else(|| {
+                    let choice = choice.to_lowercase();
+                    let mut prefixed = field.values.iter().filter(|option| option.label.to_lowercase().starts_with(&choice));
+                    prefixed.next().filter(|_| prefixed.next().is_none())
+                })
+                .ok_or_else(|| {
+                    let options: Vec<&str> = field.values.iter().map(|option| option.label.as_str()).collect();
+                    format!("\"{}\" isn't one of the choices for \"{}\": {}", choice, question.label, options.join(", "))
+                })?;
+            Ok(option_answer(option))
+        })
+        .collect()
+}
+
+// The option as an answer: its label for display, its value for sending
+pub fn option_answer(option: &FieldValue) -> Answer {
+    let value = match &option.value {
+        serde_json::Value::String(value) => value.clone(),
+        serde_json::Value::Null => option.label.clone(),
+        value => value.to_string(),
+    };
+    Answer::Choice { label: option.label.clone(), value }
+}
+
+// `[answers]` and `[demographics]` entries match the question label or the field name, ignoring case
+fn keyed_answer<'a>(answers: &'a BTreeMap<String, String>, question: &Question, field: &QuestionField) -> Option<&'a str> {
+    let field_name = field.name.trim_end_matches("[]");
+    answers
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(&question.label) || key.eq_ignore_ascii_case(field_name))
+        .map(|(_, answer)| answer.as_str())
+}
+
+// Links and work authorization, for the questions boards word their own way
+fn profile_answer(profile: &Profile, label: &str) -> Option<String> {
+    let label = label.to_lowercase();
+    let yes_no = |answer: bool| if answer { "Yes" } else { "No" }.to_string();
+    let authorization = &profile.work_authorization;
+    if label.contains("linkedin") {
+        profile.linkedin.clone()
+    } else if label.contains("github") {
+        profile.github.clone()
+    } else if label.contains("website") || label.contains("portfolio") {
+        profile.website.clone()
+    } else if label.contains("sponsorship") || label.contains("visa") {
+        authorization.requires_sponsorship.map(yes_no)
+    } else if label.contains("authorized to work") || label.contains("authorised to work") || label.contains("eligible to work") {
+        authorization.authorized.map(yes_no)
+    } else {
+        None
+    }
+}
+
+// "email: jane@example.com", "resume: resume.pdf (file)" and so on, for confirmation
+pub fn preview(filled: &FilledForm) -> Vec<String> {
+    filled
+        .answers
+        .iter()
+        .map(|(field, answer)| match filled.sources.get(field) {
+            // Profile answers are the usual case and go unmarked
+            Some(source @ (AnswerSource::Override | AnswerSource::Manual)) => {
+                format!("{}: {} ({})", field, describe(answer), source.describe())
+            }
+            _ => format!("{}: {}", field, describe(answer)),
+        })
+        .collect()
+}
+
+// Text cut to its first line and 60 characters, or the file's name
+pub fn describe(answer: &Answer) -> String {
+    match answer {
+        Answer::Text(text) => {
+            let first_line = text.lines().next().unwrap_or_default();
+            if first_line.chars().count() > 60 {
+                format!("{}...", first_line.chars().take(57).collect::<String>())
+            } else if first_line.len() < text.trim_end().len() {
+                format!("{}...", first_line)
+            } else {
+                first_line.to_string()
+            }
+        }
+        Answer::Choice { label, .. } => label.clone(),
+        Answer::File(path) => format!("{} (file)", path.file_name().unwrap_or(path.as_os_str()).to_string_lossy()),
+        Answer::Document(letter) => format!("{} (file)", letter.file_name()),
+    }
+}
+
+impl FilledForm {
+    // The cover letter going out, pasted or attached
+    pub fn cover_letter(&self) -> Option<&str> {
+        self.answers.iter().find_map(|(name, answer)| match (name.as_str(), answer) {
+            ("cover_letter_text", Answer::Text(text)) | ("cover_letter", Answer::Document(Letter { text, .. })) => Some(text.as_str()),
+            _ => None,
+        })
+    }
+
+    // The cover letter for the application log, with the file's hash when it's attached
+    pub fn sent_letter(&self) -> Option<SentLetter> {
+        self.answers.iter().find_map(|(name, answer)| match (name.as_str(), answer) {
+            ("cover_letter_text", Answer::Text(text)) => Some(SentLetter::pasted(text)),
+            ("cover_letter", Answer::Document(letter)) => Some(letter.sent()),
+            _ => None,
+        })
+    }
+
+    // The answers going to this question's fields
+    pub fn answers_for<'a>(&'a self, question: &'a Question) -> impl Iterator<Item = &'a Answer> + 'a {
+        self.answers
+            .iter()
+            .filter(|(name, _)| question.fields.iter().any(|field| &field.name == name))
+            .map(|(_, answer)| answer)
+    }
+
+    // Where the question's first answered field got its answer
+    pub fn source_for(&self, question: &Question) -> Option<AnswerSource> {
+        question.fields.iter().find_map(|field| self.sources.get(&field.name).copied())
+    }
+}
+
+fn mime_type(path: &Path) -> &'static str {
+    match path.extension().and_then(|ext| ext.to_str()).map(str::to_lowercase).as_deref() {
+        Some("pdf") => "application/pdf",
+        Some("doc") => "application/msword",
+        Some("docx") => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        Some("rtf") => "application/rtf",
+        Some("txt") => "text/plain",
+        _ => "application/octet-stream",
+    }
+}
+
+// The part and the file's size
+async fn file_part(path: &Path) -> std::io::Result<(Part, u64)> {
+    let file = tokio::fs::File::open(path).await?;
+    let metadata = file.metadata().await?;
+    if !metadata.is_file() {
+        return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "not a file"));
+    }
+    let file_name = path.file_name().unwrap_or(path.as_os_str()).to_string_lossy().into_owned();
+    let stream = reqwest::Body::wrap_stream(tokio_util::io::ReaderStream::new(file));
+    let part = Part::stream_with_length(stream, metadata.len()).file_name(file_name).mime_str(mime_type(path)).expect("valid MIME type");
+    Ok((part, metadata.len()))
+}
+
+// The multipart body for these answers, and what goes into each part.
+// Attachments are opened before anything is sent, so a bad path fails up front,
+// and streamed into the body rather than read into memory.
+async fn payload(answers: &[(String, Answer)]) -> Result<(Form, Vec<PartSummary>), SubmitError> {
+    let mut body = Form::new();
+    let mut parts = Vec::new();
+    for (name, answer) in answers {
+        let mut summary =
+            PartSummary { field: name.clone(), preview: describe(answer), file_name: None, bytes: None, content_type: None };
+        body = match answer {
+            Answer::Text(text) | Answer::Choice { value: text, .. } => body.text(name.clone(), text.clone()),
+            Answer::File(path) => {
+                let (part, bytes) = file_part(path).await.map_err(|e| {
+                    SubmitError::with_fields(
+                        format!("can't read {}: {}", path.display(), e),
+                        vec![FieldError { field: name.clone(), message: e.to_string() }],
+                    )
+                })?;
+                summary.file_name = Some(path.file_name().unwrap_or(path.as_os_str()).to_string_lossy().into_owned());
+                summary.bytes = Some(bytes);
+                summary.content_type = Some(mime_type(path).to_string());
+                body.part(name.clone(), part)
+            }
+            Answer::Document(letter) => {
+                let bytes = letter.bytes();
+                summary.file_name = Some(letter.file_name().to_string());
+                summary.bytes = Some(bytes.len() as u64);
+                summary.content_type = Some(letter.content_type().to_string());
+                let part = Part::bytes(bytes).file_name(letter.file_name()).mime_str(letter.content_type()).expect("valid MIME type");
+                body.part(name.clone(), part)
+            }
+        };
+        parts.push(summary);
+    }
+    Ok((body, parts))
+}
+
+fn submit_url(job: &JobResult) -> String {
+    format!("{}/{}/jobs/{}", API_URL, job.board_token, job.id)
+}
+
+pub async fn submit(
+    client: &reqwest::Client,
+    job: &JobResult,
+    form: &ApplicationForm,
+    answers: &[(String, Answer)],
+    api_key: Option<&str>,
+) -> Result<(), SubmitError> {
+    let (body, _) = payload(answers).await?;
+    let mut request = client.post(submit_url(job)).multipart(body);
+    if let Some(key) = api_key {
+        request = request.basic_auth(key, None::<&str>);
+    }
+    let response = request
+        .send()
+        .await
+        .map_err(|e| SubmitError { transient: true, ..SubmitError::general(format!("network error: {}", e)) })?;
+    let status = response.status();
+    let body = response.text().await.unwrap_or_default();
+    if status.is_success() {
+        return Ok(());
+    }
+    Err(rejection(status, &body, form, api_key.is_some()))
+}
+
+// Builds the body `submit` would send and reports it part by part. Unanswered
+// required questions don't stop it; they're listed with anything else that
+// would have kept the application from being sent.
+pub async fn dry_run(job: &JobResult, filled: &FilledForm, authenticated: bool) -> DryRun {
+    let mut problems = filled.problems.clone();
+    let parts = match payload(&filled.answers).await {
+        Ok((_, parts)) => parts,
+        Err(e) => {
+            problems.extend(e.fields);
+            Vec::new()
+        }
+    };
+    DryRun {
+        board_token: job.board_token.clone(),
+        job_id: job.id,
+        title: job.title.clone(),
+        company: job.company.clone(),
+        run_at: chrono::Utc::now().to_rfc3339(),
+        url: submit_url(job),
+        authenticated,
+        parts,
+        problems,
+    }
+}
+
+impl DryRun {
+    pub fn would_submit(&self) -> bool {
+        self.problems.is_empty()
+    }
+
+    // For the screen: the request, then each part, then the problems
+    pub fn lines(&self) -> Vec<String> {
+        let auth = if self.authenticated { "with the board's API key" } else { "without an API key" };
+        let mut lines = vec![format!("POST {} ({})", self.url, auth)];
+        if self.parts.is_empty() {
+            lines.push("  no parts could be built".to_string());
+        }
+        for part in &self.parts {
+            lines.push(match (&part.file_name, part.bytes) {
+                (Some(file_name), Some(bytes)) => format!(
+                    "  {}: {} ({} bytes, {})",
+                    part.field,
+                    file_name,
+                    bytes,
+                    part.content_type.as_deref().unwrap_or("application/octet-stream")
+                ),
+                _ => format!("  {}: {}", part.field, part.preview),
+            });
+        }
+        if !self.problems.is_empty() {
+            lines.push(String::new());
+            lines.push("A real submission would have stopped here, sending nothing:".to_string());
+            lines.extend(self.problems.iter().map(|problem| format!("  {}: {}", problem.field, problem.message)));
+        }
+        lines
+    }
+
+    // `dry-runs/<board>-<job id>-<UTC timestamp>.json` in the data directory
+    pub fn save(&self) -> std::io::Result<PathBuf> {
+        let dir = paths::dry_runs_dir();
+        std::fs::create_dir_all(&dir)?;
+        let name = format!("{}-{}-{}", self.board_token, self.job_id, chrono::Utc::now().format("%Y%m%dT%H%M%SZ"));
+        let path = dir.join(format!("{}.json", name));
+        let tmp = dir.join(format!("{}.json.tmp", name));
+        std::fs::write(&tmp, serde_json::to_string_pretty(self)? + "\n")?;
+        std::fs::rename(&tmp, &path)?;
+        Ok(path)
+    }
+}
+
+// A 4xx that no field can be blamed for, other than rate limiting, means the
+// board won't take applications this way at all
+fn rejection(status: reqwest::StatusCode, body: &str, form: &ApplicationForm, had_key: bool) -> SubmitError {
+    let error = explain_rejection(status, body, form, had_key);
+    let rate_limited = status == reqwest::StatusCode::TOO_MANY_REQUESTS;
+    let api_refused = status.is_client_error() && !rate_limited && error.fields.is_empty();
+    SubmitError { api_refused, transient: status.is_server_error() || rate_limited, ..error }
+}
+
+// Greenhouse answers `{"error": "..."}`, sometimes `{"errors": [...]}` or
+// `{"message": "..."}`; messages are tied to the fields and questions they name
+fn explain_rejection(status: reqwest::StatusCode, body: &str, form: &ApplicationForm, had_key: bool) -> SubmitError {
+    if matches!(status, reqwest::StatusCode::UNAUTHORIZED | reqwest::StatusCode::FORBIDDEN) {
+        return SubmitError::general(if had_key {
+            format!("Greenhouse refused the board's Job Board API key ({})", status)
+        } else {
+            format!(
+                "Greenhouse wants this board's Job Board API key ({}); store it with --set-credential and map the board under [board_credentials]",
+                status
+            )
+        });
+    }
+
+    let messages: Vec<String> = match serde_json::from_str::<serde_json::Value>(body) {
+        Ok(value) => {
+            let errors = value.get("errors").and_then(serde_json::Value::as_array).cloned().unwrap_or_default();
+            let mut messages: Vec<String> = errors
+                .iter()
+                .filter_map(|error| match error {
+                    serde_json::Value::String(message) => Some(message.clone()),
+                    error => {
+                        let message = error.get("message").and_then(serde_json::Value::as_str)?;
+                        Some(match error.get("field").and_then(serde_json::Value::as_str) {
+                            Some(field) => format!("{}: {}", field, message),
+                            None => message.to_string(),
+                        })
+                    }
+                })
+                .collect();
+            messages.extend(
+                ["error", "message"].iter().filter_map(|key| value.get(key).and_then(serde_json::Value::as_str)).map(str::to_string),
+            );
+            messages
+        }
+        Err(_) if body.trim().is_empty() => Vec::new(),
+        Err(_) => vec![body.trim().chars().take(200).collect()],
+    };
+    if messages.is_empty() {
+        return SubmitError::general(format!("Greenhouse rejected the application ({})", status));
+    }
+
+    let compliance = form.compliance.iter().flat_map(|section| &section.questions);
+    let mut fields = Vec::new();
+    for message in &messages {
+        let lower = message.to_lowercase();
+        for question in form.questions.iter().chain(compliance.clone()) {
+            for field in &question.fields {
+                let name = field.name.trim_end_matches("[]");
+                if lower.contains(name) || lower.contains(&question.label.to_lowercase()) {
+                    fields.push(FieldError { field: field.name.clone(), message: message.clone() });
+                }
+            }
+        }
+    }
+    SubmitError::with_fields(format!("Greenhouse rejected the application ({}): {}", status, messages.join("; ")), fields)
+}
@@ -0,0 +1,59 @@
+use crate::{icons, locale, JobResult};
+use std::collections::{BTreeSet, HashMap};
+
+// --summary: how the matches split up by company and by department, largest
+// groups first. A job listed under several departments counts once under each,
+// so the department counts can add up to more than the number of matches.
+
+const NO_DEPARTMENT: &str = "(no department)";
+
+pub fn print_summary(jobs: &[JobResult]) {
+    println!("\n{}MATCHES BY COMPANY", icons::COMPANY);
+    print_counts(&by_company(jobs));
+
+    println!("\n{}MATCHES BY DEPARTMENT", icons::DEPARTMENT);
+    let departments = by_department(jobs);
+    print_counts(&departments);
+    if departments.iter().map(|(_, count)| count).sum::<usize>() > jobs.len() {
+        println!("   (jobs in more than one department are counted under each)");
+    }
+}
+
+fn print_counts(counts: &[(String, usize)]) {
+    if counts.is_empty() {
+        println!("   No matches.");
+        return;
+    }
+    let width = counts.iter().map(|(name, _)| name.chars().count()).max().unwrap_or(0);
+    for (name, count) in counts {
+        println!("   {:<width$}  {}", name, locale::current().count(*count), width = width);
+    }
+}
+
+fn by_company(jobs: &[JobResult]) -> Vec<(String, usize)> {
+    sorted(jobs.iter().map(|job| vec![job.company.clone()]))
+}
+
+fn by_department(jobs: &[JobResult]) -> Vec<(String, usize)> {
+    sorted(jobs.iter().map(|job| {
+        // Greenhouse sometimes repeats a department; a job still counts once there
+        let departments: BTreeSet<&str> =
+            job.departments.iter().map(|department| department.trim()).filter(|department| !department.is_empty()).collect();
+        if departments.is_empty() {
+            vec![NO_DEPARTMENT.to_string()]
+        } else {
+            departments.into_iter().map(str::to_string).collect()
+        }
+    }))
+}
+
+// Most first, then by name
+fn sorted(groups: impl Iterator<Item = Vec<String>>) -> Vec<(String, usize)> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for group in groups.flatten() {
+        *counts.entry(group).or_default() += 1;
+    }
+    let mut counts: Vec<(String, usize)> = counts.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts
+}
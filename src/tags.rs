@@ -0,0 +1,71 @@
+use crate::JobResult;
+use ratatui::style::Color;
+
+// Free-form job tags ("referral", "dream", "visa-ok"). Tags are stored
+// lowercase without a leading '#', so "#Referral" and "referral" are the same
+// tag. In filter text, words starting with '#' select by tag.
+
+const CHIP_COLORS: &[Color] = &[
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+    Color::Green,
+    Color::Yellow,
+    Color::Red,
+    Color::LightBlue,
+    Color::LightMagenta,
+];
+
+pub fn normalize(raw: &str) -> Option<String> {
+    let tag = raw.trim().trim_start_matches('#').to_lowercase();
+    (!tag.is_empty()).then_some(tag)
+}
+
+// Tags separated by spaces or commas, deduplicated and sorted
+pub fn parse(input: &str) -> Vec<String> {
+    let mut tags: Vec<String> = input
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter_map(normalize)
+        .collect();
+    tags.sort();
+    tags.dedup();
+    tags
+}
+
+// Splits filter text into its #tags and the remaining free text
+pub fn split_filter(filter: &str) -> (Vec<String>, Option<String>) {
+    let (tags, words): (Vec<&str>, Vec<&str>) = filter.split_whitespace().partition(|word| word.starts_with('#'));
+    let text = words.join(" ");
+    (tags.into_iter().filter_map(normalize).collect(), (!text.is_empty()).then_some(text))
+}
+
+pub fn has_all(job: &JobResult, tags: &[String]) -> bool {
+    tags.iter().all(|tag| job.tags.contains(tag))
+}
+
+// Tab completion of the word being typed against the tags already in use:
+// a unique match is completed in full, several are completed to their common prefix
+pub fn complete(input: &str, known: &[String]) -> Option<String> {
+    let start = input.rfind(|c: char| c.is_whitespace() || c == ',').map_or(0, |i| i + 1);
+    let (head, word) = input.split_at(start);
+    let hash = if word.starts_with('#') { "#" } else { "" };
+    let prefix = word.trim_start_matches('#').to_lowercase();
+    if prefix.is_empty() {
+        return None;
+    }
+
+    let candidates: Vec<&String> = known.iter().filter(|tag| tag.starts_with(&prefix)).collect();
+    let first = candidates.first()?;
+    let common = candidates.iter().fold(first.as_str(), |common, tag| {
+        let len = common.chars().zip(tag.chars()).take_while(|(a, b)| a == b).map(|(a, _)| a.len_utf8()).sum();
+        &common[..len]
+    });
+    let suffix = if candidates.len() == 1 { " " } else { "" };
+    Some(format!("{}{}{}{}", head, hash, common, suffix))
+}
+
+// Each tag keeps the same chip color across rows and runs
+pub fn chip_color(tag: &str) -> Color {
+    let hash = tag.bytes().fold(0usize, |hash, byte| hash.wrapping_mul(31).wrapping_add(byte as usize));
+    CHIP_COLORS[hash % CHIP_COLORS.len()]
+}
@@ -0,0 +1,147 @@
+use crate::cli::Cli;
+use crate::store::JobStore;
+use crate::{config, credentials, icons, notify, paths, JobResult};
+use serde::Deserialize;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::error::Error;
+
+// Tracked jobs (tagged or applied to) usually vanish from their board once
+// they're filled. Each search compares them with the board listings it has
+// already fetched; any other board is listed once for all its tracked jobs,
+// which also tells a removed job apart from a board that's gone or
+// unreachable. Only a listing that came back successfully can close a job.
+
+// Job ids currently listed on each board
+pub type Listings = HashMap<String, HashSet<u64>>;
+
+#[derive(Debug, Default)]
+pub struct Outcome {
+    pub still_listed: usize,
+    // Newly missing from their board, with `closed_at` set
+    pub closed: Vec<JobResult>,
+    // Closed earlier but listed again
+    pub reopened: Vec<JobResult>,
+    // Open jobs whose board couldn't be listed this time
+    pub unverified: usize,
+}
+
+#[derive(Deserialize)]
+struct Listing {
+    jobs: Vec<ListedJob>,
+}
+
+#[derive(Deserialize)]
+struct ListedJob {
+    id: u64,
+}
+
+// Just the ids, without descriptions, so checking a board stays cheap
+async fn fetch_listing(client: &reqwest::Client, board_token: &str, credential: Option<&str>)
+    -> Result<HashSet<u64>, String> {
+    let api_url = format!("https://boards-api.greenhouse.io/v1/boards/{}/jobs", board_token);
+    let mut request = client.get(&api_url);
+    if let Some(secret) = credential {
+        request = request.bearer_auth(secret);
+    }
+    let response = request.send().await.map_err(|e| format!("network error: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("board returned status {}", response.status()));
+    }
+    let listing: Listing = response.json().await.map_err(|e| format!("unexpected response: {}", e))?;
+    Ok(listing.jobs.into_iter().map(|job| job.id).collect())
+}
+
+// List every tracked job's board that `listings` doesn't cover yet, except the
+// boards in `skip` (ones that just failed). Boards that fail stay unlisted.
+pub async fn fetch_missing_listings(
+    client: &reqwest::Client,
+    tracked: &[JobResult],
+    listings: &mut Listings,
+    skip: &HashSet<String>,
+    credentials: &HashMap<String, String>,
+) {
+    let boards: BTreeSet<&str> = tracked
+        .iter()
+        .map(|job| job.board_token.as_str())
+        .filter(|board| !listings.contains_key(*board) && !skip.contains(*board))
+        .collect();
+    for board in boards {
+        match fetch_listing(client, board, credentials.get(board).map(String::as_str)).await {
+            Ok(ids) => {
+                listings.insert(board.to_string(), ids);
+            }
+            Err(e) => eprintln!("{}Couldn't check tracked jobs on {}: {}", icons::WARNING, board, e),
+        }
+    }
+}
+
+// Close tracked jobs missing from a listed board and reopen closed ones that are back
+pub fn reconcile(store: &JobStore, tracked: Vec<JobResult>, listings: &Listings) -> rusqlite::Result<Outcome> {
+    let now = chrono::Utc::now().to_rfc3339();
+    let mut outcome = Outcome::default();
+    for mut job in tracked {
+        let Some(ids) = listings.get(&job.board_token) else {
+            if job.closed_at.is_none() {
+                outcome.unverified += 1;
+            }
+            continue;
+        };
+        match (ids.contains(&job.id), job.closed_at.is_some()) {
+            (true, false) => outcome.still_listed += 1,
+            (true, true) => {
+                store.set_closed(&job, None)?;
+                job.closed_at = None;
+                outcome.reopened.push(job);
+            }
+            (false, false) => {
+                store.set_closed(&job, Some(&now))?;
+                job.closed_at = Some(now.clone());
+                outcome.closed.push(job);
+            }
+            (false, true) => {}
+        }
+    }
+    Ok(outcome)
+}
+
+pub fn print_outcome(outcome: &Outcome) {
+    for job in &outcome.closed {
+        println!("{}Closed: {} at {} is no longer listed on its board", icons::CLOSED, job.title, job.company);
+    }
+    for job in &outcome.reopened {
+        println!("{}Reopened: {} at {} is listed again", icons::REOPENED, job.title, job.company);
+    }
+    if outcome.unverified > 0 {
+        println!("{}Couldn't check {} tracked job(s) because their board didn't answer", icons::MAYBE, outcome.unverified);
+    }
+}
+
+// `check-tracked`: list the board of every tracked job and report what closed
+pub async fn run(cli: &Cli) -> Result<(), Box<dyn Error>> {
+    let store = JobStore::open(&paths::database_path())?;
+    let tracked = store.tracked_jobs()?;
+    if tracked.is_empty() {
+        println!("{}No tracked jobs yet; tag a job or record an application to track it", icons::EMPTY);
+        return Ok(());
+    }
+
+    let config = config::Config::load();
+    let client = reqwest::Client::new();
+    let boards: HashSet<&str> = tracked.iter().map(|job| job.board_token.as_str()).collect();
+    println!("{}Checking {} tracked job(s) on {} board(s)...", icons::INSPECT, tracked.len(), boards.len());
+
+    let mut listings = Listings::new();
+    let credentials = credentials::board_credentials(&config);
+    fetch_missing_listings(&client, &tracked, &mut listings, &HashSet::new(), &credentials).await;
+    let outcome = reconcile(&store, tracked, &listings)?;
+    print_outcome(&outcome);
+    println!(
+        "{}{} still listed, {} newly closed, {} reopened",
+        icons::DONE, outcome.still_listed, outcome.closed.len(), outcome.reopened.len()
+    );
+
+    if !cli.notify.is_empty() {
+        notify::Notifications::new(cli, &config.notifications).notify_closed(&client, &outcome.closed).await;
+    }
+    Ok(())
+}
@@ -0,0 +1,174 @@
+use crate::cli::Cli;
+use crate::{build_searcher, config::Config, export_results, icons, notify, paths, update_dashboard, NewFilter, Searcher};
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Notify;
+
+// Watch mode: repeat the search on an interval and report only matches the
+// job store hasn't seen before. Discovered board tokens are reused between
+// cycles, and the interval stretches while boards are rate limiting us. Each
+// cycle also sends the follow-up reminders whose day has come.
+
+// Back off once at least this share of boards answered 429 in a cycle
+const RATE_LIMIT_BACKOFF_RATIO: f64 = 0.1;
+const MAX_BACKOFF_FACTOR: u32 = 8;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct WatchState {
+    last_cycle_at: Option<chrono::DateTime<chrono::Utc>>,
+    cycles: u64,
+    cumulative_new_matches: u64,
+}
+
+impl WatchState {
+    fn load() -> Self {
+        std::fs::read_to_string(paths::watch_state_path())
+            .ok()
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_default()
+    }
+
+    // Write to a temporary file and rename so a crash never leaves half a file
+    fn save(&self) -> std::io::Result<()> {
+        if !paths::persistent() {
+            return Ok(());
+        }
+        let path = paths::watch_state_path();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_string_pretty(self)?)?;
+        std::fs::rename(tmp, path)
+    }
+}
+
+pub async fn run(cli: &Cli, interval: Duration) -> Result<(), Box<dyn Error>> {
+    println!("{}Greenhouse Job Search - Watch Mode", icons::UNHIDDEN);
+    println!("=====================================\n");
+
+    let mut state = WatchState::load();
+    if let Some(last) = state.last_cycle_at {
+        println!(
+            "{}Resuming: {} cycle(s) so far, last at {}, {} new match(es) found in total\n",
+            icons::RETRY, state.cycles,
+            last.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M"),
+            state.cumulative_new_matches
+        );
+    }
+
+    let notifications = notify::Notifications::new(cli, &Config::load().notifications);
+    let mut searcher = build_searcher(cli).unwrap_or_else(|e| {
+        eprintln!("{}{}", icons::ERROR, e);
+        std::process::exit(2);
+    });
+    searcher.new_filter = Some(NewFilter::Unseen);
+
+    // First Ctrl-C lets the in-flight cycle finish; the loop exits afterwards
+    let stop = Arc::new(AtomicBool::new(false));
+    let wake = Arc::new(Notify::new());
+    {
+        let stop = stop.clone();
+        let wake = wake.clone();
+        tokio::spawn(async move {
+            if tokio::signal::ctrl_c().await.is_ok() {
+                println!("\n{}Stopping after the current cycle...", icons::STOP);
+                stop.store(true, Ordering::SeqCst);
+                wake.notify_one();
+            }
+        });
+    }
+
+    let mut backoff = 1;
+    loop {
+        let started = chrono::Utc::now();
+        println!("{}Cycle {} at {}", icons::ALARM, state.cycles + 1, started.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S"));
+
+        match searcher.search_jobs(cli.title_keyword(), &cli.location).await {
+            Ok(new_jobs) => {
+                state.cycles += 1;
+                state.last_cycle_at = Some(started);
+                state.cumulative_new_matches += new_jobs.len() as u64;
+                if let Err(e) = state.save() {
+                    eprintln!("{}Failed to save watch state: {}", icons::WARNING, e);
+                }
+
+                if !new_jobs.is_empty() {
+                    export_results(cli, &searcher, &new_jobs);
+                }
+                update_dashboard(cli, &searcher.cycle_matches);
+                // Runs even with nothing new so held-back notifications go out and
+                // email login problems surface on the first cycle
+                notifications.notify_new_matches(searcher.api.client(), &new_jobs).await;
+                notifications.notify_closed(searcher.api.client(), &searcher.closed_tracked).await;
+                println!(
+                    "{}{} new this cycle, {} since watching began",
+                    icons::RISING, new_jobs.len(), state.cumulative_new_matches
+                );
+
+                let run = &searcher.last_run;
+                let limited_share = run.boards_rate_limited as f64 / run.boards_searched.max(1) as f64;
+                if run.boards_rate_limited > 0 && limited_share >= RATE_LIMIT_BACKOFF_RATIO {
+                    backoff = (backoff * 2).min(MAX_BACKOFF_FACTOR);
+                    println!(
+                        "{}{} of {} boards were rate limited; stretching the interval {}x",
+                        icons::SLOW, run.boards_rate_limited, run.boards_searched, backoff
+                    );
+                } else {
+                    backoff = 1;
+                }
+            }
+            Err(e) => eprintln!("{}Watch cycle failed: {}", icons::WARNING, e),
+        }
+        remind_follow_ups(&searcher, &notifications).await;
+
+        if stop.load(Ordering::SeqCst) {
+            break;
+        }
+
+        let delay = with_jitter(interval * backoff);
+        let next = chrono::Local::now() + chrono::Duration::from_std(delay).unwrap_or_default();
+        println!("{}Next search at {} (Ctrl-C to stop)\n", icons::SLEEP, next.format("%H:%M:%S"));
+
+        tokio::select! {
+            _ = tokio::time::sleep(delay) => {}
+            _ = wake.notified() => {}
+        }
+        if stop.load(Ordering::SeqCst) {
+            break;
+        }
+    }
+
+    println!("{}Watch stopped after {} cycle(s) in total; state saved.", icons::BYE, state.cycles);
+    Ok(())
+}
+
+// Printed and notified once each; a new date from `applications update` arms one again
+async fn remind_follow_ups(searcher: &Searcher, notifications: &notify::Notifications) {
+    let Some(store) = &searcher.store else {
+        return;
+    };
+    let reminders = match store.due_reminders(chrono::Local::now().date_naive()) {
+        Ok(reminders) => reminders,
+        Err(e) => {
+            eprintln!("{}Couldn't read follow-up reminders: {}", icons::WARNING, e);
+            return;
+        }
+    };
+    for reminder in &reminders {
+        println!("{}{}", icons::REMINDER, notify::reminder_text(reminder));
+    }
+    notifications.notify_reminders(searcher.api.client(), &reminders).await;
+    if let Err(e) = store.mark_reminded(&reminders) {
+        eprintln!("{}Couldn't mark follow-up reminders as sent: {}", icons::WARNING, e);
+    }
+}
+
+// Spread requests out by ±10% so repeated runs don't line up on the same second
+fn with_jitter(delay: Duration) -> Duration {
+    let factor = 0.9 + rand::random::<f64>() * 0.2;
+    delay.mul_f64(factor)
+}